@@ -0,0 +1,740 @@
+//! JSON-RPC batching for [`RpcClient`].
+//!
+//! [`RpcClient::batch`] queues several typed calls and sends them as one JSON
+//! array in a single POST; see [`RpcBatch`].
+
+// `RpcError` is large, but handles hand back exactly what the equivalent
+// async `RpcClient` methods return; boxing it here alone would be inconsistent.
+#![allow(clippy::result_large_err)]
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::rpc::{
+    CallFunctionResponse, JsonRpcRequest, JsonRpcResponse, RpcClient, async_sleep,
+    is_retryable_status, patch_access_key_error, patch_view_function_error,
+};
+use crate::error::RpcError;
+use crate::trace;
+use crate::types::{
+    AccessKeyView, AccountId, AccountView, BlockReference, BlockView, ContractCodeView, CryptoHash,
+    GasPrice, PublicKey, ViewFunctionResult,
+};
+
+/// Source of [`RpcBatch`] ids, so a handle can tell which batch issued it.
+static NEXT_BATCH_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Converts an entry's raw result into the caller's type.
+type Finisher<R> =
+    Box<dyn FnOnce(Result<serde_json::Value, RpcError>) -> Result<R, RpcError> + Send>;
+
+/// A queued call inside an [`RpcBatch`].
+struct BatchCall {
+    method: String,
+    params: Result<serde_json::Value, serde_json::Error>,
+}
+
+/// A set of RPC calls to send together in one JSON-RPC batch request.
+///
+/// Created by [`RpcClient::batch`]. Queue calls with the typed helpers (or
+/// [`call`](Self::call) for any method), keep the returned [`BatchHandle`]s,
+/// then [`send`](Self::send) the batch and redeem each handle from the
+/// [`BatchResponse`]:
+///
+/// ```rust,no_run
+/// # use near_kit::*;
+/// # async fn example(rpc: &RpcClient) -> Result<(), near_kit::Error> {
+/// let alice: AccountId = "alice.testnet".parse()?;
+/// let bob: AccountId = "bob.testnet".parse()?;
+///
+/// let mut batch = rpc.batch();
+/// let alice_account = batch.view_account(&alice, BlockReference::default());
+/// let bob_account = batch.view_account(&bob, BlockReference::default());
+/// let mut response = batch.send().await?;
+///
+/// println!("alice: {}", response.take(alice_account)?.amount);
+/// println!("bob: {}", response.take(bob_account)?.amount);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Responses are matched to calls by JSON-RPC `id`, so the server may answer
+/// in any order. Every entry goes through the same error mapping as
+/// [`RpcClient::call`], and entries that fail with a retryable error are
+/// retried individually under the client's [`RetryConfig`](super::RetryConfig).
+///
+/// Not every endpoint accepts batches (nearcore itself does not). When the
+/// server answers with anything other than a JSON array, the batch falls back
+/// to sequential [`RpcClient::call`]s and the client remembers the rejection,
+/// so later batches go straight to sequential calls.
+#[must_use = "a batch does nothing until `send` is awaited"]
+pub struct RpcBatch<'a> {
+    rpc: &'a RpcClient,
+    id: u64,
+    calls: Vec<BatchCall>,
+}
+
+/// A typed ticket for one call queued in an [`RpcBatch`].
+///
+/// Redeem it with [`BatchResponse::take`] on the response of the batch that
+/// issued it.
+#[must_use = "a batch handle is the only way to read its call's result"]
+pub struct BatchHandle<R> {
+    batch_id: u64,
+    index: usize,
+    finish: Finisher<R>,
+    _marker: PhantomData<fn() -> R>,
+}
+
+impl<R> std::fmt::Debug for BatchHandle<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchHandle")
+            .field("batch_id", &self.batch_id)
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+/// The results of a sent [`RpcBatch`], one per queued call.
+#[derive(Debug)]
+pub struct BatchResponse {
+    batch_id: u64,
+    results: Vec<Option<Result<serde_json::Value, RpcError>>>,
+}
+
+impl BatchResponse {
+    /// Take the typed result for `handle`.
+    ///
+    /// Errors are the same [`RpcError`] variants the equivalent
+    /// [`RpcClient`] method returns. Passing a handle from a different batch
+    /// yields [`RpcError::InvalidResponse`].
+    pub fn take<R>(&mut self, handle: BatchHandle<R>) -> Result<R, RpcError> {
+        let raw = if handle.batch_id == self.batch_id {
+            self.results.get_mut(handle.index).and_then(Option::take)
+        } else {
+            None
+        };
+        let raw = raw.ok_or_else(|| {
+            RpcError::InvalidResponse("batch handle does not belong to this response".to_string())
+        })?;
+        (handle.finish)(raw)
+    }
+
+    /// Number of calls in the batch.
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Whether the batch was empty.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+}
+
+impl RpcClient {
+    /// Start a JSON-RPC batch.
+    ///
+    /// See [`RpcBatch`] for details.
+    pub fn batch(&self) -> RpcBatch<'_> {
+        RpcBatch {
+            rpc: self,
+            id: NEXT_BATCH_ID.fetch_add(1, Ordering::Relaxed),
+            calls: Vec::new(),
+        }
+    }
+}
+
+impl<'a> RpcBatch<'a> {
+    /// Number of calls queued so far.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Whether no calls have been queued.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Queue a raw RPC call, the batched equivalent of [`RpcClient::call`].
+    ///
+    /// A `params` value that fails to serialize surfaces as
+    /// [`RpcError::Json`] when its handle is taken; the rest of the batch is
+    /// unaffected.
+    pub fn call<P: Serialize, R: DeserializeOwned + 'static>(
+        &mut self,
+        method: &str,
+        params: P,
+    ) -> BatchHandle<R> {
+        self.push(method, serde_json::to_value(params), |raw| {
+            serde_json::from_value(raw?).map_err(RpcError::Json)
+        })
+    }
+
+    /// Queue [`RpcClient::view_account`].
+    pub fn view_account(
+        &mut self,
+        account_id: &AccountId,
+        block: BlockReference,
+    ) -> BatchHandle<AccountView> {
+        let mut params = serde_json::json!({
+            "account_id": account_id.to_string(),
+        });
+        self.rpc.merge_block_reference(&mut params, &block);
+        self.call("EXPERIMENTAL_view_account", params)
+    }
+
+    /// Queue [`RpcClient::view_access_key`].
+    pub fn view_access_key(
+        &mut self,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+        block: BlockReference,
+    ) -> BatchHandle<AccessKeyView> {
+        let mut params = serde_json::json!({
+            "account_id": account_id.to_string(),
+            "public_key": public_key.to_string(),
+        });
+        self.rpc.merge_block_reference(&mut params, &block);
+        let account_id = account_id.clone();
+        self.push("EXPERIMENTAL_view_access_key", Ok(params), move |raw| {
+            let raw = raw.map_err(|e| patch_access_key_error(e, &account_id))?;
+            serde_json::from_value(raw).map_err(RpcError::Json)
+        })
+    }
+
+    /// Queue [`RpcClient::view_function`].
+    pub fn view_function(
+        &mut self,
+        account_id: &AccountId,
+        method_name: &str,
+        args: &[u8],
+        block: BlockReference,
+    ) -> BatchHandle<ViewFunctionResult> {
+        let mut params = serde_json::json!({
+            "account_id": account_id.to_string(),
+            "method_name": method_name,
+            "args_base64": STANDARD.encode(args),
+        });
+        self.rpc.merge_block_reference(&mut params, &block);
+        let account_id = account_id.clone();
+        let method_name = method_name.to_string();
+        self.push("EXPERIMENTAL_call_function", Ok(params), move |raw| {
+            let raw = raw.map_err(|e| patch_view_function_error(e, &account_id, &method_name))?;
+            let response: CallFunctionResponse =
+                serde_json::from_value(raw).map_err(RpcError::Json)?;
+            Ok(response.into())
+        })
+    }
+
+    /// Queue [`RpcClient::view_code`].
+    pub fn view_code(
+        &mut self,
+        account_id: &AccountId,
+        block: BlockReference,
+    ) -> BatchHandle<ContractCodeView> {
+        let mut params = serde_json::json!({
+            "request_type": "view_code",
+            "account_id": account_id.to_string(),
+        });
+        self.rpc.merge_block_reference(&mut params, &block);
+        self.call("query", params)
+    }
+
+    /// Queue [`RpcClient::block`].
+    pub fn block(&mut self, block: BlockReference) -> BatchHandle<BlockView> {
+        self.call("block", block.to_rpc_params())
+    }
+
+    /// Queue [`RpcClient::gas_price`].
+    pub fn gas_price(&mut self, block_hash: Option<&CryptoHash>) -> BatchHandle<GasPrice> {
+        let params = match block_hash {
+            Some(hash) => serde_json::json!([hash.to_string()]),
+            None => serde_json::json!([serde_json::Value::Null]),
+        };
+        self.call("gas_price", params)
+    }
+
+    fn push<R>(
+        &mut self,
+        method: &str,
+        params: Result<serde_json::Value, serde_json::Error>,
+        finish: impl FnOnce(Result<serde_json::Value, RpcError>) -> Result<R, RpcError> + Send + 'static,
+    ) -> BatchHandle<R> {
+        let index = self.calls.len();
+        self.calls.push(BatchCall {
+            method: method.to_string(),
+            params,
+        });
+        BatchHandle {
+            batch_id: self.id,
+            index,
+            finish: Box::new(finish),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Send every queued call in one POST and collect the results.
+    ///
+    /// Returns `Err` only when the request as a whole fails (for example the
+    /// transport cannot reach the endpoint after all retries). Per-call
+    /// failures are reported by [`BatchResponse::take`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(rpc.batch_size = self.calls.len(), rpc.url = %super::rpc::sanitize_url(&self.rpc.url))))]
+    pub async fn send(self) -> Result<BatchResponse, RpcError> {
+        let rpc = self.rpc;
+        let batch_id = self.id;
+        let mut results = Vec::with_capacity(self.calls.len());
+        // Calls that made it onto the wire, as (index into `results`, method, params).
+        let mut queued = Vec::with_capacity(self.calls.len());
+        for (index, call) in self.calls.into_iter().enumerate() {
+            match call.params {
+                Ok(params) => {
+                    results.push(None);
+                    queued.push((index, call.method, params));
+                }
+                Err(e) => results.push(Some(Err(RpcError::Json(e)))),
+            }
+        }
+
+        let outcomes = match post_batch(rpc, &queued).await? {
+            Some(outcomes) => outcomes,
+            None => {
+                let mut outcomes = Vec::with_capacity(queued.len());
+                for (_, method, params) in &queued {
+                    outcomes.push(rpc.call(method, params).await);
+                }
+                outcomes
+            }
+        };
+
+        for ((index, _, _), outcome) in queued.into_iter().zip(outcomes) {
+            results[index] = Some(outcome);
+        }
+        Ok(BatchResponse { batch_id, results })
+    }
+}
+
+/// POST `calls` as one JSON-RPC batch.
+///
+/// Returns `Ok(None)` when the endpoint does not accept batches, telling the
+/// caller to fall back to sequential calls. Otherwise the outcomes line up
+/// with `calls`.
+async fn post_batch(
+    rpc: &RpcClient,
+    calls: &[(usize, String, serde_json::Value)],
+) -> Result<Option<Vec<Result<serde_json::Value, RpcError>>>, RpcError> {
+    // A batch of one buys nothing over a plain call.
+    if calls.len() < 2 || rpc.batch_unsupported.load(Ordering::Relaxed) {
+        return Ok(None);
+    }
+
+    let first_id = rpc
+        .request_id
+        .fetch_add(calls.len() as u64, Ordering::Relaxed);
+    let requests: Vec<_> = calls
+        .iter()
+        .zip(first_id..)
+        .map(|((_, method, params), id)| JsonRpcRequest {
+            jsonrpc: "2.0",
+            id,
+            method: method.as_str(),
+            params,
+        })
+        .collect();
+    let request_body = serde_json::to_vec(&requests).map_err(RpcError::Json)?;
+
+    let methods: Vec<&str> = calls.iter().map(|(_, method, _)| method.as_str()).collect();
+
+    let total_attempts = rpc.retry_config.max_retries + 1;
+    let mut attempt = 0;
+    let response = loop {
+        let permit = rpc.acquire_rate_limit(&methods).await;
+        let result = rpc
+            .transport
+            .post_json(&rpc.url, request_body.clone())
+            .await;
+        drop(permit);
+        match result {
+            Ok(response) => break response,
+            Err(e) if e.is_retryable() && attempt < total_attempts - 1 => {
+                let delay = std::cmp::min(
+                    rpc.retry_config.initial_delay_ms * 2u64.pow(attempt),
+                    rpc.retry_config.max_delay_ms,
+                );
+                trace::debug!(
+                    attempt = attempt + 1,
+                    max_attempts = total_attempts,
+                    delay_ms = delay,
+                    error = %e,
+                    "RPC batch request failed, retrying"
+                );
+                async_sleep(Duration::from_millis(delay)).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    };
+
+    let status = response.status;
+    let body = String::from_utf8_lossy(&response.body);
+    trace::trace!(payload = %body, "RPC batch response");
+    if status == 429 {
+        // Pauses the limiter; the sequential fallback then waits it out.
+        rpc.rate_limited(response.retry_after, &body);
+    }
+
+    let entries = match serde_json::from_str::<Vec<serde_json::Value>>(&body) {
+        Ok(entries) if (200..300).contains(&status) => entries,
+        _ => {
+            // A transient status says nothing about batch support; anything
+            // else (a single error object, 400, 405, ...) is a rejection
+            // worth remembering.
+            if !is_retryable_status(status) {
+                rpc.batch_unsupported.store(true, Ordering::Relaxed);
+            }
+            trace::debug!(
+                http.status = status,
+                "RPC endpoint rejected batch, falling back to sequential calls"
+            );
+            return Ok(None);
+        }
+    };
+
+    let mut by_id: HashMap<u64, serde_json::Value> = entries
+        .into_iter()
+        .filter_map(|entry| Some((entry.get("id")?.as_u64()?, entry)))
+        .collect();
+
+    let mut outcomes = Vec::with_capacity(calls.len());
+    for ((_, method, params), id) in calls.iter().zip(first_id..) {
+        let outcome = match by_id.remove(&id) {
+            Some(entry) => serde_json::from_value::<JsonRpcResponse>(entry)
+                .map_err(RpcError::Json)
+                .and_then(|response| rpc.decode_response(method, response)),
+            None => Err(RpcError::InvalidResponse(format!(
+                "Batch response is missing id {id}"
+            ))),
+        };
+        // Transient per-entry failures get the same retry budget a plain
+        // call would.
+        let outcome = match outcome {
+            Err(e) if e.is_retryable() && rpc.retry_config.max_retries > 0 => {
+                trace::debug!(error = %e, "RPC batch entry failed, retrying individually");
+                rpc.call(method, params).await
+            }
+            other => other,
+        };
+        outcomes.push(outcome);
+    }
+    Ok(Some(outcomes))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::client::RetryConfig;
+    use crate::client::transport::{BoxFuture, RpcTransport, TransportResponse};
+
+    /// Transport that replays scripted responses in order and records every
+    /// request body it receives.
+    struct ScriptedTransport {
+        responses: Mutex<Vec<(u16, String)>>,
+        requests: Mutex<Vec<serde_json::Value>>,
+    }
+
+    impl ScriptedTransport {
+        fn new(responses: Vec<(u16, String)>) -> Arc<Self> {
+            Arc::new(Self {
+                responses: Mutex::new(responses),
+                requests: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn requests(&self) -> Vec<serde_json::Value> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    impl RpcTransport for ScriptedTransport {
+        fn post_json(
+            &self,
+            _url: &str,
+            body: Vec<u8>,
+        ) -> BoxFuture<'_, Result<TransportResponse, RpcError>> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push(serde_json::from_slice(&body).unwrap());
+            let (status, body) = self.responses.lock().unwrap().remove(0);
+            Box::pin(async move { Ok(TransportResponse::new(status, body.into_bytes())) })
+        }
+    }
+
+    fn client(transport: Arc<ScriptedTransport>) -> RpcClient {
+        RpcClient::with_transport_and_retry_config(
+            "http://localhost",
+            transport,
+            RetryConfig {
+                max_retries: 0,
+                initial_delay_ms: 1,
+                max_delay_ms: 1,
+            },
+        )
+    }
+
+    fn account_json(amount: &str) -> serde_json::Value {
+        serde_json::json!({
+            "amount": amount,
+            "locked": "0",
+            "code_hash": "11111111111111111111111111111111",
+            "storage_usage": 182,
+            "storage_paid_at": 0,
+            "block_height": 1,
+            "block_hash": "11111111111111111111111111111111"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_batch_sends_one_request_and_demuxes_by_id() {
+        // Answer in reverse order: ids 1 then 0.
+        let body = serde_json::json!([
+            { "jsonrpc": "2.0", "id": 1, "result": account_json("2") },
+            { "jsonrpc": "2.0", "id": 0, "result": account_json("1") },
+        ]);
+        let transport = ScriptedTransport::new(vec![(200, body.to_string())]);
+        let rpc = client(transport.clone());
+
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        let mut batch = rpc.batch();
+        let a = batch.view_account(&alice, BlockReference::default());
+        let b = batch.view_account(&bob, BlockReference::default());
+        assert_eq!(batch.len(), 2);
+        let mut response = batch.send().await.unwrap();
+
+        assert_eq!(response.take(a).unwrap().amount.as_yoctonear(), 1);
+        assert_eq!(response.take(b).unwrap().amount.as_yoctonear(), 2);
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        let entries = requests[0].as_array().unwrap();
+        assert_eq!(entries[0]["method"], "EXPERIMENTAL_view_account");
+        assert_eq!(entries[0]["params"]["account_id"], "alice.testnet");
+        assert_eq!(entries[1]["params"]["account_id"], "bob.testnet");
+    }
+
+    #[tokio::test]
+    async fn test_batch_maps_per_entry_errors() {
+        let body = serde_json::json!([
+            {
+                "jsonrpc": "2.0",
+                "id": 0,
+                "error": {
+                    "code": -32000,
+                    "message": "Server error",
+                    "name": "HANDLER_ERROR",
+                    "cause": {
+                        "name": "UNKNOWN_ACCOUNT",
+                        "info": { "requested_account_id": "ghost.testnet" }
+                    }
+                }
+            },
+            {
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": {
+                    "code": -32000,
+                    "message": "Server error",
+                    "name": "HANDLER_ERROR",
+                    "cause": {
+                        "name": "NO_CONTRACT_CODE",
+                        "info": { "contract_account_id": "bob.testnet" }
+                    }
+                }
+            },
+            { "jsonrpc": "2.0", "id": 2, "result": { "gas_price": "100000000" } },
+        ]);
+        let transport = ScriptedTransport::new(vec![(200, body.to_string())]);
+        let rpc = client(transport);
+
+        let ghost: AccountId = "ghost.testnet".parse().unwrap();
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        let mut batch = rpc.batch();
+        let account = batch.view_account(&ghost, BlockReference::default());
+        let view = batch.view_function(&bob, "get", b"{}", BlockReference::default());
+        let price = batch.gas_price(None);
+        let mut response = batch.send().await.unwrap();
+
+        assert!(matches!(
+            response.take(account),
+            Err(RpcError::AccountNotFound { account_id, .. }) if account_id == ghost
+        ));
+        assert!(response.take(view).is_err());
+        assert_eq!(
+            response.take(price).unwrap().gas_price.as_yoctonear(),
+            100_000_000
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_patches_view_function_errors() {
+        let body = serde_json::json!([
+            {
+                "jsonrpc": "2.0",
+                "id": 0,
+                "error": {
+                    "code": -32000,
+                    "message": "Server error",
+                    "name": "HANDLER_ERROR",
+                    "cause": {
+                        "name": "CONTRACT_EXECUTION_ERROR",
+                        "info": { "vm_error": "boom", "block_height": 7 }
+                    }
+                }
+            },
+            { "jsonrpc": "2.0", "id": 1, "result": { "gas_price": "1" } },
+        ]);
+        let rpc = client(ScriptedTransport::new(vec![(200, body.to_string())]));
+
+        let contract: AccountId = "counter.testnet".parse().unwrap();
+        let mut batch = rpc.batch();
+        let view = batch.view_function(&contract, "get_num", b"{}", BlockReference::default());
+        let _price = batch.gas_price(None);
+        let mut response = batch.send().await.unwrap();
+
+        match response.take(view) {
+            Err(RpcError::ContractExecution {
+                contract_id,
+                method_name,
+                ..
+            }) => {
+                assert_eq!(contract_id, contract);
+                assert_eq!(method_name.as_deref(), Some("get_num"));
+            }
+            other => panic!("expected ContractExecution, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_reports_missing_entries() {
+        let body = serde_json::json!([
+            { "jsonrpc": "2.0", "id": 0, "result": { "gas_price": "1" } },
+        ]);
+        let rpc = client(ScriptedTransport::new(vec![(200, body.to_string())]));
+
+        let mut batch = rpc.batch();
+        let first = batch.gas_price(None);
+        let second = batch.gas_price(None);
+        let mut response = batch.send().await.unwrap();
+
+        assert!(response.take(first).is_ok());
+        assert!(matches!(
+            response.take(second),
+            Err(RpcError::InvalidResponse(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_batch_falls_back_to_sequential_calls_when_rejected() {
+        let rejection = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": { "code": -32700, "message": "Parse error" }
+        });
+        let single = |price: &str| {
+            serde_json::json!({ "jsonrpc": "2.0", "id": 0, "result": { "gas_price": price } })
+                .to_string()
+        };
+        let transport = ScriptedTransport::new(vec![
+            (400, rejection.to_string()),
+            (200, single("1")),
+            (200, single("2")),
+            // Second batch: the rejection is remembered, so no batch POST.
+            (200, single("3")),
+            (200, single("4")),
+        ]);
+        let rpc = client(transport.clone());
+
+        let mut batch = rpc.batch();
+        let first = batch.gas_price(None);
+        let second = batch.gas_price(None);
+        let mut response = batch.send().await.unwrap();
+        assert_eq!(response.take(first).unwrap().gas_price.as_yoctonear(), 1);
+        assert_eq!(response.take(second).unwrap().gas_price.as_yoctonear(), 2);
+
+        let mut batch = rpc.batch();
+        let third = batch.gas_price(None);
+        let fourth = batch.gas_price(None);
+        let mut response = batch.send().await.unwrap();
+        assert_eq!(response.take(third).unwrap().gas_price.as_yoctonear(), 3);
+        assert_eq!(response.take(fourth).unwrap().gas_price.as_yoctonear(), 4);
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 5);
+        assert!(requests[0].is_array());
+        assert!(requests[1..].iter().all(|r| r["method"] == "gas_price"));
+    }
+
+    #[tokio::test]
+    async fn test_batch_handle_from_other_batch_is_rejected() {
+        let body = serde_json::json!([
+            { "jsonrpc": "2.0", "id": 0, "result": { "gas_price": "1" } },
+            { "jsonrpc": "2.0", "id": 1, "result": { "gas_price": "1" } },
+        ]);
+        let rpc = client(ScriptedTransport::new(vec![(200, body.to_string())]));
+
+        let mut other = rpc.batch();
+        let _ = other.gas_price(None);
+        let _ = other.gas_price(None);
+        let stray = other.gas_price(None);
+
+        let mut batch = rpc.batch();
+        let _ = batch.gas_price(None);
+        let _ = batch.gas_price(None);
+        let mut response = batch.send().await.unwrap();
+
+        assert!(matches!(
+            response.take(stray),
+            Err(RpcError::InvalidResponse(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_batch_handle_with_in_range_index_from_other_batch_is_rejected() {
+        let body = serde_json::json!([
+            { "jsonrpc": "2.0", "id": 0, "result": { "gas_price": "1" } },
+            { "jsonrpc": "2.0", "id": 1, "result": { "gas_price": "1" } },
+        ]);
+        let rpc = client(ScriptedTransport::new(vec![(200, body.to_string())]));
+
+        let mut other = rpc.batch();
+        let stray = other.gas_price(None);
+
+        let mut batch = rpc.batch();
+        let first = batch.gas_price(None);
+        let _ = batch.gas_price(None);
+        let mut response = batch.send().await.unwrap();
+
+        assert!(matches!(
+            response.take(stray),
+            Err(RpcError::InvalidResponse(_))
+        ));
+        // The rejected handle leaves the entry at its index untouched.
+        assert!(response.take(first).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_empty_batch_sends_nothing() {
+        let transport = ScriptedTransport::new(vec![]);
+        let rpc = client(transport.clone());
+        let response = rpc.batch().send().await.unwrap();
+        assert!(response.is_empty());
+        assert!(transport.requests().is_empty());
+    }
+}
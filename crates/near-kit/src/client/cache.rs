@@ -0,0 +1,572 @@
+//! Response caching for [`RpcClient`](super::RpcClient).
+//!
+//! [`CacheConfig`] turns the cache on and sizes it; [`CacheStore`] is where
+//! entries live ([`LruCacheStore`] in memory by default). The crate-internal
+//! [`ResponseCache`] decides which requests may be answered from the store
+//! and for how long.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::platform::{BoxFuture, MaybeSend, MaybeSync, monotonic_millis};
+use crate::types::CryptoHash;
+
+/// Opt-in caching of RPC results.
+///
+/// Configure it on [`NearBuilder::cache`](super::NearBuilder::cache):
+///
+/// ```rust
+/// use std::time::Duration;
+/// use near_kit::{CacheConfig, Near};
+///
+/// let near = Near::mainnet()
+///     .cache(
+///         CacheConfig::new()
+///             .max_entries(50_000)
+///             .max_bytes(256 * 1024 * 1024)
+///             .finality_ttl(Duration::from_millis(500)),
+///     )
+///     .build();
+/// ```
+///
+/// Which results are reused depends on what the request is pinned to:
+///
+/// - **Immutable** — queries at a block height or hash (`at_block`,
+///   `at_block_hash`), blocks and chunks by height or hash, `gas_price` and
+///   `validators` at a block, `tx_status` waited to `FINAL`,
+///   `receipt_to_tx` and `genesis_config`. Cached until evicted.
+/// - **Finality-pinned** — the same queries at `optimistic` or `final`
+///   finality, plus latest `gas_price` and `validators`. Reused for
+///   [`finality_ttl`](Self::finality_ttl), one second by default.
+/// - **Everything else** — transactions, `status`, `network_info`, light
+///   client and sandbox methods, and all [`RpcBatch`](super::RpcBatch)
+///   requests — always goes to the network.
+///
+/// Only successful results are cached; errors are never replayed. Clones of
+/// the built [`Near`](super::Near) share one cache.
+#[derive(Clone)]
+pub struct CacheConfig {
+    max_entries: usize,
+    max_bytes: usize,
+    finality_ttl: Duration,
+    store: Option<Arc<dyn CacheStore>>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: Self::DEFAULT_MAX_ENTRIES,
+            max_bytes: Self::DEFAULT_MAX_BYTES,
+            finality_ttl: Self::DEFAULT_FINALITY_TTL,
+            store: None,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Entry limit of the built-in store unless configured otherwise.
+    pub const DEFAULT_MAX_ENTRIES: usize = 10_000;
+
+    /// Size limit of the built-in store unless configured otherwise (64 MiB).
+    pub const DEFAULT_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+    /// How long finality-pinned results are reused unless configured
+    /// otherwise.
+    pub const DEFAULT_FINALITY_TTL: Duration = Duration::from_secs(1);
+
+    /// A config with the default limits and the built-in [`LruCacheStore`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cap the number of cached results in the built-in store.
+    ///
+    /// Ignored when a custom [`store`](Self::store) is set.
+    pub fn max_entries(mut self, entries: usize) -> Self {
+        self.max_entries = entries;
+        self
+    }
+
+    /// Cap the total size, in bytes of JSON, of the results in the built-in
+    /// store.
+    ///
+    /// Ignored when a custom [`store`](Self::store) is set.
+    pub fn max_bytes(mut self, bytes: usize) -> Self {
+        self.max_bytes = bytes;
+        self
+    }
+
+    /// Set how long results of `optimistic`/`final` queries are reused.
+    /// [`Duration::ZERO`] stops caching them; immutable results are cached
+    /// regardless.
+    pub fn finality_ttl(mut self, ttl: Duration) -> Self {
+        self.finality_ttl = ttl;
+        self
+    }
+
+    /// Keep cached results in `store` instead of the built-in
+    /// [`LruCacheStore`] — for example a Redis instance shared by several
+    /// processes.
+    pub fn store(mut self, store: impl CacheStore + 'static) -> Self {
+        self.store = Some(Arc::new(store));
+        self
+    }
+}
+
+impl std::fmt::Debug for CacheConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CacheConfig")
+            .field("max_entries", &self.max_entries)
+            .field("max_bytes", &self.max_bytes)
+            .field("finality_ttl", &self.finality_ttl)
+            .field("custom_store", &self.store.is_some())
+            .finish()
+    }
+}
+
+/// Where cached RPC results are kept.
+///
+/// Keys are opaque, fixed-length strings derived from the endpoint URL, the
+/// method and the params; values are the JSON `result` of the response.
+/// `ttl` is `None` for immutable results, which may be kept as long as the
+/// store likes. A store that fails to read or write should report a miss or
+/// drop the value — the client then simply asks the node.
+///
+/// # Implementing
+///
+/// Like [`NonceStore`](super::NonceStore), the trait is object-safe and
+/// returns boxed futures ([`BoxFuture`]); wrap an async block in `Box::pin`.
+/// On native targets implementations must be `Send + Sync`.
+///
+/// ```rust,ignore
+/// struct RedisCacheStore { client: redis::Client }
+///
+/// impl CacheStore for RedisCacheStore {
+///     fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<Vec<u8>>> {
+///         Box::pin(async move { /* GET key */ })
+///     }
+///
+///     fn put<'a>(
+///         &'a self,
+///         key: &'a str,
+///         value: Vec<u8>,
+///         ttl: Option<Duration>,
+///     ) -> BoxFuture<'a, ()> {
+///         Box::pin(async move { /* SET key value [PX ttl] */ })
+///     }
+/// }
+/// ```
+pub trait CacheStore: MaybeSend + MaybeSync {
+    /// The value stored under `key`, unless it is missing or expired.
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<Vec<u8>>>;
+
+    /// Store `value` under `key`, expiring after `ttl` if one is given.
+    fn put<'a>(&'a self, key: &'a str, value: Vec<u8>, ttl: Option<Duration>) -> BoxFuture<'a, ()>;
+}
+
+/// Forwarding impl so an `Arc`-shared store can be handed to
+/// [`CacheConfig::store`] and still be inspected by the caller.
+impl<T: CacheStore + ?Sized> CacheStore for Arc<T> {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<Vec<u8>>> {
+        (**self).get(key)
+    }
+
+    fn put<'a>(&'a self, key: &'a str, value: Vec<u8>, ttl: Option<Duration>) -> BoxFuture<'a, ()> {
+        (**self).put(key, value, ttl)
+    }
+}
+
+/// In-memory least-recently-used [`CacheStore`].
+///
+/// Bounded both by entry count and by the total size of the stored values;
+/// when either limit is exceeded, the least recently read or written entries
+/// are evicted. Values larger than the size limit are not stored at all.
+/// Expired entries are dropped when next read.
+#[derive(Debug)]
+pub struct LruCacheStore {
+    max_entries: usize,
+    max_bytes: usize,
+    state: Mutex<LruState>,
+}
+
+#[derive(Debug, Default)]
+struct LruState {
+    entries: HashMap<String, LruEntry>,
+    /// Keys by last use; the first is the next to evict.
+    order: BTreeMap<u64, String>,
+    /// Use counter that orders `order`.
+    tick: u64,
+    bytes: usize,
+}
+
+#[derive(Debug)]
+struct LruEntry {
+    value: Vec<u8>,
+    /// `monotonic_millis()` reading at which the entry expires.
+    expires_at: Option<u64>,
+    tick: u64,
+}
+
+impl LruState {
+    fn remove(&mut self, key: &str) -> Option<LruEntry> {
+        let entry = self.entries.remove(key)?;
+        self.order.remove(&entry.tick);
+        self.bytes -= entry.value.len();
+        Some(entry)
+    }
+
+    fn touch(&mut self, key: &str) -> Option<&LruEntry> {
+        self.tick += 1;
+        let tick = self.tick;
+        let entry = self.entries.get_mut(key)?;
+        if let Some(key) = self.order.remove(&entry.tick) {
+            self.order.insert(tick, key);
+        }
+        entry.tick = tick;
+        Some(entry)
+    }
+}
+
+impl LruCacheStore {
+    /// Create a store holding at most `max_entries` values totalling at most
+    /// `max_bytes`.
+    pub fn new(max_entries: usize, max_bytes: usize) -> Self {
+        Self {
+            max_entries,
+            max_bytes,
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    /// Number of stored entries, including expired ones not yet dropped.
+    pub fn len(&self) -> usize {
+        self.state().entries.len()
+    }
+
+    /// Whether the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Total size of the stored values, in bytes.
+    pub fn size_bytes(&self) -> usize {
+        self.state().bytes
+    }
+
+    /// Drop every entry.
+    pub fn clear(&self) {
+        *self.state() = LruState::default();
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, LruState> {
+        // Plain bookkeeping; a panic mid-update leaves at worst a stale
+        // entry, which is no reason to stop serving the rest.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn get_now(&self, key: &str, now: u64) -> Option<Vec<u8>> {
+        let mut state = self.state();
+        let expired = state
+            .entries
+            .get(key)?
+            .expires_at
+            .is_some_and(|at| now >= at);
+        if expired {
+            state.remove(key);
+            return None;
+        }
+        state.touch(key).map(|entry| entry.value.clone())
+    }
+
+    fn put_now(&self, key: &str, value: Vec<u8>, ttl: Option<Duration>, now: u64) {
+        let mut state = self.state();
+        state.remove(key);
+        if self.max_entries == 0 || value.len() > self.max_bytes {
+            return;
+        }
+
+        state.tick += 1;
+        let tick = state.tick;
+        state.bytes += value.len();
+        state.order.insert(tick, key.to_string());
+        state.entries.insert(
+            key.to_string(),
+            LruEntry {
+                value,
+                expires_at: ttl.map(|ttl| now.saturating_add(ttl.as_millis() as u64)),
+                tick,
+            },
+        );
+
+        while state.entries.len() > self.max_entries || state.bytes > self.max_bytes {
+            let Some((_, oldest)) = state.order.pop_first() else {
+                break;
+            };
+            if let Some(entry) = state.entries.remove(&oldest) {
+                state.bytes -= entry.value.len();
+            }
+        }
+    }
+}
+
+impl Default for LruCacheStore {
+    /// A store with [`CacheConfig`]'s default limits.
+    fn default() -> Self {
+        Self::new(
+            CacheConfig::DEFAULT_MAX_ENTRIES,
+            CacheConfig::DEFAULT_MAX_BYTES,
+        )
+    }
+}
+
+impl CacheStore for LruCacheStore {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<Vec<u8>>> {
+        let value = self.get_now(key, monotonic_millis());
+        Box::pin(async move { value })
+    }
+
+    fn put<'a>(&'a self, key: &'a str, value: Vec<u8>, ttl: Option<Duration>) -> BoxFuture<'a, ()> {
+        self.put_now(key, value, ttl, monotonic_millis());
+        Box::pin(async {})
+    }
+}
+
+/// How long a request's result stays valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lifetime {
+    /// Pinned to a block, hash or final transaction: never changes.
+    Immutable,
+    /// Pinned to a finality: changes with every block.
+    Finality,
+}
+
+/// The cache entry a cacheable request maps to.
+#[derive(Debug)]
+pub(crate) struct CacheSlot {
+    key: String,
+    ttl: Option<Duration>,
+    /// `tx_status` results are only kept once the transaction is final.
+    require_final: bool,
+}
+
+/// The cache in front of an [`RpcClient`](super::RpcClient).
+pub(crate) struct ResponseCache {
+    store: Arc<dyn CacheStore>,
+    finality_ttl: Duration,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(config: CacheConfig) -> Self {
+        let store = config
+            .store
+            .unwrap_or_else(|| Arc::new(LruCacheStore::new(config.max_entries, config.max_bytes)));
+        Self {
+            store,
+            finality_ttl: config.finality_ttl,
+        }
+    }
+
+    /// The slot for `method` with `params` sent to `url`, or `None` if the
+    /// result must not be cached.
+    pub(crate) fn slot(
+        &self,
+        url: &str,
+        method: &str,
+        params: &impl Serialize,
+    ) -> Option<CacheSlot> {
+        let params = serde_json::to_value(params).ok()?;
+        let ttl = match lifetime(method, &params)? {
+            Lifetime::Immutable => None,
+            Lifetime::Finality if self.finality_ttl.is_zero() => return None,
+            Lifetime::Finality => Some(self.finality_ttl),
+        };
+        let key = format!("{url}\n{method}\n{params}");
+        Some(CacheSlot {
+            key: CryptoHash::hash(key.as_bytes()).to_string(),
+            ttl,
+            require_final: method == "EXPERIMENTAL_tx_status",
+        })
+    }
+
+    /// The cached result for `slot`, if any.
+    pub(crate) async fn get(&self, slot: &CacheSlot) -> Option<Value> {
+        let bytes = self.store.get(&slot.key).await?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Cache `result` in `slot`.
+    pub(crate) async fn put(&self, slot: &CacheSlot, result: &Value) {
+        if slot.require_final && result["final_execution_status"] != "FINAL" {
+            return;
+        }
+        if let Ok(bytes) = serde_json::to_vec(result) {
+            self.store.put(&slot.key, bytes, slot.ttl).await;
+        }
+    }
+}
+
+/// Classify a request by what its result is pinned to.
+fn lifetime(method: &str, params: &Value) -> Option<Lifetime> {
+    match method {
+        "query"
+        | "EXPERIMENTAL_view_account"
+        | "EXPERIMENTAL_view_access_key"
+        | "EXPERIMENTAL_call_function"
+        | "block"
+        | "chunk"
+        | "block_effects"
+        | "EXPERIMENTAL_changes_in_block"
+        | "EXPERIMENTAL_changes"
+        | "EXPERIMENTAL_protocol_config"
+        | "EXPERIMENTAL_congestion_level" => {
+            if params.get("block_id").is_some() || params.get("chunk_id").is_some() {
+                Some(Lifetime::Immutable)
+            } else if params.get("finality").is_some() {
+                Some(Lifetime::Finality)
+            } else {
+                // `sync_checkpoint: earliest_available` moves with garbage
+                // collection.
+                None
+            }
+        }
+        // Positional `[block_id]`, or `[null]` for the latest block.
+        "gas_price" | "validators" => match params.get(0)? {
+            Value::Null => Some(Lifetime::Finality),
+            _ => Some(Lifetime::Immutable),
+        },
+        "EXPERIMENTAL_tx_status" if params["wait_until"] == "FINAL" => Some(Lifetime::Immutable),
+        "EXPERIMENTAL_receipt_to_tx" | "genesis_config" => Some(Lifetime::Immutable),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::testing::{MockResponse, MockTransport};
+    use crate::types::{BlockReference, NearToken};
+
+    #[test]
+    fn test_lifetime_follows_block_pinning() {
+        let at_height = json!({ "account_id": "alice.near", "block_id": 100 });
+        let at_final = json!({ "account_id": "alice.near", "finality": "final" });
+        assert_eq!(
+            lifetime("EXPERIMENTAL_view_account", &at_height),
+            Some(Lifetime::Immutable)
+        );
+        assert_eq!(
+            lifetime("EXPERIMENTAL_view_account", &at_final),
+            Some(Lifetime::Finality)
+        );
+        assert_eq!(
+            lifetime("query", &json!({ "sync_checkpoint": "earliest_available" })),
+            None
+        );
+        assert_eq!(
+            lifetime("chunk", &json!({ "chunk_id": "abc" })),
+            Some(Lifetime::Immutable)
+        );
+        assert_eq!(
+            lifetime("gas_price", &json!([null])),
+            Some(Lifetime::Finality)
+        );
+        assert_eq!(
+            lifetime("gas_price", &json!(["hash"])),
+            Some(Lifetime::Immutable)
+        );
+
+        let tx =
+            |wait: &str| json!({ "tx_hash": "h", "sender_account_id": "a", "wait_until": wait });
+        assert_eq!(
+            lifetime("EXPERIMENTAL_tx_status", &tx("FINAL")),
+            Some(Lifetime::Immutable)
+        );
+        assert_eq!(lifetime("EXPERIMENTAL_tx_status", &tx("EXECUTED")), None);
+        assert_eq!(lifetime("send_tx", &json!({ "wait_until": "FINAL" })), None);
+        assert_eq!(lifetime("status", &json!([])), None);
+    }
+
+    #[test]
+    fn test_lru_evicts_by_entries_and_bytes() {
+        let store = LruCacheStore::new(2, 10);
+        store.put_now("a", vec![0; 4], None, 0);
+        store.put_now("b", vec![0; 4], None, 0);
+        // Reading `a` makes `b` the least recently used.
+        assert!(store.get_now("a", 0).is_some());
+        store.put_now("c", vec![0; 4], None, 0);
+        assert!(store.get_now("b", 0).is_none());
+        assert_eq!(store.len(), 2);
+
+        // Over the byte limit: evicts until it fits.
+        store.put_now("d", vec![0; 8], None, 0);
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.size_bytes(), 8);
+        // Larger than the whole store: not kept.
+        store.put_now("e", vec![0; 11], None, 0);
+        assert!(store.get_now("e", 0).is_none());
+        assert!(store.get_now("d", 0).is_some());
+
+        store.clear();
+        assert!(store.is_empty());
+        assert_eq!(store.size_bytes(), 0);
+    }
+
+    #[test]
+    fn test_lru_expires_entries() {
+        let store = LruCacheStore::new(10, 100);
+        store.put_now("a", vec![1], Some(Duration::from_millis(500)), 1_000);
+        store.put_now("b", vec![2], None, 1_000);
+        assert_eq!(store.get_now("a", 1_499), Some(vec![1]));
+        assert_eq!(store.get_now("a", 1_500), None);
+        assert_eq!(store.get_now("b", u64::MAX), Some(vec![2]));
+        assert_eq!(store.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_client_serves_pinned_queries_from_cache() {
+        let mock = MockTransport::new();
+        mock.on("EXPERIMENTAL_view_account")
+            .respond(MockResponse::account(NearToken::from_near(5)));
+        mock.on("EXPERIMENTAL_tx_status")
+            .respond(MockResponse::result(
+                json!({ "final_execution_status": "INCLUDED" }),
+            ));
+        let near = mock
+            .near()
+            .cache(CacheConfig::new().finality_ttl(Duration::from_secs(60)))
+            .build();
+        let rpc = near.rpc();
+        let alice = "alice.testnet".parse().unwrap();
+
+        for _ in 0..3 {
+            rpc.view_account(&alice, BlockReference::at_height(100))
+                .await
+                .unwrap();
+        }
+        assert_eq!(mock.requests_for("EXPERIMENTAL_view_account").len(), 1);
+
+        // Finality-pinned: a separate entry, reused within the TTL.
+        rpc.view_account(&alice, BlockReference::final_())
+            .await
+            .unwrap();
+        rpc.view_account(&alice, BlockReference::final_())
+            .await
+            .unwrap();
+        assert_eq!(mock.requests_for("EXPERIMENTAL_view_account").len(), 2);
+
+        // A `FINAL` wait that came back before finality is not kept.
+        let hash = CryptoHash::hash(b"tx");
+        for _ in 0..2 {
+            let _ = rpc
+                .tx_status(&hash, &alice, crate::types::TxExecutionStatus::Final)
+                .await;
+        }
+        assert_eq!(mock.requests_for("EXPERIMENTAL_tx_status").len(), 2);
+    }
+}
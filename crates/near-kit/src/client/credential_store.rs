@@ -0,0 +1,577 @@
+//! Writable credential storage.
+//!
+//! [`FileSigner`](crate::FileSigner) and [`KeyringSigner`](crate::KeyringSigner)
+//! only read keys. A [`CredentialStore`] also saves, lists and removes them, in
+//! the formats near-cli uses, so keys created with
+//! [`KeyPair::random`](crate::KeyPair::random) can be persisted without shelling
+//! out:
+//!
+//! - [`FileCredentialStore`] — `~/.near-credentials` (requires `file-signer`)
+//! - [`KeyringCredentialStore`](crate::KeyringCredentialStore) — the system
+//!   keyring (requires `keyring`)
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "file-signer")]
+//! # fn example() -> Result<(), near_kit::Error> {
+//! use near_kit::{AccountId, CredentialStore, FileCredentialStore, InMemorySigner, KeyPair};
+//!
+//! let store = FileCredentialStore::new()?;
+//! let account_id: AccountId = "bot-7.provisioner.testnet".parse()?;
+//!
+//! let key = KeyPair::random();
+//! // ... create the account with `key.public_key` ...
+//! store.save("testnet", &account_id, &key.secret_key)?;
+//!
+//! for public_key in store.list_keys("testnet", &account_id)? {
+//!     let secret_key = store.load("testnet", &account_id, &public_key)?;
+//!     let _signer = InMemorySigner::from_secret_key(account_id.clone(), secret_key)?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "file-signer")]
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+#[cfg(feature = "file-signer")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+#[cfg(feature = "file-signer")]
+use crate::error::KeyStoreError;
+use crate::platform::{MaybeSend, MaybeSync};
+use crate::types::{AccountId, PublicKey, SecretKey};
+
+/// Somewhere keys can be saved to, listed, loaded from and removed from,
+/// grouped by network and account.
+///
+/// Every method is blocking: the built-in stores touch the filesystem or the
+/// OS keyring, which is what [`FileSigner`](crate::FileSigner) and
+/// [`KeyringSigner`](crate::KeyringSigner) do at construction as well.
+pub trait CredentialStore: MaybeSend + MaybeSync {
+    /// Store `secret_key` as a key of `account_id`. Saving a key that is
+    /// already stored overwrites it.
+    fn save(
+        &self,
+        network: &str,
+        account_id: &AccountId,
+        secret_key: &SecretKey,
+    ) -> Result<(), Error>;
+
+    /// Load the secret key for `public_key`.
+    ///
+    /// Fails with [`KeyStoreError::KeyNotFound`](crate::error::KeyStoreError::KeyNotFound)
+    /// if it isn't stored.
+    fn load(
+        &self,
+        network: &str,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> Result<SecretKey, Error>;
+
+    /// Accounts with at least one stored key on `network`, sorted.
+    fn list_accounts(&self, network: &str) -> Result<Vec<AccountId>, Error>;
+
+    /// Public keys stored for `account_id` on `network`.
+    fn list_keys(&self, network: &str, account_id: &AccountId) -> Result<Vec<PublicKey>, Error>;
+
+    /// Delete the key for `public_key`.
+    ///
+    /// Fails with [`KeyStoreError::KeyNotFound`](crate::error::KeyStoreError::KeyNotFound)
+    /// if it isn't stored.
+    fn remove(
+        &self,
+        network: &str,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> Result<(), Error>;
+}
+
+/// Forwarding impl so an `Arc`-shared store (including
+/// `Arc<dyn CredentialStore>`) can be handed around a provisioning tool.
+impl<T: CredentialStore + ?Sized> CredentialStore for Arc<T> {
+    fn save(
+        &self,
+        network: &str,
+        account_id: &AccountId,
+        secret_key: &SecretKey,
+    ) -> Result<(), Error> {
+        (**self).save(network, account_id, secret_key)
+    }
+
+    fn load(
+        &self,
+        network: &str,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> Result<SecretKey, Error> {
+        (**self).load(network, account_id, public_key)
+    }
+
+    fn list_accounts(&self, network: &str) -> Result<Vec<AccountId>, Error> {
+        (**self).list_accounts(network)
+    }
+
+    fn list_keys(&self, network: &str, account_id: &AccountId) -> Result<Vec<PublicKey>, Error> {
+        (**self).list_keys(network, account_id)
+    }
+
+    fn remove(
+        &self,
+        network: &str,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> Result<(), Error> {
+        (**self).remove(network, account_id, public_key)
+    }
+}
+
+// ============================================================================
+// FileCredentialStore
+// ============================================================================
+
+/// Credential files under `~/.near-credentials`, in both layouts near-cli
+/// writes:
+///
+/// - `{network}/{account_id}.json` — one key per account, the file
+///   [`FileSigner`](crate::FileSigner) reads
+/// - `{network}/{account_id}/{public_key}.json` — one file per key, as
+///   near-cli-rs writes them (with the `:` in the key replaced by `_`)
+///
+/// [`save`](CredentialStore::save) writes the per-key file and, when the
+/// account has no single-key file yet, that one too, so the first key saved
+/// for an account is also the one `FileSigner` picks up. When
+/// [`remove`](CredentialStore::remove) deletes the key in the single-key file,
+/// the most recently saved remaining key takes its place, so `FileSigner`
+/// follows a [`rotate_key`](crate::Near::rotate_key). Listing and loading
+/// read both layouts. Files are written with owner-only permissions on Unix.
+///
+/// Requires the `file-signer` feature.
+#[cfg(feature = "file-signer")]
+#[derive(Clone, Debug)]
+pub struct FileCredentialStore {
+    root: PathBuf,
+}
+
+/// A near-cli credential file.
+#[cfg(feature = "file-signer")]
+#[derive(Serialize, Deserialize)]
+struct CredentialFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    account_id: Option<AccountId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    public_key: Option<PublicKey>,
+    #[serde(default, alias = "secret_key", skip_serializing_if = "Option::is_none")]
+    private_key: Option<String>,
+}
+
+#[cfg(feature = "file-signer")]
+impl CredentialFile {
+    fn read(path: &Path) -> Result<Option<Self>, Error> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).map(Some).map_err(|e| {
+                KeyStoreError::InvalidFormat(format!("{}: {}", path.display(), e)).into()
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(KeyStoreError::Io(e).into()),
+        }
+    }
+
+    fn secret_key(&self, path: &Path) -> Result<SecretKey, Error> {
+        let private_key = self.private_key.as_deref().ok_or_else(|| {
+            KeyStoreError::InvalidFormat(format!(
+                "Missing 'private_key' field in {}",
+                path.display()
+            ))
+        })?;
+        Ok(private_key.parse().map_err(KeyStoreError::InvalidKey)?)
+    }
+
+    /// The file's public key: derived from the private key when there is one,
+    /// otherwise the `public_key` field (encrypted keystore files carry only
+    /// that).
+    fn public_key(&self, path: &Path) -> Result<PublicKey, Error> {
+        match (&self.private_key, &self.public_key) {
+            (None, Some(public_key)) => Ok(public_key.clone()),
+            _ => Ok(self.secret_key(path)?.public_key()),
+        }
+    }
+}
+
+#[cfg(feature = "file-signer")]
+impl FileCredentialStore {
+    /// Open the store at `~/.near-credentials`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the home directory cannot be determined.
+    pub fn new() -> Result<Self, Error> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| Error::Config("Could not determine home directory".to_string()))?;
+        Ok(Self::at(home.join(".near-credentials")))
+    }
+
+    /// Open the store rooted at `root`, which holds one directory per network.
+    pub fn at(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn account_file(&self, network: &str, account_id: &AccountId) -> PathBuf {
+        self.root.join(network).join(format!("{}.json", account_id))
+    }
+
+    fn key_dir(&self, network: &str, account_id: &AccountId) -> PathBuf {
+        self.root.join(network).join(account_id.as_str())
+    }
+
+    fn key_file(&self, network: &str, account_id: &AccountId, public_key: &PublicKey) -> PathBuf {
+        self.key_dir(network, account_id)
+            .join(format!("{}.json", public_key.to_string().replace(':', "_")))
+    }
+
+    /// The most recently written per-key file that holds a private key.
+    fn newest_key_file(
+        &self,
+        network: &str,
+        account_id: &AccountId,
+    ) -> Result<Option<PathBuf>, Error> {
+        let entries = match std::fs::read_dir(self.key_dir(network, account_id)) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(KeyStoreError::Io(e).into()),
+        };
+        let mut newest = None;
+        for entry in entries {
+            let entry = entry.map_err(KeyStoreError::from)?;
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            if CredentialFile::read(&path)?.is_none_or(|file| file.private_key.is_none()) {
+                continue;
+            }
+            let modified = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .map_err(KeyStoreError::from)?;
+            if newest.as_ref().is_none_or(|(time, _)| modified >= *time) {
+                newest = Some((modified, path));
+            }
+        }
+        Ok(newest.map(|(_, path)| path))
+    }
+}
+
+#[cfg(feature = "file-signer")]
+impl CredentialStore for FileCredentialStore {
+    fn save(
+        &self,
+        network: &str,
+        account_id: &AccountId,
+        secret_key: &SecretKey,
+    ) -> Result<(), Error> {
+        let public_key = secret_key.public_key();
+        let content = serde_json::to_string_pretty(&CredentialFile {
+            account_id: Some(account_id.clone()),
+            public_key: Some(public_key.clone()),
+            private_key: Some(secret_key.to_string()),
+        })
+        .map_err(KeyStoreError::from)?;
+
+        write_private(
+            &self.key_file(network, account_id, &public_key),
+            content.as_bytes(),
+        )?;
+        let account_file = self.account_file(network, account_id);
+        if !account_file.exists() {
+            write_private(&account_file, content.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn load(
+        &self,
+        network: &str,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> Result<SecretKey, Error> {
+        let key_file = self.key_file(network, account_id, public_key);
+        let account_file = self.account_file(network, account_id);
+        for path in [key_file, account_file] {
+            if let Some(file) = CredentialFile::read(&path)?
+                && file.private_key.is_some()
+            {
+                let secret_key = file.secret_key(&path)?;
+                if &secret_key.public_key() == public_key {
+                    return Ok(secret_key);
+                }
+            }
+        }
+        Err(KeyStoreError::KeyNotFound(account_id.clone()).into())
+    }
+
+    fn list_accounts(&self, network: &str) -> Result<Vec<AccountId>, Error> {
+        let entries = match std::fs::read_dir(self.root.join(network)) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(KeyStoreError::Io(e).into()),
+        };
+        let mut accounts = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(KeyStoreError::from)?.path();
+            let name = if path.is_dir() {
+                path.file_name()
+            } else if path.extension().is_some_and(|ext| ext == "json") {
+                path.file_stem()
+            } else {
+                None
+            };
+            if let Some(Ok(account_id)) = name.and_then(|n| n.to_str()).map(str::parse::<AccountId>)
+                && !accounts.contains(&account_id)
+            {
+                accounts.push(account_id);
+            }
+        }
+        accounts.sort();
+        Ok(accounts)
+    }
+
+    fn list_keys(&self, network: &str, account_id: &AccountId) -> Result<Vec<PublicKey>, Error> {
+        let mut paths = vec![self.account_file(network, account_id)];
+        match std::fs::read_dir(self.key_dir(network, account_id)) {
+            Ok(entries) => {
+                let mut key_files = Vec::new();
+                for entry in entries {
+                    let path = entry.map_err(KeyStoreError::from)?.path();
+                    if path.extension().is_some_and(|ext| ext == "json") {
+                        key_files.push(path);
+                    }
+                }
+                key_files.sort();
+                paths.extend(key_files);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(KeyStoreError::Io(e).into()),
+        }
+
+        let mut keys = Vec::new();
+        for path in paths {
+            if let Some(file) = CredentialFile::read(&path)? {
+                let public_key = file.public_key(&path)?;
+                if !keys.contains(&public_key) {
+                    keys.push(public_key);
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    fn remove(
+        &self,
+        network: &str,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> Result<(), Error> {
+        let mut removed = false;
+        let key_file = self.key_file(network, account_id, public_key);
+        if let Some(file) = CredentialFile::read(&key_file)?
+            && &file.public_key(&key_file)? == public_key
+        {
+            std::fs::remove_file(&key_file).map_err(KeyStoreError::from)?;
+            removed = true;
+        }
+        let account_file = self.account_file(network, account_id);
+        if let Some(file) = CredentialFile::read(&account_file)?
+            && &file.public_key(&account_file)? == public_key
+        {
+            // Hand the single-key file to the newest remaining key, so a
+            // FileSigner for the account keeps working after a rotation.
+            match self.newest_key_file(network, account_id)? {
+                Some(path) => {
+                    let content = std::fs::read(&path).map_err(KeyStoreError::from)?;
+                    write_private(&account_file, &content)?;
+                }
+                None => std::fs::remove_file(&account_file).map_err(KeyStoreError::from)?,
+            }
+            removed = true;
+        }
+        // Tidy up the per-key directory once its last key is gone; a
+        // non-empty directory is left alone.
+        let _ = std::fs::remove_dir(self.key_dir(network, account_id));
+
+        if removed {
+            Ok(())
+        } else {
+            Err(KeyStoreError::KeyNotFound(account_id.clone()).into())
+        }
+    }
+}
+
+/// Write `content` to `path` through a temporary sibling and a rename, with
+/// owner-only permissions on Unix, creating parent directories as needed.
+#[cfg(feature = "file-signer")]
+pub(crate) fn write_private(path: &Path, content: &[u8]) -> Result<(), Error> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| KeyStoreError::PathError(format!("{} has no parent", path.display())))?;
+    std::fs::create_dir_all(dir).map_err(KeyStoreError::from)?;
+
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let write = || -> std::io::Result<()> {
+        use std::io::Write as _;
+        let mut out = options.open(&tmp)?;
+        out.write_all(content)?;
+        out.sync_all()?;
+        std::fs::rename(&tmp, path)
+    };
+    write().map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        KeyStoreError::Io(e).into()
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(all(test, feature = "file-signer"))]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "ed25519:3D4YudUahN1nawWogh8pAKSj92sUNMdbZGjn7kERKzYoTy8tnFQuwoGUC51DowKqorvkr2pytJSnwuSbsNVfqygr";
+    const PUBLIC: &str = "ed25519:6DSjZ8mvsRZDvFqFxo8tCKePG96omXW7eVYVSySmDk8e";
+
+    fn account(id: &str) -> AccountId {
+        id.parse().unwrap()
+    }
+
+    #[test]
+    fn test_save_writes_near_cli_layouts() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileCredentialStore::at(dir.path());
+        let alice = account("alice.testnet");
+        let secret_key: SecretKey = SECRET.parse().unwrap();
+
+        store.save("testnet", &alice, &secret_key).unwrap();
+
+        let key_file = dir.path().join(
+            "testnet/alice.testnet/ed25519_6DSjZ8mvsRZDvFqFxo8tCKePG96omXW7eVYVSySmDk8e.json",
+        );
+        let content: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&key_file).unwrap()).unwrap();
+        assert_eq!(content["account_id"], "alice.testnet");
+        assert_eq!(content["public_key"], PUBLIC);
+        assert_eq!(content["private_key"], secret_key.to_string());
+
+        // The single-key file is what FileSigner reads.
+        let signer = crate::FileSigner::from_file(
+            dir.path().join("testnet/alice.testnet.json"),
+            "alice.testnet",
+        )
+        .unwrap();
+        assert_eq!(signer.public_key().to_string(), PUBLIC);
+    }
+
+    #[test]
+    fn test_multiple_keys_list_load_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileCredentialStore::at(dir.path());
+        let alice = account("alice.testnet");
+        let first: SecretKey = SECRET.parse().unwrap();
+        let second = SecretKey::generate_ed25519();
+
+        store.save("testnet", &alice, &first).unwrap();
+        store.save("testnet", &alice, &second).unwrap();
+        store
+            .save(
+                "testnet",
+                &account("bob.testnet"),
+                &SecretKey::generate_ed25519(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            store.list_accounts("testnet").unwrap(),
+            [account("alice.testnet"), account("bob.testnet")]
+        );
+        assert!(store.list_accounts("mainnet").unwrap().is_empty());
+
+        let keys = store.list_keys("testnet", &alice).unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0], first.public_key());
+        assert!(keys.contains(&second.public_key()));
+
+        let loaded = store.load("testnet", &alice, &second.public_key()).unwrap();
+        assert_eq!(loaded.to_string(), second.to_string());
+
+        // Removing the first key takes its per-key file with it and hands
+        // the single-key file to the remaining key.
+        store
+            .remove("testnet", &alice, &first.public_key())
+            .unwrap();
+        let signer = crate::FileSigner::from_file(
+            dir.path().join("testnet/alice.testnet.json"),
+            "alice.testnet",
+        )
+        .unwrap();
+        assert_eq!(signer.public_key(), &second.public_key());
+        assert_eq!(
+            store.list_keys("testnet", &alice).unwrap(),
+            [second.public_key()]
+        );
+        let err = store
+            .remove("testnet", &alice, &first.public_key())
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::KeyStore(KeyStoreError::KeyNotFound(_))
+        ));
+
+        store
+            .remove("testnet", &alice, &second.public_key())
+            .unwrap();
+        assert!(!dir.path().join("testnet/alice.testnet").exists());
+        assert_eq!(
+            store.list_accounts("testnet").unwrap(),
+            [account("bob.testnet")]
+        );
+    }
+
+    #[test]
+    fn test_reads_legacy_single_key_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("testnet")).unwrap();
+        std::fs::write(
+            dir.path().join("testnet/alice.testnet.json"),
+            format!(r#"{{"private_key":"{SECRET}"}}"#),
+        )
+        .unwrap();
+        let store = FileCredentialStore::at(dir.path());
+        let alice = account("alice.testnet");
+        let public_key: PublicKey = PUBLIC.parse().unwrap();
+
+        assert_eq!(store.list_accounts("testnet").unwrap(), vec![alice.clone()]);
+        assert_eq!(
+            store.list_keys("testnet", &alice).unwrap(),
+            vec![public_key.clone()]
+        );
+        assert_eq!(
+            store
+                .load("testnet", &alice, &public_key)
+                .unwrap()
+                .to_string(),
+            SECRET.parse::<SecretKey>().unwrap().to_string()
+        );
+        let other = SecretKey::generate_ed25519().public_key();
+        assert!(store.load("testnet", &alice, &other).is_err());
+    }
+}
@@ -0,0 +1,760 @@
+//! Passphrase-encrypted credential files.
+//!
+//! [`EncryptedKeystore`] manages keys under the same
+//! `{root}/{network}/{account_id}.json` layout as
+//! [`FileSigner`](crate::FileSigner) (`root` defaults to `~/.near-credentials`),
+//! but the private key is sealed under a key derived from a passphrase, so a
+//! credentials directory can be copied between machines or swept into a backup
+//! without exposing it. [`EncryptedFileSigner`] loads one of those files.
+//!
+//! # File format
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "account_id": "alice.near",
+//!   "public_key": "ed25519:…",
+//!   "crypto": {
+//!     "kdf": "argon2id",
+//!     "kdf_params": { "memory_kib": 65536, "iterations": 3, "parallelism": 1, "salt": "…base64…" },
+//!     "cipher": "xchacha20poly1305",
+//!     "nonce": "…base64…",
+//!     "ciphertext": "…base64…"
+//!   }
+//! }
+//! ```
+//!
+//! The ciphertext is the `ed25519:…` secret key string, authenticated together
+//! with the account ID and public key: editing either field makes decryption
+//! fail instead of handing back a key for the wrong account. The account ID and
+//! public key stay readable, so [`EncryptedKeystore::list`] works without the
+//! passphrase. Files asking for more than 1 GiB of memory, 10 iterations or 16
+//! lanes are rejected before the key derivation runs.
+//!
+//! Requires the `encrypted-keystore` feature.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use near_kit::{EncryptedKeystore, Signer};
+//!
+//! # fn example() -> Result<(), near_kit::Error> {
+//! let keystore = EncryptedKeystore::new()?;
+//! let passphrase = std::env::var("NEAR_KEYSTORE_PASSPHRASE").unwrap();
+//!
+//! // Encrypt the plaintext key near-cli left behind, in place
+//! keystore.encrypt_existing("mainnet", "treasury.near", &passphrase)?;
+//!
+//! let signer = keystore.signer("mainnet", "treasury.near", &passphrase)?;
+//! assert_eq!(signer.account_id().as_str(), "treasury.near");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use chacha20poly1305::aead::{Aead, Payload};
+use chacha20poly1305::{KeyInit, XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+
+use super::credential_store::write_private;
+use super::signer::{InMemorySigner, Signer, SigningKey};
+use crate::error::{Error, KeyStoreError};
+use crate::types::csprng::fill_random;
+use crate::types::{AccountId, PublicKey, SecretKey, TryIntoAccountId};
+
+const FORMAT_VERSION: u32 = 1;
+const KDF: &str = "argon2id";
+const CIPHER: &str = "xchacha20poly1305";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// Argon2id cost parameters written into new files.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+struct KdfCost {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+impl KdfCost {
+    /// The most a file may ask of [`open`]: 1 GiB, ten passes, sixteen lanes.
+    /// The cost comes from the file itself, so without a bound a crafted file
+    /// could spend gigabytes or hours before the AEAD tag is even checked.
+    const MAX: Self = Self {
+        memory_kib: 1024 * 1024,
+        iterations: 10,
+        parallelism: 16,
+    };
+
+    fn check(&self) -> Result<(), Error> {
+        let max = Self::MAX;
+        if self.memory_kib > max.memory_kib
+            || self.iterations > max.iterations
+            || self.parallelism > max.parallelism
+        {
+            return Err(KeyStoreError::InvalidFormat(format!(
+                "{KDF} cost of {} KiB, {} iterations, {} lanes exceeds the maximum of \
+                 {} KiB, {} iterations, {} lanes",
+                self.memory_kib,
+                self.iterations,
+                self.parallelism,
+                max.memory_kib,
+                max.iterations,
+                max.parallelism
+            ))
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl Default for KdfCost {
+    /// RFC 9106's second recommended option: 64 MiB, three passes.
+    fn default() -> Self {
+        Self {
+            memory_kib: 64 * 1024,
+            iterations: 3,
+            parallelism: 1,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreFile {
+    version: u32,
+    account_id: AccountId,
+    public_key: PublicKey,
+    crypto: CryptoSection,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CryptoSection {
+    kdf: String,
+    kdf_params: KdfParams,
+    cipher: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KdfParams {
+    #[serde(flatten)]
+    cost: KdfCost,
+    salt: String,
+}
+
+/// A directory of passphrase-encrypted credential files.
+///
+/// See the [module docs](self) for the layout and file format. Files are
+/// written with owner-only permissions on Unix, and replaced atomically, so an
+/// interrupted [`change_passphrase`](Self::change_passphrase) leaves either the
+/// old file or the new one.
+#[derive(Clone, Debug)]
+pub struct EncryptedKeystore {
+    root: PathBuf,
+    cost: KdfCost,
+}
+
+impl EncryptedKeystore {
+    /// Open the keystore at `~/.near-credentials`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the home directory cannot be determined.
+    pub fn new() -> Result<Self, Error> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| Error::Config("Could not determine home directory".to_string()))?;
+        Ok(Self::at(home.join(".near-credentials")))
+    }
+
+    /// Open the keystore rooted at `root`, which holds one directory per
+    /// network.
+    pub fn at(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            cost: KdfCost::default(),
+        }
+    }
+
+    /// The file holding the key for `account_id` on `network`.
+    pub fn path(&self, network: impl AsRef<str>, account_id: &AccountId) -> PathBuf {
+        self.root
+            .join(network.as_ref())
+            .join(format!("{}.json", account_id))
+    }
+
+    /// Generate a new Ed25519 key for `account_id`, store it encrypted under
+    /// `passphrase`, and return its public key.
+    ///
+    /// Fails if a credential file for the account already exists.
+    pub fn create(
+        &self,
+        network: impl AsRef<str>,
+        account_id: impl TryIntoAccountId,
+        passphrase: impl AsRef<[u8]>,
+    ) -> Result<PublicKey, Error> {
+        self.import(
+            network,
+            account_id,
+            &SecretKey::generate_ed25519(),
+            passphrase,
+        )
+    }
+
+    /// Store an existing key for `account_id`, encrypted under `passphrase`,
+    /// and return its public key.
+    ///
+    /// Fails if a credential file for the account already exists; use
+    /// [`encrypt_existing`](Self::encrypt_existing) to convert a plaintext one.
+    pub fn import(
+        &self,
+        network: impl AsRef<str>,
+        account_id: impl TryIntoAccountId,
+        secret_key: &SecretKey,
+        passphrase: impl AsRef<[u8]>,
+    ) -> Result<PublicKey, Error> {
+        let account_id = account_id.try_into_account_id()?;
+        let path = self.path(network, &account_id);
+        if path.exists() {
+            return Err(
+                KeyStoreError::PathError(format!("{} already exists", path.display())).into(),
+            );
+        }
+        let file = self.seal(account_id, secret_key, passphrase.as_ref())?;
+        write_file(&path, &file)?;
+        Ok(file.public_key)
+    }
+
+    /// Replace the plaintext credential file for `account_id` (as written by
+    /// near-cli or read by [`FileSigner`](crate::FileSigner)) with an encrypted
+    /// one, and return its public key.
+    pub fn encrypt_existing(
+        &self,
+        network: impl AsRef<str>,
+        account_id: impl TryIntoAccountId,
+        passphrase: impl AsRef<[u8]>,
+    ) -> Result<PublicKey, Error> {
+        let account_id = account_id.try_into_account_id()?;
+        let path = self.path(network, &account_id);
+        let content = read_file(&path, &account_id)?;
+        let value: serde_json::Value =
+            serde_json::from_str(&content).map_err(KeyStoreError::from)?;
+        if value.get("crypto").is_some() {
+            return Err(KeyStoreError::InvalidFormat(format!(
+                "{} is already encrypted",
+                path.display()
+            ))
+            .into());
+        }
+        let secret_key: SecretKey = value
+            .get("private_key")
+            .or_else(|| value.get("secret_key"))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                KeyStoreError::InvalidFormat(format!(
+                    "Missing 'private_key' field in {}",
+                    path.display()
+                ))
+            })?
+            .parse()
+            .map_err(KeyStoreError::InvalidKey)?;
+        let file = self.seal(account_id, &secret_key, passphrase.as_ref())?;
+        write_file(&path, &file)?;
+        Ok(file.public_key)
+    }
+
+    /// Decrypt and return the key stored for `account_id`.
+    pub fn export(
+        &self,
+        network: impl AsRef<str>,
+        account_id: impl TryIntoAccountId,
+        passphrase: impl AsRef<[u8]>,
+    ) -> Result<SecretKey, Error> {
+        let account_id = account_id.try_into_account_id()?;
+        let path = self.path(network, &account_id);
+        let file = load(&path, &account_id)?;
+        open(&file, passphrase.as_ref())
+    }
+
+    /// Re-encrypt the key stored for `account_id` under `new_passphrase`, with
+    /// a fresh salt and nonce.
+    pub fn change_passphrase(
+        &self,
+        network: impl AsRef<str>,
+        account_id: impl TryIntoAccountId,
+        old_passphrase: impl AsRef<[u8]>,
+        new_passphrase: impl AsRef<[u8]>,
+    ) -> Result<(), Error> {
+        let account_id = account_id.try_into_account_id()?;
+        let path = self.path(network, &account_id);
+        let secret_key = open(&load(&path, &account_id)?, old_passphrase.as_ref())?;
+        let file = self.seal(account_id, &secret_key, new_passphrase.as_ref())?;
+        write_file(&path, &file)
+    }
+
+    /// Accounts with an encrypted key on `network`, sorted.
+    ///
+    /// Plaintext credential files in the same directory are skipped. A
+    /// network with no directory yet has no accounts.
+    pub fn list(&self, network: impl AsRef<str>) -> Result<Vec<AccountId>, Error> {
+        let dir = self.root.join(network.as_ref());
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(KeyStoreError::Io(e).into()),
+        };
+        let mut accounts = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(KeyStoreError::from)?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            if let Ok(file) = serde_json::from_str::<KeystoreFile>(&content) {
+                accounts.push(file.account_id);
+            }
+        }
+        accounts.sort();
+        Ok(accounts)
+    }
+
+    /// Decrypt the key for `account_id` into a signer.
+    pub fn signer(
+        &self,
+        network: impl AsRef<str>,
+        account_id: impl TryIntoAccountId,
+        passphrase: impl AsRef<[u8]>,
+    ) -> Result<EncryptedFileSigner, Error> {
+        let account_id = account_id.try_into_account_id()?;
+        let path = self.path(network, &account_id);
+        EncryptedFileSigner::from_file(path, account_id, passphrase)
+    }
+
+    fn seal(
+        &self,
+        account_id: AccountId,
+        secret_key: &SecretKey,
+        passphrase: &[u8],
+    ) -> Result<KeystoreFile, Error> {
+        let public_key = secret_key.public_key();
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        fill_random(&mut salt);
+        fill_random(&mut nonce);
+
+        let cipher = cipher(passphrase, &salt, self.cost)?;
+        let plaintext = secret_key.to_string();
+        let aad = associated_data(&account_id, &public_key);
+        let ciphertext = cipher
+            .encrypt(
+                &XNonce::from(nonce),
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| KeyStoreError::InvalidFormat("encryption failed".to_string()))?;
+
+        Ok(KeystoreFile {
+            version: FORMAT_VERSION,
+            account_id,
+            public_key,
+            crypto: CryptoSection {
+                kdf: KDF.to_string(),
+                kdf_params: KdfParams {
+                    cost: self.cost,
+                    salt: STANDARD.encode(salt),
+                },
+                cipher: CIPHER.to_string(),
+                nonce: STANDARD.encode(nonce),
+                ciphertext: STANDARD.encode(ciphertext),
+            },
+        })
+    }
+}
+
+/// A signer whose key is loaded from a passphrase-encrypted credential file.
+///
+/// The key is decrypted once, at construction, and held in memory from then
+/// on like an [`InMemorySigner`]. See [`EncryptedKeystore`] for creating and
+/// managing the files.
+///
+/// Requires the `encrypted-keystore` feature.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use near_kit::EncryptedFileSigner;
+///
+/// let passphrase = std::env::var("NEAR_KEYSTORE_PASSPHRASE").unwrap();
+/// // Load from ~/.near-credentials/testnet/alice.testnet.json
+/// let signer = EncryptedFileSigner::new("testnet", "alice.testnet", passphrase)?;
+/// # Ok::<(), near_kit::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct EncryptedFileSigner {
+    inner: InMemorySigner,
+}
+
+impl EncryptedFileSigner {
+    /// Load and decrypt the key for an account from the standard NEAR
+    /// credentials directory, `~/.near-credentials/{network}/{account_id}.json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The home directory cannot be determined
+    /// - The credentials file doesn't exist or isn't an encrypted keystore file
+    /// - The passphrase is wrong or the file has been tampered with
+    pub fn new(
+        network: impl AsRef<str>,
+        account_id: impl TryIntoAccountId,
+        passphrase: impl AsRef<[u8]>,
+    ) -> Result<Self, Error> {
+        EncryptedKeystore::new()?.signer(network, account_id, passphrase)
+    }
+
+    /// Load and decrypt the key in a specific keystore file.
+    ///
+    /// The file must belong to `account_id`.
+    pub fn from_file(
+        path: impl AsRef<Path>,
+        account_id: impl TryIntoAccountId,
+        passphrase: impl AsRef<[u8]>,
+    ) -> Result<Self, Error> {
+        let account_id = account_id.try_into_account_id()?;
+        let file = load(path.as_ref(), &account_id)?;
+        let secret_key = open(&file, passphrase.as_ref())?;
+        let inner = InMemorySigner::from_secret_key(account_id, secret_key)?;
+        Ok(Self { inner })
+    }
+
+    /// Get the public key.
+    pub fn public_key(&self) -> &PublicKey {
+        self.inner.public_key()
+    }
+
+    /// Unwrap into the underlying [`InMemorySigner`].
+    pub fn into_inner(self) -> InMemorySigner {
+        self.inner
+    }
+}
+
+impl std::fmt::Debug for EncryptedFileSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptedFileSigner")
+            .field("account_id", self.inner.account_id())
+            .field("public_key", self.inner.public_key())
+            .finish()
+    }
+}
+
+impl Signer for EncryptedFileSigner {
+    fn account_id(&self) -> &AccountId {
+        self.inner.account_id()
+    }
+
+    fn key(&self) -> SigningKey {
+        self.inner.key()
+    }
+}
+
+// ============================================================================
+// File handling
+// ============================================================================
+
+fn read_file(path: &Path, account_id: &AccountId) -> Result<String, Error> {
+    std::fs::read_to_string(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => KeyStoreError::KeyNotFound(account_id.clone()).into(),
+        _ => KeyStoreError::Io(e).into(),
+    })
+}
+
+/// Read an encrypted keystore file and check it belongs to `account_id`.
+fn load(path: &Path, account_id: &AccountId) -> Result<KeystoreFile, Error> {
+    let content = read_file(path, account_id)?;
+    let value: serde_json::Value = serde_json::from_str(&content).map_err(KeyStoreError::from)?;
+    if value.get("crypto").is_none() && value.get("private_key").is_some() {
+        return Err(KeyStoreError::InvalidFormat(format!(
+            "{} holds a plaintext key; encrypt it with EncryptedKeystore::encrypt_existing",
+            path.display()
+        ))
+        .into());
+    }
+    let file: KeystoreFile = serde_json::from_value(value)
+        .map_err(|e| KeyStoreError::InvalidFormat(format!("{}: {}", path.display(), e)))?;
+    if file.version != FORMAT_VERSION {
+        return Err(KeyStoreError::InvalidFormat(format!(
+            "{}: unsupported keystore version {}",
+            path.display(),
+            file.version
+        ))
+        .into());
+    }
+    if &file.account_id != account_id {
+        return Err(KeyStoreError::InvalidFormat(format!(
+            "{} holds a key for {}, not {}",
+            path.display(),
+            file.account_id,
+            account_id
+        ))
+        .into());
+    }
+    Ok(file)
+}
+
+fn write_file(path: &Path, file: &KeystoreFile) -> Result<(), Error> {
+    let content = serde_json::to_string_pretty(file).map_err(KeyStoreError::from)?;
+    write_private(path, content.as_bytes())
+}
+
+// ============================================================================
+// Cryptography
+// ============================================================================
+
+/// The account ID and public key, bound to the ciphertext as AEAD associated
+/// data.
+fn associated_data(account_id: &AccountId, public_key: &PublicKey) -> Vec<u8> {
+    format!("{}\n{}", account_id, public_key).into_bytes()
+}
+
+fn cipher(passphrase: &[u8], salt: &[u8], cost: KdfCost) -> Result<XChaCha20Poly1305, Error> {
+    let params = Params::new(cost.memory_kib, cost.iterations, cost.parallelism, Some(32))
+        .map_err(|e| KeyStoreError::InvalidFormat(format!("invalid {KDF} parameters: {e}")))?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase, salt, &mut key)
+        .map_err(|e| KeyStoreError::InvalidFormat(format!("{KDF} failed: {e}")))?;
+    Ok(XChaCha20Poly1305::new_from_slice(&key).expect("key is 32 bytes"))
+}
+
+fn open(file: &KeystoreFile, passphrase: &[u8]) -> Result<SecretKey, Error> {
+    let crypto = &file.crypto;
+    if crypto.kdf != KDF || crypto.cipher != CIPHER {
+        return Err(KeyStoreError::InvalidFormat(format!(
+            "unsupported keystore algorithms {}/{}",
+            crypto.kdf, crypto.cipher
+        ))
+        .into());
+    }
+    crypto.kdf_params.cost.check()?;
+    let decode = |field: &str, value: &str| {
+        STANDARD
+            .decode(value)
+            .map_err(|e| KeyStoreError::InvalidFormat(format!("invalid {field}: {e}")))
+    };
+    let salt = decode("salt", &crypto.kdf_params.salt)?;
+    let nonce: [u8; NONCE_LEN] = decode("nonce", &crypto.nonce)?
+        .try_into()
+        .map_err(|_| KeyStoreError::InvalidFormat(format!("nonce must be {NONCE_LEN} bytes")))?;
+    let ciphertext = decode("ciphertext", &crypto.ciphertext)?;
+
+    let cipher = cipher(passphrase, &salt, crypto.kdf_params.cost)?;
+    let aad = associated_data(&file.account_id, &file.public_key);
+    let plaintext = cipher
+        .decrypt(
+            &XNonce::from(nonce),
+            Payload {
+                msg: &ciphertext,
+                aad: &aad,
+            },
+        )
+        .map_err(|_| KeyStoreError::Decryption(file.account_id.clone()))?;
+
+    let secret_key: SecretKey = std::str::from_utf8(&plaintext)
+        .map_err(|_| KeyStoreError::Decryption(file.account_id.clone()))?
+        .parse()
+        .map_err(KeyStoreError::InvalidKey)?;
+    if secret_key.public_key() != file.public_key {
+        return Err(KeyStoreError::InvalidFormat(format!(
+            "decrypted key does not match public key {}",
+            file.public_key
+        ))
+        .into());
+    }
+    Ok(secret_key)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A keystore in a temporary directory, with the KDF cost turned down so
+    /// the tests don't spend seconds in Argon2.
+    fn keystore() -> (tempfile::TempDir, EncryptedKeystore) {
+        let dir = tempfile::tempdir().unwrap();
+        let mut keystore = EncryptedKeystore::at(dir.path());
+        keystore.cost = KdfCost {
+            memory_kib: 64,
+            iterations: 1,
+            parallelism: 1,
+        };
+        (dir, keystore)
+    }
+
+    #[test]
+    fn test_create_export_and_sign() {
+        let (_dir, keystore) = keystore();
+        let public_key = keystore
+            .create("testnet", "alice.testnet", "hunter2")
+            .unwrap();
+
+        let path = keystore.path("testnet", &"alice.testnet".parse().unwrap());
+        let content = std::fs::read_to_string(&path).unwrap();
+        let exported = keystore
+            .export("testnet", "alice.testnet", "hunter2")
+            .unwrap();
+        assert_eq!(exported.public_key(), public_key);
+        assert!(!content.contains(&exported.to_string()));
+
+        let signer = keystore
+            .signer("testnet", "alice.testnet", "hunter2")
+            .unwrap();
+        assert_eq!(signer.public_key(), &public_key);
+        assert_eq!(signer.account_id().as_str(), "alice.testnet");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn test_wrong_passphrase_and_tampering() {
+        let (_dir, keystore) = keystore();
+        keystore
+            .create("testnet", "alice.testnet", "hunter2")
+            .unwrap();
+
+        let err = keystore
+            .export("testnet", "alice.testnet", "hunter3")
+            .unwrap_err();
+        assert!(matches!(err, Error::KeyStore(KeyStoreError::Decryption(_))));
+
+        // Relabelling the file for another account breaks the AEAD tag.
+        let path = keystore.path("testnet", &"alice.testnet".parse().unwrap());
+        let bob_path = keystore.path("testnet", &"bob.testnet".parse().unwrap());
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&bob_path, content.replace("alice.testnet", "bob.testnet")).unwrap();
+        let err = keystore
+            .export("testnet", "bob.testnet", "hunter2")
+            .unwrap_err();
+        assert!(matches!(err, Error::KeyStore(KeyStoreError::Decryption(_))));
+
+        let err = keystore
+            .export("testnet", "carol.testnet", "hunter2")
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::KeyStore(KeyStoreError::KeyNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_import_change_passphrase_and_list() {
+        let (_dir, keystore) = keystore();
+        let secret_key = SecretKey::generate_ed25519();
+        keystore
+            .import("mainnet", "treasury.near", &secret_key, "old")
+            .unwrap();
+        keystore.create("mainnet", "ops.near", "other").unwrap();
+        assert!(
+            keystore
+                .import("mainnet", "treasury.near", &secret_key, "old")
+                .is_err()
+        );
+
+        keystore
+            .change_passphrase("mainnet", "treasury.near", "old", "new")
+            .unwrap();
+        assert!(keystore.export("mainnet", "treasury.near", "old").is_err());
+        let exported = keystore.export("mainnet", "treasury.near", "new").unwrap();
+        assert_eq!(exported.to_string(), secret_key.to_string());
+
+        // Plaintext files alongside are not listed.
+        std::fs::write(
+            keystore.root.join("mainnet").join("legacy.near.json"),
+            r#"{"private_key":"ed25519:3D4YudUahN1nawWogh8pAKSj92sUNMdbZGjn7kERKzYoTy8tnFQuwoGUC51DowKqorvkr2pytJSnwuSbsNVfqygr"}"#,
+        )
+        .unwrap();
+        let accounts = keystore.list("mainnet").unwrap();
+        let names: Vec<_> = accounts.iter().map(|a| a.as_str()).collect();
+        assert_eq!(names, ["ops.near", "treasury.near"]);
+        assert!(keystore.list("testnet").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_inflated_kdf_cost_is_rejected_before_the_kdf() {
+        let (_dir, keystore) = keystore();
+        keystore
+            .create("testnet", "alice.testnet", "hunter2")
+            .unwrap();
+        let path = keystore.path("testnet", &"alice.testnet".parse().unwrap());
+        let content = std::fs::read_to_string(&path).unwrap();
+
+        for (field, value) in [
+            ("memory_kib", u32::MAX),
+            ("iterations", u32::MAX),
+            ("parallelism", 17),
+        ] {
+            let mut file: serde_json::Value = serde_json::from_str(&content).unwrap();
+            file["crypto"]["kdf_params"][field] = value.into();
+            std::fs::write(&path, file.to_string()).unwrap();
+
+            let started = std::time::Instant::now();
+            let err = keystore
+                .export("testnet", "alice.testnet", "hunter2")
+                .unwrap_err();
+            assert!(
+                matches!(&err, Error::KeyStore(KeyStoreError::InvalidFormat(message)) if message.contains("exceeds the maximum")),
+                "{field}: {err:?}"
+            );
+            assert!(started.elapsed() < std::time::Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn test_encrypt_existing_plaintext() {
+        let (_dir, keystore) = keystore();
+        let secret = "ed25519:3D4YudUahN1nawWogh8pAKSj92sUNMdbZGjn7kERKzYoTy8tnFQuwoGUC51DowKqorvkr2pytJSnwuSbsNVfqygr";
+        let path = keystore.path("testnet", &"alice.testnet".parse().unwrap());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            format!(r#"{{"account_id":"alice.testnet","private_key":"{secret}"}}"#),
+        )
+        .unwrap();
+
+        let err = EncryptedFileSigner::from_file(&path, "alice.testnet", "pw").unwrap_err();
+        assert!(matches!(
+            err,
+            Error::KeyStore(KeyStoreError::InvalidFormat(_))
+        ));
+
+        keystore
+            .encrypt_existing("testnet", "alice.testnet", "pw")
+            .unwrap();
+        assert!(!std::fs::read_to_string(&path).unwrap().contains(secret));
+        let signer = EncryptedFileSigner::from_file(&path, "alice.testnet", "pw").unwrap();
+        assert_eq!(
+            signer.public_key(),
+            &secret.parse::<SecretKey>().unwrap().public_key()
+        );
+        assert!(
+            keystore
+                .encrypt_existing("testnet", "alice.testnet", "pw")
+                .is_err()
+        );
+    }
+}
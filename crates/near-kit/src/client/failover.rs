@@ -0,0 +1,653 @@
+//! Multi-endpoint failover for the JSON-RPC client.
+//!
+//! [`FailoverTransport`] wraps another [`RpcTransport`] and spreads requests
+//! over several endpoints, with per-endpoint circuit breakers.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::rpc::sanitize_url;
+// Only referenced for `default_transport`, which doesn't exist on WASI builds
+// without the `wasi-http` feature (no built-in transport).
+#[cfg(any(
+    not(all(target_arch = "wasm32", target_os = "wasi")),
+    all(feature = "wasi-http", target_env = "p2")
+))]
+use super::transport;
+use super::transport::{BoxFuture, RpcTransport, TransportResponse};
+use crate::error::RpcError;
+use crate::platform::monotonic_millis;
+use crate::trace;
+
+/// Error causes that mean "this node is behind", not "the request is wrong".
+const LAGGING_NODE_CAUSES: [&str; 3] = ["UNKNOWN_BLOCK", "NO_SYNCED_BLOCKS", "NOT_SYNCED_YET"];
+
+/// Weight of the newest sample in the latency moving average.
+const LATENCY_SMOOTHING: f64 = 0.3;
+
+/// How a [`FailoverTransport`] orders endpoints for each request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EndpointSelection {
+    /// Always try endpoints in the order they were given; later endpoints are
+    /// fallbacks only.
+    #[default]
+    Priority,
+    /// Start each request at the next endpoint in turn, spreading load evenly.
+    RoundRobin,
+    /// Prefer the endpoint with the lowest observed latency. Endpoints that
+    /// have not answered yet are tried first so every endpoint gets measured.
+    LeastLatency,
+}
+
+/// A snapshot of one endpoint's health, from [`FailoverTransport::endpoints`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointStatus {
+    /// The endpoint URL.
+    pub url: String,
+    /// Whether the endpoint is currently eligible for requests (its circuit is
+    /// closed, or its cooldown has elapsed).
+    pub available: bool,
+    /// Failures since the endpoint last answered successfully.
+    pub consecutive_failures: u32,
+    /// Smoothed latency of successful requests, if any have completed.
+    pub latency: Option<Duration>,
+}
+
+/// Mutable health state for one endpoint.
+#[derive(Debug, Default)]
+struct Health {
+    consecutive_failures: u32,
+    /// `monotonic_millis()` reading until which the circuit stays open.
+    open_until: Option<u64>,
+    /// Exponential moving average of successful request latency, in ms.
+    latency_ms: Option<f64>,
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    url: String,
+    health: Mutex<Health>,
+}
+
+impl Endpoint {
+    fn health(&self) -> std::sync::MutexGuard<'_, Health> {
+        // Health is plain counters; a panic while holding the lock cannot
+        // leave it in a state worth refusing to read.
+        self.health.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// An [`RpcTransport`] that fails over between several RPC endpoints.
+///
+/// It wraps another transport (the built-in one by default) and, for every
+/// request:
+///
+/// - picks an endpoint according to its [`EndpointSelection`] strategy,
+/// - moves on to the next endpoint when the current one fails with a
+///   retryable error (connection failure, timeout, HTTP 408/429/5xx),
+/// - redirects the request when a lagging node answers `UNKNOWN_BLOCK`,
+///   `NO_SYNCED_BLOCKS` or `NOT_SYNCED_YET`,
+/// - tracks per-endpoint health with a circuit breaker: after
+///   [`failure_threshold`](Self::failure_threshold) consecutive failures an
+///   endpoint is skipped for the [`cooldown`](Self::cooldown), then given one
+///   trial request.
+///
+/// Most callers configure it through
+/// [`NearBuilder::rpc_urls`](super::NearBuilder::rpc_urls); build one directly
+/// to tune it:
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use near_kit::{EndpointSelection, FailoverTransport, Near};
+///
+/// let transport = FailoverTransport::new([
+///     "https://free.rpc.fastnear.com",
+///     "https://rpc.mainnet.near.org",
+/// ])
+/// .selection(EndpointSelection::LeastLatency)
+/// .cooldown(Duration::from_secs(10));
+///
+/// let near = Near::custom("https://free.rpc.fastnear.com", "mainnet")
+///     .transport(transport)
+///     .build();
+/// ```
+///
+/// Failover happens inside a single transport call, so it composes with the
+/// client's [`RetryConfig`](super::RetryConfig): each retry attempt starts a
+/// fresh pass over the endpoints, skipping the ones whose circuit is open.
+/// The `url` passed to [`post_json`](RpcTransport::post_json) is ignored:
+/// requests always go to this transport's own endpoints.
+pub struct FailoverTransport {
+    inner: Arc<dyn RpcTransport>,
+    endpoints: Vec<Endpoint>,
+    selection: EndpointSelection,
+    failure_threshold: u32,
+    cooldown: Duration,
+    cursor: AtomicUsize,
+}
+
+impl FailoverTransport {
+    /// Failures in a row before an endpoint's circuit opens, by default.
+    pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+    /// How long an open circuit skips its endpoint, by default.
+    pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+    /// Create a failover transport over `urls`, using the built-in HTTP
+    /// transport.
+    ///
+    /// Only exists where a built-in transport does — see
+    /// [`RpcClient::new`](super::RpcClient::new).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `urls` is empty.
+    #[cfg(any(
+        not(all(target_arch = "wasm32", target_os = "wasi")),
+        all(feature = "wasi-http", target_env = "p2")
+    ))]
+    pub fn new(urls: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self::with_transport(urls, transport::default_transport())
+    }
+
+    /// Create a failover transport over `urls` that sends requests through
+    /// `inner`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `urls` is empty.
+    pub fn with_transport(
+        urls: impl IntoIterator<Item = impl Into<String>>,
+        inner: Arc<dyn RpcTransport>,
+    ) -> Self {
+        let endpoints: Vec<_> = urls
+            .into_iter()
+            .map(|url| Endpoint {
+                url: url.into(),
+                health: Mutex::new(Health::default()),
+            })
+            .collect();
+        assert!(
+            !endpoints.is_empty(),
+            "FailoverTransport needs at least one endpoint"
+        );
+        Self {
+            inner,
+            endpoints,
+            selection: EndpointSelection::default(),
+            failure_threshold: Self::DEFAULT_FAILURE_THRESHOLD,
+            cooldown: Self::DEFAULT_COOLDOWN,
+            cursor: AtomicUsize::new(0),
+        }
+    }
+
+    /// Set how endpoints are ordered for each request.
+    pub fn selection(mut self, selection: EndpointSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Set how many consecutive failures open an endpoint's circuit.
+    ///
+    /// Values below `1` are treated as `1`.
+    pub fn failure_threshold(mut self, failures: u32) -> Self {
+        self.failure_threshold = failures.max(1);
+        self
+    }
+
+    /// Set how long an endpoint with an open circuit is skipped.
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// The URL of the first endpoint.
+    pub fn primary_url(&self) -> &str {
+        &self.endpoints[0].url
+    }
+
+    /// A snapshot of every endpoint's health, in configuration order.
+    pub fn endpoints(&self) -> Vec<EndpointStatus> {
+        let now = monotonic_millis();
+        self.endpoints
+            .iter()
+            .map(|endpoint| {
+                let health = endpoint.health();
+                EndpointStatus {
+                    url: endpoint.url.clone(),
+                    available: health.open_until.is_none_or(|until| now >= until),
+                    consecutive_failures: health.consecutive_failures,
+                    latency: health
+                        .latency_ms
+                        .map(|ms| Duration::from_micros((ms * 1000.0) as u64)),
+                }
+            })
+            .collect()
+    }
+
+    /// Endpoint indices in the order this request should try them.
+    ///
+    /// Endpoints with an open circuit go last, soonest-to-reopen first, so a
+    /// request still has somewhere to go when every circuit is open.
+    fn plan(&self) -> Vec<usize> {
+        let now = monotonic_millis();
+        let count = self.endpoints.len();
+        let start = match self.selection {
+            EndpointSelection::RoundRobin => self.cursor.fetch_add(1, Ordering::Relaxed) % count,
+            _ => 0,
+        };
+
+        let mut available = Vec::with_capacity(count);
+        let mut open = Vec::new();
+        for index in (0..count).map(|i| (start + i) % count) {
+            let health = self.endpoints[index].health();
+            match health.open_until {
+                Some(until) if now < until => open.push((until, index)),
+                _ => available.push((health.latency_ms, index)),
+            }
+        }
+
+        if self.selection == EndpointSelection::LeastLatency {
+            // Unmeasured endpoints (None) sort first.
+            available
+                .sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        }
+        open.sort_by_key(|(until, _)| *until);
+
+        available
+            .into_iter()
+            .map(|(_, index)| index)
+            .chain(open.into_iter().map(|(_, index)| index))
+            .collect()
+    }
+
+    fn record_success(&self, index: usize, elapsed_ms: u64) {
+        let mut health = self.endpoints[index].health();
+        health.consecutive_failures = 0;
+        health.open_until = None;
+        let sample = elapsed_ms as f64;
+        health.latency_ms = Some(match health.latency_ms {
+            Some(average) => average + LATENCY_SMOOTHING * (sample - average),
+            None => sample,
+        });
+    }
+
+    fn record_failure(&self, index: usize) {
+        let mut health = self.endpoints[index].health();
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        if health.consecutive_failures >= self.failure_threshold {
+            health.open_until =
+                Some(monotonic_millis().saturating_add(self.cooldown.as_millis() as u64));
+        }
+    }
+}
+
+impl RpcTransport for FailoverTransport {
+    fn post_json(
+        &self,
+        _url: &str,
+        body: Vec<u8>,
+    ) -> BoxFuture<'_, Result<TransportResponse, RpcError>> {
+        Box::pin(async move {
+            let plan = self.plan();
+            let last = plan.len() - 1;
+            let mut outcome = None;
+
+            for (position, index) in plan.into_iter().enumerate() {
+                let endpoint = &self.endpoints[index];
+                let started = monotonic_millis();
+                let result = self.inner.post_json(&endpoint.url, body.clone()).await;
+
+                let reason = match &result {
+                    Err(e) if e.is_retryable() => {
+                        self.record_failure(index);
+                        "request failed"
+                    }
+                    Err(_) => return result,
+                    Ok(response) if is_failover_status(response.status) => {
+                        self.record_failure(index);
+                        "endpoint unavailable"
+                    }
+                    Ok(response) => {
+                        self.record_success(index, monotonic_millis().saturating_sub(started));
+                        // A lagging node is healthy, just behind: redirect the
+                        // request without counting it against the endpoint.
+                        if !is_lagging_node_response(&response.body) {
+                            return result;
+                        }
+                        "node is behind"
+                    }
+                };
+
+                if position < last {
+                    trace::debug!(
+                        endpoint = sanitize_url(&endpoint.url),
+                        reason,
+                        "RPC endpoint failed over"
+                    );
+                }
+                #[cfg(not(feature = "tracing"))]
+                let _ = reason;
+                outcome = Some(result);
+            }
+
+            outcome.expect("at least one endpoint is configured")
+        })
+    }
+}
+
+impl std::fmt::Debug for FailoverTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FailoverTransport")
+            .field(
+                "endpoints",
+                &self
+                    .endpoints
+                    .iter()
+                    .map(|endpoint| sanitize_url(&endpoint.url))
+                    .collect::<Vec<_>>(),
+            )
+            .field("selection", &self.selection)
+            .field("failure_threshold", &self.failure_threshold)
+            .field("cooldown", &self.cooldown)
+            .finish()
+    }
+}
+
+/// HTTP statuses that mean the endpoint itself is struggling: timeouts, rate
+/// limiting and server errors.
+///
+/// nearcore reports `UNKNOWN_BLOCK` with 422 and a request timeout with 408,
+/// so a well-formed JSON-RPC error body doesn't rule failover out.
+fn is_failover_status(status: u16) -> bool {
+    status == 408 || status == 429 || (500..600).contains(&status)
+}
+
+/// Whether a response body carries a lagging-node error for any request
+/// (single or batched).
+fn is_lagging_node_response(body: &[u8]) -> bool {
+    // Cheap substring check first: only bodies naming a lagging-node cause
+    // are worth parsing.
+    let text = String::from_utf8_lossy(body);
+    if !LAGGING_NODE_CAUSES.iter().any(|cause| text.contains(cause)) {
+        return false;
+    }
+
+    let has_lagging_cause = |response: &serde_json::Value| {
+        response
+            .pointer("/error/cause/name")
+            .and_then(serde_json::Value::as_str)
+            .is_some_and(|name| LAGGING_NODE_CAUSES.contains(&name))
+    };
+    match serde_json::from_str::<serde_json::Value>(&text) {
+        Ok(serde_json::Value::Array(responses)) => responses.iter().any(has_lagging_cause),
+        Ok(response) => has_lagging_cause(&response),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    /// What a scripted endpoint does with a request.
+    #[derive(Clone)]
+    enum Reply {
+        Status(u16, &'static str),
+        NetworkError,
+        InvalidUrl,
+        Slow(Duration),
+    }
+
+    /// Transport that answers per URL with a fixed [`Reply`] and records the
+    /// URLs it was asked to reach.
+    struct ScriptedTransport {
+        replies: HashMap<String, Reply>,
+        hits: Mutex<Vec<String>>,
+    }
+
+    impl ScriptedTransport {
+        fn new(replies: &[(&str, Reply)]) -> Arc<Self> {
+            Arc::new(Self {
+                replies: replies
+                    .iter()
+                    .map(|(url, reply)| (url.to_string(), reply.clone()))
+                    .collect(),
+                hits: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn hits(&self) -> Vec<String> {
+            self.hits.lock().unwrap().clone()
+        }
+    }
+
+    impl RpcTransport for ScriptedTransport {
+        fn post_json(
+            &self,
+            url: &str,
+            _body: Vec<u8>,
+        ) -> BoxFuture<'_, Result<TransportResponse, RpcError>> {
+            self.hits.lock().unwrap().push(url.to_string());
+            let reply = self.replies[url].clone();
+            Box::pin(async move {
+                match reply {
+                    Reply::Status(status, body) => {
+                        Ok(TransportResponse::new(status, body.as_bytes().to_vec()))
+                    }
+                    Reply::NetworkError => Err(RpcError::network("connection reset", None, true)),
+                    Reply::InvalidUrl => Err(RpcError::network("invalid URL", None, false)),
+                    Reply::Slow(delay) => {
+                        tokio::time::sleep(delay).await;
+                        Ok(TransportResponse::new(200, OK_BODY.as_bytes().to_vec()))
+                    }
+                }
+            })
+        }
+    }
+
+    const OK_BODY: &str = r#"{"jsonrpc":"2.0","id":0,"result":{}}"#;
+    const UNKNOWN_BLOCK_BODY: &str = r#"{"jsonrpc":"2.0","id":0,"error":{"code":-32000,"message":"Server error","name":"HANDLER_ERROR","cause":{"name":"UNKNOWN_BLOCK","info":{}}}}"#;
+
+    const A: &str = "https://a.example";
+    const B: &str = "https://b.example";
+    const C: &str = "https://c.example";
+
+    async fn send(transport: &FailoverTransport) -> Result<TransportResponse, RpcError> {
+        transport.post_json("ignored", b"{}".to_vec()).await
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_on_retryable_errors() {
+        let inner = ScriptedTransport::new(&[
+            (A, Reply::NetworkError),
+            (B, Reply::Status(503, "unavailable")),
+            (C, Reply::Status(200, OK_BODY)),
+        ]);
+        let transport = FailoverTransport::with_transport([A, B, C], inner.clone());
+
+        let response = send(&transport).await.unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(inner.hits(), [A, B, C]);
+
+        let status = transport.endpoints();
+        assert_eq!(status[0].consecutive_failures, 1);
+        assert_eq!(status[1].consecutive_failures, 1);
+        assert_eq!(status[2].consecutive_failures, 0);
+        assert!(status[2].latency.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_endpoint_fails_over() {
+        let inner = ScriptedTransport::new(&[
+            (A, Reply::Status(429, "Too Many Requests")),
+            (B, Reply::Status(200, OK_BODY)),
+        ]);
+        let transport = FailoverTransport::with_transport([A, B], inner.clone());
+
+        assert_eq!(send(&transport).await.unwrap().status, 200);
+        assert_eq!(inner.hits(), [A, B]);
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_error_is_returned_immediately() {
+        let inner =
+            ScriptedTransport::new(&[(A, Reply::InvalidUrl), (B, Reply::Status(200, OK_BODY))]);
+        let transport = FailoverTransport::with_transport([A, B], inner.clone());
+
+        assert!(send(&transport).await.is_err());
+        assert_eq!(inner.hits(), [A]);
+    }
+
+    #[tokio::test]
+    async fn test_returns_last_outcome_when_every_endpoint_fails() {
+        let inner = ScriptedTransport::new(&[
+            (A, Reply::NetworkError),
+            (B, Reply::Status(502, "bad gateway")),
+        ]);
+        let transport = FailoverTransport::with_transport([A, B], inner.clone());
+
+        let response = send(&transport).await.unwrap();
+        assert_eq!(response.status, 502);
+    }
+
+    #[tokio::test]
+    async fn test_lagging_node_redirects_without_tripping_breaker() {
+        let inner = ScriptedTransport::new(&[
+            (A, Reply::Status(422, UNKNOWN_BLOCK_BODY)),
+            (B, Reply::Status(200, OK_BODY)),
+        ]);
+        let transport =
+            FailoverTransport::with_transport([A, B], inner.clone()).failure_threshold(1);
+
+        assert_eq!(send(&transport).await.unwrap().status, 200);
+        assert_eq!(inner.hits(), [A, B]);
+        assert!(transport.endpoints()[0].available);
+        assert_eq!(transport.endpoints()[0].consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn test_lagging_node_in_batch_response_redirects() {
+        let batch = r#"[{"jsonrpc":"2.0","id":0,"result":{}},{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"Server error","cause":{"name":"NOT_SYNCED_YET"}}}]"#;
+        let inner = ScriptedTransport::new(&[
+            (A, Reply::Status(200, batch)),
+            (B, Reply::Status(200, OK_BODY)),
+        ]);
+        let transport = FailoverTransport::with_transport([A, B], inner.clone());
+
+        send(&transport).await.unwrap();
+        assert_eq!(inner.hits(), [A, B]);
+    }
+
+    #[tokio::test]
+    async fn test_lagging_response_is_returned_when_no_endpoint_has_the_block() {
+        let inner = ScriptedTransport::new(&[
+            (A, Reply::Status(422, UNKNOWN_BLOCK_BODY)),
+            (B, Reply::Status(422, UNKNOWN_BLOCK_BODY)),
+        ]);
+        let transport = FailoverTransport::with_transport([A, B], inner);
+
+        let response = send(&transport).await.unwrap();
+        assert_eq!(response.status, 422);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_after_threshold() {
+        let inner =
+            ScriptedTransport::new(&[(A, Reply::NetworkError), (B, Reply::Status(200, OK_BODY))]);
+        let transport = FailoverTransport::with_transport([A, B], inner.clone())
+            .failure_threshold(2)
+            .cooldown(Duration::from_secs(60));
+
+        send(&transport).await.unwrap();
+        send(&transport).await.unwrap();
+        assert!(!transport.endpoints()[0].available);
+
+        // The open endpoint is skipped entirely.
+        send(&transport).await.unwrap();
+        assert_eq!(inner.hits(), [A, B, A, B, B]);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_half_opens_after_cooldown() {
+        let inner =
+            ScriptedTransport::new(&[(A, Reply::NetworkError), (B, Reply::Status(200, OK_BODY))]);
+        let transport = FailoverTransport::with_transport([A, B], inner.clone())
+            .failure_threshold(1)
+            .cooldown(Duration::from_millis(20));
+
+        send(&transport).await.unwrap();
+        assert!(!transport.endpoints()[0].available);
+
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        assert!(transport.endpoints()[0].available);
+        send(&transport).await.unwrap();
+        assert_eq!(inner.hits(), [A, B, A, B]);
+    }
+
+    #[tokio::test]
+    async fn test_open_circuits_are_still_tried_as_a_last_resort() {
+        let inner = ScriptedTransport::new(&[(A, Reply::NetworkError), (B, Reply::NetworkError)]);
+        let transport = FailoverTransport::with_transport([A, B], inner.clone())
+            .failure_threshold(1)
+            .cooldown(Duration::from_secs(60));
+
+        assert!(send(&transport).await.is_err());
+        assert!(send(&transport).await.is_err());
+        assert_eq!(inner.hits().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_rotates_start_endpoint() {
+        let inner = ScriptedTransport::new(&[
+            (A, Reply::Status(200, OK_BODY)),
+            (B, Reply::Status(200, OK_BODY)),
+            (C, Reply::Status(200, OK_BODY)),
+        ]);
+        let transport = FailoverTransport::with_transport([A, B, C], inner.clone())
+            .selection(EndpointSelection::RoundRobin);
+
+        for _ in 0..4 {
+            send(&transport).await.unwrap();
+        }
+        assert_eq!(inner.hits(), [A, B, C, A]);
+    }
+
+    #[tokio::test]
+    async fn test_least_latency_prefers_fastest_endpoint() {
+        let inner = ScriptedTransport::new(&[
+            (A, Reply::Slow(Duration::from_millis(50))),
+            (B, Reply::Slow(Duration::ZERO)),
+        ]);
+        let transport = FailoverTransport::with_transport([A, B], inner.clone())
+            .selection(EndpointSelection::LeastLatency);
+
+        // First request measures A (unmeasured endpoints go first, in order);
+        // the second measures B; after that B wins.
+        send(&transport).await.unwrap();
+        send(&transport).await.unwrap();
+        send(&transport).await.unwrap();
+        send(&transport).await.unwrap();
+        assert_eq!(inner.hits(), [A, B, B, B]);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one endpoint")]
+    fn test_empty_endpoint_list_panics() {
+        let inner = ScriptedTransport::new(&[]);
+        let _ = FailoverTransport::with_transport(Vec::<String>::new(), inner);
+    }
+
+    #[test]
+    fn test_lagging_node_detection() {
+        assert!(is_lagging_node_response(UNKNOWN_BLOCK_BODY.as_bytes()));
+        assert!(!is_lagging_node_response(OK_BODY.as_bytes()));
+        // A method result merely mentioning the name is not an error.
+        assert!(!is_lagging_node_response(
+            br#"{"jsonrpc":"2.0","id":0,"result":"UNKNOWN_BLOCK"}"#
+        ));
+    }
+}
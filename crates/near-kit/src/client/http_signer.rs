@@ -0,0 +1,308 @@
+//! Signer that delegates signing to an HTTP endpoint.
+//!
+//! [`HttpSigner`] keeps no key material: every signature is requested from a
+//! signing service — a KMS proxy, a Vault transit wrapper, a custom daemon —
+//! over the same [`RpcTransport`] the RPC client uses.
+//!
+//! # Protocol
+//!
+//! Each signature is one `POST` to the endpoint with a JSON body naming the
+//! key and carrying the 32-byte hash to sign, base64-encoded:
+//!
+//! ```json
+//! { "public_key": "ed25519:…", "message": "…base64…" }
+//! ```
+//!
+//! A `2xx` response carries the signature:
+//!
+//! ```json
+//! { "signature": "ed25519:…" }
+//! ```
+//!
+//! Any other status fails with the response body in the error. The signature
+//! is checked against the public key before use, so a misrouted or
+//! misconfigured service cannot get a bad signature onto the chain.
+
+use std::sync::Arc;
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use serde::{Deserialize, Serialize};
+
+use super::signer::{Signer, SigningBackend, SigningKey};
+use super::transport::RpcTransport;
+use crate::error::{Error, SignerError};
+use crate::platform::BoxFuture;
+use crate::types::{AccountId, PublicKey, Signature, TryIntoAccountId};
+
+/// A signer whose signatures come from an HTTP signing service.
+///
+/// See the [module docs](self) for the wire protocol. Authentication (API
+/// keys, mTLS) is configured on the transport, e.g. a
+/// [`ReqwestTransport`](crate::ReqwestTransport) wrapping a client with default
+/// headers.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use near_kit::{HttpSigner, Near};
+///
+/// # async fn example() -> Result<(), near_kit::Error> {
+/// let signer = HttpSigner::new(
+///     "https://signer.internal/v1/sign",
+///     "treasury.near",
+///     "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+/// )?;
+///
+/// let near = Near::mainnet().signer(signer).build();
+/// near.transfer("bob.near", "1 NEAR").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct HttpSigner {
+    account_id: AccountId,
+    endpoint: String,
+    key: SigningKey,
+}
+
+impl HttpSigner {
+    /// Create a signer that requests signatures for `public_key` from
+    /// `endpoint` using the default HTTP transport.
+    #[cfg(any(
+        not(all(target_arch = "wasm32", target_os = "wasi")),
+        all(feature = "wasi-http", target_env = "p2")
+    ))]
+    pub fn new(
+        endpoint: impl Into<String>,
+        account_id: impl TryIntoAccountId,
+        public_key: impl AsRef<str>,
+    ) -> Result<Self, Error> {
+        Self::with_transport(
+            super::transport::default_transport(),
+            endpoint,
+            account_id,
+            public_key,
+        )
+    }
+
+    /// Create a signer that posts its requests through `transport`.
+    pub fn with_transport(
+        transport: impl RpcTransport + 'static,
+        endpoint: impl Into<String>,
+        account_id: impl TryIntoAccountId,
+        public_key: impl AsRef<str>,
+    ) -> Result<Self, Error> {
+        let account_id = account_id.try_into_account_id()?;
+        let public_key: PublicKey = public_key.as_ref().parse()?;
+        let endpoint = endpoint.into();
+        let backend = HttpBackend {
+            transport: Arc::new(transport),
+            endpoint: endpoint.clone(),
+            public_key: public_key.clone(),
+        };
+        Ok(Self {
+            account_id,
+            endpoint,
+            key: SigningKey::from_backend(public_key, backend),
+        })
+    }
+
+    /// The public key signatures are requested for.
+    pub fn public_key(&self) -> &PublicKey {
+        self.key.public_key()
+    }
+
+    /// The signing endpoint.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+}
+
+impl std::fmt::Debug for HttpSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpSigner")
+            .field("account_id", &self.account_id)
+            .field("public_key", self.public_key())
+            .field("endpoint", &self.endpoint)
+            .finish()
+    }
+}
+
+impl Signer for HttpSigner {
+    fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    fn key(&self) -> SigningKey {
+        self.key.clone()
+    }
+}
+
+#[derive(Serialize)]
+struct SignRequest<'a> {
+    public_key: &'a PublicKey,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct SignResponse {
+    signature: Signature,
+}
+
+struct HttpBackend {
+    transport: Arc<dyn RpcTransport>,
+    endpoint: String,
+    public_key: PublicKey,
+}
+
+impl SigningBackend for HttpBackend {
+    fn sign(&self, message: &[u8]) -> BoxFuture<'_, Result<Signature, SignerError>> {
+        let message = message.to_vec();
+        Box::pin(async move {
+            let failed = |reason: String| {
+                SignerError::SigningFailed(format!("signing endpoint {}: {reason}", self.endpoint))
+            };
+            let body = serde_json::to_vec(&SignRequest {
+                public_key: &self.public_key,
+                message: STANDARD.encode(&message),
+            })
+            .expect("sign request serialization should never fail");
+            let response = self
+                .transport
+                .post_json(&self.endpoint, body)
+                .await
+                .map_err(|e| failed(e.to_string()))?;
+            if !(200..300).contains(&response.status) {
+                return Err(failed(format!(
+                    "HTTP {}: {}",
+                    response.status,
+                    String::from_utf8_lossy(&response.body)
+                )));
+            }
+            let SignResponse { signature } = serde_json::from_slice(&response.body)
+                .map_err(|e| failed(format!("invalid response: {e}")))?;
+            if !signature.verify(&message, &self.public_key) {
+                return Err(failed(format!(
+                    "returned a signature that does not verify against {}",
+                    self.public_key
+                )));
+            }
+            Ok(signature)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::types::SecretKey;
+
+    /// A stand-in signing service on a local port. Answers `requests`
+    /// connections with `respond` and returns the request bodies it saw.
+    fn serve(
+        requests: usize,
+        respond: impl Fn(&Value) -> (u16, String) + Send + 'static,
+    ) -> (String, std::thread::JoinHandle<Vec<Value>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/sign", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut bodies = Vec::new();
+            for _ in 0..requests {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 1024];
+                let body = loop {
+                    let read = stream.read(&mut buffer).unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                let (name, value) = line.split_once(':')?;
+                                name.eq_ignore_ascii_case("content-length")
+                                    .then(|| value.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            break serde_json::from_str::<Value>(body).unwrap();
+                        }
+                    }
+                    assert!(read > 0, "connection closed mid-request");
+                };
+                let (status, reply) = respond(&body);
+                bodies.push(body);
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{reply}",
+                    reply.len(),
+                )
+                .unwrap();
+            }
+            bodies
+        });
+        (url, server)
+    }
+
+    #[tokio::test]
+    async fn test_signs_through_endpoint() {
+        let secret = SecretKey::generate_ed25519();
+        let public_key = secret.public_key();
+        let service_key = secret.clone();
+        let (url, server) = serve(1, move |body| {
+            let message = STANDARD.decode(body["message"].as_str().unwrap()).unwrap();
+            let signature = service_key.sign(&message);
+            (
+                200,
+                json!({ "signature": signature.to_string() }).to_string(),
+            )
+        });
+
+        let signer = HttpSigner::new(&url, "treasury.near", public_key.to_string()).unwrap();
+        let hash = crate::types::CryptoHash::hash(b"payload");
+        let signature = signer.key().sign(hash.as_bytes()).await.unwrap();
+        assert_eq!(signature, secret.sign(hash.as_bytes()));
+
+        let bodies = server.join().unwrap();
+        assert_eq!(bodies[0]["public_key"], json!(public_key.to_string()));
+        assert_eq!(
+            bodies[0]["message"],
+            json!(STANDARD.encode(hash.as_bytes()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_errors_and_bad_signatures() {
+        let public_key = SecretKey::generate_ed25519().public_key();
+        let impostor = SecretKey::generate_ed25519();
+        let (url, server) = serve(2, move |body| {
+            if body["message"] == json!(STANDARD.encode(b"first")) {
+                (403, "key not allowed".to_string())
+            } else {
+                let signature = impostor.sign(b"second");
+                (
+                    200,
+                    json!({ "signature": signature.to_string() }).to_string(),
+                )
+            }
+        });
+        let signer = HttpSigner::new(&url, "treasury.near", public_key.to_string()).unwrap();
+
+        let err = signer.key().sign(b"first").await.unwrap_err();
+        assert!(matches!(
+            &err,
+            SignerError::SigningFailed(message) if message.contains("HTTP 403: key not allowed")
+        ));
+        let err = signer.key().sign(b"second").await.unwrap_err();
+        assert!(matches!(
+            &err,
+            SignerError::SigningFailed(message) if message.contains("does not verify")
+        ));
+        server.join().unwrap();
+    }
+}
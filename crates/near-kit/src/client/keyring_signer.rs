@@ -44,6 +44,9 @@
 //!   network-config testnet
 //! ```
 
+use std::collections::BTreeMap;
+
+use crate::client::credential_store::CredentialStore;
 use crate::client::signer::{InMemorySigner, Signer, SigningKey};
 use crate::error::{Error, KeyStoreError, ParseKeyError};
 use crate::types::{AccountId, PublicKey, SecretKey, TryIntoAccountId};
@@ -112,29 +115,10 @@ impl KeyringSigner {
         // Parse public key for validation
         let public_key: PublicKey = public_key_str.parse()?;
 
-        // Construct keyring entry using near-cli-rs format
-        // Service: "near-{network}-{account_id}"
-        // Username: "{account_id}:{public_key}"
-        let service_name = format!("near-{}-{}", network, account_id);
-        let username = format!("{}:{}", account_id, public_key_str);
-
-        let entry = keyring::Entry::new(&service_name, &username).map_err(|e| {
-            Error::KeyStore(KeyStoreError::Platform(format!(
-                "Failed to access keyring: {}. On Linux, ensure a Secret Service daemon \
-                 (gnome-keyring, kwallet) is running.",
-                e
-            )))
-        })?;
-
-        let password = entry.get_password().map_err(|e| match e {
-            keyring::Error::NoEntry => {
-                Error::KeyStore(KeyStoreError::KeyNotFound(account_id.clone()))
-            }
-            _ => Error::KeyStore(KeyStoreError::Platform(format!(
-                "Failed to read from keyring: {}",
-                e
-            ))),
-        })?;
+        let entry = key_entry(network, &account_id, public_key_str)?;
+        let password = entry
+            .get_password()
+            .map_err(|e| read_error(e, &account_id))?;
 
         // Parse the stored JSON credential
         let secret_key = parse_keyring_credential(&password, &account_id, &public_key)?;
@@ -184,6 +168,200 @@ impl Signer for KeyringSigner {
     }
 }
 
+// ============================================================================
+// KeyringCredentialStore
+// ============================================================================
+
+/// A [`CredentialStore`] in the system keyring, using the entries near-cli-rs
+/// reads and [`KeyringSigner`] loads: service `near-{network}-{account_id}`,
+/// username `{account_id}:{public_key}`.
+///
+/// OS keyrings cannot be searched, so the store also keeps an index of what it
+/// has saved (service `near-kit-{network}`, username `credential-index`).
+/// [`list_accounts`](CredentialStore::list_accounts) and
+/// [`list_keys`](CredentialStore::list_keys) report only keys saved through a
+/// `KeyringCredentialStore`; keys imported by near-cli-rs can still be
+/// [`load`](CredentialStore::load)ed by public key. Concurrent writers from
+/// several processes may lose index updates.
+///
+/// Requires the `keyring` feature.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use near_kit::{AccountId, CredentialStore, KeyPair, KeyringCredentialStore};
+///
+/// # fn example() -> Result<(), near_kit::Error> {
+/// let store = KeyringCredentialStore::new();
+/// let account_id: AccountId = "alice.testnet".parse()?;
+/// let key = KeyPair::random();
+/// store.save("testnet", &account_id, &key.secret_key)?;
+/// assert!(store.list_keys("testnet", &account_id)?.contains(&key.public_key));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct KeyringCredentialStore;
+
+/// Saved keys per account, as stored in the index entry.
+type KeyIndex = BTreeMap<AccountId, Vec<PublicKey>>;
+
+impl KeyringCredentialStore {
+    /// Create a store over the system keyring.
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn index_entry(network: &str) -> Result<keyring::Entry, Error> {
+        keyring::Entry::new(&format!("near-kit-{}", network), "credential-index")
+            .map_err(access_error)
+    }
+
+    fn read_index(network: &str) -> Result<KeyIndex, Error> {
+        match Self::index_entry(network)?.get_password() {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| {
+                Error::KeyStore(KeyStoreError::InvalidFormat(format!(
+                    "Invalid near-kit keyring index for {}: {}",
+                    network, e
+                )))
+            }),
+            Err(keyring::Error::NoEntry) => Ok(KeyIndex::new()),
+            Err(e) => Err(Error::KeyStore(KeyStoreError::Platform(format!(
+                "Failed to read from keyring: {}",
+                e
+            )))),
+        }
+    }
+
+    fn update_index(network: &str, update: impl FnOnce(&mut KeyIndex)) -> Result<(), Error> {
+        let mut index = Self::read_index(network)?;
+        update(&mut index);
+        index.retain(|_, keys| !keys.is_empty());
+        let entry = Self::index_entry(network)?;
+        let result = if index.is_empty() {
+            match entry.delete_credential() {
+                Err(keyring::Error::NoEntry) => Ok(()),
+                result => result,
+            }
+        } else {
+            let json = serde_json::to_string(&index).map_err(KeyStoreError::from)?;
+            entry.set_password(&json)
+        };
+        result.map_err(write_error)
+    }
+}
+
+impl CredentialStore for KeyringCredentialStore {
+    fn save(
+        &self,
+        network: &str,
+        account_id: &AccountId,
+        secret_key: &SecretKey,
+    ) -> Result<(), Error> {
+        let public_key = secret_key.public_key();
+        let credential = serde_json::json!({
+            "public_key": public_key.to_string(),
+            "private_key": secret_key.to_string(),
+        });
+        key_entry(network, account_id, &public_key.to_string())?
+            .set_password(&credential.to_string())
+            .map_err(write_error)?;
+        Self::update_index(network, |index| {
+            let keys = index.entry(account_id.clone()).or_default();
+            if !keys.contains(&public_key) {
+                keys.push(public_key);
+            }
+        })
+    }
+
+    fn load(
+        &self,
+        network: &str,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> Result<SecretKey, Error> {
+        let password = key_entry(network, account_id, &public_key.to_string())?
+            .get_password()
+            .map_err(|e| read_error(e, account_id))?;
+        let secret_key = parse_keyring_credential(&password, account_id, public_key)?;
+        if &secret_key.public_key() != public_key {
+            return Err(Error::KeyStore(KeyStoreError::InvalidFormat(format!(
+                "Public key mismatch: stored key has {}, but requested {}",
+                secret_key.public_key(),
+                public_key
+            ))));
+        }
+        Ok(secret_key)
+    }
+
+    fn list_accounts(&self, network: &str) -> Result<Vec<AccountId>, Error> {
+        Ok(Self::read_index(network)?.into_keys().collect())
+    }
+
+    fn list_keys(&self, network: &str, account_id: &AccountId) -> Result<Vec<PublicKey>, Error> {
+        Ok(Self::read_index(network)?
+            .remove(account_id)
+            .unwrap_or_default())
+    }
+
+    fn remove(
+        &self,
+        network: &str,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> Result<(), Error> {
+        key_entry(network, account_id, &public_key.to_string())?
+            .delete_credential()
+            .map_err(|e| read_error(e, account_id))?;
+        Self::update_index(network, |index| {
+            if let Some(keys) = index.get_mut(account_id) {
+                keys.retain(|key| key != public_key);
+            }
+        })
+    }
+}
+
+// ============================================================================
+// Keyring Entries
+// ============================================================================
+
+/// The keyring entry for one key, in near-cli-rs format:
+/// service `near-{network}-{account_id}`, username `{account_id}:{public_key}`.
+fn key_entry(
+    network: &str,
+    account_id: &AccountId,
+    public_key: &str,
+) -> Result<keyring::Entry, Error> {
+    let service_name = format!("near-{}-{}", network, account_id);
+    let username = format!("{}:{}", account_id, public_key);
+    keyring::Entry::new(&service_name, &username).map_err(access_error)
+}
+
+fn access_error(e: keyring::Error) -> Error {
+    Error::KeyStore(KeyStoreError::Platform(format!(
+        "Failed to access keyring: {}. On Linux, ensure a Secret Service daemon \
+         (gnome-keyring, kwallet) is running.",
+        e
+    )))
+}
+
+fn read_error(e: keyring::Error, account_id: &AccountId) -> Error {
+    match e {
+        keyring::Error::NoEntry => Error::KeyStore(KeyStoreError::KeyNotFound(account_id.clone())),
+        _ => Error::KeyStore(KeyStoreError::Platform(format!(
+            "Failed to read from keyring: {}",
+            e
+        ))),
+    }
+}
+
+fn write_error(e: keyring::Error) -> Error {
+    Error::KeyStore(KeyStoreError::Platform(format!(
+        "Failed to write to keyring: {}",
+        e
+    )))
+}
+
 // ============================================================================
 // Credential Parsing
 // ============================================================================
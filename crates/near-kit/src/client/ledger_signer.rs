@@ -0,0 +1,573 @@
+//! Ledger hardware wallet signer.
+//!
+//! [`LedgerSigner`] signs with a key that never leaves a Ledger device running
+//! the NEAR app. It speaks the app's APDU protocol over a [`LedgerTransport`],
+//! which only moves raw APDUs: USB HID, Bluetooth, or a speculos emulator's
+//! TCP port are all up to the application, and tests can script the device.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use near_kit::{BoxFuture, LedgerSigner, LedgerTransport, Near};
+//! use near_kit::error::LedgerError;
+//!
+//! struct Hid(ledger_transport_hid::TransportNativeHID);
+//!
+//! impl LedgerTransport for Hid {
+//!     fn exchange(&self, apdu: Vec<u8>) -> BoxFuture<'_, Result<Vec<u8>, LedgerError>> {
+//!         let reply = self.0.exchange_raw(&apdu).map_err(|e| LedgerError::Transport(e.to_string()));
+//!         Box::pin(async move { reply })
+//!     }
+//! }
+//!
+//! let signer = LedgerSigner::connect(Hid(device), "alice.near").await?;
+//! let near = Near::mainnet().signer(signer).build();
+//!
+//! // The transfer is shown on the device and waits for confirmation there.
+//! near.transfer("bob.near", "1 NEAR").await?;
+//! ```
+//!
+//! The NEAR app only signs payloads it can display: transactions, NEP-366
+//! delegate actions and NEP-413 messages. [`SigningKey::sign`] on raw bytes
+//! fails.
+
+use std::sync::Arc;
+
+use crate::client::signer::{Signer, SigningBackend, SigningKey};
+use crate::error::{Error, LedgerError, SignerError};
+use crate::platform::{BoxFuture, MaybeSend, MaybeSync};
+use crate::types::nep413::{self, SignMessageParams};
+use crate::types::{
+    AccountId, DelegateAction, PublicKey, Signature, Transaction, TryIntoAccountId,
+};
+
+const CLA: u8 = 0x80;
+const INS_SIGN_TRANSACTION: u8 = 0x02;
+const INS_GET_PUBLIC_KEY: u8 = 0x04;
+const INS_SIGN_NEP413_MESSAGE: u8 = 0x07;
+const INS_SIGN_NEP366_DELEGATE_ACTION: u8 = 0x08;
+const P1_GET_PUBLIC_KEY_DISPLAY: u8 = 0x00;
+const P1_GET_PUBLIC_KEY_SILENT: u8 = 0x01;
+const P1_SIGN_NORMAL: u8 = 0x00;
+const P1_SIGN_LAST_CHUNK: u8 = 0x80;
+/// The app's network byte; the same for every NEAR network.
+const NETWORK_ID: u8 = b'W';
+/// Largest payload sent in one APDU.
+const CHUNK_SIZE: usize = 250;
+/// The deepest derivation path accepted, as in Ledger's own BIP-32 helpers.
+const MAX_PATH_COMPONENTS: usize = 10;
+
+const SW_OK: u16 = 0x9000;
+const SW_REJECTED: u16 = 0x6985;
+const SW_LOCKED: u16 = 0x5515;
+const SW_INS_NOT_SUPPORTED: u16 = 0x6D00;
+
+/// Moves APDUs between [`LedgerSigner`] and a Ledger device.
+///
+/// One call sends one command APDU and returns the device's full response,
+/// including the trailing two-byte status word. [`LedgerSigner`] serializes
+/// its own commands, so implementations need no locking of their own.
+pub trait LedgerTransport: MaybeSend + MaybeSync {
+    /// Send `apdu` and return the response.
+    fn exchange(&self, apdu: Vec<u8>) -> BoxFuture<'_, Result<Vec<u8>, LedgerError>>;
+}
+
+/// Forwarding impl so an `Arc`-shared transport (including
+/// `Arc<dyn LedgerTransport>`) can back several signers.
+impl<T: LedgerTransport + ?Sized> LedgerTransport for Arc<T> {
+    fn exchange(&self, apdu: Vec<u8>) -> BoxFuture<'_, Result<Vec<u8>, LedgerError>> {
+        (**self).exchange(apdu)
+    }
+}
+
+/// A signer backed by a Ledger device running the NEAR app.
+///
+/// Every signature asks for confirmation on the device, so signing waits on
+/// the user. Only ed25519 keys are supported, as by the app itself.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use near_kit::LedgerSigner;
+///
+/// // Second key of the default near-cli derivation.
+/// let signer = LedgerSigner::connect_with_path(transport, "alice.near", "44'/397'/0'/0'/2'").await?;
+/// println!("Ledger key: {}", signer.public_key());
+/// ```
+#[derive(Clone)]
+pub struct LedgerSigner {
+    account_id: AccountId,
+    hd_path: String,
+    device: Arc<LedgerDevice>,
+    key: SigningKey,
+}
+
+impl LedgerSigner {
+    /// The derivation path near-cli uses for Ledger keys.
+    pub const DEFAULT_HD_PATH: &'static str = "44'/397'/0'/0'/1'";
+
+    /// Connect to the key at [`DEFAULT_HD_PATH`](Self::DEFAULT_HD_PATH).
+    ///
+    /// Reads the public key from the device without asking for
+    /// confirmation.
+    pub async fn connect(
+        transport: impl LedgerTransport + 'static,
+        account_id: impl TryIntoAccountId,
+    ) -> Result<Self, Error> {
+        Self::connect_with_path(transport, account_id, Self::DEFAULT_HD_PATH).await
+    }
+
+    /// Connect to the key at a BIP-32 path such as `44'/397'/0'/0'/1'`.
+    ///
+    /// Every component must be hardened (`'` or `h`); a leading `m/` is
+    /// accepted.
+    pub async fn connect_with_path(
+        transport: impl LedgerTransport + 'static,
+        account_id: impl TryIntoAccountId,
+        hd_path: &str,
+    ) -> Result<Self, Error> {
+        let account_id = account_id.try_into_account_id()?;
+        let device = Arc::new(LedgerDevice {
+            transport: Box::new(transport),
+            path: parse_hd_path(hd_path).map_err(SignerError::from)?,
+            lock: tokio::sync::Mutex::new(()),
+        });
+        let public_key = device.public_key(false).await.map_err(SignerError::from)?;
+        Ok(Self {
+            account_id,
+            hd_path: hd_path.to_string(),
+            key: SigningKey::from_backend(public_key, LedgerBackend(device.clone())),
+            device,
+        })
+    }
+
+    /// The public key read from the device.
+    pub fn public_key(&self) -> &PublicKey {
+        self.key.public_key()
+    }
+
+    /// The derivation path of the key.
+    pub fn hd_path(&self) -> &str {
+        &self.hd_path
+    }
+
+    /// Show the public key on the device for the user to compare and
+    /// confirm, e.g. before funding the account.
+    ///
+    /// Fails with [`LedgerError::Rejected`] if the user rejects it, and with
+    /// [`LedgerError::InvalidResponse`] if the device now reports another key.
+    pub async fn confirm_public_key(&self) -> Result<(), Error> {
+        let public_key = self
+            .device
+            .public_key(true)
+            .await
+            .map_err(SignerError::from)?;
+        if public_key != *self.public_key() {
+            return Err(SignerError::from(LedgerError::InvalidResponse(format!(
+                "device reports public key {public_key}, expected {}",
+                self.public_key()
+            )))
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for LedgerSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LedgerSigner")
+            .field("account_id", &self.account_id)
+            .field("public_key", self.public_key())
+            .field("hd_path", &self.hd_path)
+            .finish()
+    }
+}
+
+impl Signer for LedgerSigner {
+    fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    fn key(&self) -> SigningKey {
+        self.key.clone()
+    }
+}
+
+// ============================================================================
+// APDU protocol
+// ============================================================================
+
+/// A Ledger device and the derivation path of the key in use.
+struct LedgerDevice {
+    transport: Box<dyn LedgerTransport>,
+    /// The serialized derivation path, sent ahead of every payload.
+    path: Vec<u8>,
+    /// Held for a whole command sequence: the chunks of two signatures
+    /// must not interleave.
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl LedgerDevice {
+    async fn command(&self, ins: u8, p1: u8, data: &[u8]) -> Result<Vec<u8>, LedgerError> {
+        let len = u8::try_from(data.len()).map_err(|_| LedgerError::PayloadTooLarge(data.len()))?;
+        let mut apdu = Vec::with_capacity(5 + data.len());
+        apdu.extend_from_slice(&[CLA, ins, p1, NETWORK_ID, len]);
+        apdu.extend_from_slice(data);
+        let response = self.transport.exchange(apdu).await?;
+        check_status(response)
+    }
+
+    async fn public_key(&self, display: bool) -> Result<PublicKey, LedgerError> {
+        let p1 = if display {
+            P1_GET_PUBLIC_KEY_DISPLAY
+        } else {
+            P1_GET_PUBLIC_KEY_SILENT
+        };
+        let _guard = self.lock.lock().await;
+        let data = self.command(INS_GET_PUBLIC_KEY, p1, &self.path).await?;
+        let bytes: [u8; 32] = data.as_slice().try_into().map_err(|_| {
+            LedgerError::InvalidResponse(format!(
+                "expected a 32-byte public key, got {} bytes",
+                data.len()
+            ))
+        })?;
+        Ok(PublicKey::ed25519_from_bytes(bytes))
+    }
+
+    /// Send the path and `payload` in chunks and return the signature the
+    /// device answers the last chunk with.
+    async fn sign(&self, ins: u8, payload: Vec<u8>) -> Result<Signature, LedgerError> {
+        let mut data = self.path.clone();
+        data.extend_from_slice(&payload);
+        let last = data.len().div_ceil(CHUNK_SIZE) - 1;
+
+        let _guard = self.lock.lock().await;
+        let mut response = Vec::new();
+        for (index, chunk) in data.chunks(CHUNK_SIZE).enumerate() {
+            let p1 = if index == last {
+                P1_SIGN_LAST_CHUNK
+            } else {
+                P1_SIGN_NORMAL
+            };
+            response = self.command(ins, p1, chunk).await?;
+        }
+        let bytes: [u8; 64] = response.as_slice().try_into().map_err(|_| {
+            LedgerError::InvalidResponse(format!(
+                "expected a 64-byte signature, got {} bytes",
+                response.len()
+            ))
+        })?;
+        Ok(Signature::ed25519_from_bytes(bytes))
+    }
+}
+
+/// Split off the status word and map failures to [`LedgerError`].
+fn check_status(mut response: Vec<u8>) -> Result<Vec<u8>, LedgerError> {
+    if response.len() < 2 {
+        return Err(LedgerError::InvalidResponse(
+            "response is missing the status word".to_string(),
+        ));
+    }
+    let status = response.split_off(response.len() - 2);
+    match u16::from_be_bytes([status[0], status[1]]) {
+        SW_OK => Ok(response),
+        SW_REJECTED => Err(LedgerError::Rejected),
+        SW_LOCKED => Err(LedgerError::Locked),
+        SW_INS_NOT_SUPPORTED => Err(LedgerError::Unsupported),
+        // "CLA not supported" from the dashboard or another app, and the
+        // newer firmware's explicit "app not open".
+        0x6E00 | 0x6E01 | 0x6511 => Err(LedgerError::AppNotOpen),
+        other => Err(LedgerError::Status(other)),
+    }
+}
+
+/// Serialize a hardened BIP-32 path as big-endian `u32`s.
+///
+/// At most [`MAX_PATH_COMPONENTS`], so the path fits in the single APDU that
+/// asks for the public key.
+fn parse_hd_path(path: &str) -> Result<Vec<u8>, LedgerError> {
+    let invalid = || LedgerError::InvalidPath(path.to_string());
+    let components = path.strip_prefix("m/").unwrap_or(path);
+    if components.split('/').count() > MAX_PATH_COMPONENTS {
+        return Err(invalid());
+    }
+    let mut bytes = Vec::new();
+    for component in components.split('/') {
+        let index = component
+            .strip_suffix('\'')
+            .or_else(|| component.strip_suffix('h'))
+            .ok_or_else(invalid)?;
+        let index: u32 = index.parse().map_err(|_| invalid())?;
+        if index >= 1 << 31 {
+            return Err(invalid());
+        }
+        bytes.extend_from_slice(&(index | (1 << 31)).to_be_bytes());
+    }
+    Ok(bytes)
+}
+
+struct LedgerBackend(Arc<LedgerDevice>);
+
+impl SigningBackend for LedgerBackend {
+    fn sign(&self, _message: &[u8]) -> BoxFuture<'_, Result<Signature, SignerError>> {
+        Box::pin(async {
+            Err(SignerError::SigningFailed(
+                "the NEAR Ledger app does not sign raw bytes; sign a transaction, \
+                 delegate action or NEP-413 message instead"
+                    .to_string(),
+            ))
+        })
+    }
+
+    fn sign_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> BoxFuture<'_, Result<Signature, SignerError>> {
+        let payload =
+            borsh::to_vec(transaction).expect("transaction serialization should never fail");
+        Box::pin(async move { Ok(self.0.sign(INS_SIGN_TRANSACTION, payload).await?) })
+    }
+
+    fn sign_delegate_action(
+        &self,
+        delegate_action: &DelegateAction,
+    ) -> BoxFuture<'_, Result<Signature, SignerError>> {
+        let payload = borsh::to_vec(delegate_action)
+            .expect("delegate action serialization should never fail");
+        Box::pin(async move {
+            Ok(self
+                .0
+                .sign(INS_SIGN_NEP366_DELEGATE_ACTION, payload)
+                .await?)
+        })
+    }
+
+    fn sign_nep413(
+        &self,
+        params: &SignMessageParams,
+    ) -> BoxFuture<'_, Result<Signature, SignerError>> {
+        let payload = nep413::payload_bytes(params);
+        Box::pin(async move { Ok(self.0.sign(INS_SIGN_NEP413_MESSAGE, payload).await?) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::types::{
+        Action, CryptoHash, DELEGATE_ACTION_PREFIX, Gas, NearToken, NonDelegateAction, SecretKey,
+    };
+
+    /// A scripted NEAR app holding `secret` at the default path.
+    struct Device {
+        secret: SecretKey,
+        apdus: Mutex<Vec<Vec<u8>>>,
+        pending: Mutex<Vec<u8>>,
+        status: Mutex<Option<u16>>,
+    }
+
+    impl Device {
+        fn new() -> Arc<Self> {
+            Arc::new(Self {
+                secret: SecretKey::generate_ed25519(),
+                apdus: Mutex::new(Vec::new()),
+                pending: Mutex::new(Vec::new()),
+                status: Mutex::new(None),
+            })
+        }
+
+        fn answer(&self, apdu: &[u8]) -> Vec<u8> {
+            let reply = |mut data: Vec<u8>| {
+                data.extend_from_slice(&SW_OK.to_be_bytes());
+                data
+            };
+            if let Some(status) = *self.status.lock().unwrap() {
+                return status.to_be_bytes().to_vec();
+            }
+            let (header, data) = apdu.split_at(5);
+            assert_eq!(&header[..1], &[CLA]);
+            assert_eq!(header[3], NETWORK_ID);
+            assert_eq!(header[4] as usize, data.len());
+            let (ins, p1) = (header[1], header[2]);
+            if ins == INS_GET_PUBLIC_KEY {
+                assert_eq!(data, parse_hd_path(LedgerSigner::DEFAULT_HD_PATH).unwrap());
+                return reply(self.secret.public_key().as_bytes().to_vec());
+            }
+
+            let mut pending = self.pending.lock().unwrap();
+            pending.extend_from_slice(data);
+            if p1 != P1_SIGN_LAST_CHUNK {
+                return reply(Vec::new());
+            }
+            let payload = std::mem::take(&mut *pending).split_off(20);
+            let signed = match ins {
+                INS_SIGN_TRANSACTION => payload,
+                INS_SIGN_NEP413_MESSAGE => {
+                    [&nep413::NEP413_TAG.to_le_bytes()[..], &payload].concat()
+                }
+                INS_SIGN_NEP366_DELEGATE_ACTION => {
+                    [&DELEGATE_ACTION_PREFIX.to_le_bytes()[..], &payload].concat()
+                }
+                _ => return SW_INS_NOT_SUPPORTED.to_be_bytes().to_vec(),
+            };
+            let signature = self.secret.sign(CryptoHash::hash(&signed).as_bytes());
+            reply(signature.as_bytes().to_vec())
+        }
+    }
+
+    impl LedgerTransport for Device {
+        fn exchange(&self, apdu: Vec<u8>) -> BoxFuture<'_, Result<Vec<u8>, LedgerError>> {
+            let response = self.answer(&apdu);
+            self.apdus.lock().unwrap().push(apdu);
+            Box::pin(async move { Ok(response) })
+        }
+    }
+
+    async fn connect(device: &Arc<Device>) -> LedgerSigner {
+        LedgerSigner::connect(device.clone(), "alice.near")
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_hd_path() {
+        let expected = [
+            0x8000_002C_u32,
+            0x8000_018D,
+            0x8000_0000,
+            0x8000_0000,
+            0x8000_0001,
+        ]
+        .iter()
+        .flat_map(|index| index.to_be_bytes())
+        .collect::<Vec<_>>();
+        assert_eq!(parse_hd_path("44'/397'/0'/0'/1'").unwrap(), expected);
+        assert_eq!(parse_hd_path("m/44h/397h/0h/0h/1h").unwrap(), expected);
+        let too_deep = ["0'"; MAX_PATH_COMPONENTS + 1].join("/");
+        assert_eq!(
+            parse_hd_path(&["0'"; MAX_PATH_COMPONENTS].join("/"))
+                .unwrap()
+                .len(),
+            4 * MAX_PATH_COMPONENTS
+        );
+        for bad in ["44'/397'/0", "44'/x'/0'", "", "2147483648'", &too_deep] {
+            assert!(matches!(
+                parse_hd_path(bad),
+                Err(LedgerError::InvalidPath(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_signs_transaction_in_chunks() {
+        let device = Device::new();
+        let signer = connect(&device).await;
+        assert_eq!(*signer.public_key(), device.secret.public_key());
+
+        let tx = Transaction::new(
+            "alice.near".parse().unwrap(),
+            signer.public_key().clone(),
+            1,
+            "contract.near".parse().unwrap(),
+            CryptoHash::ZERO,
+            vec![Action::function_call(
+                "store",
+                vec![7; 600],
+                Gas::from_tgas(30),
+                NearToken::from_yoctonear(0),
+            )],
+        );
+        let signature = signer.key().sign_transaction(&tx).await.unwrap();
+        assert_eq!(signature, device.secret.sign(tx.get_hash().as_bytes()));
+
+        let apdus = device.apdus.lock().unwrap();
+        let sign: Vec<_> = apdus
+            .iter()
+            .filter(|apdu| apdu[1] == INS_SIGN_TRANSACTION)
+            .collect();
+        assert!(sign.len() > 2);
+        assert!(sign.iter().all(|apdu| apdu.len() <= 5 + CHUNK_SIZE));
+        let (last, rest) = sign.split_last().unwrap();
+        assert_eq!(last[2], P1_SIGN_LAST_CHUNK);
+        assert!(rest.iter().all(|apdu| apdu[2] == P1_SIGN_NORMAL));
+    }
+
+    #[tokio::test]
+    async fn test_signs_delegate_action_and_nep413() {
+        let device = Device::new();
+        let signer = connect(&device).await;
+        let local = SigningKey::new(device.secret.clone());
+
+        let delegate_action = DelegateAction {
+            sender_id: "alice.near".parse().unwrap(),
+            receiver_id: "bob.near".parse().unwrap(),
+            actions: vec![
+                NonDelegateAction::from_action(Action::transfer(NearToken::from_near(1))).unwrap(),
+            ],
+            nonce: 5,
+            max_block_height: 100,
+            public_key: signer.public_key().clone(),
+        };
+        assert_eq!(
+            signer
+                .key()
+                .sign_delegate_action(&delegate_action)
+                .await
+                .unwrap(),
+            local.sign_delegate_action(&delegate_action).await.unwrap()
+        );
+
+        let params = SignMessageParams {
+            message: "Login to MyApp".to_string(),
+            recipient: "myapp.com".to_string(),
+            nonce: [3; 32],
+            callback_url: None,
+            state: None,
+        };
+        let account_id = signer.account_id().clone();
+        let signed = signer
+            .key()
+            .sign_nep413(&account_id, &params)
+            .await
+            .unwrap();
+        assert_eq!(
+            signed.signature,
+            local
+                .sign_nep413(&account_id, &params)
+                .await
+                .unwrap()
+                .signature
+        );
+
+        assert!(matches!(
+            signer.key().sign(b"raw").await,
+            Err(SignerError::SigningFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_maps_status_words() {
+        let device = Device::new();
+        let signer = connect(&device).await;
+
+        *device.status.lock().unwrap() = Some(SW_REJECTED);
+        assert!(matches!(
+            signer.confirm_public_key().await,
+            Err(Error::Signing(SignerError::Ledger(LedgerError::Rejected)))
+        ));
+
+        *device.status.lock().unwrap() = Some(0x6E01);
+        let err = LedgerSigner::connect(device.clone(), "alice.near")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Signing(SignerError::Ledger(LedgerError::AppNotOpen))
+        ));
+
+        *device.status.lock().unwrap() = None;
+        signer.confirm_public_key().await.unwrap();
+    }
+}
@@ -7,6 +7,10 @@
 //! - [`RpcClient`] — Low-level JSON-RPC client with retry logic
 //! - [`RpcTransport`] — Pluggable HTTP layer under [`RpcClient`] (reqwest by
 //!   default; `wasi:http` on `wasm32-wasip2`)
+//! - [`NonceStore`] — Where transaction nonces are tracked (in memory by
+//!   default; `FileNonceStore` shares them between processes)
+//! - [`CacheStore`] — Where cached RPC results are kept when
+//!   [`CacheConfig`] enables caching ([`LruCacheStore`] by default)
 //!
 //! # Signers
 //!
@@ -27,9 +31,18 @@
 //! - [`AccountQuery`] — Get full account info
 //! - [`AccountExistsQuery`] — Check if account exists
 //! - [`AccessKeysQuery`] — List access keys
+//! - [`StateChangesQuery`] — State changes (with causes) in a block
 //! - [`TransactionStatusQuery`] — Poll or wait for transaction progress
+//! - [`ReceiptQuery`] / [`ReceiptWait`] — Wait for one receipt of a
+//!   transaction to execute
 //! - [`ViewCall`] — Call view functions on contracts
 //!
+//! # Streams
+//!
+//! - [`BlockStream`] — Follow new blocks at a chosen finality
+//! - [`AccountActivityStream`] — Follow the transactions and receipts
+//!   touching one account
+//!
 //! # Transaction Builders
 //!
 //! Transaction builders provide a fluent API for write operations:
@@ -37,38 +50,84 @@
 //! - [`TransactionBuilder`] — Multi-action transaction builder
 //! - [`CallBuilder`] — Function call builder (part of transactions)
 //! - [`FunctionCall`] — Standalone function call for composable transactions
+//! - [`TransactionSimulation`] — Cost estimate and pre-flight checks from
+//!   [`TransactionBuilder::simulate`]
 
 // Everything that talks to the network lives behind the `rpc` feature; the
 // signers stay available in offline builds (they only do local cryptography).
 #[cfg(feature = "rpc")]
+mod batch;
+#[cfg(feature = "rpc")]
+mod cache;
+#[cfg(feature = "rpc")]
+mod failover;
+#[cfg(feature = "rpc")]
+mod http_signer;
+#[cfg(feature = "rpc")]
 mod near;
 #[cfg(feature = "rpc")]
 mod nonce_manager;
 #[cfg(feature = "rpc")]
 mod query;
 #[cfg(feature = "rpc")]
+mod rate_limit;
+#[cfg(feature = "rpc")]
+mod receipt;
+#[cfg(feature = "rpc")]
+mod rotation;
+#[cfg(feature = "rpc")]
 mod rpc;
 mod signer;
 #[cfg(feature = "rpc")]
+mod simulation;
+#[cfg(feature = "rpc")]
+mod stream;
+#[cfg(feature = "rpc")]
 mod transaction;
 #[cfg(feature = "rpc")]
 mod transport;
 
+mod credential_store;
+#[cfg(feature = "encrypted-keystore")]
+mod encrypted_signer;
 #[cfg(feature = "keyring")]
 mod keyring_signer;
+mod ledger_signer;
 
+#[cfg(feature = "rpc")]
+pub use batch::{BatchHandle, BatchResponse, RpcBatch};
+#[cfg(feature = "rpc")]
+pub use cache::{CacheConfig, CacheStore, LruCacheStore};
+#[cfg(feature = "rpc")]
+pub use failover::{EndpointSelection, EndpointStatus, FailoverTransport};
+#[cfg(feature = "rpc")]
+pub use http_signer::HttpSigner;
 #[cfg(feature = "rpc")]
 pub use near::{Near, NearBuilder, SANDBOX_ROOT_ACCOUNT, SANDBOX_ROOT_SECRET_KEY, SandboxNetwork};
+#[cfg(all(feature = "rpc", not(target_arch = "wasm32")))]
+pub use nonce_manager::FileNonceStore;
+#[cfg(feature = "rpc")]
+pub use nonce_manager::{InMemoryNonceStore, NonceKey, NonceStore};
 #[cfg(feature = "rpc")]
 pub use query::{
     AccessKeysQuery, AccountExistsQuery, AccountQuery, BalanceQuery, ContractCodeQuery,
-    GlobalContractQuery, TransactionStatusQuery, ViewCall, ViewCallBorsh,
+    GlobalContractQuery, StateChangesQuery, TransactionStatusQuery, ViewCall, ViewCallBorsh,
 };
 #[cfg(feature = "rpc")]
+pub use rate_limit::RateLimitConfig;
+#[cfg(feature = "rpc")]
+pub use receipt::{ReceiptQuery, ReceiptWait};
+#[cfg(feature = "rpc")]
+pub use rotation::{KeyRotation, RotatedKey, RotationPlan};
+#[cfg(feature = "rpc")]
 pub use rpc::{RetryConfig, RpcClient};
 #[cfg(feature = "file-signer")]
 pub use signer::FileSigner;
-pub use signer::{EnvSigner, InMemorySigner, RotatingSigner, Signer, SigningKey};
+pub use signer::{EnvSigner, InMemorySigner, RotatingSigner, Signer, SigningBackend, SigningKey};
+#[cfg(feature = "rpc")]
+pub use simulation::{SimulationFailure, TransactionSimulation};
+#[cfg(feature = "rpc")]
+pub use stream::{AccountActivity, AccountActivityStream, BlockStream};
 #[cfg(feature = "rpc")]
 pub use transaction::{
     CallBuilder, DelegateOptions, DelegateResult, FunctionCall, SignedTransactionSend,
@@ -90,5 +149,11 @@ pub use transport::ReqwestTransport;
 ))]
 pub use transport::WasiHttpTransport;
 
+pub use credential_store::CredentialStore;
+#[cfg(feature = "file-signer")]
+pub use credential_store::FileCredentialStore;
+#[cfg(feature = "encrypted-keystore")]
+pub use encrypted_signer::{EncryptedFileSigner, EncryptedKeystore};
 #[cfg(feature = "keyring")]
-pub use keyring_signer::KeyringSigner;
+pub use keyring_signer::{KeyringCredentialStore, KeyringSigner};
+pub use ledger_signer::{LedgerSigner, LedgerTransport};
@@ -8,7 +8,7 @@ use crate::contract::ContractClient;
 use crate::error::Error;
 use crate::types::{
     AccountId, ChainId, Gas, IntoGlobalContractId, IntoNearToken, NearToken, PublicKey,
-    PublishMode, StateInit, TryIntoAccountId,
+    PublishMode, StateChangesRequest, StateInit, TryIntoAccountId,
 };
 // Only used by `Near::sandbox`, which needs a built-in transport (see below).
 #[cfg(any(
@@ -17,12 +17,19 @@ use crate::types::{
 ))]
 use crate::types::SecretKey;
 
+use super::cache::CacheConfig;
+use super::failover::FailoverTransport;
+use super::nonce_manager::NonceStore;
 use super::query::{
     AccessKeysQuery, AccountExistsQuery, AccountQuery, BalanceQuery, ContractCodeQuery,
-    GlobalContractQuery, TransactionStatusQuery, ViewCall,
+    GlobalContractQuery, StateChangesQuery, TransactionStatusQuery, ViewCall,
 };
+use super::rate_limit::RateLimitConfig;
+use super::receipt::{ReceiptQuery, ReceiptWait};
+use super::rotation::KeyRotation;
 use super::rpc::{MAINNET, RetryConfig, RpcClient, TESTNET};
 use super::signer::{InMemorySigner, Signer};
+use super::stream::{self, AccountActivityStream, BlockStream};
 use super::transaction::{CallBuilder, SignedTransactionSend, TransactionBuilder};
 use super::transport::RpcTransport;
 // The module itself is only referenced for the built-in transports, which
@@ -592,6 +599,33 @@ impl Near {
         GlobalContractQuery::new(self.rpc.clone(), id.into_identifier())
     }
 
+    /// Get state changes for a set of accounts in one block.
+    ///
+    /// Covers every `EXPERIMENTAL_changes` kind — account, storage (by key
+    /// prefix), access key, gas key and contract code changes — each entry
+    /// paired with the transaction or receipt that caused it. Returns a query
+    /// builder that can be pinned to a block before awaiting.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example() -> Result<(), near_kit::Error> {
+    /// let near = Near::testnet().build();
+    /// let alice: AccountId = "alice.testnet".parse()?;
+    ///
+    /// let changes = near
+    ///     .state_changes(StateChangesRequest::all_access_key_changes([alice]))
+    ///     .finality(Finality::Optimistic)
+    ///     .await?;
+    /// println!("{} key changes in {}", changes.changes.len(), changes.block_hash);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn state_changes(&self, request: StateChangesRequest) -> StateChangesQuery {
+        StateChangesQuery::new(self.rpc.clone(), request)
+    }
+
     // ========================================================================
     // Validator / Epoch Queries
     // ========================================================================
@@ -835,6 +869,29 @@ impl Near {
         self.transaction(account_id).delete_key(public_key)
     }
 
+    /// Replace an access key of the signer's account with a new key carrying
+    /// the same permission.
+    ///
+    /// Awaiting the returned [`KeyRotation`] sends one transaction that adds
+    /// the replacement and deletes `old_public_key`, and returns a client
+    /// signing with the new key. Use [`dry_run`](KeyRotation::dry_run) to see
+    /// the planned actions without sending them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example(near: Near, old_key: PublicKey) -> Result<(), Error> {
+    /// let rotated = near.rotate_key(old_key).key_type(KeyType::Ed25519).await?;
+    /// println!("new key: {}", rotated.public_key);
+    /// let near = rotated.near;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rotate_key(&self, old_public_key: PublicKey) -> KeyRotation {
+        KeyRotation::new(self.clone(), old_public_key)
+    }
+
     // ========================================================================
     // Multi-Action Transactions
     // ========================================================================
@@ -1002,6 +1059,122 @@ impl Near {
         TransactionStatusQuery::new(self.rpc.clone(), *tx_hash, sender_id)
     }
 
+    /// Wait for a receipt to execute and return its outcome.
+    ///
+    /// Looks up the receipt's transaction with `EXPERIMENTAL_receipt_to_tx`
+    /// (nearcore 2.12 or later) and polls its status until the receipt has
+    /// executed, however long after the transaction itself that is — useful
+    /// for callbacks and resumed yields. See [`ReceiptQuery`] for the poll
+    /// interval and timeout.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example(near: &Near, receipt_id: CryptoHash) -> Result<(), Error> {
+    /// let outcome = near.receipt(&receipt_id).await?;
+    /// if let ExecutionStatus::Failure(err) = &outcome.outcome.status {
+    ///     println!("Receipt failed: {err}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn receipt(&self, receipt_id: &crate::types::CryptoHash) -> ReceiptQuery {
+        ReceiptQuery::new(self.rpc.clone(), *receipt_id)
+    }
+
+    /// Wait for the first receipt of a transaction matching `predicate` to
+    /// execute and return its outcome.
+    ///
+    /// The predicate sees each executed receipt as an
+    /// [`ExecutionNode`](crate::types::ExecutionNode), in execution order. Use
+    /// it to wait on a specific cross-contract callback rather than on the
+    /// transaction as a whole. See [`ReceiptWait`] for the poll interval and
+    /// timeout.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example(near: &Near, tx_hash: CryptoHash) -> Result<(), Error> {
+    /// let outcome = near
+    ///     .wait_for_receipts(&tx_hash, "alice.testnet", |receipt| {
+    ///         receipt.executor_id().as_str() == "usdt.tether-token.near"
+    ///             && receipt.method_names().any(|method| method == "ft_resolve_transfer")
+    ///     })
+    ///     .await?;
+    /// println!("Callback executed in block {}", outcome.block_hash);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn wait_for_receipts(
+        &self,
+        tx_hash: &crate::types::CryptoHash,
+        sender_id: impl crate::types::TryIntoAccountId,
+        predicate: impl Fn(&crate::types::ExecutionNode<'_>) -> bool + Send + Sync + 'static,
+    ) -> ReceiptWait {
+        ReceiptWait::new(self.rpc.clone(), *tx_hash, sender_id, predicate)
+    }
+
+    // ========================================================================
+    // Following the Chain
+    // ========================================================================
+
+    /// Stream new blocks as they are produced.
+    ///
+    /// Yields every block at `final` finality, in order, starting at the
+    /// current head; see [`BlockStream`] for choosing the finality, the poll
+    /// interval or a starting height, and for how gaps and reorgs are handled.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// use futures::StreamExt;
+    ///
+    /// # async fn example() -> Result<(), Error> {
+    /// let near = Near::testnet().build();
+    /// let mut blocks = near.blocks();
+    /// while let Some(block) = blocks.next().await {
+    ///     let block = block?;
+    ///     for chunk in near.chunks(&block).await? {
+    ///         println!("{} txs in shard {}", chunk.transactions.len(), chunk.header.shard_id);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn blocks(&self) -> BlockStream {
+        BlockStream::new(self.rpc.clone())
+    }
+
+    /// Stream the transactions and receipts touching an account, from new
+    /// `final` blocks.
+    ///
+    /// Shorthand for `near.blocks().watch_account(account_id)`; configure
+    /// the underlying [`BlockStream`] first to change the finality or start
+    /// height.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `account_id` is not a valid account ID.
+    pub fn watch_account(&self, account_id: impl TryIntoAccountId) -> AccountActivityStream {
+        self.blocks().watch_account(account_id)
+    }
+
+    /// Fetch the chunks produced in `block`.
+    ///
+    /// Blocks only carry chunk headers. This fetches the full chunks (with
+    /// their transactions and receipts) for the shards that produced a new
+    /// chunk at the block's height, skipping headers carried over from an
+    /// earlier block.
+    pub async fn chunks(
+        &self,
+        block: &crate::types::BlockView,
+    ) -> Result<Vec<crate::types::ChunkView>, Error> {
+        stream::block_chunks(&self.rpc, block).await
+    }
+
     // ========================================================================
     // Convenience methods
     // ========================================================================
@@ -1181,6 +1354,72 @@ impl Near {
             self.max_nonce_retries,
         ))
     }
+
+    /// Get a multi token client for a NEP-245 contract.
+    ///
+    /// Accepts either a string/`AccountId` for raw addresses, or a contract
+    /// identifier that implements [`IntoContractId`].
+    ///
+    /// [`IntoContractId`]: crate::tokens::IntoContractId
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example() -> Result<(), near_kit::Error> {
+    /// let near = Near::mainnet().build();
+    /// let mt = near.mt("intents.near")?;
+    ///
+    /// let balance = mt.balance_of("alice.near", "nep141:usdc.near").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn mt(
+        &self,
+        contract: impl crate::tokens::IntoContractId,
+    ) -> Result<crate::tokens::MultiToken, Error> {
+        let contract_id = contract.into_contract_id(&self.chain_id)?;
+        Ok(crate::tokens::MultiToken::new(
+            self.rpc.clone(),
+            self.signer.clone(),
+            contract_id,
+            self.max_nonce_retries,
+        ))
+    }
+
+    /// Get a storage management client for any NEP-145 contract.
+    ///
+    /// Accepts either a string/`AccountId` for raw addresses, or a contract
+    /// identifier that implements [`IntoContractId`].
+    ///
+    /// [`IntoContractId`]: crate::tokens::IntoContractId
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example() -> Result<(), near_kit::Error> {
+    /// let near = Near::mainnet()
+    ///     .credentials("ed25519:...", "alice.near")?
+    ///     .build();
+    /// let storage = near.storage("v2.ref-finance.near")?;
+    ///
+    /// storage.ensure_registered("alice.near").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn storage(
+        &self,
+        contract: impl crate::tokens::IntoContractId,
+    ) -> Result<crate::tokens::StorageManagement, Error> {
+        let contract_id = contract.into_contract_id(&self.chain_id)?;
+        Ok(crate::tokens::StorageManagement::new(
+            self.rpc.clone(),
+            self.signer.clone(),
+            contract_id,
+            self.max_nonce_retries,
+        ))
+    }
 }
 
 impl std::fmt::Debug for Near {
@@ -1220,8 +1459,14 @@ pub struct NearBuilder {
     /// a transport never constructs the built-in one (which doesn't even exist
     /// on WASI without the `wasi-http` feature).
     transport: Option<Arc<dyn RpcTransport>>,
+    /// Every endpoint from [`NearBuilder::rpc_urls`]; the transport is wrapped
+    /// in a [`FailoverTransport`] over them at build time.
+    failover_urls: Option<Vec<String>>,
     signer: Option<Arc<dyn Signer>>,
     retry_config: RetryConfig,
+    rate_limit: RateLimitConfig,
+    nonce_store: Option<Arc<dyn NonceStore>>,
+    cache: Option<CacheConfig>,
     chain_id: ChainId,
     max_nonce_retries: u32,
 }
@@ -1232,8 +1477,12 @@ impl NearBuilder {
         Self {
             rpc_url: rpc_url.into(),
             transport: None,
+            failover_urls: None,
             signer: None,
             retry_config: RetryConfig::default(),
+            rate_limit: RateLimitConfig::default(),
+            nonce_store: None,
+            cache: None,
             chain_id,
             max_nonce_retries: 3,
         }
@@ -1304,6 +1553,17 @@ impl NearBuilder {
         self
     }
 
+    /// Pace RPC requests with client-side rate limits.
+    ///
+    /// Unlike [`NearBuilder::retry_config`], which only reacts to failures,
+    /// this bounds the steady-state request rate, the number of concurrent
+    /// requests, and per-method budgets — see [`RateLimitConfig`]. Clones of
+    /// the built [`Near`] share one budget. No limits apply by default.
+    pub fn rate_limit(mut self, config: RateLimitConfig) -> Self {
+        self.rate_limit = config;
+        self
+    }
+
     /// Use a preconfigured HTTP client for RPC requests.
     ///
     /// This allows callers to configure transport concerns such as default
@@ -1332,6 +1592,41 @@ impl NearBuilder {
         self
     }
 
+    /// Spread requests over several RPC endpoints with automatic failover.
+    ///
+    /// Replaces the network's default URL. Requests go to the first endpoint
+    /// and move down the list when an endpoint fails with a retryable error
+    /// or reports that it is behind (`UNKNOWN_BLOCK`, `NOT_SYNCED_YET`);
+    /// repeatedly failing endpoints are skipped for a cooldown. The transport
+    /// (built-in, or one set with [`NearBuilder::transport`]) is wrapped in a
+    /// [`FailoverTransport`] with its default settings — construct one
+    /// yourself and pass it to [`NearBuilder::transport`] to pick a different
+    /// [`EndpointSelection`](super::EndpointSelection) or circuit-breaker
+    /// tuning.
+    ///
+    /// ```rust
+    /// use near_kit::Near;
+    ///
+    /// let near = Near::mainnet()
+    ///     .rpc_urls([
+    ///         "https://free.rpc.fastnear.com",
+    ///         "https://rpc.mainnet.near.org",
+    ///     ])
+    ///     .build();
+    /// assert_eq!(near.rpc_url(), "https://free.rpc.fastnear.com");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `urls` is empty.
+    pub fn rpc_urls(mut self, urls: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let urls: Vec<String> = urls.into_iter().map(Into::into).collect();
+        assert!(!urls.is_empty(), "rpc_urls needs at least one endpoint");
+        self.rpc_url = urls[0].clone();
+        self.failover_urls = Some(urls);
+        self
+    }
+
     /// Set the maximum number of transaction send attempts on `InvalidNonce` errors.
     ///
     /// When a transaction fails with `InvalidNonce`, the client automatically
@@ -1345,6 +1640,32 @@ impl NearBuilder {
         self
     }
 
+    /// Share nonce bookkeeping through a custom [`NonceStore`].
+    ///
+    /// By default every client in the process draws nonces from one
+    /// [`InMemoryNonceStore`](super::InMemoryNonceStore), so parallel sends
+    /// from a single process never collide. Workers in *separate* processes
+    /// signing with the same key need a shared store — `FileNonceStore` for
+    /// processes on one host, or your own [`NonceStore`] over a database row
+    /// lock or similar. Otherwise they race for the same nonces and spend
+    /// their [`max_nonce_retries`](Self::max_nonce_retries) recovering.
+    pub fn nonce_store(mut self, store: impl NonceStore + 'static) -> Self {
+        self.nonce_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Cache RPC results: immutable ones (queries pinned to a block height or
+    /// hash, blocks by hash, final transaction statuses) until evicted,
+    /// `optimistic`/`final` ones for a short TTL.
+    ///
+    /// Off by default. See [`CacheConfig`] for what is cached and how the
+    /// store is sized or replaced. Clones of the built [`Near`] share one
+    /// cache.
+    pub fn cache(mut self, config: CacheConfig) -> Self {
+        self.cache = Some(config);
+        self
+    }
+
     /// Build the client.
     ///
     /// # Panics
@@ -1370,12 +1691,21 @@ impl NearBuilder {
             "no built-in HTTP transport on WASI without the `wasi-http` feature; \
              supply one with `NearBuilder::transport`",
         );
+        let transport: Arc<dyn RpcTransport> = match self.failover_urls {
+            Some(urls) => Arc::new(FailoverTransport::with_transport(urls, transport)),
+            None => transport,
+        };
+        let mut rpc =
+            RpcClient::with_transport_and_retry_config(self.rpc_url, transport, self.retry_config)
+                .with_rate_limit(self.rate_limit);
+        if let Some(store) = self.nonce_store {
+            rpc = rpc.with_nonce_store(store);
+        }
+        if let Some(config) = self.cache {
+            rpc = rpc.with_cache(config);
+        }
         Near {
-            rpc: Arc::new(RpcClient::with_transport_and_retry_config(
-                self.rpc_url,
-                transport,
-                self.retry_config,
-            )),
+            rpc: Arc::new(rpc),
             signer: self.signer,
             chain_id: self.chain_id,
             max_nonce_retries: self.max_nonce_retries,
@@ -1495,6 +1825,55 @@ mod tests {
         assert!(!near.rpc_url().is_empty());
     }
 
+    #[tokio::test]
+    async fn test_near_builder_rpc_urls_fails_over() {
+        use std::sync::Mutex;
+
+        use crate::client::transport::{BoxFuture, TransportResponse};
+        use crate::error::RpcError;
+
+        /// Transport whose first endpoint is down; records every URL hit.
+        #[derive(Default)]
+        struct HalfDownTransport {
+            hits: Mutex<Vec<String>>,
+        }
+
+        impl RpcTransport for HalfDownTransport {
+            fn post_json(
+                &self,
+                url: &str,
+                _body: Vec<u8>,
+            ) -> BoxFuture<'_, Result<TransportResponse, RpcError>> {
+                self.hits.lock().unwrap().push(url.to_string());
+                let down = url.contains("down");
+                Box::pin(async move {
+                    if down {
+                        return Err(RpcError::network("connection refused", None, true));
+                    }
+                    Ok(TransportResponse::new(
+                        200,
+                        br#"{"jsonrpc":"2.0","id":0,"result":{"ok":true}}"#.to_vec(),
+                    ))
+                })
+            }
+        }
+
+        let inner = Arc::new(HalfDownTransport::default());
+        let near = Near::testnet()
+            .transport(inner.clone())
+            .rpc_urls(["https://down.example", "https://up.example"])
+            .retry_config(RetryConfig::none())
+            .build();
+        assert_eq!(near.rpc_url(), "https://down.example");
+
+        let response: serde_json::Value = near.rpc().call("status", ()).await.unwrap();
+        assert_eq!(response, serde_json::json!({ "ok": true }));
+        assert_eq!(
+            *inner.hits.lock().unwrap(),
+            ["https://down.example", "https://up.example"]
+        );
+    }
+
     #[test]
     fn test_near_builder_from_trait() {
         let builder = Near::testnet();
@@ -1,32 +1,134 @@
-//! Nonce manager for concurrent transaction handling.
+//! Nonce stores for concurrent transaction handling.
 //!
 //! Prevents nonce collisions when sending multiple transactions in parallel
-//! by caching nonces in memory and incrementing them under a lock.
+//! by remembering the last nonce handed out for each access key and
+//! incrementing it under a lock. Where that memory lives is pluggable through
+//! [`NonceStore`]:
+//!
+//! - [`InMemoryNonceStore`] (the default) — a `Mutex<HashMap>` shared by every
+//!   client in the process.
+//! - `FileNonceStore` — one file per key behind an advisory file lock,
+//!   shared by every process on the host. Not available on wasm.
+//! - Anything else (a DB row lock, a Redis counter, ...) — implement
+//!   [`NonceStore`] and pass it to
+//!   [`NearBuilder::nonce_store`](super::NearBuilder::nonce_store).
 
-use std::{collections::HashMap, sync::Mutex};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
 
 use crate::PublicKey;
+use crate::error::Error;
+use crate::platform::{BoxFuture, MaybeSend, MaybeSync};
 use crate::types::AccountId;
 
-type Network = String;
+/// Identifies the access key a nonce belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NonceKey {
+    /// The network, as the RPC URL the client talks to.
+    pub network: String,
+    /// The account that owns the key.
+    pub account_id: AccountId,
+    /// The access key.
+    pub public_key: PublicKey,
+}
+
+impl NonceKey {
+    /// Create a key.
+    pub fn new(network: impl Into<String>, account_id: AccountId, public_key: PublicKey) -> Self {
+        Self {
+            network: network.into(),
+            account_id,
+            public_key,
+        }
+    }
+}
+
+/// Hands out transaction nonces.
+///
+/// Every signed transaction draws its nonce from the client's store. The
+/// contract is the one [`InMemoryNonceStore`] implements: given the nonce the
+/// chain reports for the key (or the one an `InvalidNonce` error carried),
+/// atomically store and return `max(last_handed_out, chain_nonce) + 1`. Two
+/// callers sharing a store must never receive the same nonce for the same
+/// [`NonceKey`], so the read-modify-write has to happen under whatever lock
+/// the backend offers — a mutex, a lock file, `SELECT ... FOR UPDATE`.
+///
+/// # Implementing
+///
+/// Like [`RpcTransport`](super::RpcTransport), the trait is object-safe and
+/// returns a boxed future ([`BoxFuture`]); wrap an async block in `Box::pin`.
+/// On native targets implementations must be `Send + Sync`.
+///
+/// ```rust,ignore
+/// struct PostgresNonceStore { pool: PgPool }
+///
+/// impl NonceStore for PostgresNonceStore {
+///     fn next_nonce<'a>(
+///         &'a self,
+///         key: &'a NonceKey,
+///         chain_nonce: u64,
+///     ) -> BoxFuture<'a, Result<u64, Error>> {
+///         Box::pin(async move {
+///             // UPDATE nonces SET nonce = GREATEST(nonce, $chain_nonce) + 1
+///             //   WHERE network = $1 AND account_id = $2 AND public_key = $3
+///             //   RETURNING nonce
+///             /* ... */
+///         })
+///     }
+/// }
+/// ```
+pub trait NonceStore: MaybeSend + MaybeSync {
+    /// Reserve the next nonce for `key`, given the nonce the chain reports.
+    ///
+    /// Failures should be reported as [`Error::NonceStore`].
+    fn next_nonce<'a>(
+        &'a self,
+        key: &'a NonceKey,
+        chain_nonce: u64,
+    ) -> BoxFuture<'a, Result<u64, Error>>;
+}
+
+/// Forwarding impl so an `Arc`-shared store (including `Arc<dyn NonceStore>`)
+/// can be passed to [`NearBuilder::nonce_store`](super::NearBuilder::nonce_store)
+/// and still be inspected or reused by the caller.
+impl<T: NonceStore + ?Sized> NonceStore for Arc<T> {
+    fn next_nonce<'a>(
+        &'a self,
+        key: &'a NonceKey,
+        chain_nonce: u64,
+    ) -> BoxFuture<'a, Result<u64, Error>> {
+        (**self).next_nonce(key, chain_nonce)
+    }
+}
+
+/// The store every client uses unless configured otherwise: one
+/// [`InMemoryNonceStore`] shared across the whole process.
+pub(crate) fn default_nonce_store() -> Arc<dyn NonceStore> {
+    static DEFAULT: OnceLock<Arc<InMemoryNonceStore>> = OnceLock::new();
+    DEFAULT
+        .get_or_init(|| Arc::new(InMemoryNonceStore::new()))
+        .clone()
+}
 
-/// Manages nonces for concurrent transactions.
+/// In-process nonce cache.
 ///
 /// Prevents nonce collisions when sending multiple transactions in parallel
-/// by caching nonces in memory and incrementing them under a lock.
-pub struct NonceManager {
+/// by caching nonces in memory and incrementing them under a lock. Clients
+/// share one instance by default; processes do not — see `FileNonceStore`
+/// for that.
+pub struct InMemoryNonceStore {
     /// Cached nonces: value = last nonce handed out for this key.
-    nonces: Mutex<HashMap<(Network, AccountId, PublicKey), u64>>,
+    nonces: Mutex<HashMap<NonceKey, u64>>,
 }
 
-impl Default for NonceManager {
+impl Default for InMemoryNonceStore {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl NonceManager {
-    /// Create a new nonce manager.
+impl InMemoryNonceStore {
+    /// Create an empty store.
     pub fn new() -> Self {
         Self {
             nonces: Mutex::new(HashMap::new()),
@@ -41,14 +143,21 @@ impl NonceManager {
     /// value stays ahead of the (stale) chain nonce.
     pub fn next(
         &self,
-        network: impl Into<Network>,
+        network: impl Into<String>,
         account_id: AccountId,
         public_key: PublicKey,
         blockchain_nonce: u64,
     ) -> u64 {
+        self.next_for(
+            NonceKey::new(network, account_id, public_key),
+            blockchain_nonce,
+        )
+    }
+
+    fn next_for(&self, key: NonceKey, blockchain_nonce: u64) -> u64 {
         let mut nonces = self.nonces.lock().unwrap();
         let nonce = nonces
-            .entry((network.into(), account_id, public_key))
+            .entry(key)
             .and_modify(|n| *n = (*n).max(blockchain_nonce))
             .or_insert(blockchain_nonce);
         *nonce += 1;
@@ -56,13 +165,173 @@ impl NonceManager {
     }
 }
 
+impl NonceStore for InMemoryNonceStore {
+    fn next_nonce<'a>(
+        &'a self,
+        key: &'a NonceKey,
+        chain_nonce: u64,
+    ) -> BoxFuture<'a, Result<u64, Error>> {
+        let nonce = self.next_for(key.clone(), chain_nonce);
+        Box::pin(async move { Ok(nonce) })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use file::FileNonceStore;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod file {
+    use std::fs::{self, OpenOptions};
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, Instant};
+
+    use fs4::fs_std::FileExt;
+    use sha2::{Digest, Sha256};
+
+    use super::{NonceKey, NonceStore};
+    use crate::error::Error;
+    use crate::platform::BoxFuture;
+
+    /// How often a waiter re-checks a held lock.
+    const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+    /// Nonce store shared by every process on one host.
+    ///
+    /// Each key gets a file in the store's directory holding the last nonce
+    /// handed out. A read-modify-write happens while holding an OS advisory
+    /// lock (`flock` on Unix, `LockFileEx` on Windows) on a sibling `.lock`
+    /// file, so worker processes signing with the same key take turns instead
+    /// of colliding on-chain and burning their `InvalidNonce` retries.
+    ///
+    /// The lock is held for a read and a write of a few bytes, and the
+    /// operating system releases it if its holder dies, so a crashed worker
+    /// never leaves a key locked. The file work runs on tokio's blocking
+    /// thread pool.
+    ///
+    /// ```rust,no_run
+    /// use near_kit::{FileNonceStore, Near};
+    ///
+    /// // Every worker points at the same directory.
+    /// let near = Near::mainnet()
+    ///     .nonce_store(FileNonceStore::new("/var/run/my-relayer/nonces"))
+    ///     .build();
+    /// ```
+    #[derive(Debug, Clone)]
+    pub struct FileNonceStore {
+        dir: PathBuf,
+        lock_timeout: Duration,
+    }
+
+    impl FileNonceStore {
+        /// Create a store keeping its files in `dir` (created on first use).
+        ///
+        /// Waits up to 10 s for a lock.
+        pub fn new(dir: impl Into<PathBuf>) -> Self {
+            Self {
+                dir: dir.into(),
+                lock_timeout: Duration::from_secs(10),
+            }
+        }
+
+        /// How long to wait for another process to release a key's lock
+        /// before failing with [`Error::NonceStore`].
+        pub fn lock_timeout(mut self, timeout: Duration) -> Self {
+            self.lock_timeout = timeout;
+            self
+        }
+
+        /// The directory holding the nonce and lock files.
+        pub fn dir(&self) -> &Path {
+            &self.dir
+        }
+
+        /// Path of `key`'s nonce file. Keys are hashed: network URLs are not
+        /// valid file names.
+        pub(super) fn nonce_path(&self, key: &NonceKey) -> PathBuf {
+            let mut hasher = Sha256::new();
+            hasher.update(key.network.as_bytes());
+            hasher.update([0]);
+            hasher.update(key.account_id.as_str().as_bytes());
+            hasher.update([0]);
+            hasher.update(key.public_key.to_string().as_bytes());
+            self.dir
+                .join(format!("{}.nonce", hex::encode(hasher.finalize())))
+        }
+
+        /// Blocking body of [`next_nonce`](NonceStore::next_nonce).
+        fn next_nonce_blocking(&self, path: &Path, chain_nonce: u64) -> Result<u64, Error> {
+            fs::create_dir_all(&self.dir).map_err(|e| io_error(&self.dir, e))?;
+
+            // The lock file stays in place: deleting it would let a waiter
+            // locking the old file and a newcomer creating a fresh one both
+            // get in. Closing it releases the lock.
+            let lock_path = path.with_extension("lock");
+            let lock = OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(&lock_path)
+                .map_err(|e| io_error(&lock_path, e))?;
+            let started = Instant::now();
+            while !lock
+                .try_lock_exclusive()
+                .map_err(|e| io_error(&lock_path, e))?
+            {
+                if started.elapsed() >= self.lock_timeout {
+                    return Err(Error::NonceStore(format!(
+                        "timed out waiting for lock {}",
+                        lock_path.display()
+                    )));
+                }
+                std::thread::sleep(LOCK_POLL_INTERVAL);
+            }
+
+            // A missing or unreadable file just means "nothing cached":
+            // the chain nonce takes over.
+            let cached = fs::read_to_string(path)
+                .ok()
+                .and_then(|s| s.trim().parse::<u64>().ok())
+                .unwrap_or(0);
+            let nonce = cached.max(chain_nonce) + 1;
+
+            // Write-then-rename so a crash never leaves a torn file.
+            let tmp = path.with_extension("tmp");
+            fs::write(&tmp, nonce.to_string()).map_err(|e| io_error(&tmp, e))?;
+            fs::rename(&tmp, path).map_err(|e| io_error(path, e))?;
+            Ok(nonce)
+        }
+    }
+
+    impl NonceStore for FileNonceStore {
+        fn next_nonce<'a>(
+            &'a self,
+            key: &'a NonceKey,
+            chain_nonce: u64,
+        ) -> BoxFuture<'a, Result<u64, Error>> {
+            let store = self.clone();
+            let path = self.nonce_path(key);
+            Box::pin(async move {
+                tokio::task::spawn_blocking(move || store.next_nonce_blocking(&path, chain_nonce))
+                    .await
+                    .map_err(|e| Error::NonceStore(format!("nonce store task failed: {e}")))?
+            })
+        }
+    }
+
+    fn io_error(path: &Path, e: std::io::Error) -> Error {
+        Error::NonceStore(format!("{}: {e}", path.display()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
 
     #[test]
     fn test_nonce_manager_caching() {
-        let manager = NonceManager::new();
+        let manager = InMemoryNonceStore::new();
 
         // First call: blockchain nonce is 10, should return 11
         let nonce1 = manager.next(
@@ -100,7 +369,7 @@ mod tests {
 
     #[test]
     fn test_chain_catches_up() {
-        let manager = NonceManager::new();
+        let manager = InMemoryNonceStore::new();
 
         let nonce1 = manager.next(
             "testnet",
@@ -126,7 +395,7 @@ mod tests {
 
     #[test]
     fn test_different_keys() {
-        let manager = NonceManager::new();
+        let manager = InMemoryNonceStore::new();
 
         let nonce_alice = manager.next(
             "testnet",
@@ -162,7 +431,7 @@ mod tests {
 
     #[test]
     fn test_different_networks() {
-        let manager = NonceManager::new();
+        let manager = InMemoryNonceStore::new();
 
         let nonce_testnet = manager.next(
             "testnet",
@@ -199,7 +468,7 @@ mod tests {
 
     #[test]
     fn test_ak_nonce_from_error() {
-        let manager = NonceManager::new();
+        let manager = InMemoryNonceStore::new();
 
         // Simulate: sent tx with nonce 11, got InvalidNonce with ak_nonce=100
         let nonce1 = manager.next(
@@ -237,7 +506,7 @@ mod tests {
 
     #[test]
     fn test_lower_ak_nonce_uses_cache() {
-        let manager = NonceManager::new();
+        let manager = InMemoryNonceStore::new();
 
         // Build up cache
         assert_eq!(
@@ -285,4 +554,80 @@ mod tests {
         );
         assert_eq!(nonce, 104); // max(103, 100) + 1
     }
+
+    fn key(network: &str) -> NonceKey {
+        NonceKey::new(
+            network,
+            "alice.testnet".parse().unwrap(),
+            "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+                .parse()
+                .unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_trait() {
+        let store = InMemoryNonceStore::new();
+        assert_eq!(store.next_nonce(&key("testnet"), 10).await.unwrap(), 11);
+        assert_eq!(store.next_nonce(&key("testnet"), 10).await.unwrap(), 12);
+        assert_eq!(store.next_nonce(&key("mainnet"), 10).await.unwrap(), 11);
+    }
+
+    #[tokio::test]
+    async fn test_file_store_shared_between_instances() {
+        let dir = tempfile::tempdir().unwrap();
+        // Two stores on one directory stand in for two processes.
+        let a = FileNonceStore::new(dir.path());
+        let b = FileNonceStore::new(dir.path());
+
+        assert_eq!(a.next_nonce(&key("testnet"), 10).await.unwrap(), 11);
+        assert_eq!(b.next_nonce(&key("testnet"), 10).await.unwrap(), 12);
+        assert_eq!(a.next_nonce(&key("testnet"), 20).await.unwrap(), 21);
+        assert_eq!(b.next_nonce(&key("mainnet"), 5).await.unwrap(), 6);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_file_store_concurrent_nonces_are_unique() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut tasks = Vec::new();
+        for _ in 0..8 {
+            let store = FileNonceStore::new(dir.path());
+            tasks.push(tokio::spawn(async move {
+                let mut nonces = Vec::new();
+                for _ in 0..10 {
+                    nonces.push(store.next_nonce(&key("testnet"), 0).await.unwrap());
+                }
+                nonces
+            }));
+        }
+
+        let mut all = Vec::new();
+        for task in tasks {
+            all.extend(task.await.unwrap());
+        }
+        all.sort_unstable();
+        assert_eq!(all, (1..=80).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_file_store_lock_timeout_and_leftover_lock_file() {
+        use fs4::fs_std::FileExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileNonceStore::new(dir.path()).lock_timeout(Duration::from_millis(20));
+
+        // A lock file left behind by a crashed process holds no lock.
+        let lock = store.nonce_path(&key("testnet")).with_extension("lock");
+        std::fs::write(&lock, "").unwrap();
+        assert_eq!(store.next_nonce(&key("testnet"), 1).await.unwrap(), 2);
+
+        // A held one makes waiters time out.
+        let held = std::fs::File::open(&lock).unwrap();
+        assert!(held.try_lock_exclusive().unwrap());
+        let err = store.next_nonce(&key("testnet"), 1).await.unwrap_err();
+        assert!(matches!(err, Error::NonceStore(_)), "{err:?}");
+
+        drop(held);
+        assert_eq!(store.next_nonce(&key("testnet"), 1).await.unwrap(), 3);
+    }
 }
@@ -12,8 +12,8 @@ use serde::de::DeserializeOwned;
 use crate::error::Error;
 use crate::types::{
     AccessKeyListView, AccountBalance, AccountId, AccountView, BlockReference, ContractCodeView,
-    CryptoHash, Finality, GlobalContractId, PublicKeyHandle, Submitted, TryIntoAccountId,
-    WaitLevel,
+    CryptoHash, Finality, GlobalContractId, PublicKeyHandle, StateChangesRequest,
+    StateChangesResponse, Submitted, TryIntoAccountId, WaitLevel,
 };
 
 use super::rpc::RpcClient;
@@ -815,6 +815,85 @@ impl IntoFuture for GlobalContractQuery {
     }
 }
 
+// ============================================================================
+// StateChangesQuery
+// ============================================================================
+
+/// Query builder for the state changes of a set of accounts in one block.
+///
+/// Wraps [`RpcClient::changes`]: each returned change carries the new value
+/// and the transaction or receipt that caused it.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use near_kit::*;
+/// # async fn example() -> Result<(), near_kit::Error> {
+/// let near = Near::testnet().build();
+/// let alice: AccountId = "alice.testnet".parse()?;
+///
+/// // Balance and storage changes in the latest final block
+/// let changes = near
+///     .state_changes(StateChangesRequest::account_changes([alice.clone()]))
+///     .await?;
+///
+/// // Contract storage writes under a key prefix, at a specific block
+/// let writes = near
+///     .state_changes(StateChangesRequest::data_changes([alice], b"STATE"))
+///     .at_block(100_000_000)
+///     .await?;
+/// for change in writes.changes {
+///     println!("{:?} caused by {:?}", change.value, change.cause);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct StateChangesQuery {
+    rpc: Arc<RpcClient>,
+    request: StateChangesRequest,
+    block_ref: BlockReference,
+}
+
+impl StateChangesQuery {
+    pub(crate) fn new(rpc: Arc<RpcClient>, request: StateChangesRequest) -> Self {
+        Self {
+            rpc,
+            request,
+            block_ref: BlockReference::default(),
+        }
+    }
+
+    /// Query at a specific block height.
+    pub fn at_block(mut self, height: u64) -> Self {
+        self.block_ref = BlockReference::Height(height);
+        self
+    }
+
+    /// Query at a specific block hash.
+    pub fn at_block_hash(mut self, hash: CryptoHash) -> Self {
+        self.block_ref = BlockReference::Hash(hash);
+        self
+    }
+
+    /// Query with specific finality.
+    pub fn finality(mut self, finality: Finality) -> Self {
+        self.block_ref = BlockReference::Finality(finality);
+        self
+    }
+}
+
+impl IntoFuture for StateChangesQuery {
+    type Output = Result<StateChangesResponse, Error>;
+    type IntoFuture = crate::platform::BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let changes = self.rpc.changes(&self.request, self.block_ref).await?;
+            Ok(changes)
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -836,4 +915,19 @@ mod tests {
             BlockReference::Finality(Finality::Optimistic)
         );
     }
+
+    #[test]
+    fn test_state_changes_query_builder() {
+        let rpc = Arc::new(RpcClient::new("http://localhost:3030"));
+        let account_id: AccountId = "alice.testnet".parse().unwrap();
+        let request = StateChangesRequest::account_changes([account_id]);
+
+        let query = StateChangesQuery::new(rpc.clone(), request.clone());
+        assert_eq!(query.block_ref, BlockReference::default());
+        assert_eq!(query.request, request);
+
+        let hash = CryptoHash::hash(b"block");
+        let query = StateChangesQuery::new(rpc, request).at_block_hash(hash);
+        assert_eq!(query.block_ref, BlockReference::Hash(hash));
+    }
 }
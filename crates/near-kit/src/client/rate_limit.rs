@@ -0,0 +1,315 @@
+//! Client-side request pacing for [`RpcClient`](super::RpcClient).
+//!
+//! [`RateLimitConfig`] describes the budget; the crate-internal
+//! [`RateLimiter`] enforces it in front of every HTTP request the client
+//! sends.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use super::rpc::async_sleep;
+use crate::platform::monotonic_millis;
+use crate::trace;
+
+/// Client-side rate limits for RPC requests.
+///
+/// Every limit is opt-in; the default config limits nothing. Configure it on
+/// [`NearBuilder::rate_limit`](super::NearBuilder::rate_limit):
+///
+/// ```rust
+/// use near_kit::{Near, RateLimitConfig};
+///
+/// let near = Near::mainnet()
+///     .rate_limit(
+///         RateLimitConfig::new()
+///             .requests_per_second(10)
+///             .max_in_flight(4)
+///             .method("send_tx", 2),
+///     )
+///     .build();
+/// ```
+///
+/// - **Requests per second** is a token bucket shared by every request: it
+///   holds up to [`burst`](Self::burst) tokens (default: one second's worth)
+///   and refills at the configured rate.
+/// - **Max in flight** caps concurrent HTTP requests; extra callers wait for
+///   a slot.
+/// - **Per-method budgets** add a separate token bucket for one JSON-RPC
+///   method, on top of the shared one. They key on the method name sent on
+///   the wire — note that the typed view helpers use `EXPERIMENTAL_*` methods
+///   (for example [`RpcClient::view_account`](super::RpcClient::view_account)
+///   sends `EXPERIMENTAL_view_account`, not `query`).
+///
+/// A JSON-RPC batch takes one token per entry, from the shared bucket and
+/// from each entry's method bucket, and one in-flight slot.
+///
+/// Whether or not limits are configured, an HTTP 429 response is retried
+/// after the server's `Retry-After` delay. With a limiter configured, that
+/// delay also pauses every other request through the same client.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    requests_per_second: Option<u32>,
+    burst: Option<u32>,
+    max_in_flight: Option<usize>,
+    methods: HashMap<String, u32>,
+}
+
+impl RateLimitConfig {
+    /// A config with no limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit the steady-state request rate across all methods. `0` removes
+    /// the limit.
+    pub fn requests_per_second(mut self, requests: u32) -> Self {
+        self.requests_per_second = (requests > 0).then_some(requests);
+        self
+    }
+
+    /// Allow up to `requests` back-to-back requests before the
+    /// [`requests_per_second`](Self::requests_per_second) pace applies.
+    /// Defaults to the per-second rate.
+    pub fn burst(mut self, requests: u32) -> Self {
+        self.burst = Some(requests.max(1));
+        self
+    }
+
+    /// Cap the number of HTTP requests in flight at once. `0` removes the cap.
+    pub fn max_in_flight(mut self, requests: usize) -> Self {
+        self.max_in_flight = (requests > 0).then_some(requests);
+        self
+    }
+
+    /// Give one JSON-RPC method its own requests-per-second budget, in
+    /// addition to the shared one. `0` removes the method's budget.
+    pub fn method(mut self, method: impl Into<String>, requests_per_second: u32) -> Self {
+        let method = method.into();
+        if requests_per_second > 0 {
+            self.methods.insert(method, requests_per_second);
+        } else {
+            self.methods.remove(&method);
+        }
+        self
+    }
+
+    /// Whether this config limits anything.
+    pub fn is_unlimited(&self) -> bool {
+        self.requests_per_second.is_none()
+            && self.max_in_flight.is_none()
+            && self.methods.is_empty()
+    }
+}
+
+/// A token bucket that hands out reservations.
+///
+/// A caller takes its tokens immediately, even if that drives the balance
+/// negative, and then sleeps until the deficit has refilled. Callers are
+/// therefore served in arrival order without polling.
+#[derive(Debug)]
+struct TokenBucket {
+    /// Tokens added per millisecond.
+    rate_per_ms: f64,
+    capacity: f64,
+    /// `(tokens, last refill in monotonic ms)`.
+    state: Mutex<(f64, u64)>,
+}
+
+impl TokenBucket {
+    fn new(requests_per_second: u32, burst: u32) -> Self {
+        let capacity = f64::from(burst);
+        Self {
+            rate_per_ms: f64::from(requests_per_second) / 1000.0,
+            capacity,
+            state: Mutex::new((capacity, monotonic_millis())),
+        }
+    }
+
+    /// Take `tokens` and return how long to wait before using them.
+    fn reserve(&self, tokens: f64) -> Duration {
+        let now = monotonic_millis();
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (balance, last) = *state;
+        let refilled =
+            (balance + now.saturating_sub(last) as f64 * self.rate_per_ms).min(self.capacity);
+        let remaining = refilled - tokens;
+        *state = (remaining, now);
+        if remaining >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_micros((-remaining / self.rate_per_ms * 1000.0).ceil() as u64)
+        }
+    }
+}
+
+/// Enforces a [`RateLimitConfig`] for one client.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    shared: Option<TokenBucket>,
+    methods: HashMap<String, TokenBucket>,
+    in_flight: Option<Semaphore>,
+    /// Monotonic ms until which every request waits (set by HTTP 429).
+    paused_until: AtomicU64,
+}
+
+/// Held for the duration of one HTTP request.
+pub(crate) struct RatePermit<'a> {
+    _slot: Option<SemaphorePermit<'a>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(config: &RateLimitConfig) -> Self {
+        Self {
+            shared: config
+                .requests_per_second
+                .map(|rate| TokenBucket::new(rate, config.burst.unwrap_or(rate))),
+            methods: config
+                .methods
+                .iter()
+                .map(|(method, &rate)| (method.clone(), TokenBucket::new(rate, rate)))
+                .collect(),
+            in_flight: config.max_in_flight.map(Semaphore::new),
+            paused_until: AtomicU64::new(0),
+        }
+    }
+
+    /// Wait until one HTTP request carrying `methods` (one entry per
+    /// JSON-RPC call in it) fits the budget.
+    pub(crate) async fn acquire(&self, methods: &[&str]) -> RatePermit<'_> {
+        loop {
+            let until = self.paused_until.load(Ordering::Relaxed);
+            let now = monotonic_millis();
+            if now >= until {
+                break;
+            }
+            async_sleep(Duration::from_millis(until - now)).await;
+        }
+
+        let mut wait = self.shared.as_ref().map_or(Duration::ZERO, |bucket| {
+            bucket.reserve(methods.len() as f64)
+        });
+        for method in methods {
+            if let Some(bucket) = self.methods.get(*method) {
+                wait = wait.max(bucket.reserve(1.0));
+            }
+        }
+        if !wait.is_zero() {
+            trace::trace!(
+                wait_ms = wait.as_millis() as u64,
+                "RPC request rate limited"
+            );
+            async_sleep(wait).await;
+        }
+
+        let slot = match &self.in_flight {
+            // The semaphore is never closed, so acquiring cannot fail.
+            Some(semaphore) => semaphore.acquire().await.ok(),
+            None => None,
+        };
+        RatePermit { _slot: slot }
+    }
+
+    /// Hold every request back for `delay`, after the server asked us to.
+    pub(crate) fn pause(&self, delay: Duration) {
+        let delay_ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX);
+        let until = monotonic_millis().saturating_add(delay_ms);
+        self.paused_until.fetch_max(until, Ordering::Relaxed);
+        trace::debug!(
+            delay_ms,
+            "RPC endpoint rate limited the client, pausing requests"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_zero_removes_limits() {
+        let config = RateLimitConfig::new()
+            .requests_per_second(5)
+            .max_in_flight(2)
+            .method("send_tx", 1);
+        assert!(!config.is_unlimited());
+
+        let config = config
+            .requests_per_second(0)
+            .max_in_flight(0)
+            .method("send_tx", 0);
+        assert!(config.is_unlimited());
+        assert_eq!(config, RateLimitConfig::default());
+    }
+
+    #[test]
+    fn test_token_bucket_allows_burst_then_paces() {
+        let bucket = TokenBucket::new(10, 3);
+        for _ in 0..3 {
+            assert_eq!(bucket.reserve(1.0), Duration::ZERO);
+        }
+        // 10/s means one token per 100 ms; the fourth and fifth callers queue
+        // behind each other.
+        let fourth = bucket.reserve(1.0);
+        let fifth = bucket.reserve(1.0);
+        assert!(fourth > Duration::from_millis(80) && fourth <= Duration::from_millis(100));
+        assert!(fifth > Duration::from_millis(180) && fifth <= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_limiter_paces_shared_budget() {
+        let limiter = RateLimiter::new(&RateLimitConfig::new().requests_per_second(50).burst(1));
+        let started = monotonic_millis();
+        for _ in 0..3 {
+            limiter.acquire(&["status"]).await;
+        }
+        // One free request, then two at 20 ms intervals.
+        assert!(monotonic_millis() - started >= 35);
+    }
+
+    #[tokio::test]
+    async fn test_limiter_method_budget_is_separate() {
+        let limiter = RateLimiter::new(&RateLimitConfig::new().method("send_tx", 1));
+        limiter.acquire(&["send_tx"]).await;
+
+        // Other methods are unaffected by the exhausted send_tx budget.
+        let started = monotonic_millis();
+        for _ in 0..10 {
+            limiter.acquire(&["query"]).await;
+        }
+        assert!(monotonic_millis() - started < 50);
+
+        assert!(limiter.methods["send_tx"].reserve(1.0) > Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_limiter_caps_in_flight_requests() {
+        let limiter = RateLimiter::new(&RateLimitConfig::new().max_in_flight(1));
+        let first = limiter.acquire(&["status"]).await;
+
+        let second = limiter.acquire(&["status"]);
+        tokio::pin!(second);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(20), &mut second)
+                .await
+                .is_err()
+        );
+
+        drop(first);
+        tokio::time::timeout(Duration::from_millis(100), second)
+            .await
+            .expect("slot is released when the first permit drops");
+    }
+
+    #[tokio::test]
+    async fn test_limiter_pause_holds_requests() {
+        let limiter = RateLimiter::new(&RateLimitConfig::new().max_in_flight(8));
+        limiter.pause(Duration::from_millis(40));
+        let started = monotonic_millis();
+        limiter.acquire(&["status"]).await;
+        assert!(monotonic_millis() - started >= 35);
+    }
+}
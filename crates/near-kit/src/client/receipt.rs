@@ -0,0 +1,414 @@
+//! Waiting for individual receipts.
+//!
+//! Transaction wait levels ([`WaitLevel`](crate::types::WaitLevel)) describe
+//! a transaction as a whole. [`ReceiptQuery`] and [`ReceiptWait`] instead poll
+//! `EXPERIMENTAL_tx_status` until one receipt — a cross-contract callback, a
+//! resumed yield — has executed, and return its outcome.
+
+use std::future::IntoFuture;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::rpc::{RpcClient, async_sleep};
+use crate::error::Error;
+use crate::platform::{BoxFuture, monotonic_millis};
+use crate::types::{
+    AccountId, CryptoHash, ExecutionNode, ExecutionOutcomeWithId, ExecutionStatus,
+    TryIntoAccountId, TxExecutionStatus,
+};
+
+type Predicate = dyn Fn(&ExecutionNode<'_>) -> bool + Send + Sync;
+
+/// Default delay between `tx_status` polls.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often to poll and when to give up.
+#[derive(Debug, Clone, Copy)]
+struct Polling {
+    interval: Duration,
+    timeout: Option<Duration>,
+}
+
+impl Default for Polling {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_POLL_INTERVAL,
+            timeout: None,
+        }
+    }
+}
+
+// ============================================================================
+// ReceiptQuery
+// ============================================================================
+
+/// Awaitable outcome of one receipt, from [`Near::receipt`](super::Near::receipt).
+///
+/// Finds the receipt's transaction with `EXPERIMENTAL_receipt_to_tx` (nearcore
+/// 2.12 or later), then polls the transaction's status until the receipt has
+/// executed. The outcome is returned as soon as the node reports it, which
+/// may be before its block is final.
+///
+/// Fails with [`Error::ReceiptNotFound`] if the transaction reaches `FINAL`
+/// without executing the receipt, and with [`Error::ReceiptTimeout`] once the
+/// [`timeout`](Self::timeout), if any, elapses.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use near_kit::*;
+/// # async fn example(near: &Near, receipt_id: CryptoHash) -> Result<(), Error> {
+/// let outcome = near.receipt(&receipt_id).await?;
+/// println!("{} executed in block {}", outcome.id, outcome.block_hash);
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "receipt queries do nothing unless awaited"]
+pub struct ReceiptQuery {
+    rpc: Arc<RpcClient>,
+    receipt_id: CryptoHash,
+    polling: Polling,
+}
+
+impl ReceiptQuery {
+    pub(crate) fn new(rpc: Arc<RpcClient>, receipt_id: CryptoHash) -> Self {
+        Self {
+            rpc,
+            receipt_id,
+            polling: Polling::default(),
+        }
+    }
+
+    /// Delay between status polls. Defaults to one second.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.polling.interval = interval;
+        self
+    }
+
+    /// Give up with [`Error::ReceiptTimeout`] after `timeout`. By default the
+    /// query waits until the transaction is final.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.polling.timeout = Some(timeout);
+        self
+    }
+}
+
+impl IntoFuture for ReceiptQuery {
+    type Output = Result<ExecutionOutcomeWithId, Error>;
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let tx = self.rpc.receipt_to_tx(&self.receipt_id).await?;
+            let receipt_id = self.receipt_id;
+            wait_for_receipt(
+                &self.rpc,
+                tx.transaction_hash,
+                &tx.sender_account_id,
+                self.polling,
+                |node| *node.id() == receipt_id,
+            )
+            .await
+        })
+    }
+}
+
+// ============================================================================
+// ReceiptWait
+// ============================================================================
+
+/// Awaitable outcome of the first receipt of a transaction that matches a
+/// predicate, from [`Near::wait_for_receipts`](super::Near::wait_for_receipts).
+///
+/// Polls the transaction's status and tests each executed receipt, in
+/// execution order, against the predicate. Receipts are passed as
+/// [`ExecutionNode`]s with receipt details, so the predicate can look at the
+/// executor, the predecessor or the [called methods](ExecutionNode::method_names).
+///
+/// Fails like [`ReceiptQuery`] when no receipt matches.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use near_kit::*;
+/// # async fn example(near: &Near, tx_hash: CryptoHash) -> Result<(), Error> {
+/// // Wait for the token contract to settle an `ft_transfer_call`.
+/// let outcome = near
+///     .wait_for_receipts(&tx_hash, "alice.near", |receipt| {
+///         receipt.method_names().any(|method| method == "ft_resolve_transfer")
+///     })
+///     .await?;
+/// println!("Resolved: {:?}", outcome.outcome.status);
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "receipt waits do nothing unless awaited"]
+pub struct ReceiptWait {
+    rpc: Arc<RpcClient>,
+    tx_hash: CryptoHash,
+    sender_id: Result<AccountId, Error>,
+    predicate: Box<Predicate>,
+    polling: Polling,
+}
+
+impl ReceiptWait {
+    pub(crate) fn new(
+        rpc: Arc<RpcClient>,
+        tx_hash: CryptoHash,
+        sender_id: impl TryIntoAccountId,
+        predicate: impl Fn(&ExecutionNode<'_>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            rpc,
+            tx_hash,
+            sender_id: sender_id.try_into_account_id().map_err(Error::from),
+            predicate: Box::new(predicate),
+            polling: Polling::default(),
+        }
+    }
+
+    /// Delay between status polls. Defaults to one second.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.polling.interval = interval;
+        self
+    }
+
+    /// Give up with [`Error::ReceiptTimeout`] after `timeout`. By default the
+    /// wait lasts until the transaction is final.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.polling.timeout = Some(timeout);
+        self
+    }
+}
+
+impl IntoFuture for ReceiptWait {
+    type Output = Result<ExecutionOutcomeWithId, Error>;
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let sender_id = self.sender_id?;
+            wait_for_receipt(
+                &self.rpc,
+                self.tx_hash,
+                &sender_id,
+                self.polling,
+                &*self.predicate,
+            )
+            .await
+        })
+    }
+}
+
+/// Poll `tx_status` until a receipt matching `matches` has executed.
+async fn wait_for_receipt(
+    rpc: &RpcClient,
+    tx_hash: CryptoHash,
+    sender_id: &AccountId,
+    polling: Polling,
+    matches: impl Fn(&ExecutionNode<'_>) -> bool,
+) -> Result<ExecutionOutcomeWithId, Error> {
+    let deadline = polling
+        .timeout
+        .map(|timeout| monotonic_millis().saturating_add(timeout.as_millis() as u64));
+    loop {
+        let response = rpc
+            .tx_status(&tx_hash, sender_id, TxExecutionStatus::None)
+            .await?;
+        let found = response.outcome.as_ref().and_then(|outcome| {
+            outcome
+                .execution_tree()
+                .iter()
+                .find(|node| {
+                    !node.is_transaction()
+                        && !matches!(node.status(), ExecutionStatus::Unknown)
+                        && matches(node)
+                })
+                .map(|node| node.outcome.clone())
+        });
+        if let Some(outcome) = found {
+            return Ok(outcome);
+        }
+        // `EXECUTED_OPTIMISTIC` can still be missing refunds, so only a final
+        // transaction proves the receipt will never show up.
+        if response.final_execution_status == TxExecutionStatus::Final {
+            return Err(Error::ReceiptNotFound {
+                transaction_hash: tx_hash,
+            });
+        }
+        if deadline.is_some_and(|deadline| monotonic_millis() >= deadline) {
+            return Err(Error::ReceiptTimeout {
+                transaction_hash: tx_hash,
+            });
+        }
+        async_sleep(polling.interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::client::Near;
+    use crate::testing::{MockResponse, MockTransport};
+
+    fn hash(name: &str) -> CryptoHash {
+        CryptoHash::hash(name.as_bytes())
+    }
+
+    fn outcome(name: &str, executor: &str, children: &[&str], status: Value) -> Value {
+        json!({
+            "id": hash(name).to_string(),
+            "block_hash": hash("block").to_string(),
+            "proof": [],
+            "outcome": {
+                "executor_id": executor,
+                "gas_burnt": 0,
+                "tokens_burnt": "0",
+                "logs": [],
+                "receipt_ids": children.iter().map(|c| hash(c).to_string()).collect::<Vec<_>>(),
+                "status": status,
+            },
+        })
+    }
+
+    fn receipt(name: &str, predecessor: &str, receiver: &str, method: &str) -> Value {
+        json!({
+            "predecessor_id": predecessor,
+            "receiver_id": receiver,
+            "receipt_id": hash(name).to_string(),
+            "receipt": { "Action": {
+                "signer_id": "alice.near",
+                "signer_public_key": "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+                "gas_price": "0",
+                "actions": [{ "FunctionCall": {
+                    "method_name": method,
+                    "args": "",
+                    "gas": 0,
+                    "deposit": "0",
+                }}],
+            }},
+        })
+    }
+
+    /// An `ft_transfer_call` at `status`, whose `ft_resolve_transfer`
+    /// callback has executed only when `resolved` is set.
+    fn tx_status(status: &str, resolved: bool) -> MockResponse {
+        let ok = json!({ "SuccessValue": "" });
+        let pending = json!("Started");
+        let mut receipts_outcome = vec![
+            outcome("call", "token.near", &["receiver"], ok.clone()),
+            outcome("receiver", "dex.near", &["resolve"], ok.clone()),
+        ];
+        if resolved {
+            receipts_outcome.push(outcome("resolve", "token.near", &[], ok.clone()));
+        }
+        MockResponse::result(json!({
+            "final_execution_status": status,
+            "status": if resolved { ok.clone() } else { pending },
+            "transaction": {
+                "signer_id": "alice.near",
+                "public_key": "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+                "nonce": 1,
+                "receiver_id": "token.near",
+                "actions": [],
+                "signature": "ed25519:3s1dvMqNDCByoMnDnkhB4GPjTSXCRt4nt3Af5n1RX8W7aJ2FC6MfRf5BNXZ52EBifNJnNVBsGvke6GRYuaEYJXt5",
+                "hash": hash("tx").to_string(),
+            },
+            "transaction_outcome": outcome("tx", "alice.near", &["call"], json!({
+                "SuccessReceiptId": hash("call").to_string(),
+            })),
+            "receipts_outcome": receipts_outcome,
+            "receipts": [
+                receipt("call", "alice.near", "token.near", "ft_transfer_call"),
+                receipt("receiver", "token.near", "dex.near", "ft_on_transfer"),
+                receipt("resolve", "token.near", "token.near", "ft_resolve_transfer"),
+            ],
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_receipt_polls_until_executed() {
+        let mock = MockTransport::new();
+        mock.on("EXPERIMENTAL_receipt_to_tx")
+            .respond(MockResponse::result(json!({
+                "transaction_hash": hash("tx").to_string(),
+                "sender_account_id": "alice.near",
+            })));
+        mock.on("EXPERIMENTAL_tx_status")
+            .once()
+            .respond(tx_status("EXECUTED_OPTIMISTIC", false));
+        mock.on("EXPERIMENTAL_tx_status")
+            .respond(tx_status("EXECUTED_OPTIMISTIC", true));
+        let near: Near = mock.near().build();
+
+        let outcome = near
+            .receipt(&hash("resolve"))
+            .poll_interval(Duration::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(outcome.id, hash("resolve"));
+        assert_eq!(outcome.outcome.executor_id.as_str(), "token.near");
+
+        let polls = mock.requests_for("EXPERIMENTAL_tx_status");
+        assert_eq!(polls.len(), 2);
+        assert_eq!(polls[0].param_str("wait_until"), Some("NONE"));
+        assert_eq!(polls[0].param_str("sender_account_id"), Some("alice.near"));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_receipts_matches_callback() {
+        let mock = MockTransport::new();
+        mock.on("EXPERIMENTAL_tx_status")
+            .once()
+            .respond(tx_status("INCLUDED", false));
+        mock.on("EXPERIMENTAL_tx_status")
+            .respond(tx_status("FINAL", true));
+        let near: Near = mock.near().build();
+
+        let outcome = near
+            .wait_for_receipts(&hash("tx"), "alice.near", |receipt| {
+                receipt
+                    .method_names()
+                    .any(|method| method == "ft_resolve_transfer")
+            })
+            .poll_interval(Duration::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(outcome.id, hash("resolve"));
+        assert!(mock.requests_for("EXPERIMENTAL_receipt_to_tx").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_receipts_gives_up() {
+        let mock = MockTransport::new();
+        mock.on("EXPERIMENTAL_tx_status")
+            .respond(tx_status("FINAL", true));
+        let near: Near = mock.near().build();
+        let err = near
+            .wait_for_receipts(&hash("tx"), "alice.near", |receipt| {
+                receipt.executor_id().as_str() == "bob.near"
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::ReceiptNotFound { transaction_hash } if transaction_hash == hash("tx")
+        ));
+
+        let mock = MockTransport::new();
+        mock.on("EXPERIMENTAL_tx_status")
+            .respond(tx_status("EXECUTED_OPTIMISTIC", false));
+        let near: Near = mock.near().build();
+        let err = near
+            .wait_for_receipts(&hash("tx"), "alice.near", |receipt| {
+                receipt.method_names().any(|m| m == "ft_resolve_transfer")
+            })
+            .poll_interval(Duration::from_millis(5))
+            .timeout(Duration::from_millis(20))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::ReceiptTimeout { .. }));
+    }
+}
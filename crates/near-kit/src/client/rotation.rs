@@ -0,0 +1,414 @@
+//! Access key rotation.
+//!
+//! [`Near::rotate_key`](super::Near::rotate_key) replaces one access key of the
+//! signer's account with a freshly generated one carrying the same
+//! permission: a single transaction adds the new key and deletes the old one,
+//! so the account is never left with both or neither.
+
+use std::fmt;
+use std::future::IntoFuture;
+use std::sync::Arc;
+
+use super::credential_store::CredentialStore;
+use super::near::Near;
+use super::signer::InMemorySigner;
+use crate::error::{Error, RpcError};
+use crate::platform::BoxFuture;
+use crate::types::{
+    AccessKey, AccessKeyPermission, AccessKeyPermissionView, AccountId, Action, AddKeyAction,
+    BlockReference, FinalExecutionOutcome, KeyType, PublicKey, SecretKey,
+};
+
+/// Awaitable rotation of one access key, from
+/// [`Near::rotate_key`](super::Near::rotate_key).
+///
+/// Awaiting it looks the old key up with `view_access_key_list`, generates a
+/// replacement (Ed25519 unless [`key_type`](Self::key_type) says otherwise),
+/// and sends one transaction from the client's signer that adds the
+/// replacement with the old key's permission and deletes the old key. A
+/// function-call key keeps its receiver and method names, and its remaining
+/// allowance becomes the new key's allowance. Gas keys are not supported.
+///
+/// The result carries the new secret key — persist it, or let
+/// [`save_to`](Self::save_to) do so before the transaction is sent — and a
+/// [`Near`] that signs with it. [`dry_run`](Self::dry_run) stops short of
+/// sending and returns the planned actions instead.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use near_kit::*;
+/// # async fn example(near: Near) -> Result<(), Error> {
+/// let old_key = near.public_key().expect("signer configured");
+///
+/// // Review the change first
+/// let plan = near.rotate_key(old_key.clone()).dry_run().await?;
+/// println!("{plan}");
+///
+/// let rotated = near
+///     .rotate_key(old_key)
+///     .save_to(FileCredentialStore::new()?, "mainnet")
+///     .await?;
+/// let near = rotated.near;
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "key rotations do nothing unless awaited"]
+pub struct KeyRotation {
+    near: Near,
+    old_public_key: PublicKey,
+    key_type: KeyType,
+    new_key: Option<SecretKey>,
+    store: Option<(Arc<dyn CredentialStore>, String)>,
+}
+
+impl KeyRotation {
+    pub(crate) fn new(near: Near, old_public_key: PublicKey) -> Self {
+        Self {
+            near,
+            old_public_key,
+            key_type: KeyType::Ed25519,
+            new_key: None,
+            store: None,
+        }
+    }
+
+    /// Generate the replacement as a key of `key_type`. Defaults to Ed25519.
+    pub fn key_type(mut self, key_type: KeyType) -> Self {
+        self.key_type = key_type;
+        self
+    }
+
+    /// Rotate to `secret_key` instead of generating a replacement, e.g. one
+    /// produced by an HSM or derived from a seed phrase.
+    pub fn new_key(mut self, secret_key: SecretKey) -> Self {
+        self.new_key = Some(secret_key);
+        self
+    }
+
+    /// Save the new key to `store` under `network` before the transaction is
+    /// sent, and remove the old key from it once the rotation succeeds.
+    ///
+    /// Saving first means a crash mid-rotation cannot lose a key the chain
+    /// has already accepted. If the transaction fails, the unused new key is
+    /// left in the store. Removing the old key is best-effort.
+    pub fn save_to(
+        mut self,
+        store: impl CredentialStore + 'static,
+        network: impl Into<String>,
+    ) -> Self {
+        self.store = Some((Arc::new(store), network.into()));
+        self
+    }
+
+    /// Look up the old key and work out the rotation without sending
+    /// anything.
+    ///
+    /// The plan's new key is generated for the occasion (or is the one given
+    /// to [`new_key`](Self::new_key)) and is not stored anywhere.
+    pub async fn dry_run(self) -> Result<RotationPlan, Error> {
+        let (plan, _) = self.prepare().await?;
+        Ok(plan)
+    }
+
+    async fn prepare(&self) -> Result<(RotationPlan, SecretKey), Error> {
+        let account_id = self.near.try_account_id().ok_or(Error::NoSigner)?.clone();
+        let keys = self
+            .near
+            .rpc()
+            .view_access_key_list(&account_id, BlockReference::default())
+            .await?;
+        let old = keys
+            .keys
+            .iter()
+            .find(|key| key.public_key.refers_to(&self.old_public_key))
+            .ok_or_else(|| RpcError::AccessKeyNotFound {
+                account_id: account_id.clone(),
+                public_key: self.old_public_key.clone(),
+                block_height: Some(keys.block_height),
+                block_hash: Some(keys.block_hash),
+            })?;
+        let permission = match &old.access_key.permission {
+            AccessKeyPermissionView::FullAccess => AccessKeyPermission::FullAccess,
+            AccessKeyPermissionView::FunctionCall {
+                allowance,
+                receiver_id,
+                method_names,
+            } => AccessKeyPermission::function_call(
+                receiver_id.clone(),
+                method_names.clone(),
+                *allowance,
+            ),
+            AccessKeyPermissionView::GasKeyFunctionCall { .. }
+            | AccessKeyPermissionView::GasKeyFullAccess { .. } => {
+                return Err(Error::InvalidTransaction(format!(
+                    "{} is a gas key; rotate_key only rotates plain access keys",
+                    self.old_public_key
+                )));
+            }
+        };
+
+        let secret_key = match &self.new_key {
+            Some(secret_key) => secret_key.clone(),
+            None => match self.key_type {
+                KeyType::Ed25519 => SecretKey::generate_ed25519(),
+                KeyType::Secp256k1 => SecretKey::generate_secp256k1(),
+                KeyType::MlDsa65 => SecretKey::generate_ml_dsa65(),
+            },
+        };
+        let new_public_key = secret_key.public_key();
+        if new_public_key == self.old_public_key {
+            return Err(Error::InvalidTransaction(
+                "the new key is the key being rotated out".to_string(),
+            ));
+        }
+
+        let add_key = Action::AddKey(AddKeyAction {
+            public_key: new_public_key.clone(),
+            access_key: AccessKey {
+                nonce: 0,
+                permission: permission.clone(),
+            },
+        });
+        let plan = RotationPlan {
+            account_id,
+            old_public_key: self.old_public_key.clone(),
+            new_public_key,
+            permission,
+            actions: vec![add_key, Action::delete_key(self.old_public_key.clone())],
+        };
+        Ok((plan, secret_key))
+    }
+}
+
+impl IntoFuture for KeyRotation {
+    type Output = Result<RotatedKey, Error>;
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let (plan, secret_key) = self.prepare().await?;
+            if let Some((store, network)) = &self.store {
+                store.save(network, &plan.account_id, &secret_key)?;
+            }
+
+            let mut tx = self.near.transaction(plan.account_id.clone());
+            for action in plan.actions {
+                tx = tx.add_action(action);
+            }
+            let outcome = tx.send().await?;
+            if let Some(message) = outcome.failure_message() {
+                return Err(Error::KeyRotationFailed {
+                    account_id: plan.account_id,
+                    transaction_hash: *outcome.transaction_hash(),
+                    message,
+                });
+            }
+
+            if let Some((store, network)) = &self.store {
+                let _ = store.remove(network, &plan.account_id, &plan.old_public_key);
+            }
+            let signer = InMemorySigner::from_secret_key(plan.account_id, secret_key.clone())?;
+            Ok(RotatedKey {
+                near: self.near.with_signer(signer),
+                public_key: plan.new_public_key,
+                secret_key,
+                outcome,
+            })
+        })
+    }
+}
+
+/// What a [`KeyRotation`] will do, from [`KeyRotation::dry_run`].
+///
+/// Displays as a short human-readable summary.
+#[derive(Debug, Clone)]
+pub struct RotationPlan {
+    /// The account whose key is rotated.
+    pub account_id: AccountId,
+    /// The key being rotated out.
+    pub old_public_key: PublicKey,
+    /// The replacement key.
+    pub new_public_key: PublicKey,
+    /// The permission copied from the old key.
+    pub permission: AccessKeyPermission,
+    /// The transaction's actions, in order: `AddKey` for the new key, then
+    /// `DeleteKey` for the old one.
+    pub actions: Vec<Action>,
+}
+
+impl fmt::Display for RotationPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "rotate access key on {}:", self.account_id)?;
+        write!(f, "  add    {} (", self.new_public_key)?;
+        match &self.permission {
+            AccessKeyPermission::FunctionCall(fc) => {
+                write!(f, "function call on {}", fc.receiver_id)?;
+                if !fc.method_names.is_empty() {
+                    write!(f, ", methods {}", fc.method_names.join(", "))?;
+                }
+                match fc.allowance {
+                    Some(allowance) => write!(f, ", allowance {allowance}")?,
+                    None => write!(f, ", unlimited allowance")?,
+                }
+            }
+            _ => write!(f, "full access")?,
+        }
+        writeln!(f, ")")?;
+        write!(f, "  delete {}", self.old_public_key)
+    }
+}
+
+/// A completed rotation, from awaiting a [`KeyRotation`].
+pub struct RotatedKey {
+    /// A client sharing the original's connection that signs with the new key.
+    pub near: Near,
+    /// The new key's public half.
+    pub public_key: PublicKey,
+    /// The new key. Persist it unless [`KeyRotation::save_to`] already has.
+    pub secret_key: SecretKey,
+    /// The rotation transaction's outcome.
+    pub outcome: FinalExecutionOutcome,
+}
+
+impl fmt::Debug for RotatedKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RotatedKey")
+            .field("account_id", self.near.account_id())
+            .field("public_key", &self.public_key)
+            .field("secret_key", &self.secret_key)
+            .field("transaction_hash", self.outcome.transaction_hash())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::FakeChain;
+    use crate::types::NearToken;
+
+    fn chain_with_alice() -> (FakeChain, InMemorySigner) {
+        let chain = FakeChain::new();
+        chain.add_account("bob.near", NearToken::from_near(1));
+        let alice = chain
+            .add_account("alice.near", NearToken::from_near(10))
+            .signer("alice.near");
+        (chain, alice)
+    }
+
+    #[tokio::test]
+    async fn test_rotates_own_full_access_key() {
+        let (chain, alice) = chain_with_alice();
+        let old_key = alice.public_key().clone();
+        let near = chain.transport().near().signer(alice).build();
+
+        let rotated = near.rotate_key(old_key.clone()).await.unwrap();
+        assert!(rotated.outcome.is_success());
+        assert_eq!(rotated.near.public_key(), Some(rotated.public_key.clone()));
+        assert!(chain.nonce("alice.near", &old_key).is_none());
+        assert!(chain.nonce("alice.near", &rotated.public_key).is_some());
+
+        // The returned client signs with the new key.
+        rotated
+            .near
+            .transfer("bob.near", NearToken::from_near(1))
+            .await
+            .unwrap();
+        assert_eq!(chain.balance("bob.near"), Some(NearToken::from_near(2)));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_copies_function_call_permission() {
+        let (chain, alice) = chain_with_alice();
+        let bot_key = SecretKey::generate_ed25519().public_key();
+        let permission = AccessKeyPermission::function_call(
+            "app.near".parse().unwrap(),
+            vec!["tick".to_string()],
+            Some(NearToken::from_millinear(250)),
+        );
+        chain.add_key("alice.near", bot_key.clone(), permission.clone());
+        let near = chain.transport().near().signer(alice).build();
+
+        let plan = near
+            .rotate_key(bot_key.clone())
+            .key_type(KeyType::Secp256k1)
+            .dry_run()
+            .await
+            .unwrap();
+        assert_eq!(plan.permission, permission);
+        assert_eq!(plan.new_public_key.key_type(), KeyType::Secp256k1);
+        assert!(matches!(
+            &plan.actions[..],
+            [Action::AddKey(add), Action::DeleteKey(delete)]
+                if add.public_key == plan.new_public_key
+                    && add.access_key.permission == permission
+                    && delete.public_key == bot_key
+        ));
+        let summary = plan.to_string();
+        assert!(summary.contains("function call on app.near, methods tick"));
+        // Nothing was sent.
+        assert!(chain.nonce("alice.near", &bot_key).is_some());
+        assert!(chain.nonce("alice.near", &plan.new_public_key).is_none());
+
+        let new_key = SecretKey::generate_ed25519();
+        let rotated = near
+            .rotate_key(bot_key.clone())
+            .new_key(new_key.clone())
+            .await
+            .unwrap();
+        assert_eq!(rotated.public_key, new_key.public_key());
+        assert!(chain.nonce("alice.near", &bot_key).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_key_is_an_error() {
+        let (chain, alice) = chain_with_alice();
+        let near = chain.transport().near().signer(alice).build();
+        let stranger = SecretKey::generate_ed25519().public_key();
+
+        let err = near.rotate_key(stranger).dry_run().await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Rpc(ref rpc) if matches!(**rpc, RpcError::AccessKeyNotFound { .. })
+        ));
+    }
+
+    #[cfg(feature = "file-signer")]
+    #[tokio::test]
+    async fn test_save_to_store() {
+        use crate::client::{FileCredentialStore, Signer};
+
+        let (chain, _) = chain_with_alice();
+        let old_secret = SecretKey::generate_ed25519();
+        let old_key = old_secret.public_key();
+        chain.add_key(
+            "alice.near",
+            old_key.clone(),
+            AccessKeyPermission::FullAccess,
+        );
+        let alice = InMemorySigner::from_secret_key("alice.near", old_secret.clone()).unwrap();
+        let account_id = alice.account_id().clone();
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(FileCredentialStore::at(dir.path()));
+        store.save("sandbox", &account_id, &old_secret).unwrap();
+        let near = chain.transport().near().signer(alice).build();
+
+        let rotated = near
+            .rotate_key(old_key)
+            .save_to(store.clone(), "sandbox")
+            .await
+            .unwrap();
+        assert_eq!(
+            store.list_keys("sandbox", &account_id).unwrap(),
+            vec![rotated.public_key.clone()]
+        );
+
+        // The single-key file FileSigner reads now holds the new key.
+        let signer =
+            crate::FileSigner::from_file(dir.path().join("sandbox/alice.near.json"), "alice.near")
+                .unwrap();
+        assert_eq!(signer.public_key(), &rotated.public_key);
+    }
+}
@@ -2,12 +2,15 @@
 
 use std::num::NonZeroU32;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::Duration;
 
 use base64::{Engine as _, engine::general_purpose::STANDARD};
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 
+use super::cache::{CacheConfig, ResponseCache};
+use super::nonce_manager::{NonceStore, default_nonce_store};
+use super::rate_limit::{RateLimitConfig, RateLimiter, RatePermit};
 use super::transport::RpcTransport;
 // The module itself is only referenced for `default_transport`, which doesn't
 // exist on WASI builds without the `wasi-http` feature (no built-in transport).
@@ -21,11 +24,13 @@ use crate::trace;
 use crate::types::rpc::RawTransactionResponse;
 use crate::types::{
     AccessKeyListView, AccessKeyView, AccountId, AccountView, BlockEffects, BlockReference,
-    BlockView, CompilationError, ContractCodeView, CryptoHash, EpochValidatorInfo,
-    FunctionCallError, GasKeyNoncesView, GasPrice, GlobalContractId, GlobalContractIdentifierView,
-    HostError, MaintenanceWindow, MethodResolveError, PublicKey, PublicKeyHandle,
-    ReceiptToTxResponse, SignedTransaction, StatusResponse, TxExecutionStatus, ViewFunctionResult,
-    ViewStateAllResult, ViewStateResult,
+    BlockView, ChunkReference, ChunkView, CompilationError, CongestionLevelResponse,
+    ContractCodeView, CryptoHash, EpochValidatorInfo, FunctionCallError, GasKeyNoncesView,
+    GasPrice, GenesisConfig, GlobalContractId, GlobalContractIdentifierView, HostError,
+    LightClientBlockView, LightClientProof, LightClientProofRequest, MaintenanceWindow,
+    MethodResolveError, NetworkInfoView, ProtocolConfigView, PublicKey, PublicKeyHandle,
+    ReceiptToTxResponse, SignedTransaction, StateChangesRequest, StateChangesResponse,
+    StatusResponse, TxExecutionStatus, ViewFunctionResult, ViewStateAllResult, ViewStateResult,
 };
 
 /// Platform-appropriate async sleep, used for retry backoff.
@@ -36,7 +41,7 @@ use crate::types::{
 ///   and blocking the one thread is fine, since the whole guest is already
 ///   blocked on this future (the wasi:http transport is blocking too).
 /// - `wasm32-unknown-unknown`: no OS timers — use the JS host's via `gloo-timers`.
-async fn async_sleep(duration: Duration) {
+pub(super) async fn async_sleep(duration: Duration) {
     #[cfg(not(target_arch = "wasm32"))]
     {
         tokio::time::sleep(duration).await;
@@ -97,6 +102,9 @@ pub struct RetryConfig {
     /// subsequent retry.
     pub initial_delay_ms: u64,
     /// Upper bound on the delay between retries, in milliseconds.
+    ///
+    /// Also the longest HTTP 429 `Retry-After` the client waits out: a
+    /// longer one ends the retry loop with [`RpcError::RateLimited`].
     pub max_delay_ms: u64,
 }
 
@@ -139,11 +147,11 @@ impl Default for RetryConfig {
 
 /// JSON-RPC request structure.
 #[derive(Serialize)]
-struct JsonRpcRequest<'a, P: Serialize> {
-    jsonrpc: &'static str,
-    id: u64,
-    method: &'a str,
-    params: P,
+pub(super) struct JsonRpcRequest<'a, P: Serialize> {
+    pub(super) jsonrpc: &'static str,
+    pub(super) id: u64,
+    pub(super) method: &'a str,
+    pub(super) params: P,
 }
 
 /// JSON-RPC response structure.
@@ -152,7 +160,7 @@ struct JsonRpcRequest<'a, P: Serialize> {
 /// only after confirming no error is present. This avoids deserialization
 /// failures when the RPC returns an error with a partial/unexpected `result`.
 #[derive(Deserialize)]
-struct JsonRpcResponse {
+pub(super) struct JsonRpcResponse {
     #[allow(dead_code)]
     jsonrpc: String,
     #[allow(dead_code)]
@@ -406,7 +414,7 @@ fn extract_contract_panic_message(message: &str) -> Option<String> {
 /// Errors are returned through the JSON-RPC error envelope, so no `error`
 /// field is needed here.
 #[derive(Debug, Deserialize)]
-struct CallFunctionResponse {
+pub(super) struct CallFunctionResponse {
     result: Vec<u8>,
     #[serde(default)]
     logs: Vec<String>,
@@ -414,12 +422,32 @@ struct CallFunctionResponse {
     block_hash: CryptoHash,
 }
 
+impl From<CallFunctionResponse> for ViewFunctionResult {
+    fn from(response: CallFunctionResponse) -> Self {
+        ViewFunctionResult {
+            result: response.result,
+            logs: response.logs,
+            block_height: response.block_height,
+            block_hash: response.block_hash,
+        }
+    }
+}
+
 /// Low-level JSON-RPC client for NEAR.
 pub struct RpcClient {
-    url: String,
-    transport: Arc<dyn RpcTransport>,
-    retry_config: RetryConfig,
-    request_id: AtomicU64,
+    pub(super) url: String,
+    pub(super) transport: Arc<dyn RpcTransport>,
+    pub(super) retry_config: RetryConfig,
+    pub(super) request_id: AtomicU64,
+    /// Set once the endpoint has answered a batch with something other than
+    /// a JSON array, so later batches skip straight to sequential calls.
+    pub(super) batch_unsupported: AtomicBool,
+    /// Shared by clones, so every handle on one client draws from one budget.
+    pub(super) rate_limiter: Option<Arc<RateLimiter>>,
+    /// Where transactions signed through this client draw their nonces.
+    pub(super) nonce_store: Arc<dyn NonceStore>,
+    /// Shared by clones, like the rate limiter.
+    pub(super) cache: Option<Arc<ResponseCache>>,
 }
 
 impl RpcClient {
@@ -488,14 +516,73 @@ impl RpcClient {
             transport,
             retry_config,
             request_id: AtomicU64::new(0),
+            batch_unsupported: AtomicBool::new(false),
+            rate_limiter: None,
+            nonce_store: default_nonce_store(),
+            cache: None,
         }
     }
 
+    /// Pace this client's requests with client-side rate limits.
+    ///
+    /// See [`RateLimitConfig`]. Most callers should use
+    /// [`NearBuilder::rate_limit`](super::NearBuilder::rate_limit) instead.
+    pub fn with_rate_limit(mut self, config: RateLimitConfig) -> Self {
+        self.rate_limiter = (!config.is_unlimited()).then(|| Arc::new(RateLimiter::new(&config)));
+        self
+    }
+
+    /// Draw transaction nonces from `store` instead of the process-wide
+    /// in-memory cache.
+    ///
+    /// See [`NonceStore`]. Most callers should use
+    /// [`NearBuilder::nonce_store`](super::NearBuilder::nonce_store) instead.
+    pub fn with_nonce_store(mut self, store: Arc<dyn NonceStore>) -> Self {
+        self.nonce_store = store;
+        self
+    }
+
+    /// Answer repeated immutable and finality-pinned queries from a cache.
+    ///
+    /// See [`CacheConfig`]. Most callers should use
+    /// [`NearBuilder::cache`](super::NearBuilder::cache) instead.
+    pub fn with_cache(mut self, config: CacheConfig) -> Self {
+        self.cache = Some(Arc::new(ResponseCache::new(config)));
+        self
+    }
+
+    /// The store transactions signed through this client draw nonces from.
+    pub fn nonce_store(&self) -> &dyn NonceStore {
+        &*self.nonce_store
+    }
+
     /// Get the RPC URL.
     pub fn url(&self) -> &str {
         &self.url
     }
 
+    /// Wait for the rate limiter (if any) to admit one HTTP request carrying
+    /// `methods`.
+    pub(super) async fn acquire_rate_limit(&self, methods: &[&str]) -> Option<RatePermit<'_>> {
+        match &self.rate_limiter {
+            Some(limiter) => Some(limiter.acquire(methods).await),
+            None => None,
+        }
+    }
+
+    /// Turn an HTTP 429 into [`RpcError::RateLimited`], pausing the rate
+    /// limiter (if any) for the server's `Retry-After` delay, capped at
+    /// [`RetryConfig::max_delay_ms`].
+    pub(super) fn rate_limited(&self, retry_after: Option<Duration>, body: &str) -> RpcError {
+        if let (Some(limiter), Some(delay)) = (&self.rate_limiter, retry_after) {
+            limiter.pause(delay.min(Duration::from_millis(self.retry_config.max_delay_ms)));
+        }
+        RpcError::RateLimited {
+            message: format!("HTTP 429: {body}"),
+            retry_after,
+        }
+    }
+
     /// Make a raw RPC call with retries.
     ///
     /// Transient failures ([`RpcError::is_retryable`]) are retried according
@@ -503,12 +590,26 @@ impl RpcClient {
     /// terminal here: the node has rejected this exact signed payload, so it
     /// is returned after a single attempt without retrying (the transaction
     /// layer re-signs with a fresh nonce instead).
+    ///
+    /// With a cache configured ([`with_cache`](Self::with_cache)), cacheable
+    /// requests are answered from it when possible — see [`CacheConfig`].
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, params), fields(rpc.method = method, rpc.url = %sanitize_url(&self.url))))]
     pub async fn call<P: Serialize, R: DeserializeOwned>(
         &self,
         method: &str,
         params: P,
     ) -> Result<R, RpcError> {
+        let cached = self
+            .cache
+            .as_ref()
+            .and_then(|cache| Some((cache, cache.slot(&self.url, method, &params)?)));
+        if let Some((cache, slot)) = &cached
+            && let Some(result) = cache.get(slot).await
+        {
+            trace::trace!("RPC cache hit");
+            return serde_json::from_value(result).map_err(RpcError::Json);
+        }
+
         let total_attempts = self.retry_config.max_retries + 1;
 
         for attempt in 0..total_attempts {
@@ -521,13 +622,34 @@ impl RpcClient {
                 params: &params,
             };
 
-            match self.try_call::<R>(&request).await {
-                Ok(result) => return Ok(result),
+            match self.try_call(&request).await {
+                Ok(result) => {
+                    if let Some((cache, slot)) = &cached {
+                        cache.put(slot, &result).await;
+                    }
+                    return serde_json::from_value(result).map_err(RpcError::Json);
+                }
                 Err(e) if e.is_retryable() && attempt < total_attempts - 1 => {
-                    let delay = std::cmp::min(
+                    let mut delay = std::cmp::min(
                         self.retry_config.initial_delay_ms * 2u64.pow(attempt),
                         self.retry_config.max_delay_ms,
                     );
+                    // The server's Retry-After wins over our own backoff, up
+                    // to `max_delay_ms`; a longer wait is the caller's call.
+                    if let RpcError::RateLimited {
+                        retry_after: Some(retry_after),
+                        ..
+                    } = &e
+                    {
+                        if *retry_after > Duration::from_millis(self.retry_config.max_delay_ms) {
+                            trace::debug!(
+                                retry_after_ms = retry_after.as_millis() as u64,
+                                "RPC endpoint asked for a longer wait than max_delay_ms"
+                            );
+                            return Err(e);
+                        }
+                        delay = delay.max(retry_after.as_millis() as u64);
+                    }
                     // DEBUG, not WARN: the retry is routine and the caller
                     // sees the final outcome either way. Retryable variants
                     // are never enriched downstream, so `Display` is accurate.
@@ -557,10 +679,12 @@ impl RpcClient {
     }
 
     /// Single attempt to make an RPC call.
-    async fn try_call<R: DeserializeOwned>(
+    ///
+    /// Returns the raw `result`; [`call`](Self::call) deserializes it.
+    async fn try_call(
         &self,
         request: &JsonRpcRequest<'_, impl Serialize>,
-    ) -> Result<R, RpcError> {
+    ) -> Result<serde_json::Value, RpcError> {
         // Gated on the feature (not routed through `crate::trace`) because the
         // no-op macros would leave `json` unused.
         #[cfg(feature = "tracing")]
@@ -571,7 +695,9 @@ impl RpcClient {
         }
 
         let request_body = serde_json::to_vec(request).map_err(RpcError::Json)?;
+        let permit = self.acquire_rate_limit(&[request.method]).await;
         let response = self.transport.post_json(&self.url, request_body).await?;
+        drop(permit);
 
         let status = response.status;
         // Lossy decode matches what reqwest's `text()` did here before the
@@ -581,6 +707,10 @@ impl RpcClient {
 
         trace::trace!(payload = %body, "RPC response");
 
+        if status == 429 {
+            return Err(self.rate_limited(response.retry_after, &body));
+        }
+
         if !(200..300).contains(&status) {
             // nearcore returns non-2xx (e.g. 422 UNKNOWN_BLOCK, 408 TIMEOUT_ERROR) with
             // a well-formed JSON-RPC error body — try to decode that first so callers
@@ -603,7 +733,20 @@ impl RpcClient {
         }
 
         let rpc_response: JsonRpcResponse = serde_json::from_str(&body).map_err(RpcError::Json)?;
+        self.decode_response(request.method, rpc_response)
+    }
 
+    /// Unwrap a decoded JSON-RPC envelope into its raw `result`, mapping an
+    /// error envelope through [`parse_rpc_error`](Self::parse_rpc_error).
+    ///
+    /// Shared by single calls and [`RpcBatch`](super::RpcBatch) entries so
+    /// both surface identical [`RpcError`] variants.
+    #[allow(clippy::result_large_err)]
+    pub(super) fn decode_response(
+        &self,
+        method: &str,
+        rpc_response: JsonRpcResponse,
+    ) -> Result<serde_json::Value, RpcError> {
         if let Some(error) = rpc_response.error {
             return Err(self.parse_rpc_error(&error));
         }
@@ -616,7 +759,7 @@ impl RpcClient {
         // object (with an "error" field) instead of in the JSON-RPC error
         // envelope. Only check for this on the `query` method to avoid
         // misclassifying legitimate results from other methods.
-        if request.method == "query"
+        if method == "query"
             && let Some(error_str) = result_value.get("error").and_then(|e| e.as_str())
         {
             let synthetic = JsonRpcError {
@@ -631,7 +774,7 @@ impl RpcClient {
             return Err(self.parse_rpc_error(&synthetic));
         }
 
-        serde_json::from_value(result_value).map_err(RpcError::Json)
+        Ok(result_value)
     }
 
     /// Parse an RPC error into a specific error type.
@@ -991,23 +1134,7 @@ impl RpcClient {
         self.merge_block_reference(&mut params, &block);
         self.call("EXPERIMENTAL_view_access_key", params)
             .await
-            .map_err(|e| match e {
-                // The EXPERIMENTAL endpoint's UNKNOWN_ACCESS_KEY error omits
-                // the account_id from its info payload. Patch it in from the
-                // request params so callers get a complete error.
-                RpcError::AccessKeyNotFound {
-                    public_key,
-                    block_height,
-                    block_hash,
-                    ..
-                } => RpcError::AccessKeyNotFound {
-                    account_id: account_id.clone(),
-                    public_key,
-                    block_height,
-                    block_hash,
-                },
-                other => other,
-            })
+            .map_err(|e| patch_access_key_error(e, account_id))
     }
 
     /// View all access keys for an account, transparently following pagination.
@@ -1178,38 +1305,9 @@ impl RpcClient {
         let response: CallFunctionResponse = self
             .call("EXPERIMENTAL_call_function", params)
             .await
-            .map_err(|e| match e {
-                RpcError::ContractExecution {
-                    message,
-                    block_height,
-                    block_hash,
-                    ..
-                } => RpcError::ContractExecution {
-                    contract_id: account_id.clone(),
-                    method_name: Some(method_name.to_string()),
-                    message,
-                    block_height,
-                    block_hash,
-                },
-                RpcError::MethodNotFound {
-                    block_height,
-                    block_hash,
-                    ..
-                } => RpcError::MethodNotFound {
-                    contract_id: account_id.clone(),
-                    method_name: method_name.to_string(),
-                    block_height,
-                    block_hash,
-                },
-                other => other,
-            })?;
+            .map_err(|e| patch_view_function_error(e, account_id, method_name))?;
 
-        Ok(ViewFunctionResult {
-            result: response.result,
-            logs: response.logs,
-            block_height: response.block_height,
-            block_hash: response.block_hash,
-        })
+        Ok(response.into())
     }
 
     /// View the WASM code deployed on an account.
@@ -1388,6 +1486,15 @@ impl RpcClient {
         self.call("block", params).await
     }
 
+    /// Get a chunk's header, transactions and receipts.
+    ///
+    /// Look the chunk up by its hash, or by block and shard — see
+    /// [`ChunkReference`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, chunk)))]
+    pub async fn chunk(&self, chunk: ChunkReference) -> Result<ChunkView, RpcError> {
+        self.call("chunk", chunk.to_rpc_params()).await
+    }
+
     /// Get all state changes that occurred in a block.
     ///
     /// Uses the stabilized `block_effects` method (protocol 2.13), the new name
@@ -1398,13 +1505,69 @@ impl RpcClient {
         self.call("block_effects", params).await
     }
 
-    /// Get the network's genesis configuration as raw JSON.
+    /// Get the kinds of state change applied in a block, via the pre-2.13
+    /// method name.
+    ///
+    /// Same response as [`block_effects`](Self::block_effects), but sent as
+    /// `EXPERIMENTAL_changes_in_block` for nodes that predate the stabilized
+    /// name.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, block)))]
+    pub async fn changes_in_block(&self, block: BlockReference) -> Result<BlockEffects, RpcError> {
+        let params = block.to_rpc_params();
+        self.call("EXPERIMENTAL_changes_in_block", params).await
+    }
+
+    /// Get the state changes of one kind for a set of accounts in a block.
+    ///
+    /// Uses `EXPERIMENTAL_changes`. Unlike [`block_effects`](Self::block_effects),
+    /// which only reports *which* accounts were touched, every entry carries
+    /// the new value and the transaction or receipt that caused it.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, request, block), fields(changes_type = request.changes_type())))]
+    pub async fn changes(
+        &self,
+        request: &StateChangesRequest,
+        block: BlockReference,
+    ) -> Result<StateChangesResponse, RpcError> {
+        let mut params = serde_json::to_value(request).map_err(RpcError::Json)?;
+        self.merge_block_reference(&mut params, &block);
+        self.call("EXPERIMENTAL_changes", params).await
+    }
+
+    /// Get the protocol configuration in force at a block.
+    ///
+    /// Uses `EXPERIMENTAL_protocol_config`. Includes the runtime parameters —
+    /// storage cost per byte and every action fee — after all protocol
+    /// upgrades up to that block.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, block)))]
+    pub async fn protocol_config(
+        &self,
+        block: BlockReference,
+    ) -> Result<ProtocolConfigView, RpcError> {
+        let params = block.to_rpc_params();
+        self.call("EXPERIMENTAL_protocol_config", params).await
+    }
+
+    /// Get the congestion level of a chunk's shard.
+    ///
+    /// Uses `EXPERIMENTAL_congestion_level`. The level ranges from `0.0` to
+    /// `1.0`; at `1.0` the shard rejects new transactions.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, chunk)))]
+    pub async fn congestion_level(
+        &self,
+        chunk: ChunkReference,
+    ) -> Result<CongestionLevelResponse, RpcError> {
+        self.call("EXPERIMENTAL_congestion_level", chunk.to_rpc_params())
+            .await
+    }
+
+    /// Get the network's genesis configuration.
     ///
     /// Uses the stabilized `genesis_config` method (protocol 2.13), the new name
-    /// for `EXPERIMENTAL_genesis_config`. The genesis config is a large,
-    /// network-specific document, so it is returned as untyped JSON.
+    /// for `EXPERIMENTAL_genesis_config`. Fields not modelled by
+    /// [`GenesisConfig`] are available as raw JSON in
+    /// [`GenesisConfig::extra`].
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
-    pub async fn genesis_config(&self) -> Result<serde_json::Value, RpcError> {
+    pub async fn genesis_config(&self) -> Result<GenesisConfig, RpcError> {
         // Send an empty params array (not `null`) — this is what the other
         // no-arg methods here use, and some JSON-RPC servers require `params` to
         // be an array/object.
@@ -1431,6 +1594,12 @@ impl RpcClient {
         self.call("status", serde_json::json!([])).await
     }
 
+    /// Get the node's peer connectivity.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn network_info(&self) -> Result<NetworkInfoView, RpcError> {
+        self.call("network_info", serde_json::json!([])).await
+    }
+
     /// Get current gas price.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
     pub async fn gas_price(&self, block_hash: Option<&CryptoHash>) -> Result<GasPrice, RpcError> {
@@ -1455,6 +1624,56 @@ impl RpcClient {
         self.call("validators", params).await
     }
 
+    /// Get the next light client block after `last_block_hash`.
+    ///
+    /// Returns the last final block of the epoch after the one containing
+    /// `last_block_hash` (or of the current epoch, if that is further ahead),
+    /// with the approvals and next block producers a light client needs to
+    /// verify it. Returns `None` when the node has no newer block to offer.
+    ///
+    /// The response is untrusted — check it with
+    /// [`LightClient::advance`](crate::light_client::LightClient::advance).
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(%last_block_hash)))]
+    pub async fn next_light_client_block(
+        &self,
+        last_block_hash: &CryptoHash,
+    ) -> Result<Option<LightClientBlockView>, RpcError> {
+        let params = serde_json::json!({ "last_block_hash": last_block_hash.to_string() });
+        // "No newer block" comes back as `{}` (or `null` from older nodes).
+        let value: serde_json::Value = self.call("next_light_client_block", params).await?;
+        match &value {
+            serde_json::Value::Null => Ok(None),
+            serde_json::Value::Object(map) if map.is_empty() => Ok(None),
+            _ => serde_json::from_value(value)
+                .map(Some)
+                .map_err(RpcError::Json),
+        }
+    }
+
+    /// Get a proof that a transaction or receipt outcome is part of the chain
+    /// ending at `light_client_head`.
+    ///
+    /// Uses `EXPERIMENTAL_light_client_proof`. `light_client_head` must be a
+    /// block the caller already trusts (typically
+    /// [`LightClient::head_hash`](crate::light_client::LightClient::head_hash)),
+    /// and the outcome must be in an earlier block. Verify the response with
+    /// [`LightClient::verify_proof`](crate::light_client::LightClient::verify_proof).
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, request), fields(%light_client_head)))]
+    pub async fn light_client_proof(
+        &self,
+        request: &LightClientProofRequest,
+        light_client_head: &CryptoHash,
+    ) -> Result<LightClientProof, RpcError> {
+        let mut params = serde_json::to_value(request).map_err(RpcError::Json)?;
+        if let Some(obj) = params.as_object_mut() {
+            obj.insert(
+                "light_client_head".to_string(),
+                serde_json::Value::String(light_client_head.to_string()),
+            );
+        }
+        self.call("EXPERIMENTAL_light_client_proof", params).await
+    }
+
     /// Send a signed transaction.
     ///
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, signed_tx), fields(
@@ -1521,7 +1740,11 @@ impl RpcClient {
     }
 
     /// Merge block reference parameters into a JSON object.
-    fn merge_block_reference(&self, params: &mut serde_json::Value, block: &BlockReference) {
+    pub(super) fn merge_block_reference(
+        &self,
+        params: &mut serde_json::Value,
+        block: &BlockReference,
+    ) {
         if let serde_json::Value::Object(block_params) = block.to_rpc_params()
             && let serde_json::Value::Object(map) = params
         {
@@ -1619,6 +1842,10 @@ impl Clone for RpcClient {
             transport: self.transport.clone(),
             retry_config: self.retry_config.clone(),
             request_id: AtomicU64::new(0),
+            batch_unsupported: AtomicBool::new(self.batch_unsupported.load(Ordering::Relaxed)),
+            rate_limiter: self.rate_limiter.clone(),
+            nonce_store: self.nonce_store.clone(),
+            cache: self.cache.clone(),
         }
     }
 }
@@ -1650,20 +1877,76 @@ fn block_id_or_null(block: Option<&BlockReference>) -> serde_json::Value {
     }
 }
 
+/// Patch the caller-known account into an `AccessKeyNotFound` error.
+///
+/// The EXPERIMENTAL endpoint's UNKNOWN_ACCESS_KEY error omits the account_id
+/// from its info payload, so it is filled in from the request params.
+pub(super) fn patch_access_key_error(error: RpcError, account_id: &AccountId) -> RpcError {
+    match error {
+        RpcError::AccessKeyNotFound {
+            public_key,
+            block_height,
+            block_hash,
+            ..
+        } => RpcError::AccessKeyNotFound {
+            account_id: account_id.clone(),
+            public_key,
+            block_height,
+            block_hash,
+        },
+        other => other,
+    }
+}
+
+/// Patch the caller-known contract and method into view-function errors.
+///
+/// EXPERIMENTAL_call_function omits them from the error info.
+pub(super) fn patch_view_function_error(
+    error: RpcError,
+    account_id: &AccountId,
+    method_name: &str,
+) -> RpcError {
+    match error {
+        RpcError::ContractExecution {
+            message,
+            block_height,
+            block_hash,
+            ..
+        } => RpcError::ContractExecution {
+            contract_id: account_id.clone(),
+            method_name: Some(method_name.to_string()),
+            message,
+            block_height,
+            block_hash,
+        },
+        RpcError::MethodNotFound {
+            block_height,
+            block_hash,
+            ..
+        } => RpcError::MethodNotFound {
+            contract_id: account_id.clone(),
+            method_name: method_name.to_string(),
+            block_height,
+            block_hash,
+        },
+        other => other,
+    }
+}
+
 /// Strip query string, fragment, and userinfo from a URL for safe logging.
 ///
 /// RPC provider URLs may carry API keys as query parameters or path tokens.
 /// This returns `scheme://host/path` so credentials don't leak into tracing spans.
 // Without `tracing`, only the unit tests reference this.
 #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
-fn sanitize_url(url: &str) -> &str {
+pub(super) fn sanitize_url(url: &str) -> &str {
     // Strip query and fragment
     let end = url.find('?').or_else(|| url.find('#')).unwrap_or(url.len());
     &url[..end]
 }
 
 /// Check if an HTTP status code is retryable.
-fn is_retryable_status(status: u16) -> bool {
+pub(super) fn is_retryable_status(status: u16) -> bool {
     // 408 Request Timeout - retryable
     // 429 Too Many Requests - retryable (rate limiting)
     // 503 Service Unavailable - retryable
@@ -1722,7 +2005,7 @@ mod tests {
             _body: Vec<u8>,
         ) -> BoxFuture<'_, Result<TransportResponse, RpcError>> {
             let body = self.body.clone();
-            Box::pin(async move { Ok(TransportResponse { status: 200, body }) })
+            Box::pin(async move { Ok(TransportResponse::new(200, body)) })
         }
     }
 
@@ -1757,7 +2040,7 @@ mod tests {
             self.calls.fetch_add(1, Ordering::SeqCst);
             let status = self.status;
             let body = self.body.clone();
-            Box::pin(async move { Ok(TransportResponse { status, body }) })
+            Box::pin(async move { Ok(TransportResponse::new(status, body)) })
         }
     }
 
@@ -1997,6 +2280,199 @@ mod tests {
         assert_eq!(transport.calls(), 1);
     }
 
+    // ========================================================================
+    // Rate limiting
+    // ========================================================================
+
+    /// Transport that answers 429 (with `retry_after`) until `limited` runs
+    /// out, then succeeds.
+    struct TooManyRequestsTransport {
+        limited: AtomicUsize,
+        retry_after: Option<Duration>,
+        calls: AtomicUsize,
+    }
+
+    impl RpcTransport for TooManyRequestsTransport {
+        fn post_json(
+            &self,
+            _url: &str,
+            _body: Vec<u8>,
+        ) -> BoxFuture<'_, Result<TransportResponse, RpcError>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let limited = self
+                .limited
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            let retry_after = self.retry_after;
+            Box::pin(async move {
+                if limited {
+                    return Ok(TransportResponse::new(429, b"Too Many Requests".to_vec())
+                        .with_retry_after(retry_after));
+                }
+                Ok(TransportResponse::new(
+                    200,
+                    br#"{"jsonrpc":"2.0","id":0,"result":{"ok":true}}"#.to_vec(),
+                ))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_429_maps_to_rate_limited() {
+        let transport = Arc::new(TooManyRequestsTransport {
+            limited: AtomicUsize::new(usize::MAX),
+            retry_after: Some(Duration::from_secs(7)),
+            calls: AtomicUsize::new(0),
+        });
+        let client = RpcClient::with_transport_and_retry_config(
+            "https://example.com",
+            transport,
+            RetryConfig::none(),
+        );
+
+        let err = client
+            .call::<_, serde_json::Value>("status", ())
+            .await
+            .unwrap_err();
+        assert!(err.is_retryable());
+        assert!(
+            matches!(
+                err,
+                RpcError::RateLimited {
+                    retry_after: Some(d),
+                    ..
+                } if d == Duration::from_secs(7)
+            ),
+            "expected RateLimited, got {err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_waits_for_retry_after() {
+        let transport = Arc::new(TooManyRequestsTransport {
+            limited: AtomicUsize::new(1),
+            retry_after: Some(Duration::from_millis(60)),
+            calls: AtomicUsize::new(0),
+        });
+        let client = RpcClient::with_transport_and_retry_config(
+            "https://example.com",
+            transport.clone(),
+            RetryConfig {
+                max_delay_ms: 100,
+                ..fast_retries(1)
+            },
+        );
+
+        let started = std::time::Instant::now();
+        let response: serde_json::Value = client.call("status", ()).await.unwrap();
+        assert_eq!(response, serde_json::json!({ "ok": true }));
+        assert_eq!(transport.calls.load(Ordering::SeqCst), 2);
+        assert!(
+            started.elapsed() >= Duration::from_millis(60),
+            "the 1 ms backoff must yield to Retry-After"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_pauses_rate_limited_client() {
+        let transport = Arc::new(TooManyRequestsTransport {
+            limited: AtomicUsize::new(1),
+            retry_after: Some(Duration::from_millis(60)),
+            calls: AtomicUsize::new(0),
+        });
+        let client = RpcClient::with_transport_and_retry_config(
+            "https://example.com",
+            transport,
+            RetryConfig::none(),
+        )
+        .with_rate_limit(RateLimitConfig::new().max_in_flight(4));
+
+        let started = std::time::Instant::now();
+        assert!(
+            client
+                .call::<_, serde_json::Value>("status", ())
+                .await
+                .is_err()
+        );
+        // The next request (any method) waits out the server's Retry-After.
+        let _: serde_json::Value = client.call("block", ()).await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(55));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_beyond_max_delay_is_not_waited_out() {
+        let transport = Arc::new(TooManyRequestsTransport {
+            limited: AtomicUsize::new(1),
+            retry_after: crate::client::transport::parse_retry_after(b"999999999"),
+            calls: AtomicUsize::new(0),
+        });
+        let client = RpcClient::with_transport_and_retry_config(
+            "https://example.com",
+            transport.clone(),
+            RetryConfig {
+                max_delay_ms: 50,
+                ..fast_retries(3)
+            },
+        )
+        .with_rate_limit(RateLimitConfig::new().max_in_flight(4));
+
+        let started = std::time::Instant::now();
+        let err = client
+            .call::<_, serde_json::Value>("status", ())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                RpcError::RateLimited {
+                    retry_after: Some(d),
+                    ..
+                } if d == Duration::from_secs(999_999_999)
+            ),
+            "expected RateLimited, got {err:?}"
+        );
+        assert_eq!(transport.calls.load(Ordering::SeqCst), 1);
+
+        // The limiter pause is capped at `max_delay_ms` too.
+        let _: serde_json::Value = client.call("block", ()).await.unwrap();
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_paces_requests() {
+        let transport = CountingTransport::new(200, r#"{"jsonrpc":"2.0","id":0,"result":{}}"#);
+        let client = RpcClient::with_transport_and_retry_config(
+            "https://example.com",
+            transport.clone(),
+            RetryConfig::none(),
+        )
+        .with_rate_limit(RateLimitConfig::new().requests_per_second(20).burst(1));
+
+        let started = std::time::Instant::now();
+        for _ in 0..3 {
+            let _: serde_json::Value = client.call("status", ()).await.unwrap();
+        }
+        // One free request, then two more at 50 ms intervals.
+        assert!(started.elapsed() >= Duration::from_millis(90));
+        assert_eq!(transport.calls(), 3);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        use crate::client::transport::parse_retry_after;
+
+        assert_eq!(parse_retry_after(b"120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(b" 3 "), Some(Duration::from_secs(3)));
+        // HTTP-date form falls back to the client's own backoff.
+        assert_eq!(parse_retry_after(b"Wed, 21 Oct 2015 07:28:00 GMT"), None);
+    }
+
+    #[test]
+    fn test_unlimited_rate_limit_installs_no_limiter() {
+        let client = RpcClient::new("https://example.com").with_rate_limit(RateLimitConfig::new());
+        assert!(client.rate_limiter.is_none());
+    }
+
     // ========================================================================
     // RpcClient tests
     // ========================================================================
@@ -2275,7 +2751,7 @@ mod tests {
                 .unwrap()
                 .pop_front()
                 .expect("more RPC requests than queued responses");
-            Box::pin(async move { Ok(TransportResponse { status: 200, body }) })
+            Box::pin(async move { Ok(TransportResponse::new(200, body)) })
         }
     }
 
@@ -4594,15 +5070,12 @@ mod tests {
                 let attempt = self.calls.fetch_add(1, Ordering::SeqCst);
                 Box::pin(async move {
                     Ok(if attempt == 0 {
-                        TransportResponse {
-                            status: 503,
-                            body: b"upstream unavailable".to_vec(),
-                        }
+                        TransportResponse::new(503, b"upstream unavailable".to_vec())
                     } else {
-                        TransportResponse {
-                            status: 200,
-                            body: br#"{"jsonrpc":"2.0","id":0,"result":{"ok":true}}"#.to_vec(),
-                        }
+                        TransportResponse::new(
+                            200,
+                            br#"{"jsonrpc":"2.0","id":0,"result":{"ok":true}}"#.to_vec(),
+                        )
                     })
                 })
             }
@@ -4691,6 +5164,203 @@ mod tests {
         }
     }
 
+    // ========================================================================
+    // EXPERIMENTAL_changes
+    // ========================================================================
+
+    #[tokio::test]
+    async fn test_changes_sends_request_with_block_reference() {
+        let transport = RecordingTransport::new([serde_json::json!({
+            "block_hash": QUERY_BLOCK_HASH,
+            "changes": [{
+                "cause": {
+                    "type": "receipt_processing",
+                    "receipt_hash": "8itzpE2FYkfXEFf1Pg6BZrrXemAXHaM5p6PvmbNFK9EV",
+                },
+                "type": "data_update",
+                "change": {
+                    "account_id": "app.near",
+                    "key_base64": "U1RBVEU=",
+                    "value_base64": "dg==",
+                },
+            }],
+        })]);
+        let account: AccountId = "app.near".parse().unwrap();
+
+        let response = transport
+            .client()
+            .changes(
+                &StateChangesRequest::data_changes([account], b"STATE"),
+                BlockReference::at_height(42),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            transport.params(),
+            vec![serde_json::json!({
+                "changes_type": "data_changes",
+                "account_ids": ["app.near"],
+                "key_prefix_base64": "U1RBVEU=",
+                "block_id": 42,
+            })]
+        );
+        assert_eq!(response.block_hash, QUERY_BLOCK_HASH.parse().unwrap());
+        assert_eq!(response.changes.len(), 1);
+        assert!(matches!(
+            response.changes[0].value,
+            crate::types::StateChangeValueView::DataUpdate { .. }
+        ));
+    }
+
+    // ========================================================================
+    // Chunks and congestion
+    // ========================================================================
+
+    #[tokio::test]
+    async fn test_chunk_by_block_and_shard() {
+        let hash = "8itzpE2FYkfXEFf1Pg6BZrrXemAXHaM5p6PvmbNFK9EV";
+        let transport = RecordingTransport::new([serde_json::json!({
+            "author": "node0",
+            "header": {
+                "chunk_hash": hash,
+                "prev_block_hash": hash,
+                "outcome_root": hash,
+                "prev_state_root": hash,
+                "encoded_merkle_root": hash,
+                "encoded_length": 8,
+                "height_created": 41,
+                "height_included": 42,
+                "shard_id": 3,
+                "gas_used": 0,
+                "gas_limit": 1000000000000000u64,
+                "validator_reward": "0",
+                "balance_burnt": "0",
+                "outgoing_receipts_root": hash,
+                "tx_root": hash,
+                "validator_proposals": [],
+                "signature": "ed25519:3s1dvZdQtcAjBksMHFrysqvF63wnyMHPA4owNQmCJZ2EBakZEKdtMsLqrHdKWQjJbSRN6kRknN2WdwSBLWGCokXj",
+            },
+            "transactions": [],
+            "receipts": [],
+        })]);
+
+        let chunk = transport
+            .client()
+            .chunk(ChunkReference::at_height(42, 3))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            transport.params(),
+            vec![serde_json::json!({ "block_id": 42, "shard_id": 3 })]
+        );
+        assert_eq!(chunk.author.as_str(), "node0");
+        assert_eq!(chunk.header.shard_id, 3);
+        assert!(chunk.transactions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_congestion_level_by_chunk_hash() {
+        let transport = RecordingTransport::new([serde_json::json!({ "congestion_level": 0.25 })]);
+        let chunk_hash: CryptoHash = QUERY_BLOCK_HASH.parse().unwrap();
+
+        let level = transport
+            .client()
+            .congestion_level(ChunkReference::chunk_hash(chunk_hash))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            transport.params(),
+            vec![serde_json::json!({ "chunk_id": QUERY_BLOCK_HASH })]
+        );
+        assert_eq!(level.congestion_level, 0.25);
+    }
+
+    // ========================================================================
+    // Light client
+    // ========================================================================
+
+    #[tokio::test]
+    async fn test_next_light_client_block_empty_response_is_none() {
+        let transport = RecordingTransport::new([serde_json::json!({})]);
+        let head: CryptoHash = QUERY_BLOCK_HASH.parse().unwrap();
+
+        let block = transport
+            .client()
+            .next_light_client_block(&head)
+            .await
+            .unwrap();
+
+        assert!(block.is_none());
+        assert_eq!(
+            transport.params(),
+            vec![serde_json::json!({ "last_block_hash": QUERY_BLOCK_HASH })]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_light_client_proof_sends_head_with_request() {
+        let hash = "8itzpE2FYkfXEFf1Pg6BZrrXemAXHaM5p6PvmbNFK9EV";
+        let inner_lite = serde_json::json!({
+            "height": 42,
+            "epoch_id": hash,
+            "next_epoch_id": hash,
+            "prev_state_root": hash,
+            "outcome_root": hash,
+            "timestamp": 1700000000000000000u64,
+            "timestamp_nanosec": "1700000000000000000",
+            "next_bp_hash": hash,
+            "block_merkle_root": hash,
+        });
+        let transport = RecordingTransport::new([serde_json::json!({
+            "outcome_proof": {
+                "id": hash,
+                "outcome": {
+                    "executor_id": "alice.near",
+                    "gas_burnt": 1,
+                    "tokens_burnt": "1",
+                    "logs": [],
+                    "receipt_ids": [],
+                    "status": { "SuccessValue": "" },
+                },
+                "proof": [{ "hash": hash, "direction": "Left" }],
+                "block_hash": hash,
+            },
+            "outcome_root_proof": [],
+            "block_header_lite": {
+                "prev_block_hash": hash,
+                "inner_rest_hash": hash,
+                "inner_lite": inner_lite,
+            },
+            "block_proof": [{ "hash": hash, "direction": "Right" }],
+        })]);
+        let request = LightClientProofRequest::Receipt {
+            receipt_id: hash.parse().unwrap(),
+            receiver_id: "alice.near".parse().unwrap(),
+        };
+        let head: CryptoHash = QUERY_BLOCK_HASH.parse().unwrap();
+
+        let proof = transport
+            .client()
+            .light_client_proof(&request, &head)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            transport.params(),
+            vec![serde_json::json!({
+                "type": "receipt",
+                "receipt_id": hash,
+                "receiver_id": "alice.near",
+                "light_client_head": QUERY_BLOCK_HASH,
+            })]
+        );
+        assert_eq!(proof.block_header_lite.inner_lite.height, 42);
+        assert_eq!(proof.block_proof.len(), 1);
+    }
+
     // ========================================================================
     // Block metadata on typed `query` views
     // ========================================================================
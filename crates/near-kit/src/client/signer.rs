@@ -10,6 +10,8 @@
 //! - [`FileSigner`] - Key loaded from ~/.near-credentials
 //! - [`EnvSigner`] - Key loaded from environment variables
 //! - [`RotatingSigner`] - Multiple keys with round-robin rotation
+//! - [`LedgerSigner`](super::LedgerSigner) - Key held on a Ledger hardware wallet
+//! - [`HttpSigner`](crate::HttpSigner) - Signatures requested from a remote signing service
 //!
 //! # Example
 //!
@@ -38,8 +40,11 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::error::SignerError;
+use crate::platform::BoxFuture;
 use crate::types::nep413::{self, SignMessageParams, SignedMessage};
-use crate::types::{AccountId, PublicKey, SecretKey, Signature, TryIntoAccountId};
+use crate::types::{
+    AccountId, DelegateAction, PublicKey, SecretKey, Signature, Transaction, TryIntoAccountId,
+};
 
 // ============================================================================
 // Signer Trait
@@ -51,6 +56,9 @@ use crate::types::{AccountId, PublicKey, SecretKey, Signature, TryIntoAccountId}
 /// The `key()` method returns a [`SigningKey`] that bundles together the public
 /// key and signing capability, ensuring atomic key claiming.
 ///
+/// Signers are `Send + Sync` on every target except `wasm32-unknown-unknown`,
+/// where a signer may hold single-threaded handles such as a WebHID device.
+///
 /// # Example Implementation
 ///
 /// ```rust,ignore
@@ -71,7 +79,7 @@ use crate::types::{AccountId, PublicKey, SecretKey, Signature, TryIntoAccountId}
 ///     }
 /// }
 /// ```
-pub trait Signer: Send + Sync {
+pub trait Signer: crate::platform::MaybeSend + crate::platform::MaybeSync {
     /// The account this signer signs for.
     fn account_id(&self) -> &AccountId;
 
@@ -122,7 +130,8 @@ impl<T: Signer + ?Sized> Signer for Arc<T> {
 ///
 /// This bundles together a public key and the ability to sign with the
 /// corresponding private key. For in-memory keys, signing is instant.
-/// For hardware wallets or KMS, signing may involve async operations.
+/// For hardware wallets or KMS, signing may involve async operations; see
+/// [`SigningBackend`].
 ///
 /// # Example
 ///
@@ -156,6 +165,17 @@ impl SigningKey {
         }
     }
 
+    /// Create a signing key whose signatures come from `backend`.
+    ///
+    /// `public_key` must be the key `backend` signs for; it is what goes
+    /// into transactions.
+    pub fn from_backend(public_key: PublicKey, backend: impl SigningBackend + 'static) -> Self {
+        Self {
+            public_key,
+            backend: Arc::new(backend),
+        }
+    }
+
     /// Get the public key.
     pub fn public_key(&self) -> &PublicKey {
         &self.public_key
@@ -170,6 +190,29 @@ impl SigningKey {
         self.backend.sign(message).await
     }
 
+    /// Sign a transaction.
+    ///
+    /// Equivalent to signing [`Transaction::get_hash`], except that backends
+    /// which must see the whole transaction — hardware wallets that show it
+    /// for confirmation — receive it.
+    pub async fn sign_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<Signature, SignerError> {
+        self.backend.sign_transaction(transaction).await
+    }
+
+    /// Sign a NEP-366 delegate action (meta transaction).
+    ///
+    /// Equivalent to signing [`DelegateAction::get_hash`]; see
+    /// [`sign_transaction`](Self::sign_transaction).
+    pub async fn sign_delegate_action(
+        &self,
+        delegate_action: &DelegateAction,
+    ) -> Result<Signature, SignerError> {
+        self.backend.sign_delegate_action(delegate_action).await
+    }
+
     /// Sign a NEP-413 message for off-chain authentication.
     ///
     /// # Example
@@ -195,8 +238,7 @@ impl SigningKey {
         account_id: &AccountId,
         params: &SignMessageParams,
     ) -> Result<SignedMessage, SignerError> {
-        let hash = nep413::serialize_message(params);
-        let signature = self.sign(hash.as_bytes()).await?;
+        let signature = self.backend.sign_nep413(params).await?;
 
         Ok(SignedMessage {
             account_id: account_id.clone(),
@@ -225,18 +267,109 @@ impl std::fmt::Debug for SigningKey {
 }
 
 // ============================================================================
-// SigningBackend (internal)
+// SigningBackend
 // ============================================================================
 
-/// Internal trait for signing backends.
+/// Where a [`SigningKey`]'s signatures come from.
+///
+/// Implement this to keep the private key out of the process — in a KMS, an
+/// HSM, a Vault transit engine or a signing daemon — and wrap the backend with
+/// [`SigningKey::from_backend`]. [`HttpSigner`](crate::HttpSigner) is a
+/// reference implementation that posts the hash to a signing endpoint.
+///
+/// Only [`sign`](Self::sign) is required: it receives the SHA-256 hash of the
+/// borsh payload. The structured methods default to signing that hash, and
+/// exist for backends that must see the payload itself, such as
+/// [`LedgerSigner`](crate::LedgerSigner), which shows it for confirmation.
+///
+/// The returned futures only borrow `self`; copy the message (or payload)
+/// into them.
+///
+/// # Example
 ///
-/// This allows different implementations (in-memory, hardware wallet, KMS)
-/// to provide signing capability.
-trait SigningBackend: crate::platform::MaybeSend + crate::platform::MaybeSync {
-    fn sign(
+/// ```rust,ignore
+/// use near_kit::{BoxFuture, Signature, SigningBackend, SigningKey};
+/// use near_kit::error::SignerError;
+///
+/// struct Kms {
+///     client: kms::Client,
+///     key_id: String,
+/// }
+///
+/// impl SigningBackend for Kms {
+///     fn sign(&self, message: &[u8]) -> BoxFuture<'_, Result<Signature, SignerError>> {
+///         let message = message.to_vec();
+///         Box::pin(async move {
+///             let bytes = self
+///                 .client
+///                 .sign(&self.key_id, &message)
+///                 .await
+///                 .map_err(|e| SignerError::SigningFailed(e.to_string()))?;
+///             Ok(Signature::ed25519_from_bytes(bytes))
+///         })
+///     }
+/// }
+///
+/// let key = SigningKey::from_backend(public_key, Kms { client, key_id });
+/// ```
+pub trait SigningBackend: crate::platform::MaybeSend + crate::platform::MaybeSync {
+    /// Sign `message`, the 32-byte hash of a transaction, delegate action or
+    /// NEP-413 message.
+    fn sign(&self, message: &[u8]) -> BoxFuture<'_, Result<Signature, SignerError>>;
+
+    /// Sign a transaction. Defaults to signing [`Transaction::get_hash`].
+    fn sign_transaction(
         &self,
-        message: &[u8],
-    ) -> crate::platform::BoxFuture<'_, Result<Signature, SignerError>>;
+        transaction: &Transaction,
+    ) -> BoxFuture<'_, Result<Signature, SignerError>> {
+        self.sign(transaction.get_hash().as_bytes())
+    }
+
+    /// Sign a NEP-366 delegate action. Defaults to signing
+    /// [`DelegateAction::get_hash`].
+    fn sign_delegate_action(
+        &self,
+        delegate_action: &DelegateAction,
+    ) -> BoxFuture<'_, Result<Signature, SignerError>> {
+        self.sign(delegate_action.get_hash().as_bytes())
+    }
+
+    /// Sign a NEP-413 message. Defaults to signing
+    /// [`nep413::serialize_message`].
+    fn sign_nep413(
+        &self,
+        params: &SignMessageParams,
+    ) -> BoxFuture<'_, Result<Signature, SignerError>> {
+        self.sign(nep413::serialize_message(params).as_bytes())
+    }
+}
+
+/// Forwarding impl so an `Arc`-shared backend can back several keys.
+impl<T: SigningBackend + ?Sized> SigningBackend for Arc<T> {
+    fn sign(&self, message: &[u8]) -> BoxFuture<'_, Result<Signature, SignerError>> {
+        (**self).sign(message)
+    }
+
+    fn sign_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> BoxFuture<'_, Result<Signature, SignerError>> {
+        (**self).sign_transaction(transaction)
+    }
+
+    fn sign_delegate_action(
+        &self,
+        delegate_action: &DelegateAction,
+    ) -> BoxFuture<'_, Result<Signature, SignerError>> {
+        (**self).sign_delegate_action(delegate_action)
+    }
+
+    fn sign_nep413(
+        &self,
+        params: &SignMessageParams,
+    ) -> BoxFuture<'_, Result<Signature, SignerError>> {
+        (**self).sign_nep413(params)
+    }
 }
 
 /// In-memory signing backend using a secret key.
@@ -245,10 +378,7 @@ struct SecretKeyBackend {
 }
 
 impl SigningBackend for SecretKeyBackend {
-    fn sign(
-        &self,
-        message: &[u8],
-    ) -> crate::platform::BoxFuture<'_, Result<Signature, SignerError>> {
+    fn sign(&self, message: &[u8]) -> BoxFuture<'_, Result<Signature, SignerError>> {
         let sig = self.secret_key.sign(message);
         Box::pin(async move { Ok(sig) })
     }
@@ -843,7 +973,7 @@ impl RotatingSigner {
     /// Split into per-key [`InMemorySigner`] instances.
     ///
     /// Each signer uses a single key from the rotation pool, allowing
-    /// independent send ordering per key. The global nonce manager
+    /// independent send ordering per key. The client's nonce store
     /// automatically tracks nonces per `(account_id, public_key)`,
     /// so per-key signers coordinate correctly without extra setup.
     ///
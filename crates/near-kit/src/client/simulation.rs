@@ -0,0 +1,270 @@
+//! Pre-flight simulation for [`TransactionBuilder`](super::TransactionBuilder).
+//!
+//! [`TransactionBuilder::simulate`](super::TransactionBuilder::simulate)
+//! prices a transaction from the protocol config and the current gas price,
+//! runs its function calls as view calls where the view environment allows,
+//! and checks the signer can afford it — without signing or sending anything.
+
+use crate::error::{Error, RpcError};
+use crate::types::{AccountId, Action, BlockReference, Finality, Gas, NearToken};
+
+use super::rpc::RpcClient;
+
+/// Estimated cost and detectable failures of a transaction, from
+/// [`TransactionBuilder::simulate`](super::TransactionBuilder::simulate).
+///
+/// Protocol fees are exact for the current protocol config. Gas burnt
+/// *inside* function calls cannot be measured up front — view calls do not
+/// report gas usage — so each call's attached gas is counted in full as an
+/// upper bound. [`gas_burnt`](Self::gas_burnt) and the costs derived from it
+/// are therefore a ceiling, not a point estimate; unused gas is refunded.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use near_kit::*;
+/// # async fn example(near: Near) -> Result<(), near_kit::Error> {
+/// let simulation = near
+///     .call("counter.testnet", "increment")
+///     .gas(Gas::from_tgas(10))
+///     .simulate()
+///     .await?;
+///
+/// if simulation.is_ok() {
+///     println!("Up to {} ({})", simulation.total_cost(), simulation.gas_burnt());
+/// } else {
+///     for failure in &simulation.failures {
+///         println!("Would fail: {failure}");
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionSimulation {
+    /// Protocol fees: converting the transaction to a receipt and executing
+    /// each of its actions.
+    pub fee_gas: Gas,
+    /// Gas attached to function calls.
+    pub attached_gas: Gas,
+    /// Gas price at the latest block.
+    pub gas_price: NearToken,
+    /// NEAR attached by transfers, function calls and state init, plus any
+    /// increase in stake.
+    pub deposit: NearToken,
+    /// The signer's spendable balance, or `None` if the account was not found.
+    pub available_balance: Option<NearToken>,
+    /// Problems that would make the transaction fail.
+    pub failures: Vec<SimulationFailure>,
+    /// Function calls that could not be checked as view calls, by method
+    /// name — either they touch state views may not (writes, promises,
+    /// `predecessor_account_id`, ...) or an earlier action in the same
+    /// transaction changes the receiver's code.
+    pub unverified_calls: Vec<String>,
+    /// Number of actions whose fees the protocol config view does not
+    /// describe; they are left out of [`fee_gas`](Self::fee_gas).
+    pub unpriced_actions: usize,
+}
+
+impl TransactionSimulation {
+    /// Upper bound on the gas the transaction burns: fees plus attached gas.
+    pub fn gas_burnt(&self) -> Gas {
+        Gas::from_gas(
+            self.fee_gas
+                .as_gas()
+                .saturating_add(self.attached_gas.as_gas()),
+        )
+    }
+
+    /// [`gas_burnt`](Self::gas_burnt) priced at [`gas_price`](Self::gas_price).
+    pub fn gas_cost(&self) -> NearToken {
+        NearToken::from_yoctonear(
+            self.gas_price
+                .as_yoctonear()
+                .saturating_mul(self.gas_burnt().as_gas() as u128),
+        )
+    }
+
+    /// Gas cost plus deposits — what the signer needs to have available.
+    pub fn total_cost(&self) -> NearToken {
+        NearToken::from_yoctonear(
+            self.gas_cost()
+                .as_yoctonear()
+                .saturating_add(self.deposit.as_yoctonear()),
+        )
+    }
+
+    /// Whether no failure was detected.
+    ///
+    /// Calls in [`unverified_calls`](Self::unverified_calls) may still fail.
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// A failure detected by
+/// [`TransactionBuilder::simulate`](super::TransactionBuilder::simulate).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SimulationFailure {
+    /// The signer or receiver account does not exist.
+    #[error("account not found: {account_id}")]
+    AccountNotFound { account_id: AccountId },
+
+    /// The signer cannot cover [`TransactionSimulation::total_cost`].
+    #[error("insufficient balance: {required} required, {available} available")]
+    InsufficientBalance {
+        required: NearToken,
+        available: NearToken,
+    },
+
+    /// A function call targets an account with no contract deployed.
+    #[error("no contract deployed on {contract_id}")]
+    ContractNotDeployed { contract_id: AccountId },
+
+    /// A function call targets a method the contract does not export.
+    #[error("contract method not found: `{contract_id}::{method_name}`")]
+    MethodNotFound {
+        contract_id: AccountId,
+        method_name: String,
+    },
+
+    /// A function call panicked when run as a view call.
+    #[error("`{method_name}` panicked: {message}")]
+    ContractPanic {
+        method_name: String,
+        message: String,
+    },
+}
+
+/// Simulate `actions` sent by `signer_id` to `receiver_id`.
+pub(crate) async fn simulate(
+    rpc: &RpcClient,
+    signer_id: &AccountId,
+    receiver_id: &AccountId,
+    actions: &[Action],
+) -> Result<TransactionSimulation, Error> {
+    let block = BlockReference::Finality(Finality::Final);
+
+    let config = rpc.protocol_config(block).await?;
+    let fees = &config.runtime_config.transaction_costs;
+    let gas_price = rpc.gas_price(None).await?.gas_price;
+
+    let mut failures = Vec::new();
+    let balance = match rpc.view_account(signer_id, block).await {
+        Ok(account) => Some((account.available(), account.locked)),
+        Err(RpcError::AccountNotFound { .. }) => {
+            failures.push(SimulationFailure::AccountNotFound {
+                account_id: signer_id.clone(),
+            });
+            None
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let sender_is_receiver = signer_id == receiver_id;
+    let mut fee_gas = fees.receipt_fee(sender_is_receiver).as_gas();
+    let mut attached_gas = 0u64;
+    let mut deposit = 0u128;
+    let mut unpriced_actions = 0;
+    let mut unverified_calls = Vec::new();
+    // Once an action replaces (or creates) the receiver's code, later calls
+    // would run against code the node has not seen yet.
+    let mut code_changed = false;
+
+    for action in actions {
+        match fees.action_fee(action, sender_is_receiver) {
+            Some(fee) => fee_gas = fee_gas.saturating_add(fee.as_gas()),
+            None => unpriced_actions += 1,
+        }
+
+        match action {
+            Action::Transfer(transfer) => {
+                deposit = deposit.saturating_add(transfer.deposit.as_yoctonear());
+            }
+            Action::DeterministicStateInit(init) => {
+                deposit = deposit.saturating_add(init.deposit.as_yoctonear());
+                code_changed = true;
+            }
+            Action::Stake(stake) => {
+                let already_locked = balance.map_or(0, |(_, locked)| locked.as_yoctonear());
+                deposit = deposit
+                    .saturating_add(stake.stake.as_yoctonear().saturating_sub(already_locked));
+            }
+            Action::CreateAccount(_)
+            | Action::DeployContract(_)
+            | Action::UseGlobalContract(_)
+            | Action::DeleteAccount(_) => code_changed = true,
+            Action::FunctionCall(call) => {
+                deposit = deposit.saturating_add(call.deposit.as_yoctonear());
+                attached_gas = attached_gas.saturating_add(call.gas.as_gas());
+
+                if code_changed {
+                    unverified_calls.push(call.method_name.clone());
+                    continue;
+                }
+                match rpc
+                    .view_function(receiver_id, &call.method_name, &call.args, block)
+                    .await
+                {
+                    Ok(_) => {}
+                    Err(RpcError::AccountNotFound { account_id, .. }) => {
+                        failures.push(SimulationFailure::AccountNotFound { account_id });
+                    }
+                    Err(RpcError::ContractNotDeployed { account_id, .. }) => {
+                        failures.push(SimulationFailure::ContractNotDeployed {
+                            contract_id: account_id,
+                        });
+                    }
+                    Err(RpcError::MethodNotFound {
+                        contract_id,
+                        method_name,
+                        ..
+                    }) => {
+                        failures.push(SimulationFailure::MethodNotFound {
+                            contract_id,
+                            method_name,
+                        });
+                    }
+                    Err(RpcError::ContractPanic { message, .. }) => {
+                        failures.push(SimulationFailure::ContractPanic {
+                            method_name: call.method_name.clone(),
+                            message,
+                        });
+                    }
+                    // Most often `ProhibitedInView`: the method does something
+                    // only a real transaction may do.
+                    Err(RpcError::ContractExecution { .. }) => {
+                        unverified_calls.push(call.method_name.clone());
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut simulation = TransactionSimulation {
+        fee_gas: Gas::from_gas(fee_gas),
+        attached_gas: Gas::from_gas(attached_gas),
+        gas_price,
+        deposit: NearToken::from_yoctonear(deposit),
+        available_balance: balance.map(|(available, _)| available),
+        failures,
+        unverified_calls,
+        unpriced_actions,
+    };
+
+    if let Some(available) = simulation.available_balance {
+        let required = simulation.total_cost();
+        if required > available {
+            simulation
+                .failures
+                .push(SimulationFailure::InsufficientBalance {
+                    required,
+                    available,
+                });
+        }
+    }
+
+    Ok(simulation)
+}
@@ -0,0 +1,820 @@
+//! Streams that follow the chain by polling the RPC.
+//!
+//! [`BlockStream`] yields every block at a chosen finality, in order;
+//! [`AccountActivityStream`] narrows it to the transactions and receipts
+//! touching one account.
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::{Stream, StreamExt};
+
+use super::rpc::{RpcClient, async_sleep};
+use crate::error::{Error, RpcError};
+use crate::types::{
+    AccountId, BlockReference, BlockView, ChunkReference, ChunkView, CryptoHash, Finality, Receipt,
+    TransactionView, TryIntoAccountId,
+};
+
+/// A boxed stream that is `Send` everywhere except `wasm32-unknown-unknown`
+/// (see [`BoxFuture`](crate::platform::BoxFuture)).
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+type BoxStream<T> = Pin<Box<dyn Stream<Item = T> + Send>>;
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+type BoxStream<T> = Pin<Box<dyn Stream<Item = T>>>;
+
+/// How many recently yielded blocks are remembered to find where a reorg
+/// forked off.
+const MAX_REORG_DEPTH: usize = 64;
+
+/// A stream of blocks, from [`Near::blocks`](super::Near::blocks).
+///
+/// Polls `block` at the configured finality (`final` by default) every
+/// [`poll_interval`](Self::poll_interval) and yields each new block once, in
+/// chain order:
+///
+/// - **Gaps** — when the head moved by more than one block since the last
+///   poll, the blocks in between are fetched by height before the head is
+///   yielded. Heights that were skipped (no block was produced) are passed
+///   over.
+/// - **Reorgs** — at `optimistic` finality a yielded block can be replaced by
+///   another at the same height. The stream then walks back from the new
+///   block to the last block both branches share and yields the new branch
+///   from there, so a block whose height is not above the previously yielded
+///   one replaces the blocks at that height and above.
+/// - **Chunks** — blocks only carry chunk headers; fetch the chunks of a
+///   yielded block with [`Near::chunks`](super::Near::chunks).
+///
+/// Failed polls are yielded as errors and retried after the poll interval;
+/// the stream never ends on its own.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use near_kit::*;
+/// use futures::StreamExt;
+///
+/// # async fn example() -> Result<(), Error> {
+/// let near = Near::testnet().build();
+/// let mut blocks = near.blocks().finality(Finality::Optimistic);
+/// while let Some(block) = blocks.next().await {
+///     let block = block?;
+///     println!("#{} {}", block.header.height, block.header.hash);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "streams do nothing unless polled"]
+pub struct BlockStream {
+    rpc: Arc<RpcClient>,
+    config: FollowConfig,
+    inner: Option<BoxStream<Result<BlockView, Error>>>,
+}
+
+#[derive(Debug, Clone)]
+struct FollowConfig {
+    finality: Finality,
+    poll_interval: Duration,
+    from_height: Option<u64>,
+}
+
+impl BlockStream {
+    /// Poll interval unless configured otherwise: about one block.
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    pub(crate) fn new(rpc: Arc<RpcClient>) -> Self {
+        Self {
+            rpc,
+            config: FollowConfig {
+                finality: Finality::Final,
+                poll_interval: Self::DEFAULT_POLL_INTERVAL,
+                from_height: None,
+            },
+            inner: None,
+        }
+    }
+
+    /// Follow blocks at this finality instead of `final`.
+    pub fn finality(mut self, finality: Finality) -> Self {
+        self.config.finality = finality;
+        self
+    }
+
+    /// Set how long to wait between polls once the stream has caught up.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.config.poll_interval = interval;
+        self
+    }
+
+    /// Start at `height` instead of the current head, backfilling every block
+    /// since.
+    ///
+    /// Regular nodes only keep a few epochs of blocks; start further back
+    /// through an archival endpoint.
+    pub fn from_height(mut self, height: u64) -> Self {
+        self.config.from_height = Some(height);
+        self
+    }
+
+    /// Narrow the stream to the transactions and receipts touching
+    /// `account_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `account_id` is not a valid account ID.
+    pub fn watch_account(self, account_id: impl TryIntoAccountId) -> AccountActivityStream {
+        let account_id = account_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        AccountActivityStream::new(self, account_id)
+    }
+}
+
+impl Stream for BlockStream {
+    type Item = Result<BlockView, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let inner = this.inner.get_or_insert_with(|| {
+            let follower = Follower::new(this.rpc.clone(), this.config.clone());
+            Box::pin(futures::stream::unfold(
+                follower,
+                |mut follower| async move {
+                    let item = follower.next_block().await;
+                    Some((item, follower))
+                },
+            ))
+        });
+        inner.poll_next_unpin(cx)
+    }
+}
+
+impl std::fmt::Debug for BlockStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockStream")
+            .field("config", &self.config)
+            .field("started", &self.inner.is_some())
+            .finish()
+    }
+}
+
+/// Height and hash of a yielded block.
+#[derive(Debug, Clone, Copy)]
+struct Tip {
+    height: u64,
+    hash: CryptoHash,
+}
+
+/// The polling state behind a [`BlockStream`].
+struct Follower {
+    rpc: Arc<RpcClient>,
+    config: FollowConfig,
+    /// Recently yielded blocks, oldest first; the last one is the tip.
+    recent: VecDeque<Tip>,
+    /// Blocks ready to be yielded.
+    pending: VecDeque<BlockView>,
+    /// The newest head seen, while the blocks before it are backfilled.
+    head: Option<BlockView>,
+    /// Next height to backfill.
+    next_height: u64,
+    /// Whether the last step failed, so the next one waits first.
+    failed: bool,
+}
+
+impl Follower {
+    fn new(rpc: Arc<RpcClient>, config: FollowConfig) -> Self {
+        Self {
+            rpc,
+            config,
+            recent: VecDeque::new(),
+            pending: VecDeque::new(),
+            head: None,
+            next_height: 0,
+            failed: false,
+        }
+    }
+
+    async fn next_block(&mut self) -> Result<BlockView, Error> {
+        if std::mem::take(&mut self.failed) {
+            async_sleep(self.config.poll_interval).await;
+        }
+        let result = self.step().await;
+        self.failed = result.is_err();
+        result
+    }
+
+    async fn step(&mut self) -> Result<BlockView, Error> {
+        loop {
+            if let Some(block) = self.pending.pop_front() {
+                self.remember(&block);
+                return Ok(block);
+            }
+
+            if let Some(head_height) = self.head.as_ref().map(|head| head.header.height)
+                && self.next_height <= head_height
+            {
+                let height = self.next_height;
+                let block = if height == head_height {
+                    self.head.take().expect("head is set")
+                } else {
+                    match self.rpc.block(BlockReference::at_height(height)).await {
+                        Ok(block) => block,
+                        // No block was produced at this height.
+                        Err(RpcError::UnknownBlock(_)) => {
+                            self.next_height += 1;
+                            continue;
+                        }
+                        Err(e) => return Err(e.into()),
+                    }
+                };
+                self.link(block).await?;
+                continue;
+            }
+
+            let head = self
+                .rpc
+                .block(BlockReference::Finality(self.config.finality))
+                .await?;
+            match self.recent.back().copied() {
+                None => {
+                    self.next_height = self.config.from_height.unwrap_or(head.header.height);
+                    if self.next_height > head.header.height {
+                        // Started ahead of the chain: wait for it to get there.
+                        async_sleep(self.config.poll_interval).await;
+                    } else {
+                        self.head = Some(head);
+                    }
+                }
+                Some(tip) if head.header.height > tip.height => {
+                    self.next_height = tip.height + 1;
+                    self.head = Some(head);
+                }
+                Some(tip) if head.header.height == tip.height && head.header.hash != tip.hash => {
+                    self.link(head).await?;
+                }
+                Some(_) => async_sleep(self.config.poll_interval).await,
+            }
+        }
+    }
+
+    /// Queue `block` behind the tip, first queueing the rest of its branch if
+    /// it does not build on the tip.
+    async fn link(&mut self, block: BlockView) -> Result<(), Error> {
+        self.next_height = block.header.height + 1;
+        let builds_on_tip = self
+            .recent
+            .back()
+            .is_none_or(|tip| tip.hash == block.header.prev_hash);
+        if builds_on_tip {
+            self.pending.push_back(block);
+            return Ok(());
+        }
+
+        let mut branch = vec![block];
+        loop {
+            let prev_hash = branch
+                .last()
+                .expect("branch is never empty")
+                .header
+                .prev_hash;
+            if let Some(fork) = self.recent.iter().rposition(|tip| tip.hash == prev_hash) {
+                self.recent.truncate(fork + 1);
+                break;
+            }
+            if branch.len() >= MAX_REORG_DEPTH {
+                // The fork point is older than anything remembered.
+                self.recent.clear();
+                break;
+            }
+            branch.push(self.rpc.block(BlockReference::at_hash(prev_hash)).await?);
+        }
+        self.pending.extend(branch.into_iter().rev());
+        Ok(())
+    }
+
+    fn remember(&mut self, block: &BlockView) {
+        // A replacement block drops the ones it replaces.
+        while self
+            .recent
+            .back()
+            .is_some_and(|tip| tip.height >= block.header.height)
+        {
+            self.recent.pop_back();
+        }
+        self.recent.push_back(Tip {
+            height: block.header.height,
+            hash: block.header.hash,
+        });
+        if self.recent.len() > MAX_REORG_DEPTH {
+            self.recent.pop_front();
+        }
+    }
+}
+
+/// Fetch the chunks produced in `block` (those whose `height_included` is the
+/// block's height), in shard order.
+pub(crate) async fn block_chunks(
+    rpc: &RpcClient,
+    block: &BlockView,
+) -> Result<Vec<ChunkView>, Error> {
+    let fetches = block
+        .chunks
+        .iter()
+        .filter(|chunk| chunk.height_included == block.header.height)
+        .map(|chunk| rpc.chunk(ChunkReference::chunk_hash(chunk.chunk_hash)));
+    let chunks = futures::future::try_join_all(fetches).await?;
+    Ok(chunks)
+}
+
+/// A transaction or receipt touching a watched account, from
+/// [`AccountActivityStream`].
+#[derive(Debug, Clone)]
+pub enum AccountActivity {
+    /// A transaction signed by or sent to the account.
+    Transaction {
+        /// Height of the block whose chunk included it.
+        block_height: u64,
+        /// Hash of that block.
+        block_hash: CryptoHash,
+        /// Shard of the chunk.
+        shard_id: u64,
+        /// The transaction.
+        transaction: TransactionView,
+    },
+    /// A receipt sent by or to the account.
+    Receipt {
+        /// Height of the block whose chunk included it.
+        block_height: u64,
+        /// Hash of that block.
+        block_hash: CryptoHash,
+        /// Shard of the chunk.
+        shard_id: u64,
+        /// The receipt.
+        receipt: Receipt,
+    },
+}
+
+impl AccountActivity {
+    /// Height of the block the activity was included in.
+    pub fn block_height(&self) -> u64 {
+        match self {
+            Self::Transaction { block_height, .. } | Self::Receipt { block_height, .. } => {
+                *block_height
+            }
+        }
+    }
+
+    /// Hash of the block the activity was included in.
+    pub fn block_hash(&self) -> &CryptoHash {
+        match self {
+            Self::Transaction { block_hash, .. } | Self::Receipt { block_hash, .. } => block_hash,
+        }
+    }
+}
+
+/// The activity in `chunks` of the block at `height` that touches
+/// `account_id`, transactions first.
+fn chunk_activity(
+    account_id: &AccountId,
+    height: u64,
+    hash: CryptoHash,
+    chunks: Vec<ChunkView>,
+) -> Vec<AccountActivity> {
+    let mut activity = Vec::new();
+    for chunk in chunks {
+        let shard_id = chunk.header.shard_id;
+        activity.extend(
+            chunk
+                .transactions
+                .into_iter()
+                .filter(|tx| tx.signer_id == *account_id || tx.receiver_id == *account_id)
+                .map(|transaction| AccountActivity::Transaction {
+                    block_height: height,
+                    block_hash: hash,
+                    shard_id,
+                    transaction,
+                }),
+        );
+        activity.extend(
+            chunk
+                .receipts
+                .into_iter()
+                .filter(|r| r.predecessor_id == *account_id || r.receiver_id == *account_id)
+                .map(|receipt| AccountActivity::Receipt {
+                    block_height: height,
+                    block_hash: hash,
+                    shard_id,
+                    receipt,
+                }),
+        );
+    }
+    activity
+}
+
+/// A stream of the transactions and receipts touching one account, from
+/// [`Near::watch_account`](super::Near::watch_account) or
+/// [`BlockStream::watch_account`].
+///
+/// Fetches the chunks of every block the underlying [`BlockStream`] yields
+/// and keeps the transactions signed by or sent to the account and the
+/// receipts sent by or to it. On a reorg, activity from the replaced blocks
+/// is not retracted; compare [`AccountActivity::block_hash`] against the
+/// chain if that matters (or follow `final` blocks, the default).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use near_kit::*;
+/// use futures::StreamExt;
+///
+/// # async fn example() -> Result<(), Error> {
+/// let near = Near::testnet().build();
+/// let mut activity = near.watch_account("alice.testnet");
+/// while let Some(item) = activity.next().await {
+///     match item? {
+///         AccountActivity::Transaction { transaction, .. } => {
+///             println!("tx {}", transaction.hash)
+///         }
+///         AccountActivity::Receipt { receipt, .. } => {
+///             println!("receipt {}", receipt.receipt_id)
+///         }
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "streams do nothing unless polled"]
+pub struct AccountActivityStream {
+    account_id: AccountId,
+    inner: BoxStream<Result<AccountActivity, Error>>,
+}
+
+impl AccountActivityStream {
+    fn new(blocks: BlockStream, account_id: AccountId) -> Self {
+        let rpc = blocks.rpc.clone();
+        let watched = account_id.clone();
+        let inner = blocks
+            .then(move |block| {
+                let rpc = rpc.clone();
+                let account_id = watched.clone();
+                async move {
+                    let block = block?;
+                    let chunks = block_chunks(&rpc, &block).await?;
+                    Ok(chunk_activity(
+                        &account_id,
+                        block.header.height,
+                        block.header.hash,
+                        chunks,
+                    ))
+                }
+            })
+            .flat_map(|result: Result<Vec<AccountActivity>, Error>| match result {
+                Ok(activity) => {
+                    futures::stream::iter(activity.into_iter().map(Ok).collect::<Vec<_>>())
+                }
+                Err(e) => futures::stream::iter(vec![Err(e)]),
+            });
+        Self {
+            account_id,
+            inner: Box::pin(inner),
+        }
+    }
+
+    /// The watched account.
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+}
+
+impl Stream for AccountActivityStream {
+    type Item = Result<AccountActivity, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+impl std::fmt::Debug for AccountActivityStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccountActivityStream")
+            .field("account_id", &self.account_id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::client::Near;
+    use crate::testing::{MockRequest, MockResponse, MockTransport};
+
+    fn hash(name: &str) -> CryptoHash {
+        CryptoHash::hash(name.as_bytes())
+    }
+
+    /// A block named `name` at `height` building on `prev`.
+    fn block(name: &str, height: u64, prev: &str) -> Value {
+        let zero = CryptoHash::ZERO.to_string();
+        json!({
+            "author": "mock",
+            "chunks": [],
+            "header": {
+                "height": height,
+                "hash": hash(name).to_string(),
+                "prev_hash": hash(prev).to_string(),
+                "prev_state_root": zero,
+                "chunk_receipts_root": zero,
+                "chunk_headers_root": zero,
+                "chunk_tx_root": zero,
+                "outcome_root": zero,
+                "chunks_included": 0,
+                "challenges_root": zero,
+                "timestamp": height,
+                "timestamp_nanosec": height.to_string(),
+                "random_value": zero,
+                "gas_price": "100000000",
+                "total_supply": "0",
+                "last_final_block": zero,
+                "last_ds_final_block": zero,
+                "epoch_id": zero,
+                "next_epoch_id": zero,
+                "next_bp_hash": zero,
+                "block_merkle_root": zero,
+                "signature": format!("ed25519:{}", "1".repeat(64)),
+                "latest_protocol_version": 80,
+            },
+        })
+    }
+
+    /// A chain served by height, by hash and as a sequence of heads.
+    struct Chain {
+        blocks: Vec<Value>,
+        heads: Mutex<VecDeque<Value>>,
+    }
+
+    impl Chain {
+        fn answer(&self, request: &MockRequest) -> MockResponse {
+            let find = |key: &str, value: &Value| {
+                self.blocks
+                    .iter()
+                    .rev()
+                    .find(|block| block["header"][key] == *value)
+                    .cloned()
+            };
+            let found = match request.params.get("block_id") {
+                Some(height @ Value::Number(_)) => find("height", height),
+                Some(hash) => find("hash", hash),
+                None => {
+                    let mut heads = self.heads.lock().unwrap();
+                    let head = heads.front().cloned();
+                    if heads.len() > 1 {
+                        heads.pop_front();
+                    }
+                    head
+                }
+            };
+            match found {
+                Some(block) => MockResponse::result(block),
+                None => MockResponse::handler_error("UNKNOWN_BLOCK", json!({})),
+            }
+        }
+    }
+
+    fn near_on(chain: Chain) -> (MockTransport, Near) {
+        let mock = MockTransport::new();
+        let chain = Arc::new(chain);
+        mock.on("block")
+            .respond_with(move |request| chain.answer(request));
+        let near = mock.near().build();
+        (mock, near)
+    }
+
+    fn heights(blocks: &[BlockView]) -> Vec<(u64, CryptoHash)> {
+        blocks
+            .iter()
+            .map(|b| (b.header.height, b.header.hash))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_backfills_gaps_and_skipped_heights() {
+        // Height 12 was skipped; the head jumps from 10 to 14.
+        let chain = Chain {
+            blocks: vec![
+                block("b10", 10, "b9"),
+                block("b11", 11, "b10"),
+                block("b13", 13, "b11"),
+                block("b14", 14, "b13"),
+            ],
+            heads: Mutex::new(VecDeque::from([
+                block("b10", 10, "b9"),
+                block("b14", 14, "b13"),
+            ])),
+        };
+        let (_mock, near) = near_on(chain);
+        let blocks: Vec<BlockView> = near
+            .blocks()
+            .poll_interval(Duration::ZERO)
+            .take(4)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(
+            heights(&blocks),
+            vec![
+                (10, hash("b10")),
+                (11, hash("b11")),
+                (13, hash("b13")),
+                (14, hash("b14"))
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_from_height_backfills_from_the_start() {
+        let chain = Chain {
+            blocks: vec![
+                block("b5", 5, "b4"),
+                block("b6", 6, "b5"),
+                block("b7", 7, "b6"),
+            ],
+            heads: Mutex::new(VecDeque::from([block("b7", 7, "b6")])),
+        };
+        let (mock, near) = near_on(chain);
+        let blocks: Vec<BlockView> = near
+            .blocks()
+            .from_height(5)
+            .take(3)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(
+            heights(&blocks),
+            vec![(5, hash("b5")), (6, hash("b6")), (7, hash("b7"))]
+        );
+        assert_eq!(mock.requests_for("block").len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_from_height_ahead_of_head_polls_at_the_interval() {
+        let chain = Chain {
+            blocks: vec![block("b5", 5, "b4"), block("b6", 6, "b5")],
+            heads: Mutex::new(VecDeque::from([
+                block("b5", 5, "b4"),
+                block("b5", 5, "b4"),
+                block("b5", 5, "b4"),
+                block("b6", 6, "b5"),
+            ])),
+        };
+        let (mock, near) = near_on(chain);
+        let started = std::time::Instant::now();
+        let blocks: Vec<BlockView> = near
+            .blocks()
+            .from_height(6)
+            .poll_interval(Duration::from_millis(20))
+            .take(1)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(heights(&blocks), vec![(6, hash("b6"))]);
+        assert_eq!(mock.requests_for("block").len(), 4);
+        assert!(started.elapsed() >= Duration::from_millis(60));
+    }
+
+    #[tokio::test]
+    async fn test_reorg_yields_the_new_branch_from_the_fork() {
+        // b11 and b12 are replaced by b11' and b12', which build on b10.
+        // Lookups by height already see the new branch.
+        let chain = Chain {
+            blocks: vec![
+                block("b10", 10, "b9"),
+                block("b11", 11, "b10"),
+                block("b12", 12, "b11"),
+                block("b11'", 11, "b10"),
+                block("b12'", 12, "b11'"),
+            ],
+            heads: Mutex::new(VecDeque::from([
+                block("b10", 10, "b9"),
+                block("b12", 12, "b11"),
+                block("b12'", 12, "b11'"),
+            ])),
+        };
+        let (_mock, near) = near_on(chain);
+        let blocks: Vec<BlockView> = near
+            .blocks()
+            .finality(Finality::Optimistic)
+            .poll_interval(Duration::ZERO)
+            .take(6)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(
+            heights(&blocks),
+            vec![
+                (10, hash("b10")),
+                // Height 11 already resolves to the new branch...
+                (11, hash("b11'")),
+                // ...but the head is still on the old one, so its branch
+                // replaces b11'...
+                (11, hash("b11")),
+                (12, hash("b12")),
+                // ...until the new head arrives and replaces both.
+                (11, hash("b11'")),
+                (12, hash("b12'")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_yielded_and_retried() {
+        let mock = MockTransport::new();
+        mock.on("block")
+            .once()
+            .respond(MockResponse::handler_error("NO_SYNCED_BLOCKS", json!({})));
+        mock.on("block")
+            .respond(MockResponse::result(block("b1", 1, "b0")));
+        let near = mock.near().build();
+
+        let mut blocks = near.blocks().poll_interval(Duration::ZERO);
+        assert!(blocks.next().await.unwrap().is_err());
+        let block = blocks.next().await.unwrap().unwrap();
+        assert_eq!(block.header.hash, hash("b1"));
+    }
+
+    #[test]
+    fn test_chunk_activity_filters_by_account() {
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let zero = CryptoHash::ZERO.to_string();
+        let tx = |signer: &str, receiver: &str| {
+            json!({
+                "signer_id": signer,
+                "public_key": "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+                "nonce": 1,
+                "receiver_id": receiver,
+                "hash": zero,
+                "actions": [],
+                "signature": format!("ed25519:{}", "1".repeat(64)),
+            })
+        };
+        let receipt = |predecessor: &str, receiver: &str| {
+            json!({
+                "predecessor_id": predecessor,
+                "receiver_id": receiver,
+                "receipt_id": zero,
+                "receipt": { "Data": { "data_id": zero, "data": null } },
+            })
+        };
+        let mut chunk = json!({
+            "author": "mock",
+            "header": {
+                "chunk_hash": zero,
+                "prev_block_hash": zero,
+                "outcome_root": zero,
+                "prev_state_root": zero,
+                "encoded_merkle_root": zero,
+                "encoded_length": 0,
+                "height_created": 5,
+                "height_included": 5,
+                "shard_id": 3,
+                "gas_used": 0,
+                "gas_limit": 0,
+                "validator_reward": "0",
+                "balance_burnt": "0",
+                "outgoing_receipts_root": zero,
+                "tx_root": zero,
+                "validator_proposals": [],
+                "signature": format!("ed25519:{}", "1".repeat(64)),
+            },
+            "transactions": [tx("alice.testnet", "bob.testnet"), tx("bob.testnet", "carol.testnet")],
+            "receipts": [receipt("bob.testnet", "alice.testnet"), receipt("system", "bob.testnet")],
+        });
+        chunk["transactions"]
+            .as_array_mut()
+            .unwrap()
+            .push(tx("carol.testnet", "alice.testnet"));
+        let chunk: ChunkView = serde_json::from_value(chunk).unwrap();
+
+        let activity = chunk_activity(&alice, 5, hash("b5"), vec![chunk]);
+        assert_eq!(activity.len(), 3);
+        assert!(matches!(
+            &activity[0],
+            AccountActivity::Transaction { transaction, shard_id: 3, .. }
+                if transaction.receiver_id.as_str() == "bob.testnet"
+        ));
+        assert!(matches!(&activity[1], AccountActivity::Transaction { .. }));
+        assert!(matches!(
+            &activity[2],
+            AccountActivity::Receipt { receipt, .. } if receipt.predecessor_id.as_str() == "bob.testnet"
+        ));
+        assert_eq!(activity[2].block_height(), 5);
+        assert_eq!(activity[2].block_hash(), &hash("b5"));
+    }
+}
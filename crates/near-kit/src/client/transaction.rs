@@ -31,7 +31,7 @@
 use std::fmt;
 use std::future::IntoFuture;
 use std::marker::PhantomData;
-use std::sync::{Arc, OnceLock};
+use std::sync::Arc;
 
 use crate::trace::{self, Instrument};
 
@@ -43,16 +43,10 @@ use crate::types::{
     Transaction, TryIntoAccountId, UseGlobalContractAction, WaitLevel,
 };
 
-use super::nonce_manager::NonceManager;
+use super::nonce_manager::NonceKey;
 use super::rpc::RpcClient;
 use super::signer::Signer;
-
-/// Global nonce manager shared across all TransactionBuilder instances.
-/// This is an implementation detail - not exposed to users.
-fn nonce_manager() -> &'static NonceManager {
-    static NONCE_MANAGER: OnceLock<NonceManager> = OnceLock::new();
-    NONCE_MANAGER.get_or_init(NonceManager::new)
-}
+use super::simulation::{self, TransactionSimulation};
 
 /// Produce a comma-separated summary of action types for tracing spans.
 ///
@@ -531,8 +525,7 @@ impl TransactionBuilder {
         };
 
         // Sign the delegate action
-        let hash = delegate_action.get_hash();
-        let signature = key.sign(hash.as_bytes()).await?;
+        let signature = key.sign_delegate_action(&delegate_action).await?;
 
         // Create signed delegate action
         let signed_delegate_action = delegate_action.sign(signature);
@@ -781,13 +774,12 @@ impl TransactionBuilder {
                 .await?;
             let block_hash = access_key.block_hash;
 
-            let network = self.rpc.url().to_string();
-            let nonce = nonce_manager().next(
-                network,
-                signer_id.clone(),
-                public_key.clone(),
-                access_key.nonce,
-            );
+            let nonce_key = NonceKey::new(self.rpc.url(), signer_id.clone(), public_key.clone());
+            let nonce = self
+                .rpc
+                .nonce_store()
+                .next_nonce(&nonce_key, access_key.nonce)
+                .await?;
 
             let tx = Transaction::new(
                 signer_id,
@@ -930,13 +922,12 @@ impl TransactionBuilder {
                 .await?;
             let block_hash = access_key.block_hash;
 
-            let network = self.rpc.url().to_string();
-            let nonce = nonce_manager().next(
-                network,
-                signer_id.clone(),
-                public_key.clone(),
-                access_key.nonce,
-            );
+            let nonce_key = NonceKey::new(self.rpc.url(), signer_id.clone(), public_key.clone());
+            let nonce = self
+                .rpc
+                .nonce_store()
+                .next_nonce(&nonce_key, access_key.nonce)
+                .await?;
 
             // Build transaction
             let tx = Transaction::new(
@@ -949,10 +940,9 @@ impl TransactionBuilder {
             );
 
             // Sign with the key
-            let tx_hash = tx.get_hash();
-            let signature = key.sign(tx_hash.as_bytes()).await?;
+            let signature = key.sign_transaction(&tx).await?;
 
-            trace::debug!(tx_hash = %tx_hash, nonce, "Transaction signed");
+            trace::debug!(tx_hash = %tx.get_hash(), nonce, "Transaction signed");
 
             Ok(SignedTransaction {
                 transaction: tx,
@@ -1026,7 +1016,7 @@ impl TransactionBuilder {
         );
 
         // Sign
-        let signature = key.sign(tx.get_hash().as_bytes()).await?;
+        let signature = key.sign_transaction(&tx).await?;
 
         Ok(SignedTransaction {
             transaction: tx,
@@ -1034,6 +1024,56 @@ impl TransactionBuilder {
         })
     }
 
+    /// Estimate the transaction's cost and check for failures without sending it.
+    ///
+    /// Prices every action from the current protocol config and gas price,
+    /// runs function calls as view calls where possible (catching missing
+    /// contracts, missing methods and panics), and checks the signer's
+    /// balance covers gas and deposits. Nothing is signed, and the nonce is
+    /// not touched. See [`TransactionSimulation`] for what the estimate does
+    /// and does not cover.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example(near: Near) -> Result<(), near_kit::Error> {
+    /// let simulation = near.transaction("bob.testnet")
+    ///     .transfer(NearToken::from_near(1))
+    ///     .simulate()
+    ///     .await?;
+    ///
+    /// println!("Costs up to {}", simulation.total_cost());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn simulate(self) -> Result<TransactionSimulation, Error> {
+        if self.actions.is_empty() {
+            return Err(Error::InvalidTransaction(
+                "Transaction must have at least one action".to_string(),
+            ));
+        }
+
+        let signer = self
+            .signer_override
+            .or(self.signer)
+            .ok_or(Error::NoSigner)?;
+
+        let signer_id = signer.account_id().clone();
+
+        let span = trace::info_span!(
+            "simulate_transaction",
+            sender = %signer_id,
+            receiver = %self.receiver_id,
+            action_count = self.actions.len(),
+            actions = %actions_summary(&self.actions),
+        );
+
+        simulation::simulate(&self.rpc, &signer_id, &self.receiver_id, &self.actions)
+            .instrument(span)
+            .await
+    }
+
     /// Send the transaction.
     ///
     /// Returns a [`TransactionSend`] that defaults to [`crate::types::ExecutedOptimistic`] wait level.
@@ -1457,6 +1497,13 @@ impl CallBuilder {
         self.finish().sign().await
     }
 
+    /// Estimate the transaction's cost and check for failures without sending it.
+    ///
+    /// See [`TransactionBuilder::simulate`] for details.
+    pub async fn simulate(self) -> Result<TransactionSimulation, Error> {
+        self.finish().simulate().await
+    }
+
     /// Send the transaction.
     pub fn send(self) -> TransactionSend {
         self.finish().send()
@@ -1636,7 +1683,6 @@ impl<W: WaitLevel> IntoFuture for TransactionSend<W> {
                 // Retry loop for transient InvalidTxErrors (nonce conflicts, expired block hash)
                 let max_nonce_retries = builder.max_nonce_retries;
                 let wait_until = W::STATUS;
-                let network = builder.rpc.url().to_string();
                 let mut last_error: Option<Error> = None;
                 let mut last_ak_nonce: Option<u64> = None;
 
@@ -1659,14 +1705,18 @@ impl<W: WaitLevel> IntoFuture for TransactionSend<W> {
 
                     // Resolve nonce: prefer ak_nonce from a prior InvalidNonce
                     // error (more recent than the view_access_key result), then
-                    // fall back to the chain nonce. The nonce manager takes
+                    // fall back to the chain nonce. The nonce store takes
                     // max(cached, provided) so stale values are harmless.
-                    let nonce = nonce_manager().next(
-                        network.clone(),
-                        signer_id.clone(),
-                        public_key.clone(),
-                        last_ak_nonce.take().unwrap_or(access_key.nonce),
-                    );
+                    let nonce_key =
+                        NonceKey::new(builder.rpc.url(), signer_id.clone(), public_key.clone());
+                    let nonce = builder
+                        .rpc
+                        .nonce_store()
+                        .next_nonce(
+                            &nonce_key,
+                            last_ak_nonce.take().unwrap_or(access_key.nonce),
+                        )
+                        .await?;
 
                     // Build transaction
                     let tx = Transaction::new(
@@ -1679,7 +1729,7 @@ impl<W: WaitLevel> IntoFuture for TransactionSend<W> {
                     );
 
                     // Sign with the key
-                    let signature = match key.sign(tx.get_hash().as_bytes()).await {
+                    let signature = match key.sign_transaction(&tx).await {
                         Ok(sig) => sig,
                         Err(e) => return Err(Error::Signing(e)),
                     };
@@ -1766,7 +1816,10 @@ mod tests {
     use std::sync::atomic::{AtomicUsize, Ordering};
 
     use super::*;
-    use crate::client::{BoxFuture, InMemorySigner, RpcTransport, TransportResponse};
+    use crate::client::{
+        BoxFuture, InMemoryNonceStore, InMemorySigner, RpcTransport, SimulationFailure,
+        TransportResponse,
+    };
     use crate::types::{SecretKey, Submitted};
 
     /// Create a TransactionBuilder for unit tests (no real network needed).
@@ -1978,7 +2031,7 @@ mod tests {
                 }
                 other => panic!("unexpected RPC method {other}"),
             };
-            Box::pin(async move { Ok(TransportResponse { status: 200, body }) })
+            Box::pin(async move { Ok(TransportResponse::new(200, body)) })
         }
     }
 
@@ -2047,4 +2100,180 @@ mod tests {
             "each attempt re-fetches the access key for a fresh block hash"
         );
     }
+
+    #[tokio::test]
+    async fn sign_draws_nonce_from_configured_store() {
+        let secret_key = SecretKey::generate_ed25519();
+        let store = Arc::new(InMemoryNonceStore::new());
+        // Another worker already used nonces up to 42 on this key.
+        store.next(
+            "http://mock.invalid",
+            "alice.testnet".parse().unwrap(),
+            secret_key.public_key(),
+            41,
+        );
+        let near = crate::Near::custom("http://mock.invalid", "test")
+            .transport(NonceRetryTransport::new(vec![]))
+            .signer(InMemorySigner::from_secret_key("alice.testnet", secret_key).unwrap())
+            .nonce_store(store)
+            .build();
+
+        let signed = near
+            .transaction("bob.testnet")
+            .transfer(NearToken::from_near(1))
+            .sign()
+            .await
+            .unwrap();
+        // The chain reports nonce 5; the store is ahead of it.
+        assert_eq!(signed.transaction.nonce, 43);
+    }
+
+    /// Transport answering the RPCs `simulate` makes: every fee is 1 Ggas
+    /// send + 1 Ggas execution, the gas price is 100,000,000 yoctoNEAR, and
+    /// the signer holds 10 NEAR. Function calls report a missing method.
+    struct SimulationTransport;
+
+    impl RpcTransport for SimulationTransport {
+        fn post_json(
+            &self,
+            _url: &str,
+            body: Vec<u8>,
+        ) -> BoxFuture<'_, Result<TransportResponse, RpcError>> {
+            let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let fee = serde_json::json!({
+                "send_sir": 1_000_000_000u64,
+                "send_not_sir": 1_000_000_000u64,
+                "execution": 1_000_000_000u64,
+            });
+            let mut response = match request["method"].as_str().unwrap() {
+                "EXPERIMENTAL_protocol_config" => serde_json::json!({ "result": {
+                    "protocol_version": 80,
+                    "genesis_time": "2020-07-21T16:55:51.591948Z",
+                    "chain_id": "testnet",
+                    "genesis_height": 1,
+                    "epoch_length": 43200,
+                    "gas_limit": 1_000_000_000_000_000u64,
+                    "min_gas_price": "100000000",
+                    "max_gas_price": "10000000000000000000000",
+                    "transaction_validity_period": 86400,
+                    "runtime_config": {
+                        "storage_amount_per_byte": "10000000000000000000",
+                        "transaction_costs": {
+                            "action_receipt_creation_config": fee,
+                            "data_receipt_creation_config": {
+                                "base_cost": fee,
+                                "cost_per_byte": fee,
+                            },
+                            "action_creation_config": {
+                                "create_account_cost": fee,
+                                "deploy_contract_cost": fee,
+                                "deploy_contract_cost_per_byte": fee,
+                                "function_call_cost": fee,
+                                "function_call_cost_per_byte": fee,
+                                "transfer_cost": fee,
+                                "stake_cost": fee,
+                                "add_key_cost": {
+                                    "full_access_cost": fee,
+                                    "function_call_cost": fee,
+                                    "function_call_cost_per_byte": fee,
+                                },
+                                "delete_key_cost": fee,
+                                "delete_account_cost": fee,
+                                "delegate_cost": fee,
+                            },
+                            "storage_usage_config": {
+                                "num_bytes_account": 100,
+                                "num_extra_bytes_record": 40,
+                            },
+                            "burnt_gas_reward": [3, 10],
+                            "pessimistic_gas_price_inflation_ratio": [103, 100],
+                        },
+                    },
+                }}),
+                "gas_price" => serde_json::json!({ "result": { "gas_price": "100000000" } }),
+                "EXPERIMENTAL_view_account" => serde_json::json!({ "result": {
+                    "amount": NearToken::from_near(10).as_yoctonear().to_string(),
+                    "locked": "0",
+                    "code_hash": "11111111111111111111111111111111",
+                    "storage_usage": 0,
+                    "block_height": 100,
+                    "block_hash": "11111111111111111111111111111111",
+                }}),
+                "EXPERIMENTAL_call_function" => serde_json::json!({ "error": {
+                    "name": "HANDLER_ERROR",
+                    "cause": {
+                        "name": "CONTRACT_EXECUTION_ERROR",
+                        "info": { "vm_error": { "MethodResolveError": "MethodNotFound" } },
+                    },
+                    "code": -32000,
+                    "message": "Server error",
+                }}),
+                other => panic!("unexpected RPC method {other}"),
+            };
+            response["jsonrpc"] = "2.0".into();
+            response["id"] = request["id"].clone();
+            let body = serde_json::to_vec(&response).unwrap();
+            Box::pin(async move { Ok(TransportResponse::new(200, body)) })
+        }
+    }
+
+    fn simulation_client() -> crate::Near {
+        let signer =
+            InMemorySigner::from_secret_key("alice.testnet", SecretKey::generate_ed25519())
+                .unwrap();
+        crate::Near::custom("http://mock.invalid", "test")
+            .transport(SimulationTransport)
+            .signer(signer)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn simulate_prices_actions_and_reports_missing_method() {
+        let simulation = simulation_client()
+            .transaction("contract.testnet")
+            .transfer(NearToken::from_near(1))
+            .call("missing")
+            .gas(Gas::from_tgas(30))
+            .simulate()
+            .await
+            .unwrap();
+
+        // Receipt + transfer + function call (base, plus 7 bytes of method
+        // name) at 2 Ggas each.
+        assert_eq!(simulation.fee_gas, Gas::from_ggas(2 + 2 + 2 + 2 * 7));
+        assert_eq!(simulation.attached_gas, Gas::from_tgas(30));
+        assert_eq!(simulation.deposit, NearToken::from_near(1));
+        assert_eq!(
+            simulation.gas_cost(),
+            NearToken::from_yoctonear(100_000_000 * simulation.gas_burnt().as_gas() as u128)
+        );
+        assert_eq!(
+            simulation.failures,
+            vec![SimulationFailure::MethodNotFound {
+                contract_id: "contract.testnet".parse().unwrap(),
+                method_name: "missing".to_string(),
+            }]
+        );
+        assert!(!simulation.is_ok());
+    }
+
+    #[tokio::test]
+    async fn simulate_reports_insufficient_balance_and_skips_calls_after_deploy() {
+        let simulation = simulation_client()
+            .transaction("contract.testnet")
+            .deploy(vec![0u8; 4])
+            .transfer(NearToken::from_near(20))
+            .call("init")
+            .simulate()
+            .await
+            .unwrap();
+
+        assert_eq!(simulation.unverified_calls, vec!["init".to_string()]);
+        assert_eq!(simulation.available_balance, Some(NearToken::from_near(10)));
+        assert!(matches!(
+            simulation.failures.as_slice(),
+            [SimulationFailure::InsufficientBalance { required, .. }]
+                if *required == simulation.total_cost()
+        ));
+    }
 }
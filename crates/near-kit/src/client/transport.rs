@@ -25,6 +25,7 @@
 //! traffic instead of exposing raw HTTP.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::error::RpcError;
 pub use crate::platform::BoxFuture;
@@ -52,12 +53,61 @@ compile_error!(
 /// and keeping the trait encoding-agnostic means transports never have to
 /// know that NEAR's JSON-RPC happens to be UTF-8 text. The RPC layer owns the
 /// (lossy) UTF-8 decode, exactly as it did when reqwest's `text()` did it.
+///
+/// Construct it with [`new`](Self::new) (plus
+/// [`with_retry_after`](Self::with_retry_after) where the server sent one):
+/// the struct is `#[non_exhaustive]` so later fields don't break custom
+/// transports.
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct TransportResponse {
     /// The HTTP status code (e.g. `200`).
     pub status: u16,
     /// The complete response body.
     pub body: Vec<u8>,
+    /// The `Retry-After` delay, if the response carried one in its
+    /// delta-seconds form. The RPC layer waits this long before retrying an
+    /// HTTP 429.
+    pub retry_after: Option<Duration>,
+}
+
+impl TransportResponse {
+    /// A response with no `Retry-After` header.
+    pub fn new(status: u16, body: Vec<u8>) -> Self {
+        Self {
+            status,
+            body,
+            retry_after: None,
+        }
+    }
+
+    /// Set the `Retry-After` delay the server sent, if any.
+    pub fn with_retry_after(mut self, retry_after: Option<Duration>) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+}
+
+/// Parse a `Retry-After` header value in its delta-seconds form.
+///
+/// The HTTP-date form is rare from RPC providers and is ignored (`None`);
+/// the retry loop then falls back to its own backoff.
+// Only the built-in transports call this, and WASI without `wasi-http` has none.
+#[cfg_attr(
+    all(
+        target_arch = "wasm32",
+        target_os = "wasi",
+        not(all(feature = "wasi-http", target_env = "p2"))
+    ),
+    allow(dead_code)
+)]
+pub(crate) fn parse_retry_after(value: &[u8]) -> Option<Duration> {
+    std::str::from_utf8(value)
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
 }
 
 /// The HTTP layer under [`RpcClient`](super::rpc::RpcClient).
@@ -203,8 +253,12 @@ impl RpcTransport for ReqwestTransport {
             // as it was before the transport seam existed.
             let response = request.send().await?;
             let status = response.status().as_u16();
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| parse_retry_after(value.as_bytes()));
             let body = response.bytes().await?.to_vec();
-            Ok(TransportResponse { status, body })
+            Ok(TransportResponse::new(status, body).with_retry_after(retry_after))
         })
     }
 }
@@ -440,6 +494,11 @@ mod wasi_http {
         // the RPC layer interpret them (nearcore sends JSON-RPC error bodies
         // with 4xx/5xx statuses).
         let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(&"retry-after".to_string())
+            .first()
+            .and_then(|value| super::parse_retry_after(value));
 
         let incoming_body = response.consume().map_err(|()| {
             RpcError::network("wasi:http response body already consumed", None, false)
@@ -466,7 +525,7 @@ mod wasi_http {
         // Finish the incoming body (drops the trailers future we never read).
         let _ = IncomingBody::finish(incoming_body);
 
-        Ok(TransportResponse { status, body: buf })
+        Ok(TransportResponse::new(status, buf).with_retry_after(retry_after))
     }
 
     /// The `wasi:http` request parts extracted from an RPC endpoint URL.
@@ -170,6 +170,85 @@ pub enum ActionViewConversionError {
     NestedDelegate,
 }
 
+/// Error verifying light client data against a trusted head.
+///
+/// Returned by the offline verifier in [`crate::light_client`]. Any of these
+/// means the data must not be trusted — either the RPC node sent something
+/// inconsistent, or the trusted head is not an ancestor-compatible checkpoint.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum LightClientError {
+    /// The block is not ahead of the current head.
+    #[error("Block at height {height} does not advance the head at height {head_height}")]
+    StaleBlock {
+        /// Height of the rejected block.
+        height: u64,
+        /// Height of the current head.
+        head_height: u64,
+    },
+
+    /// The block is neither in the head's epoch nor in the next one.
+    #[error("Block epoch {epoch_id} is neither the head's epoch nor the next one")]
+    UnexpectedEpoch {
+        /// Epoch of the rejected block.
+        epoch_id: CryptoHash,
+    },
+
+    /// The block producers of the block's epoch are not known.
+    #[error("Block producers for epoch {epoch_id} are not known")]
+    UnknownBlockProducers {
+        /// Epoch whose block producers are missing.
+        epoch_id: CryptoHash,
+    },
+
+    /// The first block of a new epoch did not carry the next epoch's
+    /// block producers.
+    #[error("Block enters a new epoch but does not carry the next block producers")]
+    MissingNextBlockProducers,
+
+    /// The carried next block producers do not hash to `next_bp_hash`.
+    #[error("Next block producers do not match next_bp_hash {expected}, got {computed}")]
+    NextBlockProducersMismatch {
+        /// `next_bp_hash` committed by the block header.
+        expected: CryptoHash,
+        /// Hash of the block producers actually carried.
+        computed: CryptoHash,
+    },
+
+    /// A block producer's approval signature does not verify.
+    #[error("Invalid approval signature from block producer {account_id}")]
+    InvalidApprovalSignature {
+        /// The block producer whose signature failed.
+        account_id: AccountId,
+    },
+
+    /// Approvals carry no more than 2/3 of the epoch's block producer stake.
+    #[error("Approvals carry {approved} yoctoNEAR of {total} staked, need more than 2/3")]
+    InsufficientApprovals {
+        /// Stake of the producers that signed, in yoctoNEAR.
+        approved: u128,
+        /// Stake of all the epoch's producers, in yoctoNEAR.
+        total: u128,
+    },
+
+    /// The outcome's merkle path does not lead to the block's outcome root.
+    #[error("Outcome proof computes outcome root {computed}, block commits {expected}")]
+    OutcomeRootMismatch {
+        /// Outcome root committed by the block header.
+        expected: CryptoHash,
+        /// Root computed from the proof.
+        computed: CryptoHash,
+    },
+
+    /// The block's merkle path does not lead to the head's block merkle root.
+    #[error("Block proof computes block merkle root {computed}, head commits {expected}")]
+    BlockMerkleRootMismatch {
+        /// Block merkle root committed by the trusted head.
+        expected: CryptoHash,
+        /// Root computed from the proof.
+        computed: CryptoHash,
+    },
+}
+
 /// Error during signing operations.
 #[derive(Debug, Clone, Error, PartialEq, Eq)]
 pub enum SignerError {
@@ -181,6 +260,52 @@ pub enum SignerError {
 
     #[error("Key derivation failed: {0}")]
     KeyDerivationFailed(String),
+
+    #[error(transparent)]
+    Ledger(#[from] LedgerError),
+}
+
+/// Error talking to a Ledger device running the NEAR app.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum LedgerError {
+    /// The [`LedgerTransport`](crate::LedgerTransport) could not reach the
+    /// device.
+    #[error("Ledger transport error: {0}")]
+    Transport(String),
+
+    /// The user rejected the request on the device.
+    #[error("Request rejected on the Ledger device")]
+    Rejected,
+
+    /// The device is locked.
+    #[error("Ledger device is locked")]
+    Locked,
+
+    /// The NEAR app is not open on the device.
+    #[error("NEAR app is not open on the Ledger device")]
+    AppNotOpen,
+
+    /// The installed NEAR app does not support the request; NEP-413 and
+    /// NEP-366 signing need a recent app version.
+    #[error("The NEAR Ledger app does not support this request; update the app")]
+    Unsupported,
+
+    /// Any other status word.
+    #[error("Ledger returned status {0:#06x}")]
+    Status(u16),
+
+    /// The response did not have the expected shape.
+    #[error("Invalid Ledger response: {0}")]
+    InvalidResponse(String),
+
+    /// The BIP-32 derivation path could not be parsed, or has more than ten
+    /// components.
+    #[error("Invalid HD path '{0}': expected up to ten hardened components like 44'/397'/0'/0'/1'")]
+    InvalidPath(String),
+
+    /// A command payload did not fit the one-byte APDU length field.
+    #[error("Ledger command payload of {0} bytes exceeds the 255-byte APDU limit")]
+    PayloadTooLarge(usize),
 }
 
 /// Error during keystore operations.
@@ -206,6 +331,9 @@ pub enum KeyStoreError {
 
     #[error("Platform keyring error: {0}")]
     Platform(String),
+
+    #[error("Could not decrypt key for {0}: wrong passphrase or corrupted keystore file")]
+    Decryption(AccountId),
 }
 
 // ============================================================================
@@ -235,6 +363,16 @@ pub enum RpcError {
         retryable: bool,
     },
 
+    /// The endpoint answered HTTP 429 Too Many Requests.
+    ///
+    /// `retry_after` carries the server's `Retry-After` delay when it sent
+    /// one; the client's retry loop waits at least that long.
+    #[error("Rate limited by RPC endpoint: {message}")]
+    RateLimited {
+        message: String,
+        retry_after: Option<std::time::Duration>,
+    },
+
     #[error("Timeout after {0} retries")]
     Timeout(u32),
 
@@ -481,6 +619,7 @@ impl RpcError {
             }
             RpcError::Timeout(_) => true,
             RpcError::Network { retryable, .. } => *retryable,
+            RpcError::RateLimited { .. } => true,
             RpcError::ShardUnavailable(_) => true,
             RpcError::NodeNotSynced(_) => true,
             RpcError::InternalError(_) => true,
@@ -594,6 +733,7 @@ impl RpcError {
             #[cfg(all(feature = "rpc", not(all(target_arch = "wasm32", target_os = "wasi"))))]
             RpcError::Http(_) => "Http",
             RpcError::Network { .. } => "Network",
+            RpcError::RateLimited { .. } => "RateLimited",
             RpcError::Timeout(_) => "Timeout",
             RpcError::Json(_) => "Json",
             RpcError::InvalidResponse(_) => "InvalidResponse",
@@ -708,6 +848,47 @@ pub enum Error {
     #[error("Invalid transaction: {0}")]
     InvalidTransaction(String),
 
+    // ─── Nonces ───
+    /// A [`NonceStore`](crate::NonceStore) could not hand out a nonce (I/O
+    /// failure, lock timeout, backend error).
+    #[error("Nonce store error: {0}")]
+    NonceStore(String),
+
+    // ─── Receipts ───
+    /// The transaction finished executing without the receipt that
+    /// [`Near::receipt`](crate::Near::receipt) or
+    /// [`Near::wait_for_receipts`](crate::Near::wait_for_receipts) waited for.
+    #[error("Transaction {transaction_hash} finished without executing the awaited receipt")]
+    ReceiptNotFound { transaction_hash: CryptoHash },
+
+    /// The awaited receipt had not executed when the wait's timeout elapsed.
+    #[error("Timed out waiting for a receipt of transaction {transaction_hash}")]
+    ReceiptTimeout { transaction_hash: CryptoHash },
+
+    // ─── Storage management ───
+    /// The `storage_deposit` sent by
+    /// [`StorageManagement::ensure_registered`](crate::tokens::StorageManagement::ensure_registered)
+    /// failed on chain; the account is still unregistered.
+    #[error(
+        "Registering {account_id} with {contract_id} failed in transaction {transaction_hash}: {message}"
+    )]
+    StorageRegistrationFailed {
+        contract_id: AccountId,
+        account_id: AccountId,
+        transaction_hash: CryptoHash,
+        message: String,
+    },
+
+    // ─── Key rotation ───
+    /// The transaction sent by [`Near::rotate_key`](crate::Near::rotate_key)
+    /// failed on chain; the old key is still in place.
+    #[error("Key rotation for {account_id} failed in transaction {transaction_hash}: {message}")]
+    KeyRotationFailed {
+        account_id: AccountId,
+        transaction_hash: CryptoHash,
+        message: String,
+    },
+
     // ─── Signing ───
     #[error("Signing failed: {0}")]
     Signing(#[from] SignerError),
@@ -716,6 +897,10 @@ pub enum Error {
     #[error(transparent)]
     KeyStore(#[from] KeyStoreError),
 
+    // ─── Light client ───
+    #[error("Light client verification failed: {0}")]
+    LightClient(#[from] LightClientError),
+
     // ─── Serialization ───
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
@@ -908,6 +1093,10 @@ mod tests {
             KeyStoreError::Platform("keyring locked".to_string()).to_string(),
             "Platform keyring error: keyring locked"
         );
+        assert_eq!(
+            KeyStoreError::Decryption("alice.near".parse().unwrap()).to_string(),
+            "Could not decrypt key for alice.near: wrong passphrase or corrupted keystore file"
+        );
     }
 
     // ========================================================================
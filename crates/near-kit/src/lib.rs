@@ -243,6 +243,10 @@
 //! // NFTs (NEP-171)
 //! let nft = near.nft("nft.testnet")?;
 //! let tokens = nft.tokens_for_owner("alice.testnet", None, Some(10)).await?;
+//!
+//! // Multi tokens (NEP-245)
+//! let mt = near.mt("intents.near")?;
+//! let balance = mt.balance_of("alice.near", "nep141:usdc.near").await?;
 //! # Ok(())
 //! # }
 //! ```
@@ -298,9 +302,12 @@
 //! |--------|----------|
 //! | [`InMemorySigner`] | Simple scripts with a private key |
 //! | [`FileSigner`] | Load from `~/.near-credentials` (near-cli compatible) |
+//! | `EncryptedFileSigner` | Passphrase-encrypted `~/.near-credentials` files — requires `encrypted-keystore` feature |
 //! | [`EnvSigner`] | CI/CD environments via `NEAR_ACCOUNT_ID` / `NEAR_PRIVATE_KEY` |
 //! | [`RotatingSigner`] | High-throughput with multiple keys (avoids nonce collisions) |
 //! | [`KeyringSigner`] | System keyring (macOS Keychain, etc.) — requires `keyring` feature |
+//! | [`LedgerSigner`] | Ledger hardware wallet, over an application-provided [`LedgerTransport`] |
+//! | [`HttpSigner`] | Remote signing service (KMS proxy, Vault transit, signing daemon) |
 //!
 #![cfg_attr(feature = "rpc", doc = "```rust,no_run")]
 #![cfg_attr(not(feature = "rpc"), doc = "```rust,ignore")]
@@ -350,6 +357,14 @@
 //! avoid nonce collisions). For one-off overrides on a single transaction, use
 //! [`.sign_with()`](TransactionBuilder::sign_with) on the transaction builder.
 //!
+//! ### Storing Keys
+//!
+//! The signers above only read keys. To persist new ones — e.g. a
+//! [`KeyPair::random`] generated while provisioning an account — use a
+//! [`CredentialStore`]: `FileCredentialStore` writes near-cli compatible
+//! files under `~/.near-credentials`, `KeyringCredentialStore` writes
+//! near-cli-rs keyring entries. Both list and remove keys as well.
+//!
 //! ## Sandbox Testing
 //!
 //! Enable the `sandbox` feature for local testing with [`near-sandbox`](https://crates.io/crates/near-sandbox):
@@ -374,6 +389,33 @@
 //! }
 //! ```
 //!
+//! ## Mock Testing
+//!
+//! For unit tests that should not start a container, the `testing` feature
+//! enables the `testing` module. It provides `MockTransport`, a scriptable
+//! transport with canned responses, error injection and request capture, and
+//! `FakeChain`, an in-process chain that executes transfers and function
+//! calls against tracked balances and nonces:
+//!
+#![cfg_attr(feature = "testing", doc = "```rust,no_run")]
+#![cfg_attr(not(feature = "testing"), doc = "```rust,ignore")]
+//! use near_kit::*;
+//! use near_kit::testing::FakeChain;
+//!
+//! #[tokio::test]
+//! async fn test_transfer() {
+//!     let chain = FakeChain::new();
+//!     let alice = chain
+//!         .add_account("alice.testnet", NearToken::from_near(10))
+//!         .signer("alice.testnet");
+//!     chain.add_account("bob.testnet", NearToken::ZERO);
+//!
+//!     let near = chain.transport().near().signer(alice).build();
+//!     near.transfer("bob.testnet", NearToken::from_near(1)).await.unwrap();
+//!     assert_eq!(chain.balance("bob.testnet"), Some(NearToken::from_near(1)));
+//! }
+//! ```
+//!
 //! ## WebAssembly support
 //!
 //! `near-kit` compiles for `wasm32-unknown-unknown` (Dioxus, Leptos, Yew, etc.) with
@@ -472,7 +514,8 @@
 //! `rpc` feature. With `default-features = false` you keep the offline core:
 //! all the types, the signers ([`InMemorySigner`], [`EnvSigner`], ...),
 //! transaction construction and signing via [`Transaction`](types::Transaction)
-//! (`new` → `sign` → `to_bytes`), and NEP-413 [`nep413::verify_signature`].
+//! (`new` → `sign` → `to_bytes`), NEP-413 [`nep413::verify_signature`], and
+//! light client proof verification ([`light_client`]).
 //! The fluent [`TransactionBuilder`] is part of the RPC layer — it is created
 //! from a [`Near`] client — so it requires `rpc`.
 //! This is what you want on targets with no network stack at all — sign
@@ -491,8 +534,10 @@
 //! | `wasi-http` | Yes | Built-in `wasi:http` transport for `wasm32-wasip2` (implies `rpc`; no-op elsewhere). Disable on WASI hosts without `wasi:http` and inject a transport via [`NearBuilder::transport`] |
 //! | `keyring` | Yes | System keyring signer (macOS Keychain, Windows Credential Manager, etc.) |
 //! | `file-signer` | Yes | [`FileSigner`] for loading keys from `~/.near-credentials` |
+//! | `encrypted-keystore` | No | `EncryptedKeystore` / `EncryptedFileSigner`: passphrase-encrypted credential files (Argon2id + XChaCha20-Poly1305) |
 //! | `tracing` | Yes | [`tracing`](https://docs.rs/tracing) spans and events for RPC calls and transactions (see below) |
 //! | `sandbox` | No | Integration with `near-sandbox` for local testing (implies `rpc`) |
+//! | `testing` | No | `MockTransport`, `FakeChain` and cassette transports for unit tests (implies `rpc`) |
 //! | `js` | No | JS-host entropy backend (`getrandom`'s `wasm_js`) for `wasm32-unknown-unknown` |
 //!
 //! ### Tracing
@@ -535,6 +580,8 @@ pub mod client;
 pub mod contract;
 pub mod error;
 mod platform;
+#[cfg(any(feature = "testing", all(test, feature = "rpc")))]
+pub mod testing;
 #[cfg(feature = "rpc")]
 pub mod tokens;
 mod trace;
@@ -546,6 +593,7 @@ pub mod sandbox;
 
 // Re-export commonly used types at crate root
 pub use error::{Error, RpcError};
+pub use types::light_client;
 pub use types::nep413;
 pub use types::*;
 
@@ -556,14 +604,20 @@ pub use contract::{Contract, ContractClient};
 // Re-export client types
 #[cfg(feature = "rpc")]
 pub use client::{
-    AccessKeysQuery, AccountExistsQuery, AccountQuery, BalanceQuery, BoxFuture, CallBuilder,
-    ContractCodeQuery, DelegateOptions, DelegateResult, FunctionCall, GlobalContractQuery, Near,
-    NearBuilder, RetryConfig, RpcClient, RpcTransport, SandboxNetwork, SignedTransactionSend,
-    TransactionBuilder, TransactionSend, TransactionStatusQuery, TransportResponse, ViewCall,
-    ViewCallBorsh,
+    AccessKeysQuery, AccountActivity, AccountActivityStream, AccountExistsQuery, AccountQuery,
+    BalanceQuery, BatchHandle, BatchResponse, BlockStream, BoxFuture, CacheConfig, CacheStore,
+    CallBuilder, ContractCodeQuery, DelegateOptions, DelegateResult, EndpointSelection,
+    EndpointStatus, FailoverTransport, FunctionCall, GlobalContractQuery, HttpSigner,
+    InMemoryNonceStore, KeyRotation, LruCacheStore, Near, NearBuilder, NonceKey, NonceStore,
+    RateLimitConfig, ReceiptQuery, ReceiptWait, RetryConfig, RotatedKey, RotationPlan, RpcBatch,
+    RpcClient, RpcTransport, SandboxNetwork, SignedTransactionSend, SimulationFailure,
+    StateChangesQuery, TransactionBuilder, TransactionSend, TransactionSimulation,
+    TransactionStatusQuery, TransportResponse, ViewCall, ViewCallBorsh,
 };
 // Only the built-in transport matching the build configuration exists (see
 // client/mod.rs); WASI without `wasi-http` has none.
+#[cfg(all(feature = "rpc", not(target_arch = "wasm32")))]
+pub use client::FileNonceStore;
 #[cfg(all(feature = "rpc", not(all(target_arch = "wasm32", target_os = "wasi"))))]
 pub use client::ReqwestTransport;
 #[cfg(all(
@@ -573,20 +627,32 @@ pub use client::ReqwestTransport;
     target_env = "p2"
 ))]
 pub use client::WasiHttpTransport;
+// `LedgerTransport` implementations name it even without the RPC layer,
+// which otherwise re-exports it alongside `RpcTransport`.
+#[cfg(not(feature = "rpc"))]
+pub use platform::BoxFuture;
 // The signers do local cryptography only — they stay available offline.
-pub use client::{EnvSigner, InMemorySigner, RotatingSigner, Signer, SigningKey};
+pub use client::{
+    CredentialStore, EnvSigner, InMemorySigner, LedgerSigner, LedgerTransport, RotatingSigner,
+    Signer, SigningBackend, SigningKey,
+};
 
 #[cfg(feature = "file-signer")]
-pub use client::FileSigner;
+pub use client::{FileCredentialStore, FileSigner};
+
+#[cfg(feature = "encrypted-keystore")]
+pub use client::{EncryptedFileSigner, EncryptedKeystore};
 
 #[cfg(feature = "keyring")]
-pub use client::KeyringSigner;
+pub use client::{KeyringCredentialStore, KeyringSigner};
 
 // Re-export token types
 #[cfg(feature = "rpc")]
 pub use tokens::{
-    FtAmount, FtMetadata, FungibleToken, IntoContractId, KnownToken, NftContractMetadata, NftToken,
-    NftTokenMetadata, NonFungibleToken, StorageBalance, StorageBalanceBounds, USDC, USDT, W_NEAR,
+    FtAmount, FtMetadata, FtTransfer, FtTransferCall, FtTransferCallOutcome, FtTransferOutcome,
+    FtTransferRecord, FungibleToken, IntoContractId, KnownToken, MtToken, MultiToken,
+    NftContractMetadata, NftToken, NftTokenMetadata, NonFungibleToken, Payout, StorageBalance,
+    StorageBalanceBounds, StorageManagement, USDC, USDT, W_NEAR,
 };
 
 // Re-export proc macros. `#[contract]` expands to code that uses `Near`,
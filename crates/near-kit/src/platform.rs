@@ -44,3 +44,27 @@ impl<T: Sync> MaybeSync for T {}
 pub trait MaybeSync {}
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 impl<T> MaybeSync for T {}
+
+/// Milliseconds elapsed since an arbitrary process-wide origin, on a
+/// monotonic clock where the platform has one.
+///
+/// `std::time::Instant` panics on `wasm32-unknown-unknown`, so JS hosts read
+/// the host's `Date.now()` instead. That is wall-clock time and can go
+/// backwards, so callers must use saturating arithmetic on readings. Only
+/// differences between two readings are meaningful.
+#[cfg(feature = "rpc")]
+pub(crate) fn monotonic_millis() -> u64 {
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    {
+        use std::sync::OnceLock;
+        use std::time::Instant;
+
+        static ORIGIN: OnceLock<Instant> = OnceLock::new();
+        ORIGIN.get_or_init(Instant::now).elapsed().as_millis() as u64
+    }
+
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    {
+        js_sys::Date::now() as u64
+    }
+}
@@ -0,0 +1,713 @@
+//! Record-and-replay transports backed by cassette files.
+//!
+//! A cassette is a JSON file holding the JSON-RPC exchanges of one session,
+//! in the order they completed:
+//!
+//! ```json
+//! {
+//!   "interactions": [
+//!     {
+//!       "request": { "jsonrpc": "2.0", "id": 0, "method": "block", "params": { "finality": "final" } },
+//!       "response": { "status": 200, "body": { "json": { "jsonrpc": "2.0", "id": 0, "result": {} } } }
+//!     }
+//!   ]
+//! }
+//! ```
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::client::{
+    InMemoryNonceStore, Near, NearBuilder, RetryConfig, RpcTransport, TransportResponse,
+};
+use crate::error::RpcError;
+use crate::platform::BoxFuture;
+use crate::types::{CryptoHash, SignedTransaction};
+
+/// URL of the client built by [`ReplayTransport::near`].
+const REPLAY_URL: &str = "http://replay.invalid";
+
+/// Network name of the client built by [`ReplayTransport::near`].
+const REPLAY_NETWORK: &str = "replay";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Cassette {
+    interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    fn load(path: &Path) -> std::io::Result<Self> {
+        let json = std::fs::read(path)?;
+        serde_json::from_slice(&json).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid cassette {}: {e}", path.display()),
+            )
+        })
+    }
+
+    fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_vec_pretty(self).expect("cassettes always serialize");
+        std::fs::write(path, json)
+    }
+}
+
+/// One request body and the HTTP response it got.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    /// The JSON-RPC call, or an array of calls for a batch.
+    request: Value,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    body: RecordedBody,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retry_after_secs: Option<u64>,
+}
+
+/// Response bodies are stored as JSON when they parse, so cassettes stay
+/// readable and diffable; anything else (proxy error pages, ...) is kept as
+/// text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RecordedBody {
+    Json(Value),
+    Text(String),
+}
+
+impl RecordedResponse {
+    fn from_transport(response: &TransportResponse) -> Self {
+        let body = match serde_json::from_slice(&response.body) {
+            Ok(json) => RecordedBody::Json(json),
+            Err(_) => RecordedBody::Text(String::from_utf8_lossy(&response.body).into_owned()),
+        };
+        Self {
+            status: response.status,
+            body,
+            retry_after_secs: response.retry_after.map(|delay| delay.as_secs()),
+        }
+    }
+}
+
+// ============================================================================
+// Recording
+// ============================================================================
+
+/// An [`RpcTransport`] that forwards every request to another transport and
+/// records the exchange into a cassette file for [`ReplayTransport`].
+///
+/// Every HTTP response is recorded, including JSON-RPC errors and non-2xx
+/// statuses; requests that got no response at all (the inner transport
+/// returned `Err`) are not. The cassette is written by [`save`](Self::save),
+/// and when the last clone is dropped if anything was recorded since.
+///
+/// Clones share the recording, so keep one to call `save` after handing the
+/// transport to [`NearBuilder::transport`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use near_kit::*;
+/// use near_kit::testing::RecordingTransport;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let recorder = RecordingTransport::new(
+///     ReqwestTransport::new(),
+///     "tests/cassettes/balance.json",
+/// );
+/// let near = Near::testnet().transport(recorder.clone()).build();
+/// near.balance("alice.testnet").await?;
+/// recorder.save()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RecordingTransport {
+    inner: Arc<Recorder>,
+}
+
+struct Recorder {
+    transport: Arc<dyn RpcTransport>,
+    path: PathBuf,
+    state: Mutex<Recording>,
+}
+
+#[derive(Default)]
+struct Recording {
+    cassette: Cassette,
+    /// Whether interactions were recorded since the last save.
+    dirty: bool,
+}
+
+impl Recorder {
+    fn save(&self) -> std::io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.cassette.save(&self.path)?;
+        state.dirty = false;
+        Ok(())
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        let dirty = self.state.get_mut().map(|s| s.dirty).unwrap_or(false);
+        if dirty {
+            // Best effort: a test that needs to see write errors calls
+            // `save` itself.
+            let _ = self.save();
+        }
+    }
+}
+
+impl RecordingTransport {
+    /// Record the traffic of `transport` into the cassette at `path`.
+    ///
+    /// The file is overwritten on save; missing parent directories are
+    /// created.
+    pub fn new(transport: impl RpcTransport + 'static, path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner: Arc::new(Recorder {
+                transport: Arc::new(transport),
+                path: path.into(),
+                state: Mutex::new(Recording::default()),
+            }),
+        }
+    }
+
+    /// The cassette file this transport writes.
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// How many exchanges have been recorded.
+    pub fn recorded(&self) -> usize {
+        self.inner.state.lock().unwrap().cassette.interactions.len()
+    }
+
+    /// Write the cassette to disk.
+    pub fn save(&self) -> std::io::Result<()> {
+        self.inner.save()
+    }
+}
+
+impl RpcTransport for RecordingTransport {
+    fn post_json(
+        &self,
+        url: &str,
+        body: Vec<u8>,
+    ) -> BoxFuture<'_, Result<TransportResponse, RpcError>> {
+        // Requests that aren't JSON can't be replayed by matching, so they
+        // are passed through unrecorded.
+        let request: Option<Value> = serde_json::from_slice(&body).ok();
+        let url = url.to_string();
+        Box::pin(async move {
+            let response = self.inner.transport.post_json(&url, body).await?;
+            if let Some(request) = request {
+                let mut state = self.inner.state.lock().unwrap();
+                state.cassette.interactions.push(Interaction {
+                    request,
+                    response: RecordedResponse::from_transport(&response),
+                });
+                state.dirty = true;
+            }
+            Ok(response)
+        })
+    }
+}
+
+impl std::fmt::Debug for RecordingTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecordingTransport")
+            .field("path", &self.inner.path)
+            .field("recorded", &self.recorded())
+            .finish_non_exhaustive()
+    }
+}
+
+// ============================================================================
+// Matching
+// ============================================================================
+
+/// Which parts of a request must equal the recorded one for
+/// [`ReplayTransport`] to serve the recorded response.
+///
+/// The default compares the method and the complete `params`. JSON-RPC `id`s
+/// are never compared; replayed responses carry the live request's ids.
+///
+/// Values that differ between runs can be excluded:
+///
+/// ```rust
+/// use near_kit::testing::MatchRules;
+///
+/// let rules = MatchRules::new()
+///     .ignore_block_hashes()
+///     .ignore_nonces()
+///     .ignore_param("sender_account_id");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MatchRules {
+    ignored_params: BTreeSet<String>,
+    ignore_block_hashes: bool,
+    ignore_nonces: bool,
+}
+
+impl MatchRules {
+    /// Match on method and all params.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ignore the top-level param `name` (e.g. `"wait_until"`).
+    pub fn ignore_param(mut self, name: impl Into<String>) -> Self {
+        self.ignored_params.insert(name.into());
+        self
+    }
+
+    /// Ignore block hashes: `block_id`s given as a hash (heights still
+    /// count), hashes in positional params such as `gas_price`'s, and the
+    /// `block_hash` a transaction was signed against.
+    ///
+    /// Transaction hashes (`tx_hash`) are ignored too, since they change
+    /// with the block hash.
+    pub fn ignore_block_hashes(mut self) -> Self {
+        self.ignore_block_hashes = true;
+        self
+    }
+
+    /// Ignore the nonce of transactions sent with `send_tx`.
+    ///
+    /// Transaction hashes (`tx_hash`) are ignored too, since they change
+    /// with the nonce.
+    pub fn ignore_nonces(mut self) -> Self {
+        self.ignore_nonces = true;
+        self
+    }
+
+    /// The part of a request body that has to match: the method and
+    /// normalized params of each call.
+    fn key(&self, request: &Value) -> Value {
+        match request {
+            Value::Array(calls) => calls.iter().map(|call| self.call_key(call)).collect(),
+            call => self.call_key(call),
+        }
+    }
+
+    fn call_key(&self, call: &Value) -> Value {
+        let mut params = call.get("params").cloned().unwrap_or(Value::Null);
+        match &mut params {
+            Value::Object(map) => {
+                for name in &self.ignored_params {
+                    map.remove(name);
+                }
+                if self.ignore_block_hashes && map.get("block_id").is_some_and(Value::is_string) {
+                    map.remove("block_id");
+                }
+                if self.ignore_block_hashes || self.ignore_nonces {
+                    map.remove("tx_hash");
+                    if let Some(Value::String(tx)) = map.get_mut("signed_tx_base64") {
+                        *tx = self.transaction_key(tx);
+                    }
+                }
+            }
+            Value::Array(items) if self.ignore_block_hashes => {
+                for item in items {
+                    if item
+                        .as_str()
+                        .is_some_and(|s| s.parse::<CryptoHash>().is_ok())
+                    {
+                        *item = Value::Null;
+                    }
+                }
+            }
+            _ => {}
+        }
+        json!({ "method": call.get("method"), "params": params })
+    }
+
+    /// The hash of the transaction with the ignored fields zeroed.
+    ///
+    /// The signature covers those fields, so it is left out as well.
+    fn transaction_key(&self, signed_tx_base64: &str) -> String {
+        let Ok(signed) = SignedTransaction::from_base64(signed_tx_base64) else {
+            return signed_tx_base64.to_string();
+        };
+        let mut tx = signed.transaction;
+        if self.ignore_nonces {
+            tx.nonce = 0;
+        }
+        if self.ignore_block_hashes {
+            tx.block_hash = CryptoHash::ZERO;
+        }
+        tx.get_hash().to_string()
+    }
+}
+
+// ============================================================================
+// Replay
+// ============================================================================
+
+/// An [`RpcTransport`] that answers requests from a cassette written by
+/// [`RecordingTransport`], without touching the network.
+///
+/// Each request is matched against the recorded ones under the transport's
+/// [`MatchRules`]. Matching recordings are served in recorded order, so a
+/// balance read before and after a transfer gets the two balances that were
+/// recorded; once they are used up, the last one is repeated (which keeps
+/// polling loops working). A request that matches nothing fails with
+/// [`RpcError::InvalidResponse`].
+///
+/// Clones share the cassette and the replay position.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use near_kit::*;
+/// use near_kit::testing::{MatchRules, ReplayTransport};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let replay = ReplayTransport::load("tests/cassettes/balance.json")?
+///     .match_rules(MatchRules::new().ignore_block_hashes());
+/// let near = replay.near().build();
+/// near.balance("alice.testnet").await?;
+/// replay.verify();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ReplayTransport {
+    inner: Arc<Replay>,
+}
+
+struct Replay {
+    interactions: Vec<Interaction>,
+    rules: MatchRules,
+    /// Whether each interaction has been served.
+    played: Mutex<Vec<bool>>,
+}
+
+impl ReplayTransport {
+    /// Load the cassette at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can't be read or isn't a cassette
+    /// ([`std::io::ErrorKind::InvalidData`]).
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self::from_cassette(Cassette::load(path.as_ref())?))
+    }
+
+    fn from_cassette(cassette: Cassette) -> Self {
+        let played = vec![false; cassette.interactions.len()];
+        Self {
+            inner: Arc::new(Replay {
+                interactions: cassette.interactions,
+                rules: MatchRules::default(),
+                played: Mutex::new(played),
+            }),
+        }
+    }
+
+    /// Set how requests are matched against the recording.
+    ///
+    /// Resets the replay position.
+    pub fn match_rules(self, rules: MatchRules) -> Self {
+        let played = vec![false; self.inner.interactions.len()];
+        Self {
+            inner: Arc::new(Replay {
+                interactions: self.inner.interactions.clone(),
+                rules,
+                played: Mutex::new(played),
+            }),
+        }
+    }
+
+    /// A [`NearBuilder`] wired to this transport.
+    ///
+    /// Retries are disabled, since a replayed failure fails the same way
+    /// again, and the client gets its own nonce store. Add a signer as usual.
+    pub fn near(&self) -> NearBuilder {
+        Near::custom(REPLAY_URL, REPLAY_NETWORK)
+            .transport(self.clone())
+            .retry_config(RetryConfig::none())
+            .nonce_store(InMemoryNonceStore::new())
+    }
+
+    /// How many recorded exchanges have not been served yet.
+    pub fn unplayed(&self) -> usize {
+        let played = self.inner.played.lock().unwrap();
+        played.iter().filter(|played| !**played).count()
+    }
+
+    /// Panic unless every recorded exchange was served at least once.
+    ///
+    /// # Panics
+    ///
+    /// Lists the methods of the exchanges that were never requested.
+    pub fn verify(&self) {
+        let played = self.inner.played.lock().unwrap();
+        let missing: Vec<String> = self
+            .inner
+            .interactions
+            .iter()
+            .zip(played.iter())
+            .filter(|(_, played)| !**played)
+            .map(|(interaction, _)| describe(&interaction.request))
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "ReplayTransport: {} recorded request(s) were never replayed: {}",
+            missing.len(),
+            missing.join(", ")
+        );
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn replay(&self, body: &[u8]) -> Result<TransportResponse, RpcError> {
+        let request: Value = serde_json::from_slice(body).map_err(|e| {
+            RpcError::InvalidResponse(format!("ReplayTransport: request is not JSON: {e}"))
+        })?;
+        let key = self.inner.rules.key(&request);
+
+        let index = {
+            let mut played = self.inner.played.lock().unwrap();
+            let candidates: Vec<usize> = self
+                .inner
+                .interactions
+                .iter()
+                .enumerate()
+                .filter(|(_, interaction)| self.inner.rules.key(&interaction.request) == key)
+                .map(|(i, _)| i)
+                .collect();
+            let index = candidates
+                .iter()
+                .copied()
+                .find(|&i| !played[i])
+                .or_else(|| candidates.last().copied())
+                .ok_or_else(|| {
+                    RpcError::InvalidResponse(format!(
+                        "ReplayTransport: no recorded response for {}",
+                        describe(&request)
+                    ))
+                })?;
+            played[index] = true;
+            index
+        };
+
+        let interaction = &self.inner.interactions[index];
+        let response = &interaction.response;
+        let body = match &response.body {
+            RecordedBody::Json(json) => {
+                let json = with_live_ids(json.clone(), &interaction.request, &request);
+                serde_json::to_vec(&json).expect("JSON values always serialize")
+            }
+            RecordedBody::Text(text) => text.clone().into_bytes(),
+        };
+        Ok(TransportResponse::new(response.status, body)
+            .with_retry_after(response.retry_after_secs.map(Duration::from_secs)))
+    }
+}
+
+impl RpcTransport for ReplayTransport {
+    fn post_json(
+        &self,
+        _url: &str,
+        body: Vec<u8>,
+    ) -> BoxFuture<'_, Result<TransportResponse, RpcError>> {
+        let response = self.replay(&body);
+        Box::pin(async move { response })
+    }
+}
+
+impl std::fmt::Debug for ReplayTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReplayTransport")
+            .field("interactions", &self.inner.interactions.len())
+            .field("unplayed", &self.unplayed())
+            .field("rules", &self.inner.rules)
+            .finish()
+    }
+}
+
+/// The method (or methods, for a batch) of a request, for messages.
+fn describe(request: &Value) -> String {
+    let method = |call: &Value| format!("`{}`", call["method"].as_str().unwrap_or("?"));
+    match request {
+        Value::Array(calls) => format!(
+            "batch [{}]",
+            calls.iter().map(method).collect::<Vec<_>>().join(", ")
+        ),
+        call => method(call),
+    }
+}
+
+/// Rewrite the JSON-RPC ids of a recorded response to those of the live
+/// request, pairing recorded and live calls by position.
+fn with_live_ids(mut response: Value, recorded: &Value, live: &Value) -> Value {
+    match (&mut response, recorded, live) {
+        (Value::Array(envelopes), Value::Array(recorded), Value::Array(live)) => {
+            for envelope in envelopes {
+                let position = recorded
+                    .iter()
+                    .position(|call| call.get("id") == envelope.get("id"));
+                if let Some(id) = position.and_then(|i| live.get(i)?.get("id")) {
+                    envelope["id"] = id.clone();
+                }
+            }
+        }
+        (Value::Object(envelope), _, _) => {
+            if let Some(id) = live.get("id") {
+                envelope.insert("id".to_string(), id.clone());
+            }
+        }
+        _ => {}
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::InMemorySigner;
+    use crate::testing::{FakeChain, MockResponse, MockTransport};
+    use crate::types::{NearToken, SecretKey};
+
+    #[tokio::test]
+    async fn test_replays_a_recorded_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cassettes").join("transfer.json");
+
+        let chain = FakeChain::new();
+        let alice = chain
+            .add_account("alice.testnet", NearToken::from_near(10))
+            .signer("alice.testnet");
+        chain.add_account("bob.testnet", NearToken::ZERO);
+        let recorder = RecordingTransport::new(chain.transport(), &path);
+        let near = chain
+            .transport()
+            .near()
+            .transport(recorder.clone())
+            .signer(alice.clone())
+            .build();
+        near.transfer("bob.testnet", NearToken::from_near(1))
+            .await
+            .unwrap();
+        let recorded_balance = near.balance("bob.testnet").await.unwrap().total;
+        recorder.save().unwrap();
+        let recorded = recorder.recorded();
+        assert!(recorded >= 3);
+
+        let replay = ReplayTransport::load(&path).unwrap();
+        assert_eq!(replay.unplayed(), recorded);
+        let near = replay.near().signer(alice).build();
+        near.transfer("bob.testnet", NearToken::from_near(1))
+            .await
+            .unwrap();
+        let balance = near.balance("bob.testnet").await.unwrap().total;
+        assert_eq!(balance, recorded_balance);
+        replay.verify();
+
+        let err = near.balance("carol.testnet").await.unwrap_err();
+        assert!(err.to_string().contains("no recorded response"));
+    }
+
+    #[test]
+    fn test_match_rules_ignore_volatile_fields() {
+        let signer =
+            InMemorySigner::from_secret_key("alice.testnet", SecretKey::generate_ed25519())
+                .unwrap();
+        let tx = |nonce: u64, block: &[u8]| {
+            crate::types::Transaction::new(
+                "alice.testnet".parse().unwrap(),
+                signer.public_key().clone(),
+                nonce,
+                "bob.testnet".parse().unwrap(),
+                CryptoHash::hash(block),
+                vec![],
+            )
+            .complete(crate::types::Signature::ed25519_from_bytes([0; 64]))
+            .to_base64()
+        };
+        let send = |signed_tx: String| {
+            json!({ "jsonrpc": "2.0", "id": 0, "method": "send_tx",
+                    "params": { "signed_tx_base64": signed_tx, "wait_until": "FINAL" } })
+        };
+
+        let exact = MatchRules::new();
+        let nonces = MatchRules::new().ignore_nonces();
+        let both = nonces.clone().ignore_block_hashes();
+        let (a, b, c) = (send(tx(1, b"a")), send(tx(2, b"a")), send(tx(2, b"b")));
+        assert_ne!(exact.key(&a), exact.key(&b));
+        assert_eq!(nonces.key(&a), nonces.key(&b));
+        assert_ne!(nonces.key(&b), nonces.key(&c));
+        assert_eq!(both.key(&a), both.key(&c));
+
+        let at_hash = |hash: &str| {
+            json!({ "id": 4, "method": "EXPERIMENTAL_view_account",
+                    "params": { "account_id": "alice.testnet", "block_id": hash } })
+        };
+        let one = at_hash(&CryptoHash::hash(b"1").to_string());
+        let two = at_hash(&CryptoHash::hash(b"2").to_string());
+        assert_ne!(exact.key(&one), exact.key(&two));
+        assert_eq!(both.key(&one), both.key(&two));
+
+        let waits = MatchRules::new().ignore_param("wait_until");
+        let mut optimistic = a.clone();
+        optimistic["params"]["wait_until"] = json!("EXECUTED_OPTIMISTIC");
+        assert_eq!(waits.key(&a), waits.key(&optimistic));
+    }
+
+    #[tokio::test]
+    async fn test_replay_rewrites_batch_ids_and_repeats_last_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("batch.json");
+
+        let mock = MockTransport::new();
+        mock.on("EXPERIMENTAL_view_account")
+            .respond_with(|request| {
+                let near = if request.account_id() == Some("alice.testnet") {
+                    1
+                } else {
+                    2
+                };
+                MockResponse::account(NearToken::from_near(near))
+            });
+        let recorder = RecordingTransport::new(mock.clone(), &path);
+        let near = mock.near().transport(recorder.clone()).build();
+        let block = crate::types::BlockReference::final_();
+        let mut batch = near.rpc().batch();
+        let _ = batch.view_account(&"alice.testnet".parse().unwrap(), block);
+        let _ = batch.view_account(&"bob.testnet".parse().unwrap(), block);
+        batch.send().await.unwrap();
+        drop(near);
+        drop(recorder);
+
+        let replay = ReplayTransport::load(&path).unwrap();
+        let near = replay.near().build();
+        // A fresh client numbers its requests differently; replay twice so
+        // the second batch is served by repeating the recording.
+        for _ in 0..2 {
+            near.balance("carol.testnet").await.unwrap_err();
+            let mut batch = near.rpc().batch();
+            let alice = batch.view_account(&"alice.testnet".parse().unwrap(), block);
+            let bob = batch.view_account(&"bob.testnet".parse().unwrap(), block);
+            let mut response = batch.send().await.unwrap();
+            assert_eq!(
+                response.take(alice).unwrap().amount,
+                NearToken::from_near(1)
+            );
+            assert_eq!(response.take(bob).unwrap().amount, NearToken::from_near(2));
+        }
+        assert_eq!(replay.unplayed(), 0);
+    }
+}
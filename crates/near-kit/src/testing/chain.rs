@@ -0,0 +1,1070 @@
+//! A small stateful chain that answers the RPC methods the client uses.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use crate::client::InMemorySigner;
+use crate::error::RpcError;
+use crate::types::{
+    AccessKey, AccessKeyPermission, AccountId, Action, CryptoHash, Gas, NearToken, PublicKey,
+    SecretKey, SignedTransaction, TryIntoAccountId,
+};
+
+use super::mock::{MockRequest, MockResponse, MockTransport};
+use super::wire::{self, ReceiptStatus};
+
+/// Height of the first block of a new [`FakeChain`].
+const GENESIS_HEIGHT: u64 = 1;
+
+/// Gas price reported by `gas_price` and `block`, in yoctoNEAR.
+const GAS_PRICE: u128 = 100_000_000;
+
+type CallHandler = dyn Fn(&mut CallContext) -> Result<Vec<u8>, String> + Send + Sync;
+
+/// An in-process chain that tracks balances, access-key nonces and block
+/// height, so transactions round-trip without a node.
+///
+/// Put it behind a [`MockTransport`] with [`transport`](Self::transport); the
+/// transport answers from the chain whatever its own expectations do not
+/// claim. The chain serves:
+///
+/// - `EXPERIMENTAL_view_account`, `EXPERIMENTAL_view_access_key` and the
+///   `query` forms of `view_account`, `view_access_key` and
+///   `view_access_key_list`
+/// - `EXPERIMENTAL_call_function`, answered by handlers registered with
+///   [`on_call`](Self::on_call)
+/// - `send_tx` and `EXPERIMENTAL_tx_status`
+/// - `block`, `gas_price` and `status`
+///
+/// Every query sees the latest state regardless of the requested block.
+///
+/// `send_tx` checks the signature, the signer's key and nonce, function-call
+/// key restrictions and the signer's balance, rejecting the transaction with
+/// the same `InvalidTxError`s a node returns. A valid transaction is executed
+/// immediately as one receipt in a new block: `CreateAccount`, `Transfer`,
+/// `FunctionCall`, `DeployContract`, `AddKey`, `DeleteKey` and
+/// `DeleteAccount` are applied, and a failing action rolls the receipt back
+/// and refunds its deposits. Gas is not metered — no fees are charged — and
+/// other actions (staking, delegation, global contracts, gas keys) are
+/// refused with [`RpcError::InvalidResponse`].
+///
+/// Clones share the same state.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use near_kit::*;
+/// use near_kit::testing::FakeChain;
+///
+/// # async fn example() -> Result<(), Error> {
+/// let chain = FakeChain::new();
+/// chain.add_account("bob.testnet", NearToken::from_near(1));
+/// chain.add_account("counter.testnet", NearToken::from_near(10));
+/// let alice = chain
+///     .add_account("alice.testnet", NearToken::from_near(100))
+///     .signer("alice.testnet");
+/// chain.on_call("counter.testnet", "increment", |_ctx| Ok(b"1".to_vec()));
+///
+/// let near = chain.transport().near().signer(alice).build();
+/// near.transfer("bob.testnet", NearToken::from_near(5)).await?;
+/// near.call("counter.testnet", "increment").await?;
+///
+/// assert_eq!(chain.balance("bob.testnet"), Some(NearToken::from_near(6)));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct FakeChain {
+    state: Arc<Mutex<State>>,
+}
+
+struct State {
+    height: u64,
+    accounts: BTreeMap<AccountId, Account>,
+    handlers: HashMap<(AccountId, String), Arc<CallHandler>>,
+    /// Outcomes of executed transactions, by hash.
+    transactions: HashMap<CryptoHash, Value>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            height: GENESIS_HEIGHT,
+            accounts: BTreeMap::new(),
+            handlers: HashMap::new(),
+            transactions: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Account {
+    amount: u128,
+    code_hash: CryptoHash,
+    keys: Vec<(PublicKey, AccessKey)>,
+}
+
+impl Account {
+    fn new(amount: u128) -> Self {
+        Self {
+            amount,
+            code_hash: CryptoHash::ZERO,
+            keys: Vec::new(),
+        }
+    }
+
+    fn key(&self, public_key: &PublicKey) -> Option<&AccessKey> {
+        self.keys
+            .iter()
+            .find(|(key, _)| key == public_key)
+            .map(|(_, access_key)| access_key)
+    }
+
+    fn key_mut(&mut self, public_key: &PublicKey) -> Option<&mut AccessKey> {
+        self.keys
+            .iter_mut()
+            .find(|(key, _)| key == public_key)
+            .map(|(_, access_key)| access_key)
+    }
+}
+
+/// A function call being executed by a [`FakeChain`] handler.
+#[derive(Debug, Clone)]
+pub struct CallContext {
+    /// Contract being called.
+    pub contract_id: AccountId,
+    /// Method being called.
+    pub method_name: String,
+    /// Raw call arguments.
+    pub args: Vec<u8>,
+    /// Account that signed the transaction, or `None` for a view call.
+    pub predecessor_id: Option<AccountId>,
+    /// NEAR attached to the call (zero for view calls).
+    pub deposit: NearToken,
+    /// Gas attached to the call (zero for view calls).
+    pub gas: Gas,
+    logs: Vec<String>,
+}
+
+impl CallContext {
+    /// Whether this is a view call rather than part of a transaction.
+    pub fn is_view(&self) -> bool {
+        self.predecessor_id.is_none()
+    }
+
+    /// Deserialize the arguments as JSON. The error converts into a handler
+    /// failure with `?`.
+    pub fn args_json<T: DeserializeOwned>(&self) -> Result<T, String> {
+        serde_json::from_slice(&self.args).map_err(|e| format!("invalid JSON arguments: {e}"))
+    }
+
+    /// Emit a log line, e.g. an `EVENT_JSON:` event.
+    pub fn log(&mut self, line: impl Into<String>) {
+        self.logs.push(line.into());
+    }
+}
+
+impl FakeChain {
+    /// Create an empty chain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A [`MockTransport`] that answers from this chain.
+    pub fn transport(&self) -> MockTransport {
+        MockTransport::with_chain(self.clone())
+    }
+
+    /// Create (or replace) an account holding `balance`, with no keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `account_id` is not a valid account ID.
+    pub fn add_account(&self, account_id: impl TryIntoAccountId, balance: NearToken) -> &Self {
+        let account_id = account_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        self.lock()
+            .accounts
+            .insert(account_id, Account::new(balance.as_yoctonear()));
+        self
+    }
+
+    /// Add an access key to an existing account.
+    ///
+    /// Like on chain, the key's nonce starts at the current block height
+    /// times 10⁶.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `account_id` is invalid or the account does not exist.
+    pub fn add_key(
+        &self,
+        account_id: impl TryIntoAccountId,
+        public_key: PublicKey,
+        permission: AccessKeyPermission,
+    ) -> &Self {
+        let account_id = account_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        let mut state = self.lock();
+        let nonce = state.initial_nonce();
+        let account = state
+            .accounts
+            .get_mut(&account_id)
+            .unwrap_or_else(|| panic!("FakeChain: account {account_id} does not exist"));
+        account.keys.retain(|(key, _)| key != &public_key);
+        account
+            .keys
+            .push((public_key, AccessKey { nonce, permission }));
+        self
+    }
+
+    /// Give an existing account a new full-access key and return a signer
+    /// for it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `account_id` is invalid or the account does not exist.
+    pub fn signer(&self, account_id: impl TryIntoAccountId) -> InMemorySigner {
+        let account_id = account_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        let secret_key = SecretKey::generate_ed25519();
+        self.add_key(
+            account_id.clone(),
+            secret_key.public_key(),
+            AccessKeyPermission::FullAccess,
+        );
+        InMemorySigner::from_secret_key(account_id, secret_key)
+            .expect("account ID was already validated")
+    }
+
+    /// Handle calls to `method` on `contract_id`, both view calls and
+    /// `FunctionCall` actions.
+    ///
+    /// The handler returns the call's raw return value, or a panic message
+    /// that fails the call. Registering a handler makes the account count as
+    /// having a contract. Handlers run while the chain is locked and must not
+    /// call back into it; keep contract state in the closure instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `contract_id` is not a valid account ID.
+    pub fn on_call(
+        &self,
+        contract_id: impl TryIntoAccountId,
+        method: &str,
+        handler: impl Fn(&mut CallContext) -> Result<Vec<u8>, String> + Send + Sync + 'static,
+    ) -> &Self {
+        let contract_id = contract_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        self.lock()
+            .handlers
+            .insert((contract_id, method.to_string()), Arc::new(handler));
+        self
+    }
+
+    /// Whether `account_id` exists.
+    pub fn account_exists(&self, account_id: impl TryIntoAccountId) -> bool {
+        account_id
+            .try_into_account_id()
+            .is_ok_and(|account_id| self.lock().accounts.contains_key(&account_id))
+    }
+
+    /// The balance of `account_id`, or `None` if it does not exist.
+    pub fn balance(&self, account_id: impl TryIntoAccountId) -> Option<NearToken> {
+        let account_id = account_id.try_into_account_id().ok()?;
+        let amount = self.lock().accounts.get(&account_id)?.amount;
+        Some(NearToken::from_yoctonear(amount))
+    }
+
+    /// The nonce of an access key, or `None` if the key does not exist.
+    pub fn nonce(&self, account_id: impl TryIntoAccountId, public_key: &PublicKey) -> Option<u64> {
+        let account_id = account_id.try_into_account_id().ok()?;
+        let state = self.lock();
+        Some(state.accounts.get(&account_id)?.key(public_key)?.nonce)
+    }
+
+    /// The latest block height. Each executed transaction produces a block.
+    pub fn block_height(&self) -> u64 {
+        self.lock().height
+    }
+
+    /// Produce `blocks` empty blocks.
+    pub fn advance(&self, blocks: u64) {
+        let mut state = self.lock();
+        state.height = state.height.saturating_add(blocks);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+
+    /// Answer `request`, or `None` if the chain does not serve its method.
+    pub(super) fn handle(&self, request: &MockRequest) -> Option<MockResponse> {
+        let mut state = self.lock();
+        let reply = match (request.method.as_str(), request.request_type()) {
+            ("EXPERIMENTAL_view_account", _) | ("query", Some("view_account")) => {
+                state.view_account(request)
+            }
+            ("EXPERIMENTAL_view_access_key", _) | ("query", Some("view_access_key")) => {
+                state.view_access_key(request)
+            }
+            ("query", Some("view_access_key_list")) => state.view_access_key_list(request),
+            ("EXPERIMENTAL_call_function", _) => state.call_function(request),
+            ("EXPERIMENTAL_tx_status", _) => state.tx_status(request),
+            ("send_tx", _) => return Some(state.send_tx(request)),
+            ("block", _) => Ok(wire::block_view(state.height, GAS_PRICE)),
+            ("gas_price", _) => Ok(json!({ "gas_price": GAS_PRICE.to_string() })),
+            ("status", _) => Ok(wire::status_view(state.height)),
+            _ => return None,
+        };
+        Some(match reply {
+            Ok(result) => MockResponse::result(result),
+            Err(error) => MockResponse::rpc_error(error),
+        })
+    }
+}
+
+impl std::fmt::Debug for FakeChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.lock();
+        f.debug_struct("FakeChain")
+            .field("height", &state.height)
+            .field("accounts", &state.accounts.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// The receipt an action is applied in.
+struct Receipt<'a> {
+    predecessor_id: &'a AccountId,
+    receiver_id: &'a AccountId,
+    /// Whether the predecessor may act as the receiver account.
+    is_owner: bool,
+}
+
+/// Why a transaction was not executed.
+enum Rejection {
+    /// An `InvalidTxError`, as a node reports it.
+    Invalid(Value),
+    /// Something the fake chain does not model.
+    Unsupported(String),
+}
+
+impl State {
+    fn initial_nonce(&self) -> u64 {
+        self.height.saturating_mul(1_000_000)
+    }
+
+    fn code_hash(&self, account_id: &AccountId, account: &Account) -> CryptoHash {
+        if account.code_hash != CryptoHash::ZERO {
+            return account.code_hash;
+        }
+        if self
+            .handlers
+            .keys()
+            .any(|(contract, _)| contract == account_id)
+        {
+            return CryptoHash::hash(format!("FakeChain contract {account_id}").as_bytes());
+        }
+        CryptoHash::ZERO
+    }
+
+    fn account(&self, account_id: &AccountId) -> Result<&Account, Value> {
+        self.accounts.get(account_id).ok_or_else(|| {
+            let mut info = wire::block_context(self.height);
+            info["requested_account_id"] = json!(account_id.as_str());
+            wire::handler_error(
+                "UNKNOWN_ACCOUNT",
+                info,
+                json!(format!("account {account_id} does not exist while viewing")),
+            )
+        })
+    }
+
+    fn view_account(&self, request: &MockRequest) -> Result<Value, Value> {
+        let account_id = account_param(request)?;
+        let account = self.account(&account_id)?;
+        Ok(wire::account_view(
+            account.amount,
+            0,
+            self.code_hash(&account_id, account),
+            self.height,
+        ))
+    }
+
+    fn view_access_key(&self, request: &MockRequest) -> Result<Value, Value> {
+        let account_id = account_param(request)?;
+        let public_key: PublicKey = request
+            .param_str("public_key")
+            .and_then(|key| key.parse().ok())
+            .ok_or_else(|| wire::parse_error("missing or invalid `public_key`"))?;
+        let account = self.account(&account_id)?;
+        let access_key = account.key(&public_key).ok_or_else(|| {
+            let mut info = wire::block_context(self.height);
+            info["public_key"] = json!(public_key.to_string());
+            wire::handler_error(
+                "UNKNOWN_ACCESS_KEY",
+                info,
+                json!(format!(
+                    "access key {public_key} does not exist while viewing"
+                )),
+            )
+        })?;
+        Ok(wire::access_key_view(
+            access_key.nonce,
+            &access_key.permission,
+            self.height,
+        ))
+    }
+
+    fn view_access_key_list(&self, request: &MockRequest) -> Result<Value, Value> {
+        let account_id = account_param(request)?;
+        let account = self.account(&account_id)?;
+        let keys: Vec<Value> = account
+            .keys
+            .iter()
+            .map(|(public_key, access_key)| {
+                json!({
+                    "public_key": public_key.to_string(),
+                    "access_key": {
+                        "nonce": access_key.nonce,
+                        "permission": wire::permission_view(&access_key.permission),
+                    },
+                })
+            })
+            .collect();
+        let mut view = wire::block_context(self.height);
+        view["keys"] = json!(keys);
+        Ok(view)
+    }
+
+    fn call_function(&self, request: &MockRequest) -> Result<Value, Value> {
+        let contract_id = account_param(request)?;
+        let method_name = request
+            .param_str("method_name")
+            .ok_or_else(|| wire::parse_error("missing `method_name`"))?;
+        let args = request
+            .function_args()
+            .ok_or_else(|| wire::parse_error("missing or invalid `args_base64`"))?;
+        self.account(&contract_id)?;
+
+        let mut ctx = CallContext {
+            contract_id,
+            method_name: method_name.to_string(),
+            args,
+            predecessor_id: None,
+            deposit: NearToken::from_yoctonear(0),
+            gas: Gas::from_gas(0),
+            logs: Vec::new(),
+        };
+        match self.run(&mut ctx) {
+            Ok(result) => Ok(wire::view_result(&result, &ctx.logs, self.height)),
+            Err(error) => {
+                let mut info = wire::block_context(self.height);
+                info["vm_error"] = error["FunctionCallError"].clone();
+                Err(wire::handler_error(
+                    "CONTRACT_EXECUTION_ERROR",
+                    info,
+                    json!(format!("Function call returned an error: {error}")),
+                ))
+            }
+        }
+    }
+
+    /// Run a function call, returning its result or an `ActionErrorKind`.
+    fn run(&self, ctx: &mut CallContext) -> Result<Vec<u8>, Value> {
+        let handler = self
+            .handlers
+            .get(&(ctx.contract_id.clone(), ctx.method_name.clone()))
+            .cloned();
+        let function_call_error = |error: Value| json!({ "FunctionCallError": error });
+        match handler {
+            Some(handler) => handler(ctx).map_err(|message| {
+                function_call_error(json!({
+                    "ExecutionError": format!("Smart contract panicked: {message}"),
+                }))
+            }),
+            None => {
+                let code_hash = self
+                    .accounts
+                    .get(&ctx.contract_id)
+                    .map_or(CryptoHash::ZERO, |account| {
+                        self.code_hash(&ctx.contract_id, account)
+                    });
+                if code_hash == CryptoHash::ZERO {
+                    Err(function_call_error(json!({
+                        "CompilationError": {
+                            "CodeDoesNotExist": { "account_id": ctx.contract_id.as_str() }
+                        }
+                    })))
+                } else {
+                    Err(function_call_error(
+                        json!({ "MethodResolveError": "MethodNotFound" }),
+                    ))
+                }
+            }
+        }
+    }
+
+    fn tx_status(&self, request: &MockRequest) -> Result<Value, Value> {
+        let tx_hash: CryptoHash = request
+            .param_str("tx_hash")
+            .and_then(|hash| hash.parse().ok())
+            .ok_or_else(|| wire::parse_error("missing or invalid `tx_hash`"))?;
+        let outcome = self.transactions.get(&tx_hash).ok_or_else(|| {
+            wire::handler_error(
+                "UNKNOWN_TRANSACTION",
+                json!({ "requested_transaction_hash": tx_hash.to_string() }),
+                json!(format!("Transaction {tx_hash} doesn't exist")),
+            )
+        })?;
+        // The chain executes instantly, so every known transaction is final.
+        Ok(wire::tx_response("FINAL", outcome.clone()))
+    }
+
+    fn send_tx(&mut self, request: &MockRequest) -> MockResponse {
+        let Some(signed_tx) = request.signed_transaction() else {
+            return MockResponse::rpc_error(wire::parse_error(
+                "missing or undecodable `signed_tx_base64`",
+            ));
+        };
+        match self.execute(&signed_tx) {
+            Ok(outcome) => {
+                self.transactions
+                    .insert(signed_tx.get_hash(), outcome.clone());
+                MockResponse::result(wire::tx_response(request.wait_until(), outcome))
+            }
+            Err(Rejection::Invalid(error)) => MockResponse::invalid_tx(error),
+            Err(Rejection::Unsupported(message)) => MockResponse::error(move || {
+                RpcError::InvalidResponse(format!("FakeChain: {message}"))
+            }),
+        }
+    }
+
+    /// Validate and execute a transaction, returning its outcome.
+    fn execute(&mut self, signed_tx: &SignedTransaction) -> Result<Value, Rejection> {
+        let tx = &signed_tx.transaction;
+        if let Some(action) = tx.actions.iter().find(|action| !is_supported(action)) {
+            return Err(Rejection::Unsupported(format!(
+                "unsupported action {action:?}"
+            )));
+        }
+        if !signed_tx
+            .signature
+            .verify(signed_tx.get_hash().as_bytes(), &tx.public_key)
+        {
+            return Err(Rejection::Invalid(json!("InvalidSignature")));
+        }
+
+        let signer = self.accounts.get(&tx.signer_id).ok_or_else(|| {
+            Rejection::Invalid(json!({
+                "SignerDoesNotExist": { "signer_id": tx.signer_id.as_str() }
+            }))
+        })?;
+        let access_key = signer.key(&tx.public_key).ok_or_else(|| {
+            Rejection::Invalid(json!({
+                "InvalidAccessKeyError": {
+                    "AccessKeyNotFound": {
+                        "account_id": tx.signer_id.as_str(),
+                        "public_key": tx.public_key.to_string(),
+                    }
+                }
+            }))
+        })?;
+        if tx.nonce <= access_key.nonce {
+            return Err(Rejection::Invalid(json!({
+                "InvalidNonce": { "tx_nonce": tx.nonce, "ak_nonce": access_key.nonce }
+            })));
+        }
+        check_permission(&access_key.permission, &tx.receiver_id, &tx.actions)
+            .map_err(|error| Rejection::Invalid(json!({ "InvalidAccessKeyError": error })))?;
+
+        let cost = tx
+            .actions
+            .iter()
+            .map(attached_deposit)
+            .fold(0u128, u128::saturating_add);
+        if signer.amount < cost {
+            return Err(Rejection::Invalid(json!({
+                "NotEnoughBalance": {
+                    "balance": signer.amount.to_string(),
+                    "cost": cost.to_string(),
+                    "signer_id": tx.signer_id.as_str(),
+                }
+            })));
+        }
+
+        // Valid: consume the nonce and the deposits, then run the receipt in
+        // a new block.
+        let signer = self
+            .accounts
+            .get_mut(&tx.signer_id)
+            .expect("signer was checked above");
+        signer.amount -= cost;
+        signer
+            .key_mut(&tx.public_key)
+            .expect("key was checked above")
+            .nonce = tx.nonce;
+        self.height += 1;
+
+        let snapshot = self.accounts.clone();
+        let mut logs = Vec::new();
+        let mut status = ReceiptStatus::Success(Vec::new());
+        // Only the account itself may change its keys, code or existence —
+        // or whoever created it earlier in the same receipt.
+        let mut is_owner = tx.signer_id == tx.receiver_id;
+        for (index, action) in tx.actions.iter().enumerate() {
+            let receipt = Receipt {
+                predecessor_id: &tx.signer_id,
+                receiver_id: &tx.receiver_id,
+                is_owner,
+            };
+            match self.apply(&receipt, action, &mut logs) {
+                Ok(value) => {
+                    is_owner |= matches!(action, Action::CreateAccount(_));
+                    status = ReceiptStatus::Success(value);
+                }
+                Err(kind) => {
+                    self.accounts = snapshot;
+                    if let Some(signer) = self.accounts.get_mut(&tx.signer_id) {
+                        signer.amount += cost;
+                    }
+                    logs.clear();
+                    status = ReceiptStatus::Failure {
+                        index: index as u64,
+                        kind,
+                    };
+                    break;
+                }
+            }
+        }
+
+        Ok(wire::final_outcome(signed_tx, self.height, &status, &logs))
+    }
+
+    /// Apply one action of `receipt`, returning the action's return value or
+    /// an `ActionErrorKind`.
+    fn apply(
+        &mut self,
+        receipt: &Receipt<'_>,
+        action: &Action,
+        logs: &mut Vec<String>,
+    ) -> Result<Vec<u8>, Value> {
+        let Receipt {
+            predecessor_id,
+            receiver_id,
+            is_owner,
+        } = *receipt;
+        if let Action::CreateAccount(_) = action {
+            if self.accounts.contains_key(receiver_id) {
+                return Err(json!({
+                    "AccountAlreadyExists": { "account_id": receiver_id.as_str() }
+                }));
+            }
+            self.accounts.insert(receiver_id.clone(), Account::new(0));
+            return Ok(Vec::new());
+        }
+
+        let initial_nonce = self.initial_nonce();
+        let Some(receiver) = self.accounts.get_mut(receiver_id) else {
+            return Err(json!({
+                "AccountDoesNotExist": { "account_id": receiver_id.as_str() }
+            }));
+        };
+        let no_permission = || {
+            json!({
+                "ActorNoPermission": {
+                    "account_id": receiver_id.as_str(),
+                    "actor_id": predecessor_id.as_str(),
+                }
+            })
+        };
+
+        match action {
+            Action::Transfer(transfer) => {
+                receiver.amount += transfer.deposit.as_yoctonear();
+                Ok(Vec::new())
+            }
+            Action::DeployContract(deploy) => {
+                if !is_owner {
+                    return Err(no_permission());
+                }
+                receiver.code_hash = CryptoHash::hash(&deploy.code);
+                Ok(Vec::new())
+            }
+            Action::AddKey(add) => {
+                if !is_owner {
+                    return Err(no_permission());
+                }
+                if receiver.key(&add.public_key).is_some() {
+                    return Err(json!({
+                        "AddKeyAlreadyExists": {
+                            "account_id": receiver_id.as_str(),
+                            "public_key": add.public_key.to_string(),
+                        }
+                    }));
+                }
+                receiver.keys.push((
+                    add.public_key.clone(),
+                    AccessKey {
+                        nonce: initial_nonce,
+                        permission: add.access_key.permission.clone(),
+                    },
+                ));
+                Ok(Vec::new())
+            }
+            Action::DeleteKey(delete) => {
+                if !is_owner {
+                    return Err(no_permission());
+                }
+                if receiver.key(&delete.public_key).is_none() {
+                    return Err(json!({
+                        "DeleteKeyDoesNotExist": {
+                            "account_id": receiver_id.as_str(),
+                            "public_key": delete.public_key.to_string(),
+                        }
+                    }));
+                }
+                receiver.keys.retain(|(key, _)| key != &delete.public_key);
+                Ok(Vec::new())
+            }
+            Action::DeleteAccount(delete) => {
+                if !is_owner {
+                    return Err(no_permission());
+                }
+                let amount = receiver.amount;
+                self.accounts.remove(receiver_id);
+                if let Some(beneficiary) = self.accounts.get_mut(&delete.beneficiary_id) {
+                    beneficiary.amount += amount;
+                }
+                Ok(Vec::new())
+            }
+            Action::FunctionCall(call) => {
+                receiver.amount += call.deposit.as_yoctonear();
+                let mut ctx = CallContext {
+                    contract_id: receiver_id.clone(),
+                    method_name: call.method_name.clone(),
+                    args: call.args.clone(),
+                    predecessor_id: Some(predecessor_id.clone()),
+                    deposit: call.deposit,
+                    gas: call.gas,
+                    logs: Vec::new(),
+                };
+                let result = self.run(&mut ctx);
+                logs.append(&mut ctx.logs);
+                result
+            }
+            _ => unreachable!("unsupported actions are rejected before execution"),
+        }
+    }
+}
+
+fn account_param(request: &MockRequest) -> Result<AccountId, Value> {
+    request
+        .account_id()
+        .and_then(|account_id| account_id.parse().ok())
+        .ok_or_else(|| wire::parse_error("missing or invalid `account_id`"))
+}
+
+fn is_supported(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::CreateAccount(_)
+            | Action::Transfer(_)
+            | Action::FunctionCall(_)
+            | Action::DeployContract(_)
+            | Action::AddKey(_)
+            | Action::DeleteKey(_)
+            | Action::DeleteAccount(_)
+    )
+}
+
+/// NEAR attached to an action.
+fn attached_deposit(action: &Action) -> u128 {
+    match action {
+        Action::Transfer(transfer) => transfer.deposit.as_yoctonear(),
+        Action::FunctionCall(call) => call.deposit.as_yoctonear(),
+        _ => 0,
+    }
+}
+
+/// Check a transaction against its access key's permission, returning an
+/// `InvalidAccessKeyError`.
+fn check_permission(
+    permission: &AccessKeyPermission,
+    receiver_id: &AccountId,
+    actions: &[Action],
+) -> Result<(), Value> {
+    let function_call = match permission {
+        AccessKeyPermission::FullAccess | AccessKeyPermission::GasKeyFullAccess(_) => {
+            return Ok(());
+        }
+        AccessKeyPermission::FunctionCall(fc) | AccessKeyPermission::GasKeyFunctionCall(_, fc) => {
+            fc
+        }
+    };
+
+    if function_call.receiver_id != *receiver_id {
+        return Err(json!({
+            "ReceiverMismatch": {
+                "ak_receiver": function_call.receiver_id.as_str(),
+                "tx_receiver": receiver_id.as_str(),
+            }
+        }));
+    }
+    for action in actions {
+        let Action::FunctionCall(call) = action else {
+            return Err(json!("RequiresFullAccess"));
+        };
+        if call.deposit.as_yoctonear() != 0 {
+            return Err(json!("DepositWithFunctionCall"));
+        }
+        if !function_call.method_names.is_empty()
+            && !function_call.method_names.contains(&call.method_name)
+        {
+            return Err(json!({
+                "MethodNameMismatch": { "method_name": call.method_name }
+            }));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::Signer;
+    use crate::types::{Final, FunctionCallPermission, InvalidTxError};
+    use crate::{Error, Near};
+
+    fn chain_with_alice() -> (FakeChain, Near) {
+        let chain = FakeChain::new();
+        let alice = chain
+            .add_account("alice.testnet", NearToken::from_near(100))
+            .signer("alice.testnet");
+        chain.add_account("bob.testnet", NearToken::from_near(1));
+        let near = chain.transport().near().signer(alice).build();
+        (chain, near)
+    }
+
+    #[tokio::test]
+    async fn test_transfer_moves_balance_and_consumes_nonce() {
+        let (chain, near) = chain_with_alice();
+        let public_key = near.signer().unwrap().public_key();
+        let start_nonce = chain.nonce("alice.testnet", &public_key).unwrap();
+        let start_height = chain.block_height();
+
+        let outcome = near
+            .transfer("bob.testnet", NearToken::from_near(10))
+            .await
+            .unwrap();
+        assert!(outcome.is_success());
+
+        assert_eq!(
+            chain.balance("alice.testnet"),
+            Some(NearToken::from_near(90))
+        );
+        assert_eq!(chain.balance("bob.testnet"), Some(NearToken::from_near(11)));
+        assert_eq!(
+            chain.nonce("alice.testnet", &public_key),
+            Some(start_nonce + 1)
+        );
+        assert_eq!(chain.block_height(), start_height + 1);
+
+        let balance = near.balance("bob.testnet").await.unwrap();
+        assert_eq!(balance.total, NearToken::from_near(11));
+
+        // The stored outcome is served by tx_status.
+        let status = near
+            .tx_status(outcome.transaction_hash(), "alice.testnet")
+            .wait_until::<Final>()
+            .await
+            .unwrap();
+        assert_eq!(status.transaction_hash(), outcome.transaction_hash());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_transactions_like_a_node() {
+        let (chain, near) = chain_with_alice();
+
+        let err = near
+            .transfer("bob.testnet", NearToken::from_near(1_000))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::InvalidTx(e) if matches!(**e, InvalidTxError::NotEnoughBalance { .. })),
+            "{err:?}"
+        );
+
+        // A key the chain has never seen.
+        let stranger =
+            InMemorySigner::from_secret_key("alice.testnet", SecretKey::generate_ed25519())
+                .unwrap();
+        let err = chain
+            .transport()
+            .near()
+            .signer(stranger)
+            .build()
+            .transfer("bob.testnet", NearToken::from_near(1))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::Rpc(e) if matches!(**e, RpcError::AccessKeyNotFound { .. })),
+            "{err:?}"
+        );
+        assert_eq!(
+            chain.balance("alice.testnet"),
+            Some(NearToken::from_near(100))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_function_calls_reach_handlers() {
+        let (chain, near) = chain_with_alice();
+        chain.add_account("counter.testnet", NearToken::from_near(5));
+        let count = Arc::new(Mutex::new(0u64));
+        let state = count.clone();
+        chain.on_call("counter.testnet", "increment", move |ctx| {
+            let by: u64 = ctx.args_json::<Value>()?["by"].as_u64().unwrap_or(1);
+            let mut count = state.lock().unwrap();
+            *count += by;
+            ctx.log(format!("count is {count}"));
+            Ok(serde_json::to_vec(&*count).unwrap())
+        });
+        let state = count.clone();
+        chain.on_call("counter.testnet", "get_count", move |_| {
+            Ok(serde_json::to_vec(&*state.lock().unwrap()).unwrap())
+        });
+        chain.on_call("counter.testnet", "fail", |_| Err("nope".to_string()));
+
+        let outcome = near
+            .call("counter.testnet", "increment")
+            .args(json!({ "by": 3 }))
+            .deposit(NearToken::from_near(1))
+            .await
+            .unwrap();
+        assert_eq!(outcome.json::<u64>().unwrap(), 3);
+        assert_eq!(outcome.receipts_outcome[0].outcome.logs, vec!["count is 3"]);
+        assert_eq!(
+            chain.balance("counter.testnet"),
+            Some(NearToken::from_near(6))
+        );
+
+        let count: u64 = near.view("counter.testnet", "get_count").await.unwrap();
+        assert_eq!(count, 3);
+
+        // A failing call is rolled back and its deposit refunded.
+        let outcome = near
+            .call("counter.testnet", "fail")
+            .deposit(NearToken::from_near(1))
+            .await
+            .unwrap();
+        assert!(outcome.failure_message().unwrap().contains("nope"));
+        assert_eq!(
+            chain.balance("counter.testnet"),
+            Some(NearToken::from_near(6))
+        );
+        assert_eq!(
+            chain.balance("alice.testnet"),
+            Some(NearToken::from_near(99))
+        );
+
+        let err = near
+            .view::<Value>("counter.testnet", "missing")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::Rpc(ref e) if matches!(**e, RpcError::MethodNotFound { .. })),
+            "{err:?}"
+        );
+        let err = near
+            .view::<Value>("bob.testnet", "anything")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::Rpc(ref e) if matches!(**e, RpcError::ContractNotDeployed { .. })),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_function_call_keys_are_restricted() {
+        let chain = FakeChain::new();
+        chain.add_account("alice.testnet", NearToken::from_near(10));
+        chain.add_account("app.testnet", NearToken::from_near(10));
+        chain.on_call("app.testnet", "play", |_| Ok(Vec::new()));
+        let secret_key = SecretKey::generate_ed25519();
+        chain.add_key(
+            "alice.testnet",
+            secret_key.public_key(),
+            AccessKeyPermission::FunctionCall(FunctionCallPermission {
+                allowance: None,
+                receiver_id: "app.testnet".parse().unwrap(),
+                method_names: vec!["play".to_string()],
+            }),
+        );
+        let signer = InMemorySigner::from_secret_key("alice.testnet", secret_key).unwrap();
+        let near = chain.transport().near().signer(signer).build();
+
+        near.call("app.testnet", "play").await.unwrap();
+        let err = near.call("app.testnet", "cheat").await.unwrap_err();
+        assert!(matches!(err, Error::InvalidTx(_)), "{err:?}");
+        let err = near
+            .transfer("app.testnet", NearToken::from_near(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidTx(_)), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn test_account_lifecycle() {
+        let (chain, near) = chain_with_alice();
+        let new_key = SecretKey::generate_ed25519();
+
+        near.transaction("carol.alice.testnet")
+            .create_account()
+            .transfer(NearToken::from_near(5))
+            .add_full_access_key(new_key.public_key())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            chain.balance("carol.alice.testnet"),
+            Some(NearToken::from_near(5))
+        );
+        let keys = near.access_keys("carol.alice.testnet").await.unwrap();
+        assert_eq!(keys.keys.len(), 1);
+
+        let outcome = near
+            .transaction("carol.alice.testnet")
+            .delete_account("alice.testnet")
+            .send()
+            .await
+            .unwrap();
+        assert!(outcome.is_failure(), "only carol may delete carol");
+
+        let carol = InMemorySigner::from_secret_key("carol.alice.testnet", new_key).unwrap();
+        chain
+            .transport()
+            .near()
+            .signer(carol)
+            .build()
+            .transaction("carol.alice.testnet")
+            .delete_account("alice.testnet")
+            .send()
+            .await
+            .unwrap();
+        assert!(!chain.account_exists("carol.alice.testnet"));
+        assert_eq!(
+            chain.balance("alice.testnet"),
+            Some(NearToken::from_near(100))
+        );
+    }
+}
@@ -0,0 +1,771 @@
+//! Scriptable [`RpcTransport`] for tests.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use crate::client::{
+    InMemoryNonceStore, Near, NearBuilder, RetryConfig, RpcTransport, TransportResponse,
+};
+use crate::error::RpcError;
+use crate::platform::BoxFuture;
+use crate::types::{AccessKeyPermission, NearToken, SignedTransaction};
+
+use super::chain::FakeChain;
+use super::wire::{self, ReceiptStatus};
+
+/// URL of the client built by [`MockTransport::near`].
+const MOCK_URL: &str = "http://mock.invalid";
+
+/// Block height reported by canned [`MockResponse`]s.
+const MOCK_BLOCK_HEIGHT: u64 = 100;
+
+type Matcher = dyn Fn(&MockRequest) -> bool + Send + Sync;
+type Responder = dyn Fn(&MockRequest) -> MockResponse + Send + Sync;
+
+/// An [`RpcTransport`] that answers JSON-RPC requests from a scriptable
+/// router instead of the network.
+///
+/// Expectations registered with [`on`](Self::on) are tried in registration
+/// order; the first one whose method (and optional matcher) fits the request
+/// and that has calls left answers it. Requests no expectation claims fall
+/// through to the [`FakeChain`] given to [`with_chain`](Self::with_chain), if
+/// any, and otherwise fail with [`RpcError::InvalidResponse`]. Every request —
+/// answered or not — is recorded for [`requests`](Self::requests).
+///
+/// Clones share the router and the request log.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use near_kit::*;
+/// use near_kit::testing::{MockResponse, MockTransport};
+///
+/// # async fn example() -> Result<(), Error> {
+/// let mock = MockTransport::new();
+/// mock.on("EXPERIMENTAL_view_account")
+///     .respond(MockResponse::account(NearToken::from_near(5)));
+/// mock.on("EXPERIMENTAL_call_function")
+///     .respond(MockResponse::view_result(42u64));
+///
+/// let near = mock.near().build();
+/// assert_eq!(near.balance("alice.testnet").await?.total, NearToken::from_near(5));
+/// assert_eq!(near.view::<u64>("counter.testnet", "get_count").await?, 42);
+///
+/// assert_eq!(mock.requests_for("EXPERIMENTAL_view_account").len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    routes: Mutex<Vec<Route>>,
+    requests: Mutex<Vec<MockRequest>>,
+    chain: Option<FakeChain>,
+}
+
+struct Route {
+    method: String,
+    matcher: Option<Arc<Matcher>>,
+    expected: Option<usize>,
+    calls: usize,
+    responder: Arc<Responder>,
+}
+
+impl Route {
+    fn matches(&self, request: &MockRequest) -> bool {
+        self.method == request.method
+            && self.expected.is_none_or(|expected| self.calls < expected)
+            && self.matcher.as_ref().is_none_or(|matcher| matcher(request))
+    }
+}
+
+/// What the transport sends back for one JSON-RPC call.
+enum Wire {
+    /// A JSON-RPC response envelope.
+    Envelope(Value),
+    /// A raw HTTP response that replaces the whole reply.
+    Raw(TransportResponse),
+}
+
+impl MockTransport {
+    /// Create a transport with no expectations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a transport that answers unclaimed requests from `chain`.
+    pub fn with_chain(chain: FakeChain) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                chain: Some(chain),
+                ..Inner::default()
+            }),
+        }
+    }
+
+    /// The chain behind this transport, if any.
+    pub fn chain(&self) -> Option<&FakeChain> {
+        self.inner.chain.as_ref()
+    }
+
+    /// A [`NearBuilder`] wired to this transport.
+    ///
+    /// Retries are disabled so injected errors surface on the first attempt,
+    /// and the client gets its own nonce store so tests do not share nonces
+    /// through the process-wide default. Add a signer as usual.
+    pub fn near(&self) -> NearBuilder {
+        Near::custom(MOCK_URL, wire::CHAIN_ID)
+            .transport(self.clone())
+            .retry_config(RetryConfig::none())
+            .nonce_store(InMemoryNonceStore::new())
+    }
+
+    /// Start an expectation for requests to the RPC `method`.
+    ///
+    /// Nothing is registered until the expectation is given a response with
+    /// [`respond`](Expectation::respond) or
+    /// [`respond_with`](Expectation::respond_with).
+    pub fn on(&self, method: impl Into<String>) -> Expectation<'_> {
+        Expectation {
+            transport: self,
+            method: method.into(),
+            matcher: None,
+            expected: None,
+        }
+    }
+
+    /// Every request received so far, in order.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.inner.requests.lock().unwrap().clone()
+    }
+
+    /// The requests received so far for `method`, in order.
+    pub fn requests_for(&self, method: &str) -> Vec<MockRequest> {
+        self.inner
+            .requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|request| request.method == method)
+            .cloned()
+            .collect()
+    }
+
+    /// Assert that every expectation limited with
+    /// [`times`](Expectation::times) was used up.
+    ///
+    /// # Panics
+    ///
+    /// Panics listing the expectations that were called fewer times than
+    /// expected.
+    pub fn verify(&self) {
+        let routes = self.inner.routes.lock().unwrap();
+        let unmet: Vec<String> = routes
+            .iter()
+            .filter_map(|route| {
+                let expected = route.expected.filter(|&expected| route.calls < expected)?;
+                Some(format!(
+                    "`{}` expected {expected} call(s), got {}",
+                    route.method, route.calls
+                ))
+            })
+            .collect();
+        assert!(
+            unmet.is_empty(),
+            "MockTransport: unmet expectations: {}",
+            unmet.join("; ")
+        );
+    }
+
+    fn add_route(&self, route: Route) {
+        self.inner.routes.lock().unwrap().push(route);
+    }
+
+    /// Answer one JSON-RPC call.
+    #[allow(clippy::result_large_err)]
+    fn handle(&self, request: MockRequest) -> Result<Wire, RpcError> {
+        self.inner.requests.lock().unwrap().push(request.clone());
+
+        // Run the responder outside the lock so it may inspect the transport.
+        let responder = self
+            .inner
+            .routes
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|route| route.matches(&request))
+            .map(|route| {
+                route.calls += 1;
+                route.responder.clone()
+            });
+
+        let response = match responder {
+            Some(responder) => responder(&request),
+            None => self
+                .inner
+                .chain
+                .as_ref()
+                .and_then(|chain| chain.handle(&request))
+                .ok_or_else(|| {
+                    RpcError::InvalidResponse(format!(
+                        "MockTransport: no expectation matches `{}`",
+                        request.method
+                    ))
+                })?,
+        };
+        response.into_wire(&request)
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn dispatch(&self, body: &[u8]) -> Result<TransportResponse, RpcError> {
+        let body: Value = serde_json::from_slice(body).map_err(|e| {
+            RpcError::InvalidResponse(format!("MockTransport: request is not JSON: {e}"))
+        })?;
+        let ok = |value: Value| {
+            TransportResponse::new(
+                200,
+                serde_json::to_vec(&value).expect("JSON values always serialize"),
+            )
+        };
+
+        match body {
+            Value::Array(calls) => {
+                let mut envelopes = Vec::with_capacity(calls.len());
+                for call in calls {
+                    match self.handle(MockRequest::from_json(call))? {
+                        Wire::Envelope(envelope) => envelopes.push(envelope),
+                        Wire::Raw(response) => return Ok(response),
+                    }
+                }
+                Ok(ok(Value::Array(envelopes)))
+            }
+            call => match self.handle(MockRequest::from_json(call))? {
+                Wire::Envelope(envelope) => Ok(ok(envelope)),
+                Wire::Raw(response) => Ok(response),
+            },
+        }
+    }
+}
+
+impl RpcTransport for MockTransport {
+    fn post_json(
+        &self,
+        _url: &str,
+        body: Vec<u8>,
+    ) -> BoxFuture<'_, Result<TransportResponse, RpcError>> {
+        let response = self.dispatch(&body);
+        Box::pin(async move { response })
+    }
+}
+
+impl std::fmt::Debug for MockTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockTransport")
+            .field("expectations", &self.inner.routes.lock().unwrap().len())
+            .field("requests", &self.inner.requests.lock().unwrap().len())
+            .field("chain", &self.inner.chain.is_some())
+            .finish()
+    }
+}
+
+/// An expectation being configured, from [`MockTransport::on`].
+#[must_use = "an expectation is only registered by `respond` or `respond_with`"]
+pub struct Expectation<'a> {
+    transport: &'a MockTransport,
+    method: String,
+    matcher: Option<Arc<Matcher>>,
+    expected: Option<usize>,
+}
+
+impl Expectation<'_> {
+    /// Only answer requests for which `matcher` returns `true`.
+    ///
+    /// The matcher runs while the router is locked and must not call back
+    /// into the transport.
+    pub fn matching(
+        mut self,
+        matcher: impl Fn(&MockRequest) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.matcher = Some(Arc::new(matcher));
+        self
+    }
+
+    /// Answer at most `n` requests; later requests fall through to the next
+    /// matching expectation. [`MockTransport::verify`] checks all `n` were
+    /// made.
+    pub fn times(mut self, n: usize) -> Self {
+        self.expected = Some(n);
+        self
+    }
+
+    /// Shorthand for [`times(1)`](Self::times).
+    pub fn once(self) -> Self {
+        self.times(1)
+    }
+
+    /// Answer every matching request with `response`.
+    pub fn respond(self, response: MockResponse) {
+        self.respond_with(move |_| response.clone());
+    }
+
+    /// Answer every matching request with the response `responder` builds
+    /// from it.
+    pub fn respond_with(
+        self,
+        responder: impl Fn(&MockRequest) -> MockResponse + Send + Sync + 'static,
+    ) {
+        self.transport.add_route(Route {
+            method: self.method,
+            matcher: self.matcher,
+            expected: self.expected,
+            calls: 0,
+            responder: Arc::new(responder),
+        });
+    }
+}
+
+/// A JSON-RPC call received by a [`MockTransport`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockRequest {
+    /// The RPC method, e.g. `send_tx`.
+    pub method: String,
+    /// The call's `params` (`null` if absent).
+    pub params: Value,
+    id: Value,
+}
+
+impl MockRequest {
+    fn from_json(call: Value) -> Self {
+        Self {
+            method: call["method"].as_str().unwrap_or_default().to_string(),
+            params: call.get("params").cloned().unwrap_or(Value::Null),
+            id: call.get("id").cloned().unwrap_or(json!(0)),
+        }
+    }
+
+    /// A named parameter as a string.
+    pub fn param_str(&self, name: &str) -> Option<&str> {
+        self.params.get(name)?.as_str()
+    }
+
+    /// The `request_type` of a `query` call.
+    pub fn request_type(&self) -> Option<&str> {
+        self.param_str("request_type")
+    }
+
+    /// The `account_id` parameter of account, access key and function call
+    /// queries.
+    pub fn account_id(&self) -> Option<&str> {
+        self.param_str("account_id")
+    }
+
+    /// The decoded `args_base64` of a function call.
+    pub fn function_args(&self) -> Option<Vec<u8>> {
+        STANDARD.decode(self.param_str("args_base64")?).ok()
+    }
+
+    /// The decoded transaction of a `send_tx` call.
+    pub fn signed_transaction(&self) -> Option<SignedTransaction> {
+        SignedTransaction::from_base64(self.param_str("signed_tx_base64")?).ok()
+    }
+
+    /// The `wait_until` of a `send_tx` or `tx_status` call, defaulting like
+    /// nearcore does.
+    pub(super) fn wait_until(&self) -> &str {
+        self.param_str("wait_until")
+            .unwrap_or("EXECUTED_OPTIMISTIC")
+    }
+}
+
+/// A scripted answer to a [`MockRequest`].
+///
+/// Canned views ([`account`](Self::account), [`access_key`](Self::access_key),
+/// [`view_result`](Self::view_result)) report block height 100. Transaction
+/// outcomes ([`tx_success`](Self::tx_success), [`tx_failure`](Self::tx_failure))
+/// are built from the `send_tx` request they answer, so the hash, signer and
+/// actions match what the client sent.
+#[derive(Clone)]
+pub struct MockResponse(Reply);
+
+#[derive(Clone)]
+enum Reply {
+    Result(Value),
+    Error(Value),
+    Transport(Arc<dyn Fn() -> RpcError + Send + Sync>),
+    Http {
+        status: u16,
+        body: Vec<u8>,
+        retry_after: Option<Duration>,
+    },
+    Outcome {
+        status: ReceiptStatus,
+        logs: Vec<String>,
+    },
+}
+
+impl MockResponse {
+    /// A successful JSON-RPC result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `result` cannot be serialized to JSON.
+    pub fn result(result: impl Serialize) -> Self {
+        Self(Reply::Result(
+            serde_json::to_value(result).expect("mock result must serialize to JSON"),
+        ))
+    }
+
+    /// A JSON-RPC error object, passed through as-is.
+    pub fn rpc_error(error: Value) -> Self {
+        Self(Reply::Error(error))
+    }
+
+    /// A nearcore `HANDLER_ERROR` with the given cause, e.g.
+    /// `handler_error("UNKNOWN_ACCOUNT", json!({"requested_account_id": "bob.near"}))`.
+    pub fn handler_error(cause: &str, info: Value) -> Self {
+        Self::rpc_error(wire::handler_error(cause, info, json!(cause)))
+    }
+
+    /// An `INVALID_TRANSACTION` error carrying an `InvalidTxError`, e.g.
+    /// `invalid_tx(json!({"InvalidNonce": {"tx_nonce": 5, "ak_nonce": 9}}))`.
+    pub fn invalid_tx(error: Value) -> Self {
+        Self::rpc_error(wire::invalid_tx_error(error))
+    }
+
+    /// Fail the request at the transport level with the error `error` builds.
+    ///
+    /// This reaches the caller unchanged, so it can inject any
+    /// [`RpcError`] variant — including the retryable ones, which
+    /// [`MockTransport::near`] does not retry.
+    pub fn error(error: impl Fn() -> RpcError + Send + Sync + 'static) -> Self {
+        Self(Reply::Transport(Arc::new(error)))
+    }
+
+    /// A raw HTTP response.
+    pub fn http(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self(Reply::Http {
+            status,
+            body: body.into(),
+            retry_after: None,
+        })
+    }
+
+    /// An HTTP 429 with a `Retry-After` delay.
+    pub fn rate_limited(retry_after: Duration) -> Self {
+        Self(Reply::Http {
+            status: 429,
+            body: b"Too Many Requests".to_vec(),
+            retry_after: Some(retry_after),
+        })
+    }
+
+    /// An `AccountView` with `amount` liquid balance and no contract.
+    pub fn account(amount: NearToken) -> Self {
+        Self(Reply::Result(wire::account_view(
+            amount.as_yoctonear(),
+            0,
+            crate::types::CryptoHash::ZERO,
+            MOCK_BLOCK_HEIGHT,
+        )))
+    }
+
+    /// A full-access `AccessKeyView` at `nonce`.
+    pub fn access_key(nonce: u64) -> Self {
+        Self(Reply::Result(wire::access_key_view(
+            nonce,
+            &AccessKeyPermission::FullAccess,
+            MOCK_BLOCK_HEIGHT,
+        )))
+    }
+
+    /// A view call returning `value` as JSON.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` cannot be serialized to JSON.
+    pub fn view_result(value: impl Serialize) -> Self {
+        Self::view_result_bytes(
+            serde_json::to_vec(&value).expect("mock view result must serialize to JSON"),
+        )
+    }
+
+    /// A view call returning raw bytes (e.g. Borsh).
+    pub fn view_result_bytes(bytes: impl Into<Vec<u8>>) -> Self {
+        Self(Reply::Result(wire::view_result(
+            &bytes.into(),
+            &[],
+            MOCK_BLOCK_HEIGHT,
+        )))
+    }
+
+    /// A successful execution outcome whose receipt returns `value` as JSON.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` cannot be serialized to JSON.
+    pub fn tx_success(value: impl Serialize) -> Self {
+        Self::tx_success_bytes(
+            serde_json::to_vec(&value).expect("mock return value must serialize to JSON"),
+        )
+    }
+
+    /// A successful execution outcome whose receipt returns raw bytes. Use
+    /// an empty value for transactions that return nothing.
+    pub fn tx_success_bytes(bytes: impl Into<Vec<u8>>) -> Self {
+        Self(Reply::Outcome {
+            status: ReceiptStatus::Success(bytes.into()),
+            logs: Vec::new(),
+        })
+    }
+
+    /// A failed execution outcome: the first action failed with the
+    /// `ActionErrorKind` `kind`, e.g.
+    /// `json!({"AccountDoesNotExist": {"account_id": "bob.near"}})`.
+    pub fn tx_failure(kind: Value) -> Self {
+        Self(Reply::Outcome {
+            status: ReceiptStatus::Failure { index: 0, kind },
+            logs: Vec::new(),
+        })
+    }
+
+    /// A failed execution outcome for a contract panic with `message`.
+    pub fn tx_panic(message: &str) -> Self {
+        Self::tx_failure(json!({
+            "FunctionCallError": {
+                "ExecutionError": format!("Smart contract panicked: {message}"),
+            }
+        }))
+    }
+
+    /// Logs emitted by the receipt of a `tx_*` outcome.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is not a `tx_*` response.
+    pub fn with_logs(mut self, logs: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        match &mut self.0 {
+            Reply::Outcome {
+                logs: outcome_logs, ..
+            } => {
+                outcome_logs.extend(logs.into_iter().map(Into::into));
+            }
+            _ => panic!("MockResponse::with_logs only applies to tx_* responses"),
+        }
+        self
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn into_wire(self, request: &MockRequest) -> Result<Wire, RpcError> {
+        let reply = match self.0 {
+            Reply::Result(result) => Ok(result),
+            Reply::Error(error) => Err(error),
+            Reply::Transport(error) => return Err(error()),
+            Reply::Http {
+                status,
+                body,
+                retry_after,
+            } => {
+                return Ok(Wire::Raw(
+                    TransportResponse::new(status, body).with_retry_after(retry_after),
+                ));
+            }
+            Reply::Outcome { status, logs } => {
+                let signed_tx = request.signed_transaction().ok_or_else(|| {
+                    RpcError::InvalidResponse(format!(
+                        "MockTransport: a tx outcome cannot answer `{}`, which carries no signed transaction",
+                        request.method
+                    ))
+                })?;
+                let outcome = wire::final_outcome(&signed_tx, MOCK_BLOCK_HEIGHT, &status, &logs);
+                Ok(wire::tx_response(request.wait_until(), outcome))
+            }
+        };
+        Ok(Wire::Envelope(wire::envelope(&request.id, reply)))
+    }
+}
+
+impl std::fmt::Debug for MockResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Reply::Result(result) => f.debug_tuple("Result").field(result).finish(),
+            Reply::Error(error) => f.debug_tuple("Error").field(error).finish(),
+            Reply::Transport(error) => f.debug_tuple("Transport").field(&error()).finish(),
+            Reply::Http { status, .. } => f.debug_tuple("Http").field(status).finish(),
+            Reply::Outcome { status, .. } => f.debug_tuple("Outcome").field(status).finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::InMemorySigner;
+    use crate::types::{FinalExecutionStatus, SecretKey};
+
+    fn signer() -> InMemorySigner {
+        InMemorySigner::from_secret_key("alice.testnet", SecretKey::generate_ed25519()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_routes_by_method_and_records_requests() {
+        let mock = MockTransport::new();
+        mock.on("EXPERIMENTAL_view_account")
+            .respond(MockResponse::account(NearToken::from_near(5)));
+        mock.on("EXPERIMENTAL_call_function")
+            .respond(MockResponse::view_result(serde_json::json!({ "count": 3 })));
+        let near = mock.near().build();
+
+        let balance = near.balance("alice.testnet").await.unwrap();
+        assert_eq!(balance.total, NearToken::from_near(5));
+        let view: Value = near
+            .view("counter.testnet", "get_count")
+            .args(serde_json::json!({ "id": 1 }))
+            .await
+            .unwrap();
+        assert_eq!(view["count"], 3);
+
+        let calls = mock.requests_for("EXPERIMENTAL_call_function");
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].account_id(), Some("counter.testnet"));
+        assert_eq!(calls[0].param_str("method_name"), Some("get_count"));
+        assert_eq!(calls[0].function_args().unwrap(), br#"{"id":1}"#);
+        assert_eq!(mock.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_matchers_and_call_limits() {
+        let mock = MockTransport::new();
+        mock.on("EXPERIMENTAL_view_account")
+            .matching(|request| request.account_id() == Some("bob.testnet"))
+            .respond(MockResponse::account(NearToken::from_near(2)));
+        mock.on("EXPERIMENTAL_view_account")
+            .once()
+            .respond(MockResponse::account(NearToken::from_near(1)));
+        let near = mock.near().build();
+
+        let bob = near.balance("bob.testnet").await.unwrap();
+        assert_eq!(bob.total, NearToken::from_near(2));
+        let alice = near.balance("alice.testnet").await.unwrap();
+        assert_eq!(alice.total, NearToken::from_near(1));
+        mock.verify();
+
+        // The `once` expectation is used up.
+        let err = near.balance("alice.testnet").await.unwrap_err();
+        assert!(err.to_string().contains("no expectation matches"), "{err}");
+    }
+
+    #[test]
+    #[should_panic(expected = "`status` expected 2 call(s), got 0")]
+    fn test_verify_reports_unmet_expectations() {
+        let mock = MockTransport::new();
+        mock.on("status").times(2).respond(MockResponse::result(()));
+        mock.verify();
+    }
+
+    #[tokio::test]
+    async fn test_injects_rpc_and_transport_errors() {
+        let mock = MockTransport::new();
+        mock.on("EXPERIMENTAL_view_account")
+            .matching(|request| request.account_id() == Some("missing.testnet"))
+            .respond(MockResponse::handler_error(
+                "UNKNOWN_ACCOUNT",
+                serde_json::json!({ "requested_account_id": "missing.testnet" }),
+            ));
+        mock.on("EXPERIMENTAL_view_account")
+            .matching(|request| request.account_id() == Some("slow.testnet"))
+            .respond(MockResponse::error(|| RpcError::Timeout(3)));
+        mock.on("EXPERIMENTAL_view_account")
+            .respond(MockResponse::rate_limited(Duration::from_secs(3)));
+        let rpc = mock.near().build().rpc().clone();
+        let block = crate::types::BlockReference::final_();
+
+        let err = rpc
+            .view_account(&"missing.testnet".parse().unwrap(), block)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RpcError::AccountNotFound { .. }), "{err:?}");
+
+        let err = rpc
+            .view_account(&"slow.testnet".parse().unwrap(), block)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RpcError::Timeout(3)), "{err:?}");
+
+        let err = rpc
+            .view_account(&"alice.testnet".parse().unwrap(), block)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, RpcError::RateLimited { retry_after: Some(d), .. } if d == Duration::from_secs(3)),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tx_outcomes_match_the_sent_transaction() {
+        let mock = MockTransport::new();
+        mock.on("EXPERIMENTAL_view_access_key")
+            .respond(MockResponse::access_key(7));
+        mock.on("send_tx")
+            .once()
+            .respond(MockResponse::tx_success("done").with_logs(["hello"]));
+        mock.on("send_tx")
+            .respond(MockResponse::tx_panic("out of stock"));
+        let near = mock.near().signer(signer()).build();
+
+        let outcome = near.call("shop.testnet", "buy").send().await.unwrap();
+        assert!(matches!(
+            &outcome.status,
+            FinalExecutionStatus::SuccessValue(_)
+        ));
+        assert_eq!(outcome.json::<String>().unwrap(), "done");
+        assert_eq!(outcome.transaction.receiver_id.as_str(), "shop.testnet");
+        assert_eq!(outcome.transaction.nonce, 8);
+        assert_eq!(outcome.receipts_outcome[0].outcome.logs, vec!["hello"]);
+
+        let sent = mock.requests_for("send_tx")[0]
+            .signed_transaction()
+            .unwrap();
+        assert_eq!(&sent.get_hash(), outcome.transaction_hash());
+
+        let outcome = near.call("shop.testnet", "buy").send().await.unwrap();
+        assert!(outcome.is_failure());
+        assert!(outcome.failure_message().unwrap().contains("out of stock"));
+    }
+
+    #[tokio::test]
+    async fn test_batches_answer_each_call() {
+        let mock = MockTransport::new();
+        mock.on("EXPERIMENTAL_view_account")
+            .respond_with(|request| {
+                let near = if request.account_id() == Some("alice.testnet") {
+                    1
+                } else {
+                    2
+                };
+                MockResponse::account(NearToken::from_near(near))
+            });
+        let near = mock.near().build();
+
+        let block = crate::types::BlockReference::final_();
+        let mut batch = near.rpc().batch();
+        let alice = batch.view_account(&"alice.testnet".parse().unwrap(), block);
+        let bob = batch.view_account(&"bob.testnet".parse().unwrap(), block);
+        let mut response = batch.send().await.unwrap();
+
+        assert_eq!(
+            response.take(alice).unwrap().amount,
+            NearToken::from_near(1)
+        );
+        assert_eq!(response.take(bob).unwrap().amount, NearToken::from_near(2));
+        assert_eq!(mock.requests_for("EXPERIMENTAL_view_account").len(), 2);
+    }
+}
@@ -0,0 +1,51 @@
+//! In-process test doubles for the RPC layer.
+//!
+//! [`MockTransport`] is an [`RpcTransport`](crate::RpcTransport) with a
+//! scriptable router: per-method expectations, canned views and execution
+//! outcomes, injected errors, and a log of every request for assertions.
+//! [`FakeChain`] is a small stateful chain behind it that tracks balances,
+//! access-key nonces and block height, so transfers, function calls and
+//! `send_tx` round-trip through the real client without a node or container.
+//!
+//! Both plug into [`Near`](crate::Near) through
+//! [`MockTransport::near`], which returns an ordinary
+//! [`NearBuilder`](crate::NearBuilder):
+//!
+//! ```rust,no_run
+//! # use near_kit::*;
+//! use near_kit::testing::{FakeChain, MockResponse};
+//!
+//! # async fn example() -> Result<(), Error> {
+//! let chain = FakeChain::new();
+//! let alice = chain
+//!     .add_account("alice.testnet", NearToken::from_near(10))
+//!     .signer("alice.testnet");
+//! chain.add_account("bob.testnet", NearToken::ZERO);
+//!
+//! let mock = chain.transport();
+//! // Expectations take precedence over the chain.
+//! mock.on("gas_price")
+//!     .respond(MockResponse::result(serde_json::json!({ "gas_price": "1" })));
+//!
+//! let near = mock.near().signer(alice).build();
+//! near.transfer("bob.testnet", NearToken::from_near(1)).await?;
+//!
+//! assert_eq!(chain.balance("bob.testnet"), Some(NearToken::from_near(1)));
+//! assert_eq!(mock.requests_for("send_tx").len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! For tests against real network traffic, [`RecordingTransport`] captures a
+//! session into a cassette file once, and [`ReplayTransport`] serves it back
+//! on every later run, matching requests by method and params under
+//! configurable [`MatchRules`].
+
+mod cassette;
+mod chain;
+mod mock;
+mod wire;
+
+pub use cassette::{MatchRules, RecordingTransport, ReplayTransport};
+pub use chain::{CallContext, FakeChain};
+pub use mock::{Expectation, MockRequest, MockResponse, MockTransport};
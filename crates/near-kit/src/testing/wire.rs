@@ -0,0 +1,316 @@
+//! JSON shapes of the RPC responses produced by [`MockResponse`](super::MockResponse)
+//! and [`FakeChain`](super::FakeChain).
+//!
+//! Only the fields the client deserializes are filled in; hashes that would
+//! be Merkle roots on a real node are zero.
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use serde_json::{Value, json};
+
+use crate::types::{
+    AccessKeyPermission, Action, CryptoHash, FunctionCallPermission, SignedTransaction,
+};
+
+/// Chain ID reported by `status` and used by [`MockTransport::near`](super::MockTransport::near).
+pub(super) const CHAIN_ID: &str = "mock";
+
+/// Protocol version reported by `block` and `status`.
+const PROTOCOL_VERSION: u32 = 80;
+
+/// Wait levels at which a node answers `send_tx` with the execution outcome.
+const EXECUTED_LEVELS: [&str; 3] = ["EXECUTED_OPTIMISTIC", "EXECUTED", "FINAL"];
+
+/// Deterministic hash of the block at `height`.
+pub(super) fn block_hash(height: u64) -> CryptoHash {
+    CryptoHash::hash(&height.to_le_bytes())
+}
+
+/// Deterministic ID of the single receipt a transaction produces.
+fn receipt_id(tx_hash: &CryptoHash) -> CryptoHash {
+    let mut data = tx_hash.as_bytes().to_vec();
+    data.extend_from_slice(b"receipt");
+    CryptoHash::hash(&data)
+}
+
+/// Wrap a result or error object in a JSON-RPC response envelope.
+pub(super) fn envelope(id: &Value, reply: Result<Value, Value>) -> Value {
+    match reply {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    }
+}
+
+/// A `HANDLER_ERROR` error object with a structured cause.
+pub(super) fn handler_error(cause: &str, info: Value, data: Value) -> Value {
+    json!({
+        "name": "HANDLER_ERROR",
+        "cause": { "name": cause, "info": info },
+        "code": -32000,
+        "message": "Server error",
+        "data": data,
+    })
+}
+
+/// A `REQUEST_VALIDATION_ERROR` for malformed request parameters.
+pub(super) fn parse_error(message: &str) -> Value {
+    json!({
+        "name": "REQUEST_VALIDATION_ERROR",
+        "cause": { "name": "PARSE_ERROR", "info": { "error_message": message } },
+        "code": -32700,
+        "message": "Parse error",
+        "data": message,
+    })
+}
+
+/// An `INVALID_TRANSACTION` error carrying an `InvalidTxError` such as
+/// `{"InvalidNonce": {"tx_nonce": 5, "ak_nonce": 7}}`.
+pub(super) fn invalid_tx_error(error: Value) -> Value {
+    let data = json!({ "TxExecutionError": { "InvalidTxError": error } });
+    handler_error("INVALID_TRANSACTION", data.clone(), data)
+}
+
+/// Block context carried by view errors.
+pub(super) fn block_context(height: u64) -> Value {
+    json!({ "block_height": height, "block_hash": block_hash(height).to_string() })
+}
+
+pub(super) fn account_view(
+    amount: u128,
+    locked: u128,
+    code_hash: CryptoHash,
+    height: u64,
+) -> Value {
+    json!({
+        "amount": amount.to_string(),
+        "locked": locked.to_string(),
+        "code_hash": code_hash.to_string(),
+        "storage_usage": 0,
+        "storage_paid_at": 0,
+        "block_height": height,
+        "block_hash": block_hash(height).to_string(),
+    })
+}
+
+pub(super) fn permission_view(permission: &AccessKeyPermission) -> Value {
+    let function_call = |fc: &FunctionCallPermission| {
+        json!({
+            "allowance": fc.allowance.map(|a| a.as_yoctonear().to_string()),
+            "receiver_id": fc.receiver_id.as_str(),
+            "method_names": fc.method_names,
+        })
+    };
+    match permission {
+        AccessKeyPermission::FullAccess => json!("FullAccess"),
+        AccessKeyPermission::FunctionCall(fc) => json!({ "FunctionCall": function_call(fc) }),
+        AccessKeyPermission::GasKeyFunctionCall(info, fc) => {
+            let mut view = function_call(fc);
+            view["balance"] = json!(info.balance.as_yoctonear().to_string());
+            view["num_nonces"] = json!(info.num_nonces);
+            json!({ "GasKeyFunctionCall": view })
+        }
+        AccessKeyPermission::GasKeyFullAccess(info) => json!({
+            "GasKeyFullAccess": {
+                "balance": info.balance.as_yoctonear().to_string(),
+                "num_nonces": info.num_nonces,
+            }
+        }),
+    }
+}
+
+pub(super) fn access_key_view(nonce: u64, permission: &AccessKeyPermission, height: u64) -> Value {
+    json!({
+        "nonce": nonce,
+        "permission": permission_view(permission),
+        "block_height": height,
+        "block_hash": block_hash(height).to_string(),
+    })
+}
+
+/// Result of `EXPERIMENTAL_call_function`.
+pub(super) fn view_result(result: &[u8], logs: &[String], height: u64) -> Value {
+    json!({
+        "result": result,
+        "logs": logs,
+        "block_height": height,
+        "block_hash": block_hash(height).to_string(),
+    })
+}
+
+/// The `ActionView` of an action, or `None` for actions the mock does not
+/// render.
+fn action_view(action: &Action) -> Option<Value> {
+    let view = match action {
+        Action::CreateAccount(_) => json!("CreateAccount"),
+        Action::DeployContract(deploy) => json!({
+            "DeployContract": { "code": STANDARD.encode(CryptoHash::hash(&deploy.code).as_bytes()) }
+        }),
+        Action::FunctionCall(call) => json!({
+            "FunctionCall": {
+                "method_name": call.method_name,
+                "args": STANDARD.encode(&call.args),
+                "gas": call.gas.as_gas(),
+                "deposit": call.deposit.as_yoctonear().to_string(),
+            }
+        }),
+        Action::Transfer(transfer) => json!({
+            "Transfer": { "deposit": transfer.deposit.as_yoctonear().to_string() }
+        }),
+        Action::Stake(stake) => json!({
+            "Stake": {
+                "stake": stake.stake.as_yoctonear().to_string(),
+                "public_key": stake.public_key.to_string(),
+            }
+        }),
+        Action::AddKey(add) => json!({
+            "AddKey": {
+                "public_key": add.public_key.to_string(),
+                "access_key": {
+                    "nonce": add.access_key.nonce,
+                    "permission": permission_view(&add.access_key.permission),
+                },
+            }
+        }),
+        Action::DeleteKey(delete) => json!({
+            "DeleteKey": { "public_key": delete.public_key.to_string() }
+        }),
+        Action::DeleteAccount(delete) => json!({
+            "DeleteAccount": { "beneficiary_id": delete.beneficiary_id.as_str() }
+        }),
+        _ => return None,
+    };
+    Some(view)
+}
+
+/// How the transaction's receipt finished.
+#[derive(Debug, Clone)]
+pub(super) enum ReceiptStatus {
+    /// Returned these bytes.
+    Success(Vec<u8>),
+    /// Action `index` failed with this `ActionErrorKind`.
+    Failure { index: u64, kind: Value },
+}
+
+/// A `FinalExecutionOutcome` for a transaction executed as a single receipt
+/// in the block at `height`.
+pub(super) fn final_outcome(
+    signed_tx: &SignedTransaction,
+    height: u64,
+    status: &ReceiptStatus,
+    logs: &[String],
+) -> Value {
+    let tx = &signed_tx.transaction;
+    let tx_hash = signed_tx.get_hash();
+    let receipt_id = receipt_id(&tx_hash).to_string();
+    let block_hash = block_hash(height).to_string();
+    let status = match status {
+        ReceiptStatus::Success(value) => json!({ "SuccessValue": STANDARD.encode(value) }),
+        ReceiptStatus::Failure { index, kind } => {
+            json!({ "Failure": { "ActionError": { "index": index, "kind": kind } } })
+        }
+    };
+
+    json!({
+        "status": status,
+        "transaction": {
+            "signer_id": tx.signer_id.as_str(),
+            "public_key": tx.public_key.to_string(),
+            "nonce": tx.nonce,
+            "receiver_id": tx.receiver_id.as_str(),
+            "actions": tx.actions.iter().filter_map(action_view).collect::<Vec<_>>(),
+            "signature": signed_tx.signature.to_string(),
+            "hash": tx_hash.to_string(),
+        },
+        "transaction_outcome": {
+            "id": tx_hash.to_string(),
+            "block_hash": block_hash,
+            "proof": [],
+            "outcome": {
+                "executor_id": tx.signer_id.as_str(),
+                "gas_burnt": 0,
+                "tokens_burnt": "0",
+                "logs": [],
+                "receipt_ids": [receipt_id],
+                "status": { "SuccessReceiptId": receipt_id },
+            },
+        },
+        "receipts_outcome": [{
+            "id": receipt_id,
+            "block_hash": block_hash,
+            "proof": [],
+            "outcome": {
+                "executor_id": tx.receiver_id.as_str(),
+                "gas_burnt": 0,
+                "tokens_burnt": "0",
+                "logs": logs,
+                "receipt_ids": [],
+                "status": status,
+            },
+        }],
+    })
+}
+
+/// The `send_tx`/`EXPERIMENTAL_tx_status` result for an executed
+/// transaction, as seen at `wait_until`.
+///
+/// Like a node, only the executed wait levels carry the outcome.
+pub(super) fn tx_response(wait_until: &str, outcome: Value) -> Value {
+    if !EXECUTED_LEVELS.contains(&wait_until) {
+        return json!({ "final_execution_status": wait_until });
+    }
+    let mut response = outcome;
+    response["final_execution_status"] = json!(wait_until);
+    response
+}
+
+pub(super) fn block_view(height: u64, gas_price: u128) -> Value {
+    let hash = block_hash(height).to_string();
+    let zero = CryptoHash::ZERO.to_string();
+    let timestamp = height.saturating_mul(1_000_000_000);
+    json!({
+        "author": "mock",
+        "chunks": [],
+        "header": {
+            "height": height,
+            "prev_height": height.saturating_sub(1),
+            "hash": hash,
+            "prev_hash": block_hash(height.saturating_sub(1)).to_string(),
+            "prev_state_root": zero,
+            "chunk_receipts_root": zero,
+            "chunk_headers_root": zero,
+            "chunk_tx_root": zero,
+            "outcome_root": zero,
+            "chunks_included": 0,
+            "challenges_root": zero,
+            "timestamp": timestamp,
+            "timestamp_nanosec": timestamp.to_string(),
+            "random_value": zero,
+            "gas_price": gas_price.to_string(),
+            "total_supply": "0",
+            "last_final_block": hash,
+            "last_ds_final_block": hash,
+            "epoch_id": zero,
+            "next_epoch_id": zero,
+            "next_bp_hash": zero,
+            "block_merkle_root": zero,
+            "signature": format!("ed25519:{}", "1".repeat(64)),
+            "latest_protocol_version": PROTOCOL_VERSION,
+        },
+    })
+}
+
+pub(super) fn status_view(height: u64) -> Value {
+    json!({
+        "protocol_version": PROTOCOL_VERSION,
+        "latest_protocol_version": PROTOCOL_VERSION,
+        "chain_id": CHAIN_ID,
+        "genesis_hash": block_hash(0).to_string(),
+        "validators": [],
+        "sync_info": {
+            "latest_block_hash": block_hash(height).to_string(),
+            "latest_block_height": height,
+            "latest_block_time": "1970-01-01T00:00:00.000000000Z",
+            "syncing": false,
+        },
+        "version": { "version": "mock", "build": "mock" },
+    })
+}
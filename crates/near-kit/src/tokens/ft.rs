@@ -1,15 +1,20 @@
 //! Fungible token client (NEP-141).
 
+use std::future::IntoFuture;
+use std::marker::PhantomData;
 use std::sync::Arc;
 
 use crate::trace::{self, Instrument};
 use serde::Serialize;
 use tokio::sync::OnceCell;
 
-use crate::client::{CallBuilder, RpcClient, Signer, TransactionBuilder};
+use crate::client::{CallBuilder, FunctionCall, RpcClient, Signer, TransactionBuilder};
 use crate::error::Error;
+use crate::platform::BoxFuture;
 use crate::types::{
-    AccountId, BlockReference, Finality, Gas, IntoNearToken, NearToken, TryIntoAccountId,
+    AccountId, ActionError, ActionView, BlockReference, ExecutedOptimistic, ExecutionStatus,
+    FinalExecutionOutcome, Finality, FtEvent, Gas, IntoGas, IntoNearToken, NearToken,
+    TryIntoAccountId, VersionedDelegateActionPayloadView, WaitLevel,
 };
 
 use super::types::{FtAmount, FtMetadata, StorageBalance, StorageBalanceBounds};
@@ -295,6 +300,9 @@ impl FungibleToken {
 
     /// Register an account on this token contract (storage_deposit).
     ///
+    /// Always passes `registration_only: true`. For the rest of NEP-145 —
+    /// withdrawals, unregistering — use [`Near::storage()`](crate::Near::storage).
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -353,6 +361,10 @@ impl FungibleToken {
     /// This automatically attaches 1 yoctoNEAR as required by NEP-141 for
     /// security (prevents function-call access key abuse).
     ///
+    /// The transfer fails on-chain if the receiver has no storage balance on
+    /// the contract; [`transfer_registered`](Self::transfer_registered)
+    /// registers it in the same transaction.
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -428,12 +440,107 @@ impl FungibleToken {
             .gas(Gas::from_tgas(30))
     }
 
+    /// Transfer tokens, registering the receiver on the contract first if it
+    /// isn't already (ft_transfer).
+    ///
+    /// Before sending, checks the receiver's storage balance. If it has none,
+    /// a `storage_deposit` of the contract's
+    /// [`storage_balance_bounds`](Self::storage_balance_bounds) minimum, paid
+    /// by the signer, is prepended to the same transaction — so the
+    /// registration is undone if the transfer fails. Awaiting it returns an
+    /// [`FtTransferOutcome`] saying whether that happened.
+    ///
+    /// The check runs when the transfer is awaited, so the returned
+    /// [`FtTransfer`] can only be sent, not signed offline, turned into a
+    /// delegate action or combined with other actions. For those, pair
+    /// [`storage_deposit`](Self::storage_deposit) with
+    /// [`transfer`](Self::transfer) yourself.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example() -> Result<(), near_kit::Error> {
+    /// let near = Near::mainnet()
+    ///     .credentials("ed25519:...", "alice.near")?
+    ///     .build();
+    /// let usdc = near.ft(tokens::USDC)?;
+    ///
+    /// // Carol may never have held USDC
+    /// let sent = usdc.transfer_registered("carol.near", 1_500_000_u128).await?;
+    /// if sent.registered_receiver() {
+    ///     println!("Registered carol on the way");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transfer_registered(
+        &self,
+        receiver_id: impl TryIntoAccountId,
+        amount: impl Into<u128>,
+    ) -> FtTransfer {
+        let receiver_id: AccountId = receiver_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        trace::debug!(contract = %self.contract_id, receiver = %receiver_id, "ft_transfer");
+        #[derive(Serialize)]
+        struct TransferArgs {
+            receiver_id: String,
+            amount: String,
+        }
+
+        let call = FunctionCall::new("ft_transfer")
+            .args(TransferArgs {
+                receiver_id: receiver_id.to_string(),
+                amount: amount.into().to_string(),
+            })
+            .deposit(NearToken::from_yoctonear(1))
+            .gas(Gas::from_tgas(30));
+        FtTransfer::new(self.clone(), receiver_id, call, true)
+    }
+
+    /// Transfer tokens with a memo, registering the receiver first if it
+    /// isn't already (ft_transfer).
+    ///
+    /// Same as [`transfer_registered`](Self::transfer_registered) but with an
+    /// optional memo field.
+    pub fn transfer_registered_with_memo(
+        &self,
+        receiver_id: impl TryIntoAccountId,
+        amount: impl Into<u128>,
+        memo: impl Into<String>,
+    ) -> FtTransfer {
+        let receiver_id: AccountId = receiver_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+
+        #[derive(Serialize)]
+        struct TransferArgs {
+            receiver_id: String,
+            amount: String,
+            memo: String,
+        }
+
+        let call = FunctionCall::new("ft_transfer")
+            .args(TransferArgs {
+                receiver_id: receiver_id.to_string(),
+                amount: amount.into().to_string(),
+                memo: memo.into(),
+            })
+            .deposit(NearToken::from_yoctonear(1))
+            .gas(Gas::from_tgas(30));
+        FtTransfer::new(self.clone(), receiver_id, call, true)
+    }
+
     /// Transfer tokens with a callback to the receiver (ft_transfer_call).
     ///
     /// This calls `ft_on_transfer` on the receiver contract, allowing it to
     /// handle the tokens (e.g., for swaps, deposits, etc.).
     ///
     /// The receiver can return unused tokens, which will be refunded to the sender.
+    /// To find out how much it kept, use
+    /// [`transfer_call_resolved`](Self::transfer_call_resolved), or pass the
+    /// outcome to [`FtTransferCallOutcome::from_outcome`].
     ///
     /// # Example
     ///
@@ -479,6 +586,98 @@ impl FungibleToken {
             .deposit(NearToken::from_yoctonear(1))
             .gas(Gas::from_tgas(100))
     }
+
+    /// Transfer tokens with a callback to the receiver and report how much
+    /// it kept (ft_transfer_call).
+    ///
+    /// Sends the same call as [`transfer_call`](Self::transfer_call).
+    /// Awaiting it returns an [`FtTransferCallOutcome`] with the amount the
+    /// receiver kept, the amount refunded, the receiver's failure if any, and
+    /// the `ft_transfer` events. Amounts are in the token's units, or raw if
+    /// the token has no `ft_metadata`.
+    ///
+    /// Like [`transfer_registered`](Self::transfer_registered), the returned
+    /// [`FtTransferCall`] can only be sent. A `transfer_call` that was signed
+    /// offline or delegated is interpreted with
+    /// [`FtTransferCallOutcome::from_outcome`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example() -> Result<(), near_kit::Error> {
+    /// let near = Near::mainnet()
+    ///     .credentials("ed25519:...", "alice.near")?
+    ///     .build();
+    /// let usdc = near.ft(tokens::USDC)?;
+    ///
+    /// let result = usdc
+    ///     .transfer_call_resolved("defi.near", 1_000_000_u128, r#"{"action":"deposit"}"#)
+    ///     .await?;
+    /// println!("Deposited {}, refunded {}", result.used, result.refunded);
+    /// if let Some(failure) = &result.receiver_failure {
+    ///     println!("defi.near rejected the deposit: {failure}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transfer_call_resolved(
+        &self,
+        receiver_id: impl TryIntoAccountId,
+        amount: impl Into<u128>,
+        msg: impl Into<String>,
+    ) -> FtTransferCall {
+        self.resolved_transfer_call(receiver_id, amount.into(), msg.into(), false)
+    }
+
+    /// Transfer tokens with a callback to the receiver, registering the
+    /// receiver first if it isn't already (ft_transfer_call).
+    ///
+    /// Registers the receiver as [`transfer_registered`](Self::transfer_registered)
+    /// does and reports the result as
+    /// [`transfer_call_resolved`](Self::transfer_call_resolved) does, with
+    /// the deposit paid in [`FtTransferCallOutcome::registration_deposit`].
+    pub fn transfer_call_registered(
+        &self,
+        receiver_id: impl TryIntoAccountId,
+        amount: impl Into<u128>,
+        msg: impl Into<String>,
+    ) -> FtTransferCall {
+        self.resolved_transfer_call(receiver_id, amount.into(), msg.into(), true)
+    }
+
+    fn resolved_transfer_call(
+        &self,
+        receiver_id: impl TryIntoAccountId,
+        amount: u128,
+        msg: String,
+        register_receiver: bool,
+    ) -> FtTransferCall {
+        let receiver_id: AccountId = receiver_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        trace::debug!(contract = %self.contract_id, receiver = %receiver_id, "ft_transfer_call");
+
+        #[derive(Serialize)]
+        struct TransferCallArgs {
+            receiver_id: String,
+            amount: String,
+            msg: String,
+        }
+
+        let call = FunctionCall::new("ft_transfer_call")
+            .args(TransferCallArgs {
+                receiver_id: receiver_id.to_string(),
+                amount: amount.to_string(),
+                msg,
+            })
+            .deposit(NearToken::from_yoctonear(1))
+            .gas(Gas::from_tgas(100));
+        FtTransferCall {
+            transfer: FtTransfer::new(self.clone(), receiver_id, call, register_receiver),
+            amount,
+        }
+    }
 }
 
 impl Clone for FungibleToken {
@@ -502,3 +701,665 @@ impl std::fmt::Debug for FungibleToken {
             .finish()
     }
 }
+
+// =============================================================================
+// FtTransfer
+// =============================================================================
+
+/// Builder for an `ft_transfer` that registers the receiver first if needed,
+/// returned by [`FungibleToken::transfer_registered`] and
+/// [`FungibleToken::transfer_registered_with_memo`].
+///
+/// Awaiting it sends the transfer and returns an [`FtTransferOutcome`].
+#[must_use = "transfers are not sent unless awaited"]
+pub struct FtTransfer<W: WaitLevel = ExecutedOptimistic> {
+    token: FungibleToken,
+    receiver_id: AccountId,
+    call: FunctionCall,
+    register_receiver: bool,
+    _marker: PhantomData<W>,
+}
+
+impl FtTransfer {
+    fn new(
+        token: FungibleToken,
+        receiver_id: AccountId,
+        call: FunctionCall,
+        register_receiver: bool,
+    ) -> Self {
+        Self {
+            token,
+            receiver_id,
+            call,
+            register_receiver,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<W: WaitLevel<Response = FinalExecutionOutcome>> FtTransfer<W> {
+    /// Override the gas attached to the transfer call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gas string cannot be parsed. Use [`Gas`]'s `FromStr` impl
+    /// for fallible parsing of user input.
+    pub fn gas(mut self, gas: impl IntoGas) -> Self {
+        self.call = self.call.gas(gas);
+        self
+    }
+
+    /// Override the signer.
+    pub fn sign_with(mut self, signer: impl Signer + 'static) -> Self {
+        self.token = self.token.with_signer(signer);
+        self
+    }
+
+    /// Override the number of nonce retries on `InvalidNonce` errors.
+    pub fn max_nonce_retries(mut self, retries: u32) -> Self {
+        self.token.max_nonce_retries = retries;
+        self
+    }
+
+    /// Set the execution wait level. Only levels that wait for execution are
+    /// accepted, since the outcome is needed to report the result.
+    pub fn wait_until<W2: WaitLevel<Response = FinalExecutionOutcome>>(self) -> FtTransfer<W2> {
+        FtTransfer {
+            token: self.token,
+            receiver_id: self.receiver_id,
+            call: self.call,
+            register_receiver: self.register_receiver,
+            _marker: PhantomData,
+        }
+    }
+
+    async fn execute(self) -> Result<FtTransferOutcome, Error> {
+        let registration_deposit = if self.register_receiver
+            && !self.token.is_registered(self.receiver_id.clone()).await?
+        {
+            Some(self.token.storage_balance_bounds().await?.min)
+        } else {
+            None
+        };
+
+        let tx = match registration_deposit {
+            Some(deposit) => {
+                trace::debug!(contract = %self.token.contract_id, receiver = %self.receiver_id, %deposit, "registering receiver");
+                self.token
+                    .storage_deposit(self.receiver_id, deposit)
+                    .finish()
+            }
+            None => self.token.transaction(),
+        };
+        let outcome = tx.add_action(self.call).wait_until::<W>().await?;
+
+        Ok(FtTransferOutcome {
+            outcome,
+            registration_deposit,
+        })
+    }
+}
+
+impl<W: WaitLevel<Response = FinalExecutionOutcome>> IntoFuture for FtTransfer<W> {
+    type Output = Result<FtTransferOutcome, Error>;
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.execute())
+    }
+}
+
+impl<W: WaitLevel> std::fmt::Debug for FtTransfer<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FtTransfer")
+            .field("contract_id", &self.token.contract_id)
+            .field("receiver_id", &self.receiver_id)
+            .field("call", &self.call)
+            .field("register_receiver", &self.register_receiver)
+            .finish()
+    }
+}
+
+/// Result of an awaited [`FtTransfer`].
+#[derive(Debug, Clone)]
+pub struct FtTransferOutcome {
+    /// The transaction outcome. As with any call, an on-chain failure is
+    /// reported here rather than as an error.
+    pub outcome: FinalExecutionOutcome,
+    /// The storage deposit paid to register the receiver, if it was
+    /// unregistered.
+    pub registration_deposit: Option<NearToken>,
+}
+
+impl FtTransferOutcome {
+    /// Whether the receiver was registered as part of this transaction.
+    pub fn registered_receiver(&self) -> bool {
+        self.registration_deposit.is_some()
+    }
+}
+
+// =============================================================================
+// FtTransferCall
+// =============================================================================
+
+/// Builder for an `ft_transfer_call`, returned by
+/// [`FungibleToken::transfer_call_resolved`] and
+/// [`FungibleToken::transfer_call_registered`].
+///
+/// Awaiting it sends the transfer and interprets the result as an
+/// [`FtTransferCallOutcome`].
+#[must_use = "transfers are not sent unless awaited"]
+pub struct FtTransferCall<W: WaitLevel = ExecutedOptimistic> {
+    transfer: FtTransfer<W>,
+    amount: u128,
+}
+
+impl<W: WaitLevel<Response = FinalExecutionOutcome>> FtTransferCall<W> {
+    /// Override the gas attached to the transfer call. The receiver's
+    /// `ft_on_transfer` gets what is left after the token contract's share.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gas string cannot be parsed. Use [`Gas`]'s `FromStr` impl
+    /// for fallible parsing of user input.
+    pub fn gas(mut self, gas: impl IntoGas) -> Self {
+        self.transfer = self.transfer.gas(gas);
+        self
+    }
+
+    /// Override the signer.
+    pub fn sign_with(mut self, signer: impl Signer + 'static) -> Self {
+        self.transfer = self.transfer.sign_with(signer);
+        self
+    }
+
+    /// Override the number of nonce retries on `InvalidNonce` errors.
+    pub fn max_nonce_retries(mut self, retries: u32) -> Self {
+        self.transfer = self.transfer.max_nonce_retries(retries);
+        self
+    }
+
+    /// Set the execution wait level. Only levels that wait for execution are
+    /// accepted, since the outcome is needed to report the result.
+    pub fn wait_until<W2: WaitLevel<Response = FinalExecutionOutcome>>(self) -> FtTransferCall<W2> {
+        FtTransferCall {
+            transfer: self.transfer.wait_until::<W2>(),
+            amount: self.amount,
+        }
+    }
+
+    async fn execute(self) -> Result<FtTransferCallOutcome, Error> {
+        let token = self.transfer.token.clone();
+        let FtTransferOutcome {
+            outcome,
+            registration_deposit,
+        } = self.transfer.execute().await?;
+
+        // Fetched only after sending: a token without NEP-148 metadata can
+        // still be transferred, and its amounts are reported raw.
+        let metadata = match token.metadata().await {
+            Ok(metadata) => metadata.clone(),
+            Err(_e) => {
+                trace::debug!(contract = %token.contract_id, error = %_e, "no ft_metadata, reporting raw amounts");
+                FtMetadata {
+                    spec: String::new(),
+                    name: String::new(),
+                    symbol: String::new(),
+                    decimals: 0,
+                    icon: None,
+                    reference: None,
+                    reference_hash: None,
+                }
+            }
+        };
+
+        let mut result = FtTransferCallOutcome::from_outcome(&outcome, self.amount, &metadata)?;
+        result.registration_deposit = registration_deposit;
+        Ok(result)
+    }
+}
+
+impl<W: WaitLevel<Response = FinalExecutionOutcome>> IntoFuture for FtTransferCall<W> {
+    type Output = Result<FtTransferCallOutcome, Error>;
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.execute())
+    }
+}
+
+impl<W: WaitLevel> std::fmt::Debug for FtTransferCall<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FtTransferCall")
+            .field("transfer", &self.transfer)
+            .field("amount", &self.amount)
+            .finish()
+    }
+}
+
+/// The result of an `ft_transfer_call`: what the receiver kept and what came
+/// back.
+///
+/// Returned by an awaited [`FtTransferCall`], or built from the outcome of a
+/// [`transfer_call`](FungibleToken::transfer_call) sent any other way with
+/// [`from_outcome`](Self::from_outcome).
+///
+/// If the transaction itself fails — e.g. the sender's balance is too low —
+/// an error is returned instead, since nothing was transferred.
+#[derive(Debug, Clone)]
+pub struct FtTransferCallOutcome {
+    /// Amount the receiver kept.
+    pub used: FtAmount,
+    /// Amount returned to the sender: whatever `ft_on_transfer` reported as
+    /// unused, or everything if it failed.
+    pub refunded: FtAmount,
+    /// Why the receiver's `ft_on_transfer` (or a call it made) failed, if it
+    /// did. The token contract refunds the whole amount in that case.
+    pub receiver_failure: Option<ActionError>,
+    /// `ft_transfer` events the token contract emitted, including the
+    /// transfer back to the sender for any refund.
+    pub transfers: Vec<FtTransferRecord>,
+    /// The storage deposit paid to register the receiver, if
+    /// [`transfer_call_registered`](FungibleToken::transfer_call_registered)
+    /// found it unregistered. Always `None` from
+    /// [`from_outcome`](Self::from_outcome).
+    pub registration_deposit: Option<NearToken>,
+    /// The transaction outcome.
+    pub outcome: FinalExecutionOutcome,
+}
+
+impl FtTransferCallOutcome {
+    /// Interpret the outcome of an `ft_transfer_call` of `amount` raw units,
+    /// e.g. one signed offline or sent as a delegate action.
+    ///
+    /// `outcome` must be the executed outcome of the transaction (or the
+    /// meta-transaction) carrying the call, and `metadata` the token's
+    /// [`metadata`](FungibleToken::metadata).
+    ///
+    /// # Errors
+    ///
+    /// Fails if the transaction failed, or did not end in an
+    /// `ft_transfer_call` return value.
+    pub fn from_outcome(
+        outcome: &FinalExecutionOutcome,
+        amount: u128,
+        metadata: &FtMetadata,
+    ) -> Result<Self, Error> {
+        // A relayed meta-transaction goes to the sender first; the token
+        // contract is the receiver of its delegate action.
+        let contract_id = outcome
+            .transaction
+            .actions
+            .iter()
+            .find_map(|action| match action {
+                ActionView::Delegate {
+                    delegate_action, ..
+                } => Some(&delegate_action.receiver_id),
+                ActionView::DelegateV2 {
+                    delegate_action: VersionedDelegateActionPayloadView::V2(delegate_action),
+                    ..
+                } => Some(&delegate_action.receiver_id),
+                _ => None,
+            })
+            .unwrap_or(&outcome.transaction.receiver_id);
+
+        // `ft_transfer_call` resolves to `ft_resolve_transfer`'s return value:
+        // the amount the receiver kept.
+        let used: String = outcome.json()?;
+        let used: u128 = used.parse().map_err(|_| {
+            Error::Rpc(Box::new(crate::error::RpcError::InvalidResponse(format!(
+                "Invalid ft_transfer_call result: {}",
+                used
+            ))))
+        })?;
+
+        // Everything the token contract doesn't run itself is the receiver's
+        // side: `ft_on_transfer` and whatever it calls.
+        let receiver_failure = outcome
+            .receipts_outcome
+            .iter()
+            .filter(|receipt| receipt.outcome.executor_id != *contract_id)
+            .find_map(|receipt| match &receipt.outcome.status {
+                ExecutionStatus::Failure(error) => Some(error.clone()),
+                _ => None,
+            });
+
+        let transfers = outcome
+            .events()
+            .filter(|event| event.executor_id == *contract_id)
+            .filter_map(|event| match event.ft_event() {
+                Some(FtEvent::Transfer(transfers)) => Some(transfers),
+                _ => None,
+            })
+            .flatten()
+            .map(|transfer| FtTransferRecord {
+                old_owner_id: transfer.old_owner_id,
+                new_owner_id: transfer.new_owner_id,
+                amount: FtAmount::from_metadata(transfer.amount, metadata),
+                memo: transfer.memo,
+            })
+            .collect();
+
+        Ok(Self {
+            used: FtAmount::from_metadata(used, metadata),
+            refunded: FtAmount::from_metadata(amount.saturating_sub(used), metadata),
+            receiver_failure,
+            transfers,
+            registration_deposit: None,
+            outcome: outcome.clone(),
+        })
+    }
+}
+
+/// One `ft_transfer` event with its amount in the token's units.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FtTransferRecord {
+    /// Sender.
+    pub old_owner_id: AccountId,
+    /// Receiver.
+    pub new_owner_id: AccountId,
+    /// Amount transferred.
+    pub amount: FtAmount,
+    /// Optional memo.
+    pub memo: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    use base64::{Engine as _, engine::general_purpose::STANDARD};
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::testing::{FakeChain, MockRequest, MockResponse};
+    use crate::types::{Action, CryptoHash};
+
+    /// A token contract that only lets registered accounts receive tokens.
+    fn chain_with_token() -> (FakeChain, crate::Near) {
+        let chain = FakeChain::new();
+        chain.add_account("token.near", NearToken::from_near(10));
+        chain.add_account("bob.near", NearToken::from_near(1));
+        let alice = chain
+            .add_account("alice.near", NearToken::from_near(10))
+            .signer("alice.near");
+
+        let registered = Arc::new(Mutex::new(HashSet::from(["alice.near".to_string()])));
+        let is_registered = |registered: &Mutex<HashSet<String>>, account: &str| {
+            registered.lock().unwrap().contains(account)
+        };
+        let views = registered.clone();
+        chain.on_call("token.near", "storage_balance_of", move |ctx| {
+            let args: Value = ctx.args_json()?;
+            let balance = is_registered(&views, args["account_id"].as_str().unwrap())
+                .then(|| json!({ "total": "1250000000000000000000", "available": "0" }));
+            Ok(serde_json::to_vec(&balance).unwrap())
+        });
+        chain.on_call("token.near", "storage_balance_bounds", |_ctx| {
+            Ok(br#"{"min":"1250000000000000000000","max":"1250000000000000000000"}"#.to_vec())
+        });
+        let deposits = registered.clone();
+        chain.on_call("token.near", "storage_deposit", move |ctx| {
+            let args: Value = ctx.args_json()?;
+            assert_eq!(args["registration_only"], json!(true));
+            deposits
+                .lock()
+                .unwrap()
+                .insert(args["account_id"].as_str().unwrap().to_string());
+            Ok(b"{}".to_vec())
+        });
+        chain.on_call("token.near", "ft_metadata", |_ctx| {
+            Ok(br#"{"spec":"ft-1.0.0","name":"Token","symbol":"TKN","decimals":2}"#.to_vec())
+        });
+        // Stands in for the whole ft_transfer_call → ft_on_transfer →
+        // ft_resolve_transfer chain: the receiver keeps 60 and returns 40.
+        chain.on_call("token.near", "ft_transfer_call", |ctx| {
+            ctx.log(r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"alice.near","new_owner_id":"bob.near","amount":"100"}]}"#);
+            ctx.log(r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"bob.near","new_owner_id":"alice.near","amount":"40","memo":"refund"}]}"#);
+            Ok(br#""60""#.to_vec())
+        });
+        chain.on_call("token.near", "ft_transfer", move |ctx| {
+            let args: Value = ctx.args_json()?;
+            if !is_registered(&registered, args["receiver_id"].as_str().unwrap()) {
+                return Err("The account bob.near is not registered".to_string());
+            }
+            Ok(Vec::new())
+        });
+
+        let near = chain.transport().near().signer(alice).build();
+        (chain, near)
+    }
+
+    #[tokio::test]
+    async fn test_transfer_fails_for_unregistered_receiver() {
+        let (_chain, near) = chain_with_token();
+        let ft = near.ft("token.near").unwrap();
+
+        let outcome = ft.transfer("bob.near", 100_u128).await.unwrap();
+        assert!(outcome.is_failure());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_registered_registers_once() {
+        let (_chain, near) = chain_with_token();
+        let ft = near.ft("token.near").unwrap();
+
+        let sent = ft.transfer_registered("bob.near", 100_u128).await.unwrap();
+        assert!(sent.outcome.is_success());
+        assert_eq!(
+            sent.registration_deposit,
+            Some(NearToken::from_yoctonear(1_250_000_000_000_000_000_000))
+        );
+
+        let sent = ft
+            .transfer_registered_with_memo("bob.near", 100_u128, "again")
+            .await
+            .unwrap();
+        assert!(sent.outcome.is_success());
+        assert!(!sent.registered_receiver());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_call_reports_used_and_refunded() {
+        let (_chain, near) = chain_with_token();
+        let ft = near.ft("token.near").unwrap();
+
+        let result = ft
+            .transfer_call_resolved("bob.near", 100_u128, "swap")
+            .await
+            .unwrap();
+        assert_eq!(result.used.raw(), 60);
+        assert_eq!(result.refunded.raw(), 40);
+        assert_eq!(result.refunded.to_string(), "0.4 TKN");
+        assert!(result.receiver_failure.is_none());
+        assert!(result.registration_deposit.is_none());
+
+        assert_eq!(result.transfers.len(), 2);
+        assert_eq!(result.transfers[1].old_owner_id.as_str(), "bob.near");
+        assert_eq!(result.transfers[1].amount.raw(), 40);
+        assert_eq!(result.transfers[1].memo.as_deref(), Some("refund"));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_call_registered_prepends_storage_deposit() {
+        let (chain, _near) = chain_with_token();
+        let mock = chain.transport();
+        let near = mock.near().signer(chain.signer("alice.near")).build();
+        let ft = near.ft("token.near").unwrap();
+
+        let result = ft
+            .transfer_call_registered("bob.near", 100_u128, "swap")
+            .await
+            .unwrap();
+        assert!(result.outcome.is_success());
+        assert_eq!(result.used.raw(), 60);
+        assert_eq!(
+            result.registration_deposit,
+            Some(NearToken::from_yoctonear(1_250_000_000_000_000_000_000))
+        );
+
+        let tx = mock.requests_for("send_tx")[0]
+            .signed_transaction()
+            .unwrap();
+        let calls: Vec<_> = tx
+            .transaction
+            .actions
+            .iter()
+            .map(|action| match action {
+                Action::FunctionCall(call) => (call.method_name.as_str(), call.deposit),
+                other => panic!("unexpected action {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            calls,
+            [
+                (
+                    "storage_deposit",
+                    NearToken::from_yoctonear(1_250_000_000_000_000_000_000)
+                ),
+                ("ft_transfer_call", NearToken::from_yoctonear(1)),
+            ]
+        );
+
+        // Registered now, so the second call sends the transfer alone.
+        let result = ft
+            .transfer_call_registered("bob.near", 100_u128, "swap")
+            .await
+            .unwrap();
+        assert!(result.registration_deposit.is_none());
+        let tx = mock.requests_for("send_tx")[1]
+            .signed_transaction()
+            .unwrap();
+        assert_eq!(tx.transaction.actions.len(), 1);
+    }
+
+    /// The outcome of an `ft_transfer_call` whose `ft_on_transfer` panicked,
+    /// so `ft_resolve_transfer` refunded all 100 tokens. `FakeChain` runs a
+    /// transaction as a single receipt, so the cross-contract receipts are
+    /// spelled out here.
+    fn refunded_transfer_call(request: &MockRequest) -> MockResponse {
+        let tx = request.signed_transaction().unwrap();
+        let hash = tx.get_hash().to_string();
+        let ids: Vec<String> = (0..3u8)
+            .map(|i| CryptoHash::hash(&[i]).to_string())
+            .collect();
+        let receipt = |id: &str, executor: &str, logs: Value, status: Value| {
+            json!({
+                "id": id,
+                "block_hash": hash,
+                "proof": [],
+                "outcome": {
+                    "executor_id": executor,
+                    "gas_burnt": 0,
+                    "tokens_burnt": "0",
+                    "logs": logs,
+                    "receipt_ids": [],
+                    "status": status,
+                },
+            })
+        };
+        let nothing_used = STANDARD.encode(br#""0""#);
+
+        MockResponse::result(json!({
+            "final_execution_status": "EXECUTED_OPTIMISTIC",
+            "status": { "SuccessValue": nothing_used },
+            "transaction": {
+                "signer_id": tx.transaction.signer_id.as_str(),
+                "public_key": tx.transaction.public_key.to_string(),
+                "nonce": tx.transaction.nonce,
+                "receiver_id": tx.transaction.receiver_id.as_str(),
+                "actions": [],
+                "signature": tx.signature.to_string(),
+                "hash": hash,
+            },
+            "transaction_outcome": receipt(
+                &hash,
+                "alice.near",
+                json!([]),
+                json!({ "SuccessReceiptId": ids[0] }),
+            ),
+            "receipts_outcome": [
+                receipt(
+                    &ids[0],
+                    "token.near",
+                    json!([r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"alice.near","new_owner_id":"bob.near","amount":"100"}]}"#]),
+                    json!({ "SuccessReceiptId": ids[2] }),
+                ),
+                receipt(
+                    &ids[1],
+                    "bob.near",
+                    json!([]),
+                    json!({ "Failure": { "ActionError": { "index": 0, "kind": {
+                        "FunctionCallError": { "ExecutionError": "Smart contract panicked: no pool" }
+                    } } } }),
+                ),
+                receipt(
+                    &ids[2],
+                    "token.near",
+                    json!([r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"bob.near","new_owner_id":"alice.near","amount":"100","memo":"refund"}]}"#]),
+                    json!({ "SuccessValue": nothing_used }),
+                ),
+            ],
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_transfer_call_reports_receiver_failure_and_full_refund() {
+        let (chain, _near) = chain_with_token();
+        let mock = chain.transport();
+        mock.on("send_tx").respond_with(refunded_transfer_call);
+        let near = mock.near().signer(chain.signer("alice.near")).build();
+        let ft = near.ft("token.near").unwrap();
+
+        let result = ft
+            .transfer_call_resolved("bob.near", 100_u128, "swap")
+            .await
+            .unwrap();
+        assert!(result.outcome.is_success());
+        assert_eq!(result.used.raw(), 0);
+        assert_eq!(result.refunded.raw(), 100);
+        assert_eq!(result.refunded.to_string(), "1 TKN");
+        let failure = result.receiver_failure.expect("ft_on_transfer failed");
+        assert!(failure.to_string().contains("no pool"), "{failure}");
+        assert_eq!(result.transfers.len(), 2);
+        assert_eq!(result.transfers[1].amount.raw(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_from_outcome_interprets_a_plain_transfer_call() {
+        let (_chain, near) = chain_with_token();
+        let ft = near.ft("token.near").unwrap();
+        let metadata = ft.metadata().await.unwrap();
+
+        let outcome = ft
+            .transfer_call("bob.near", 100_u128, "swap")
+            .await
+            .unwrap();
+        let result = FtTransferCallOutcome::from_outcome(&outcome, 100, metadata).unwrap();
+        assert_eq!(result.used.to_string(), "0.6 TKN");
+        assert_eq!(result.refunded.raw(), 40);
+        assert!(result.receiver_failure.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_call_without_metadata_reports_raw_amounts() {
+        let chain = FakeChain::new();
+        chain.add_account("token.near", NearToken::from_near(10));
+        let alice = chain
+            .add_account("alice.near", NearToken::from_near(10))
+            .signer("alice.near");
+        chain.on_call("token.near", "ft_transfer_call", |_ctx| {
+            Ok(br#""60""#.to_vec())
+        });
+        let near = chain.transport().near().signer(alice).build();
+        let ft = near.ft("token.near").unwrap();
+
+        let result = ft
+            .transfer_call_resolved("bob.near", 100_u128, "swap")
+            .await
+            .unwrap();
+        assert_eq!(result.used.raw(), 60);
+        assert_eq!(result.refunded.raw(), 40);
+    }
+}
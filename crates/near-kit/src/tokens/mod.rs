@@ -1,4 +1,5 @@
-//! Token helpers for NEP-141 (Fungible Tokens) and NEP-171 (Non-Fungible Tokens).
+//! Token helpers for NEP-141 (Fungible Tokens), NEP-171 (Non-Fungible Tokens) and
+//! NEP-245 (Multi Tokens).
 //!
 //! This module provides ergonomic APIs for interacting with standard NEAR token contracts.
 //!
@@ -110,13 +111,67 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! # Multi Tokens (NEP-245)
+//!
+//! ```rust,no_run
+//! use near_kit::*;
+//!
+//! # async fn example() -> Result<(), near_kit::Error> {
+//! let near = Near::mainnet().build();
+//!
+//! // Get a multi token client
+//! let mt = near.mt("intents.near")?;
+//!
+//! // Query balances - raw amounts, one per token ID
+//! let balances = mt
+//!     .batch_balance_of("alice.near", ["nep141:usdc.near", "nep141:wrap.near"])
+//!     .await?;
+//!
+//! // Transfer tokens (requires signer)
+//! let near = Near::mainnet()
+//!     .credentials("ed25519:...", "alice.near")?
+//!     .build();
+//! let mt = near.mt("intents.near")?;
+//!
+//! mt.transfer("bob.near", "nep141:usdc.near", 1_500_000_u128).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Storage Management (NEP-145)
+//!
+//! Contracts that charge for per-account storage — tokens, marketplaces,
+//! DEXes — share the NEP-145 `storage_*` methods:
+//!
+//! ```rust,no_run
+//! use near_kit::*;
+//!
+//! # async fn example() -> Result<(), near_kit::Error> {
+//! let near = Near::mainnet()
+//!     .credentials("ed25519:...", "alice.near")?
+//!     .build();
+//! let storage = near.storage("v2.ref-finance.near")?;
+//!
+//! // Deposit the minimum only if alice isn't registered yet
+//! storage.ensure_registered("alice.near").await?;
+//!
+//! // Withdraw whatever isn't needed to cover storage
+//! storage.storage_withdraw_all().await?;
+//! # Ok(())
+//! # }
+//! ```
 
 mod ft;
 mod known;
+mod mt;
 mod nft;
+mod storage;
 mod types;
 
 pub use ft::*;
 pub use known::{IntoContractId, KnownToken, USDC, USDT, W_NEAR};
+pub use mt::*;
 pub use nft::*;
+pub use storage::*;
 pub use types::*;
@@ -0,0 +1,629 @@
+//! Multi token client (NEP-245).
+
+use std::sync::Arc;
+
+use crate::trace::{self, Instrument};
+use serde::Serialize;
+
+use crate::client::{CallBuilder, RpcClient, Signer, TransactionBuilder};
+use crate::error::Error;
+use crate::types::{AccountId, BlockReference, Finality, Gas, NearToken, TryIntoAccountId};
+
+use super::types::MtToken;
+
+// =============================================================================
+// MultiToken
+// =============================================================================
+
+/// Client for interacting with a NEP-245 Multi Token contract.
+///
+/// Create via [`Near::mt()`](crate::Near::mt).
+///
+/// A multi token contract holds many tokens at once, each identified by a
+/// token ID string and each with its own balances. Amounts are raw `u128`
+/// values — the standard has no per-token decimals.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use near_kit::*;
+///
+/// # async fn example() -> Result<(), near_kit::Error> {
+/// let near = Near::mainnet().build();
+/// let mt = near.mt("intents.near")?;
+///
+/// // Balance of a single token
+/// let usdc = mt.balance_of("alice.near", "nep141:usdc.near").await?;
+///
+/// // Balances of several tokens in one call
+/// let balances = mt
+///     .batch_balance_of("alice.near", ["nep141:usdc.near", "nep141:wrap.near"])
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct MultiToken {
+    rpc: Arc<RpcClient>,
+    signer: Option<Arc<dyn Signer>>,
+    contract_id: AccountId,
+    max_nonce_retries: u32,
+}
+
+impl MultiToken {
+    /// Create a new MultiToken client.
+    pub(crate) fn new(
+        rpc: Arc<RpcClient>,
+        signer: Option<Arc<dyn Signer>>,
+        contract_id: AccountId,
+        max_nonce_retries: u32,
+    ) -> Self {
+        Self {
+            rpc,
+            signer,
+            contract_id,
+            max_nonce_retries,
+        }
+    }
+
+    /// Get the contract ID.
+    pub fn contract_id(&self) -> &AccountId {
+        &self.contract_id
+    }
+
+    /// Create a new client with a different signer, sharing the same RPC connection.
+    pub fn with_signer(&self, signer: impl Signer + 'static) -> Self {
+        Self {
+            rpc: self.rpc.clone(),
+            signer: Some(Arc::new(signer)),
+            contract_id: self.contract_id.clone(),
+            max_nonce_retries: self.max_nonce_retries,
+        }
+    }
+
+    /// Create a transaction builder for this contract.
+    fn transaction(&self) -> TransactionBuilder {
+        TransactionBuilder::new(
+            self.rpc.clone(),
+            self.signer.clone(),
+            self.contract_id.clone(),
+            self.max_nonce_retries,
+        )
+    }
+
+    // =========================================================================
+    // View Methods
+    // =========================================================================
+
+    /// Get the raw balance of one token for an account (mt_balance_of).
+    pub async fn balance_of(
+        &self,
+        account_id: impl TryIntoAccountId,
+        token_id: impl AsRef<str>,
+    ) -> Result<u128, Error> {
+        let account_id: AccountId = account_id.try_into_account_id()?;
+        let token_id = token_id.as_ref();
+        let span = trace::debug_span!("mt_balance_of", contract = %self.contract_id, %account_id, token_id);
+
+        async {
+            #[derive(Serialize)]
+            struct Args<'a> {
+                account_id: &'a str,
+                token_id: &'a str,
+            }
+
+            let args = serde_json::to_vec(&Args {
+                account_id: account_id.as_str(),
+                token_id,
+            })?;
+
+            let result = self
+                .rpc
+                .view_function(
+                    &self.contract_id,
+                    "mt_balance_of",
+                    &args,
+                    BlockReference::Finality(Finality::Optimistic),
+                )
+                .await?;
+
+            let balance: String = result.json()?;
+            parse_amount(&balance)
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Get the raw balances of several tokens for an account in one call
+    /// (mt_batch_balance_of).
+    ///
+    /// The result is in the same order as `token_ids`.
+    pub async fn batch_balance_of(
+        &self,
+        account_id: impl TryIntoAccountId,
+        token_ids: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<Vec<u128>, Error> {
+        let account_id: AccountId = account_id.try_into_account_id()?;
+        let token_ids: Vec<String> = token_ids.into_iter().map(Into::into).collect();
+        let span = trace::debug_span!(
+            "mt_batch_balance_of",
+            contract = %self.contract_id,
+            %account_id,
+            tokens = token_ids.len()
+        );
+
+        async {
+            #[derive(Serialize)]
+            struct Args<'a> {
+                account_id: &'a str,
+                token_ids: &'a [String],
+            }
+
+            let args = serde_json::to_vec(&Args {
+                account_id: account_id.as_str(),
+                token_ids: &token_ids,
+            })?;
+
+            let result = self
+                .rpc
+                .view_function(
+                    &self.contract_id,
+                    "mt_batch_balance_of",
+                    &args,
+                    BlockReference::Finality(Finality::Optimistic),
+                )
+                .await?;
+
+            let balances: Vec<String> = result.json()?;
+            if balances.len() != token_ids.len() {
+                return Err(Error::Rpc(Box::new(
+                    crate::error::RpcError::InvalidResponse(format!(
+                        "mt_batch_balance_of returned {} balances for {} tokens",
+                        balances.len(),
+                        token_ids.len()
+                    )),
+                )));
+            }
+            balances.iter().map(|b| parse_amount(b)).collect()
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Get the total supply of a token (mt_supply).
+    ///
+    /// Returns `None` if the token doesn't exist.
+    pub async fn supply(&self, token_id: impl AsRef<str>) -> Result<Option<u128>, Error> {
+        #[derive(Serialize)]
+        struct Args<'a> {
+            token_id: &'a str,
+        }
+
+        let args = serde_json::to_vec(&Args {
+            token_id: token_id.as_ref(),
+        })?;
+
+        let result = self
+            .rpc
+            .view_function(
+                &self.contract_id,
+                "mt_supply",
+                &args,
+                BlockReference::Finality(Finality::Optimistic),
+            )
+            .await?;
+
+        let supply: Option<String> = result.json()?;
+        supply.as_deref().map(parse_amount).transpose()
+    }
+
+    /// Get a specific token by ID (mt_token).
+    ///
+    /// Returns `None` if the token doesn't exist.
+    pub async fn token(&self, token_id: impl Into<String>) -> Result<Option<MtToken>, Error> {
+        Ok(self.tokens([token_id]).await?.pop().flatten())
+    }
+
+    /// Get several tokens by ID in one call (mt_token).
+    ///
+    /// The result is in the same order as `token_ids`, with `None` for tokens
+    /// that don't exist.
+    pub async fn tokens(
+        &self,
+        token_ids: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<Vec<Option<MtToken>>, Error> {
+        let token_ids: Vec<String> = token_ids.into_iter().map(Into::into).collect();
+
+        #[derive(Serialize)]
+        struct Args<'a> {
+            token_ids: &'a [String],
+        }
+
+        let args = serde_json::to_vec(&Args {
+            token_ids: &token_ids,
+        })?;
+
+        let result = self
+            .rpc
+            .view_function(
+                &self.contract_id,
+                "mt_token",
+                &args,
+                BlockReference::Finality(Finality::Optimistic),
+            )
+            .await?;
+
+        result.json().map_err(Error::from)
+    }
+
+    // =========================================================================
+    // Transfer Methods
+    // =========================================================================
+
+    /// Transfer an amount of one token to a receiver (mt_transfer).
+    ///
+    /// # Security
+    ///
+    /// This automatically attaches 1 yoctoNEAR as required by NEP-245 for
+    /// security (prevents function-call access key abuse).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example() -> Result<(), near_kit::Error> {
+    /// let near = Near::mainnet()
+    ///     .credentials("ed25519:...", "alice.near")?
+    ///     .build();
+    /// let mt = near.mt("intents.near")?;
+    ///
+    /// mt.transfer("bob.near", "nep141:usdc.near", 1_500_000_u128).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transfer(
+        &self,
+        receiver_id: impl TryIntoAccountId,
+        token_id: impl AsRef<str>,
+        amount: impl Into<u128>,
+    ) -> CallBuilder {
+        let receiver_id: AccountId = receiver_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        let amount = amount.into();
+        trace::debug!(contract = %self.contract_id, token_id = token_id.as_ref(), receiver = %receiver_id, amount = %amount, "mt_transfer");
+        #[derive(Serialize)]
+        struct TransferArgs {
+            receiver_id: String,
+            token_id: String,
+            amount: String,
+        }
+
+        self.transaction()
+            .call("mt_transfer")
+            .args(TransferArgs {
+                receiver_id: receiver_id.to_string(),
+                token_id: token_id.as_ref().to_string(),
+                amount: amount.to_string(),
+            })
+            .deposit(NearToken::from_yoctonear(1))
+            .gas(Gas::from_tgas(30))
+    }
+
+    /// Transfer amounts of several tokens to a receiver in one call
+    /// (mt_batch_transfer).
+    ///
+    /// `transfers` is a list of `(token_id, amount)` pairs. Attaches
+    /// 1 yoctoNEAR and 50 Tgas; raise the gas with
+    /// [`CallBuilder::gas`] for very large batches.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example() -> Result<(), near_kit::Error> {
+    /// let near = Near::mainnet()
+    ///     .credentials("ed25519:...", "alice.near")?
+    ///     .build();
+    /// let mt = near.mt("intents.near")?;
+    ///
+    /// mt.batch_transfer(
+    ///     "bob.near",
+    ///     [("nep141:usdc.near", 1_500_000_u128), ("nep141:wrap.near", 10_u128.pow(24))],
+    /// )
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn batch_transfer(
+        &self,
+        receiver_id: impl TryIntoAccountId,
+        transfers: impl IntoIterator<Item = (impl Into<String>, impl Into<u128>)>,
+    ) -> CallBuilder {
+        let receiver_id: AccountId = receiver_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        let (token_ids, amounts) = split_transfers(transfers);
+        trace::debug!(contract = %self.contract_id, tokens = token_ids.len(), receiver = %receiver_id, "mt_batch_transfer");
+        #[derive(Serialize)]
+        struct BatchTransferArgs {
+            receiver_id: String,
+            token_ids: Vec<String>,
+            amounts: Vec<String>,
+        }
+
+        self.transaction()
+            .call("mt_batch_transfer")
+            .args(BatchTransferArgs {
+                receiver_id: receiver_id.to_string(),
+                token_ids,
+                amounts,
+            })
+            .deposit(NearToken::from_yoctonear(1))
+            .gas(Gas::from_tgas(50))
+    }
+
+    /// Transfer an amount of one token with a callback to the receiver
+    /// (mt_transfer_call).
+    ///
+    /// This calls `mt_on_transfer` on the receiver contract.
+    pub fn transfer_call(
+        &self,
+        receiver_id: impl TryIntoAccountId,
+        token_id: impl AsRef<str>,
+        amount: impl Into<u128>,
+        msg: impl Into<String>,
+    ) -> CallBuilder {
+        let receiver_id: AccountId = receiver_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        let amount = amount.into();
+        trace::debug!(contract = %self.contract_id, token_id = token_id.as_ref(), receiver = %receiver_id, amount = %amount, "mt_transfer_call");
+        #[derive(Serialize)]
+        struct TransferCallArgs {
+            receiver_id: String,
+            token_id: String,
+            amount: String,
+            msg: String,
+        }
+
+        self.transaction()
+            .call("mt_transfer_call")
+            .args(TransferCallArgs {
+                receiver_id: receiver_id.to_string(),
+                token_id: token_id.as_ref().to_string(),
+                amount: amount.to_string(),
+                msg: msg.into(),
+            })
+            .deposit(NearToken::from_yoctonear(1))
+            .gas(Gas::from_tgas(100))
+    }
+
+    /// Transfer amounts of several tokens with a callback to the receiver
+    /// (mt_batch_transfer_call).
+    ///
+    /// This calls `mt_on_transfer` on the receiver contract once for the
+    /// whole batch. Attaches 1 yoctoNEAR and 150 Tgas.
+    pub fn batch_transfer_call(
+        &self,
+        receiver_id: impl TryIntoAccountId,
+        transfers: impl IntoIterator<Item = (impl Into<String>, impl Into<u128>)>,
+        msg: impl Into<String>,
+    ) -> CallBuilder {
+        let receiver_id: AccountId = receiver_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        let (token_ids, amounts) = split_transfers(transfers);
+        trace::debug!(contract = %self.contract_id, tokens = token_ids.len(), receiver = %receiver_id, "mt_batch_transfer_call");
+        #[derive(Serialize)]
+        struct BatchTransferCallArgs {
+            receiver_id: String,
+            token_ids: Vec<String>,
+            amounts: Vec<String>,
+            msg: String,
+        }
+
+        self.transaction()
+            .call("mt_batch_transfer_call")
+            .args(BatchTransferCallArgs {
+                receiver_id: receiver_id.to_string(),
+                token_ids,
+                amounts,
+                msg: msg.into(),
+            })
+            .deposit(NearToken::from_yoctonear(1))
+            .gas(Gas::from_tgas(150))
+    }
+}
+
+impl std::fmt::Debug for MultiToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultiToken")
+            .field("contract_id", &self.contract_id)
+            .finish()
+    }
+}
+
+/// Split `(token_id, amount)` pairs into the parallel `token_ids` and
+/// string-encoded `amounts` arrays NEP-245 batch methods take.
+fn split_transfers(
+    transfers: impl IntoIterator<Item = (impl Into<String>, impl Into<u128>)>,
+) -> (Vec<String>, Vec<String>) {
+    transfers
+        .into_iter()
+        .map(|(token_id, amount)| (token_id.into(), amount.into().to_string()))
+        .unzip()
+}
+
+fn parse_amount(value: &str) -> Result<u128, Error> {
+    value.parse().map_err(|_| {
+        Error::Rpc(Box::new(crate::error::RpcError::InvalidResponse(format!(
+            "Invalid amount format: {}",
+            value
+        ))))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::testing::{FakeChain, MockTransport};
+    use crate::types::Action;
+
+    fn chain_with_mt() -> (FakeChain, MockTransport, crate::Near) {
+        let chain = FakeChain::new();
+        chain.add_account("intents.near", NearToken::from_near(10));
+        for method in [
+            "mt_transfer",
+            "mt_batch_transfer",
+            "mt_transfer_call",
+            "mt_batch_transfer_call",
+        ] {
+            chain.on_call("intents.near", method, |_ctx| Ok(Vec::new()));
+        }
+        let alice = chain
+            .add_account("alice.near", NearToken::from_near(10))
+            .signer("alice.near");
+        let mock = chain.transport();
+        let near = mock.near().signer(alice).build();
+        (chain, mock, near)
+    }
+
+    /// The method, JSON args, deposit and gas of the last call sent.
+    fn last_call(mock: &MockTransport) -> (String, Value, NearToken, Gas) {
+        let tx = mock
+            .requests_for("send_tx")
+            .pop()
+            .unwrap()
+            .signed_transaction()
+            .unwrap();
+        match &tx.transaction.actions[..] {
+            [Action::FunctionCall(call)] => (
+                call.method_name.clone(),
+                serde_json::from_slice(&call.args).unwrap(),
+                call.deposit,
+                call.gas,
+            ),
+            actions => panic!("expected one function call, got {actions:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfers_encode_args_and_attach_one_yocto() {
+        let (_chain, mock, near) = chain_with_mt();
+        let mt = near.mt("intents.near").unwrap();
+        let one_yocto = NearToken::from_yoctonear(1);
+
+        mt.transfer("bob.near", "nep141:usdc.near", 1_500_000_u128)
+            .await
+            .unwrap();
+        assert_eq!(
+            last_call(&mock),
+            (
+                "mt_transfer".to_string(),
+                json!({
+                    "receiver_id": "bob.near",
+                    "token_id": "nep141:usdc.near",
+                    "amount": "1500000",
+                }),
+                one_yocto,
+                Gas::from_tgas(30),
+            )
+        );
+
+        mt.batch_transfer(
+            "bob.near",
+            [
+                ("nep141:usdc.near", 1_u128),
+                ("nep141:wrap.near", 10_u128.pow(24)),
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            last_call(&mock),
+            (
+                "mt_batch_transfer".to_string(),
+                json!({
+                    "receiver_id": "bob.near",
+                    "token_ids": ["nep141:usdc.near", "nep141:wrap.near"],
+                    "amounts": ["1", "1000000000000000000000000"],
+                }),
+                one_yocto,
+                Gas::from_tgas(50),
+            )
+        );
+
+        mt.transfer_call("pool.near", "nep141:usdc.near", 7_u128, "swap")
+            .await
+            .unwrap();
+        assert_eq!(
+            last_call(&mock),
+            (
+                "mt_transfer_call".to_string(),
+                json!({
+                    "receiver_id": "pool.near",
+                    "token_id": "nep141:usdc.near",
+                    "amount": "7",
+                    "msg": "swap",
+                }),
+                one_yocto,
+                Gas::from_tgas(100),
+            )
+        );
+
+        mt.batch_transfer_call("pool.near", [("nep141:usdc.near", 7_u128)], "swap")
+            .await
+            .unwrap();
+        assert_eq!(
+            last_call(&mock),
+            (
+                "mt_batch_transfer_call".to_string(),
+                json!({
+                    "receiver_id": "pool.near",
+                    "token_ids": ["nep141:usdc.near"],
+                    "amounts": ["7"],
+                    "msg": "swap",
+                }),
+                one_yocto,
+                Gas::from_tgas(150),
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_balance_of_rejects_a_short_result() {
+        let (chain, _mock, near) = chain_with_mt();
+        chain.on_call("intents.near", "mt_batch_balance_of", |ctx| {
+            let args: Value = ctx.args_json()?;
+            assert_eq!(args["token_ids"], json!(["a", "b"]));
+            Ok(br#"["1"]"#.to_vec())
+        });
+        let mt = near.mt("intents.near").unwrap();
+
+        let err = mt
+            .batch_balance_of("alice.near", ["a", "b"])
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("returned 1 balances for 2 tokens"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_supply_of_unknown_token_is_none() {
+        let (chain, _mock, near) = chain_with_mt();
+        chain.on_call("intents.near", "mt_supply", |ctx| {
+            let args: Value = ctx.args_json()?;
+            let supply = (args["token_id"] == "nep141:usdc.near").then_some("1000");
+            Ok(serde_json::to_vec(&supply).unwrap())
+        });
+        let mt = near.mt("intents.near").unwrap();
+
+        assert_eq!(mt.supply("nep141:usdc.near").await.unwrap(), Some(1000));
+        assert_eq!(mt.supply("nep141:nope.near").await.unwrap(), None);
+    }
+}
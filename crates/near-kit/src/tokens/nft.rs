@@ -8,9 +8,11 @@ use tokio::sync::OnceCell;
 
 use crate::client::{CallBuilder, RpcClient, Signer, TransactionBuilder};
 use crate::error::Error;
-use crate::types::{AccountId, BlockReference, Finality, Gas, NearToken, TryIntoAccountId};
+use crate::types::{
+    AccountId, BlockReference, Finality, Gas, NearToken, STORAGE_AMOUNT_PER_BYTE, TryIntoAccountId,
+};
 
-use super::types::{NftContractMetadata, NftToken};
+use super::types::{NftContractMetadata, NftToken, Payout};
 
 // =============================================================================
 // NonFungibleToken
@@ -448,6 +450,329 @@ impl NonFungibleToken {
             .deposit(NearToken::from_yoctonear(1))
             .gas(Gas::from_tgas(100))
     }
+
+    // =========================================================================
+    // Approval Methods (NEP-178)
+    // =========================================================================
+
+    /// Check whether an account is approved to transfer a token (nft_is_approved).
+    ///
+    /// With `approval_id`, also checks that the approval has that ID — use this
+    /// to detect an approval that was revoked and re-granted since a listing
+    /// was created.
+    pub async fn is_approved(
+        &self,
+        token_id: impl AsRef<str>,
+        approved_account_id: impl TryIntoAccountId,
+        approval_id: Option<u64>,
+    ) -> Result<bool, Error> {
+        let approved_account_id: AccountId = approved_account_id.try_into_account_id()?;
+
+        #[derive(Serialize)]
+        struct Args<'a> {
+            token_id: &'a str,
+            approved_account_id: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            approval_id: Option<u64>,
+        }
+
+        let args = serde_json::to_vec(&Args {
+            token_id: token_id.as_ref(),
+            approved_account_id: approved_account_id.as_str(),
+            approval_id,
+        })?;
+
+        let result = self
+            .rpc
+            .view_function(
+                &self.contract_id,
+                "nft_is_approved",
+                &args,
+                BlockReference::Finality(Finality::Optimistic),
+            )
+            .await?;
+
+        result.json().map_err(Error::from)
+    }
+
+    /// Approve an account to transfer a token on the owner's behalf (nft_approve).
+    ///
+    /// # Deposit
+    ///
+    /// Attaches the storage deposit for one approval entry as computed by the
+    /// NEP-178 reference implementation; contracts refund any excess. Override
+    /// it with [`CallBuilder::deposit`] for contracts that charge more.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example() -> Result<(), near_kit::Error> {
+    /// let near = Near::testnet()
+    ///     .credentials("ed25519:...", "alice.near")?
+    ///     .build();
+    /// let nft = near.nft("nft-contract.near")?;
+    ///
+    /// nft.approve("token-123", "marketplace.near").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn approve(
+        &self,
+        token_id: impl AsRef<str>,
+        account_id: impl TryIntoAccountId,
+    ) -> CallBuilder {
+        let account_id: AccountId = account_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        trace::debug!(contract = %self.contract_id, token_id = token_id.as_ref(), account = %account_id, "nft_approve");
+        #[derive(Serialize)]
+        struct ApproveArgs {
+            token_id: String,
+            account_id: String,
+        }
+
+        let deposit = approval_storage_deposit(&account_id);
+        self.transaction()
+            .call("nft_approve")
+            .args(ApproveArgs {
+                token_id: token_id.as_ref().to_string(),
+                account_id: account_id.to_string(),
+            })
+            .deposit(deposit)
+            .gas(Gas::from_tgas(30))
+    }
+
+    /// Approve an account and notify it (nft_approve with `msg`).
+    ///
+    /// The contract calls `nft_on_approve` on the approved account with `msg`,
+    /// which is how marketplaces receive listings. Attaches the same deposit
+    /// as [`approve`](Self::approve) and 100 Tgas for the callback.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example() -> Result<(), near_kit::Error> {
+    /// let near = Near::testnet()
+    ///     .credentials("ed25519:...", "alice.near")?
+    ///     .build();
+    /// let nft = near.nft("nft-contract.near")?;
+    ///
+    /// nft.approve_with_msg("token-123", "marketplace.near", r#"{"price":"10"}"#)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn approve_with_msg(
+        &self,
+        token_id: impl AsRef<str>,
+        account_id: impl TryIntoAccountId,
+        msg: impl Into<String>,
+    ) -> CallBuilder {
+        let account_id: AccountId = account_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        trace::debug!(contract = %self.contract_id, token_id = token_id.as_ref(), account = %account_id, "nft_approve");
+        #[derive(Serialize)]
+        struct ApproveArgs {
+            token_id: String,
+            account_id: String,
+            msg: String,
+        }
+
+        let deposit = approval_storage_deposit(&account_id);
+        self.transaction()
+            .call("nft_approve")
+            .args(ApproveArgs {
+                token_id: token_id.as_ref().to_string(),
+                account_id: account_id.to_string(),
+                msg: msg.into(),
+            })
+            .deposit(deposit)
+            .gas(Gas::from_tgas(100))
+    }
+
+    /// Revoke one account's approval for a token (nft_revoke).
+    ///
+    /// Attaches 1 yoctoNEAR as required by NEP-178.
+    pub fn revoke(
+        &self,
+        token_id: impl AsRef<str>,
+        account_id: impl TryIntoAccountId,
+    ) -> CallBuilder {
+        let account_id: AccountId = account_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        trace::debug!(contract = %self.contract_id, token_id = token_id.as_ref(), account = %account_id, "nft_revoke");
+        #[derive(Serialize)]
+        struct RevokeArgs {
+            token_id: String,
+            account_id: String,
+        }
+
+        self.transaction()
+            .call("nft_revoke")
+            .args(RevokeArgs {
+                token_id: token_id.as_ref().to_string(),
+                account_id: account_id.to_string(),
+            })
+            .deposit(NearToken::from_yoctonear(1))
+            .gas(Gas::from_tgas(30))
+    }
+
+    /// Revoke every approval for a token (nft_revoke_all).
+    ///
+    /// Attaches 1 yoctoNEAR as required by NEP-178.
+    pub fn revoke_all(&self, token_id: impl AsRef<str>) -> CallBuilder {
+        trace::debug!(contract = %self.contract_id, token_id = token_id.as_ref(), "nft_revoke_all");
+        #[derive(Serialize)]
+        struct RevokeAllArgs {
+            token_id: String,
+        }
+
+        self.transaction()
+            .call("nft_revoke_all")
+            .args(RevokeAllArgs {
+                token_id: token_id.as_ref().to_string(),
+            })
+            .deposit(NearToken::from_yoctonear(1))
+            .gas(Gas::from_tgas(30))
+    }
+
+    // =========================================================================
+    // Payout Methods (NEP-199)
+    // =========================================================================
+
+    /// Get how a sale of `balance` would be split for a token (nft_payout).
+    ///
+    /// `max_len_payout` caps the number of accounts the contract may return;
+    /// contracts with more royalty holders than that fail the call.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example() -> Result<(), near_kit::Error> {
+    /// let near = Near::testnet().build();
+    /// let nft = near.nft("nft-contract.near")?;
+    ///
+    /// let payout = nft
+    ///     .payout("token-123", NearToken::from_near(10).as_yoctonear(), Some(10))
+    ///     .await?;
+    /// for (account, amount) in &payout.payout {
+    ///     println!("{account}: {amount}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn payout(
+        &self,
+        token_id: impl AsRef<str>,
+        balance: impl Into<u128>,
+        max_len_payout: Option<u32>,
+    ) -> Result<Payout, Error> {
+        #[derive(Serialize)]
+        struct Args<'a> {
+            token_id: &'a str,
+            balance: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            max_len_payout: Option<u32>,
+        }
+
+        let args = serde_json::to_vec(&Args {
+            token_id: token_id.as_ref(),
+            balance: balance.into().to_string(),
+            max_len_payout,
+        })?;
+
+        let result = self
+            .rpc
+            .view_function(
+                &self.contract_id,
+                "nft_payout",
+                &args,
+                BlockReference::Finality(Finality::Optimistic),
+            )
+            .await?;
+
+        result.json().map_err(Error::from)
+    }
+
+    /// Transfer a sold token and get its payout in one call (nft_transfer_payout).
+    ///
+    /// Called by the approved marketplace when settling a sale of `balance`.
+    /// The returned [`Payout`] is the transaction's result; decode it with
+    /// [`json`](crate::FinalExecutionOutcome::json) and pay out each entry.
+    ///
+    /// Attaches 1 yoctoNEAR as required by NEP-171 and 50 Tgas.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example() -> Result<(), near_kit::Error> {
+    /// let near = Near::testnet()
+    ///     .credentials("ed25519:...", "marketplace.near")?
+    ///     .build();
+    /// let nft = near.nft("nft-contract.near")?;
+    ///
+    /// let outcome = nft
+    ///     .transfer_payout(
+    ///         "buyer.near",
+    ///         "token-123",
+    ///         Some(7),
+    ///         NearToken::from_near(10).as_yoctonear(),
+    ///         Some(10),
+    ///     )
+    ///     .await?;
+    /// let payout: Payout = outcome.json()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transfer_payout(
+        &self,
+        receiver_id: impl TryIntoAccountId,
+        token_id: impl AsRef<str>,
+        approval_id: Option<u64>,
+        balance: impl Into<u128>,
+        max_len_payout: Option<u32>,
+    ) -> CallBuilder {
+        let receiver_id: AccountId = receiver_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        trace::debug!(contract = %self.contract_id, token_id = token_id.as_ref(), receiver = %receiver_id, "nft_transfer_payout");
+        #[derive(Serialize)]
+        struct TransferPayoutArgs {
+            receiver_id: String,
+            token_id: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            approval_id: Option<u64>,
+            balance: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            max_len_payout: Option<u32>,
+        }
+
+        self.transaction()
+            .call("nft_transfer_payout")
+            .args(TransferPayoutArgs {
+                receiver_id: receiver_id.to_string(),
+                token_id: token_id.as_ref().to_string(),
+                approval_id,
+                balance: balance.into().to_string(),
+                max_len_payout,
+            })
+            .deposit(NearToken::from_yoctonear(1))
+            .gas(Gas::from_tgas(50))
+    }
+}
+
+/// Storage deposit for one NEP-178 approval entry: the account ID plus the
+/// map entry's length prefix and `u64` approval ID, as the reference
+/// implementation charges.
+fn approval_storage_deposit(account_id: &AccountId) -> NearToken {
+    let bytes = account_id.as_str().len() as u128 + 4 + 8;
+    NearToken::from_yoctonear(STORAGE_AMOUNT_PER_BYTE * bytes)
 }
 
 impl Clone for NonFungibleToken {
@@ -470,3 +795,190 @@ impl std::fmt::Debug for NonFungibleToken {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::testing::{FakeChain, MockTransport};
+    use crate::types::Action;
+
+    fn chain_with_nft() -> (FakeChain, MockTransport, crate::Near) {
+        let chain = FakeChain::new();
+        chain.add_account("nft.near", NearToken::from_near(10));
+        for method in [
+            "nft_approve",
+            "nft_revoke",
+            "nft_revoke_all",
+            "nft_transfer_payout",
+        ] {
+            chain.on_call("nft.near", method, |_ctx| Ok(Vec::new()));
+        }
+        let alice = chain
+            .add_account("alice.near", NearToken::from_near(10))
+            .signer("alice.near");
+        let mock = chain.transport();
+        let near = mock.near().signer(alice).build();
+        (chain, mock, near)
+    }
+
+    /// The method, JSON args, deposit and gas of the last call sent.
+    fn last_call(mock: &MockTransport) -> (String, Value, NearToken, Gas) {
+        let tx = mock
+            .requests_for("send_tx")
+            .pop()
+            .unwrap()
+            .signed_transaction()
+            .unwrap();
+        match &tx.transaction.actions[..] {
+            [Action::FunctionCall(call)] => (
+                call.method_name.clone(),
+                serde_json::from_slice(&call.args).unwrap(),
+                call.deposit,
+                call.gas,
+            ),
+            actions => panic!("expected one function call, got {actions:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_approve_attaches_approval_storage_deposit() {
+        let (_chain, mock, near) = chain_with_nft();
+        let nft = near.nft("nft.near").unwrap();
+        // "market.near" (11 bytes) + 4-byte length prefix + 8-byte approval ID.
+        let deposit = NearToken::from_yoctonear(23 * STORAGE_AMOUNT_PER_BYTE);
+
+        nft.approve("token-1", "market.near").await.unwrap();
+        assert_eq!(
+            last_call(&mock),
+            (
+                "nft_approve".to_string(),
+                json!({ "token_id": "token-1", "account_id": "market.near" }),
+                deposit,
+                Gas::from_tgas(30),
+            )
+        );
+
+        nft.approve_with_msg("token-1", "market.near", r#"{"price":"10"}"#)
+            .await
+            .unwrap();
+        assert_eq!(
+            last_call(&mock),
+            (
+                "nft_approve".to_string(),
+                json!({
+                    "token_id": "token-1",
+                    "account_id": "market.near",
+                    "msg": r#"{"price":"10"}"#,
+                }),
+                deposit,
+                Gas::from_tgas(100),
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revokes_attach_one_yocto() {
+        let (_chain, mock, near) = chain_with_nft();
+        let nft = near.nft("nft.near").unwrap();
+
+        nft.revoke("token-1", "market.near").await.unwrap();
+        assert_eq!(
+            last_call(&mock),
+            (
+                "nft_revoke".to_string(),
+                json!({ "token_id": "token-1", "account_id": "market.near" }),
+                NearToken::from_yoctonear(1),
+                Gas::from_tgas(30),
+            )
+        );
+
+        nft.revoke_all("token-1").await.unwrap();
+        assert_eq!(
+            last_call(&mock),
+            (
+                "nft_revoke_all".to_string(),
+                json!({ "token_id": "token-1" }),
+                NearToken::from_yoctonear(1),
+                Gas::from_tgas(30),
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_is_approved_sends_approval_id_only_when_given() {
+        let (chain, _mock, near) = chain_with_nft();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let seen = calls.clone();
+        chain.on_call("nft.near", "nft_is_approved", move |ctx| {
+            let args: Value = ctx.args_json()?;
+            let approved = args.get("approval_id").is_none_or(|id| id == 3);
+            seen.lock().unwrap().push(args);
+            Ok(serde_json::to_vec(&approved).unwrap())
+        });
+        let nft = near.nft("nft.near").unwrap();
+
+        assert!(
+            nft.is_approved("token-1", "market.near", None)
+                .await
+                .unwrap()
+        );
+        assert!(
+            nft.is_approved("token-1", "market.near", Some(3))
+                .await
+                .unwrap()
+        );
+        assert!(
+            !nft.is_approved("token-1", "market.near", Some(4))
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            calls.lock().unwrap()[..2],
+            [
+                json!({ "token_id": "token-1", "approved_account_id": "market.near" }),
+                json!({
+                    "token_id": "token-1",
+                    "approved_account_id": "market.near",
+                    "approval_id": 3,
+                }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_payout_and_transfer_payout_args() {
+        let (chain, mock, near) = chain_with_nft();
+        chain.on_call("nft.near", "nft_payout", |ctx| {
+            let args: Value = ctx.args_json()?;
+            assert_eq!(args, json!({ "token_id": "token-1", "balance": "100" }));
+            Ok(br#"{"payout":{"alice.near":"90","artist.near":"10"}}"#.to_vec())
+        });
+        let nft = near.nft("nft.near").unwrap();
+
+        let payout = nft.payout("token-1", 100_u128, None).await.unwrap();
+        assert_eq!(payout.payout["artist.near"], 10);
+
+        nft.transfer_payout("bob.near", "token-1", Some(7), 100_u128, Some(10))
+            .await
+            .unwrap();
+        assert_eq!(
+            last_call(&mock),
+            (
+                "nft_transfer_payout".to_string(),
+                json!({
+                    "receiver_id": "bob.near",
+                    "token_id": "token-1",
+                    "approval_id": 7,
+                    "balance": "100",
+                    "max_len_payout": 10,
+                }),
+                NearToken::from_yoctonear(1),
+                Gas::from_tgas(50),
+            )
+        );
+    }
+}
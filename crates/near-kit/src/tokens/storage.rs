@@ -0,0 +1,526 @@
+//! Storage management client (NEP-145).
+
+use std::sync::Arc;
+
+use crate::trace::{self, Instrument};
+use serde::Serialize;
+use tokio::sync::OnceCell;
+
+use crate::client::{CallBuilder, RpcClient, Signer, TransactionBuilder};
+use crate::error::Error;
+use crate::types::{
+    AccountId, BlockReference, FinalExecutionOutcome, Finality, Gas, IntoNearToken, NearToken,
+    TryIntoAccountId,
+};
+
+use super::types::{StorageBalance, StorageBalanceBounds};
+
+// =============================================================================
+// StorageManagement
+// =============================================================================
+
+/// Client for the NEP-145 storage management methods of any contract.
+///
+/// Create via [`Near::storage()`](crate::Near::storage).
+///
+/// Fungible tokens, NFT marketplaces, DEXes and social contracts all charge
+/// for per-account storage through the same `storage_*` methods; this client
+/// covers them independently of the contract's other standards.
+///
+/// # Caching
+///
+/// Storage balance bounds are lazily fetched and cached on first use.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use near_kit::*;
+///
+/// # async fn example() -> Result<(), near_kit::Error> {
+/// let near = Near::mainnet()
+///     .credentials("ed25519:...", "alice.near")?
+///     .build();
+/// let storage = near.storage("v2.ref-finance.near")?;
+///
+/// // Register bob if he isn't already, paying the contract's minimum
+/// storage.ensure_registered("bob.near").await?;
+///
+/// if let Some(balance) = storage.storage_balance_of("alice.near").await? {
+///     println!("Withdrawable: {}", balance.available);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct StorageManagement {
+    rpc: Arc<RpcClient>,
+    signer: Option<Arc<dyn Signer>>,
+    contract_id: AccountId,
+    storage_bounds: OnceCell<StorageBalanceBounds>,
+    max_nonce_retries: u32,
+}
+
+impl StorageManagement {
+    /// Create a new StorageManagement client.
+    pub(crate) fn new(
+        rpc: Arc<RpcClient>,
+        signer: Option<Arc<dyn Signer>>,
+        contract_id: AccountId,
+        max_nonce_retries: u32,
+    ) -> Self {
+        Self {
+            rpc,
+            signer,
+            contract_id,
+            storage_bounds: OnceCell::new(),
+            max_nonce_retries,
+        }
+    }
+
+    /// Get the contract ID.
+    pub fn contract_id(&self) -> &AccountId {
+        &self.contract_id
+    }
+
+    /// Create a new client with a different signer, sharing the same RPC connection.
+    ///
+    /// Storage bounds will be re-fetched on first access.
+    pub fn with_signer(&self, signer: impl Signer + 'static) -> Self {
+        Self {
+            rpc: self.rpc.clone(),
+            signer: Some(Arc::new(signer)),
+            contract_id: self.contract_id.clone(),
+            storage_bounds: OnceCell::new(),
+            max_nonce_retries: self.max_nonce_retries,
+        }
+    }
+
+    /// Create a transaction builder for this contract.
+    fn transaction(&self) -> TransactionBuilder {
+        TransactionBuilder::new(
+            self.rpc.clone(),
+            self.signer.clone(),
+            self.contract_id.clone(),
+            self.max_nonce_retries,
+        )
+    }
+
+    // =========================================================================
+    // View Methods
+    // =========================================================================
+
+    /// Check if an account is registered on this contract.
+    pub async fn is_registered(&self, account_id: impl TryIntoAccountId) -> Result<bool, Error> {
+        let balance = self.storage_balance_of(account_id).await?;
+        Ok(balance.is_some())
+    }
+
+    /// Get storage balance for an account (storage_balance_of).
+    ///
+    /// Returns `None` if the account is not registered.
+    pub async fn storage_balance_of(
+        &self,
+        account_id: impl TryIntoAccountId,
+    ) -> Result<Option<StorageBalance>, Error> {
+        let account_id: AccountId = account_id.try_into_account_id()?;
+        let span =
+            trace::debug_span!("storage_balance_of", contract = %self.contract_id, %account_id);
+
+        async {
+            #[derive(Serialize)]
+            struct Args<'a> {
+                account_id: &'a str,
+            }
+
+            let args = serde_json::to_vec(&Args {
+                account_id: account_id.as_str(),
+            })?;
+
+            let result = self
+                .rpc
+                .view_function(
+                    &self.contract_id,
+                    "storage_balance_of",
+                    &args,
+                    BlockReference::Finality(Finality::Optimistic),
+                )
+                .await?;
+
+            result.json().map_err(Error::from)
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Get storage balance bounds for this contract (storage_balance_bounds).
+    ///
+    /// Returns the minimum and maximum storage deposit amounts. Bounds are
+    /// cached after the first call.
+    pub async fn storage_balance_bounds(&self) -> Result<&StorageBalanceBounds, Error> {
+        self.storage_bounds
+            .get_or_try_init(|| async {
+                let result = self
+                    .rpc
+                    .view_function(
+                        &self.contract_id,
+                        "storage_balance_bounds",
+                        &[],
+                        BlockReference::Finality(Finality::Optimistic),
+                    )
+                    .await
+                    .map_err(Error::from)?;
+                result.json::<StorageBalanceBounds>().map_err(Error::from)
+            })
+            .await
+    }
+
+    // =========================================================================
+    // Change Methods
+    // =========================================================================
+
+    /// Deposit storage balance for an account (storage_deposit).
+    ///
+    /// With `registration_only: Some(true)` the contract keeps only the
+    /// minimum and refunds the rest; with `None` the contract's default
+    /// applies, which for most contracts keeps the whole deposit.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example() -> Result<(), near_kit::Error> {
+    /// let near = Near::mainnet()
+    ///     .credentials("ed25519:...", "alice.near")?
+    ///     .build();
+    /// let storage = near.storage("social.near")?;
+    ///
+    /// // Top up alice's storage on a contract that grows with usage
+    /// storage
+    ///     .storage_deposit("alice.near", NearToken::from_millinear(500), None)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn storage_deposit(
+        &self,
+        account_id: impl TryIntoAccountId,
+        deposit: impl IntoNearToken,
+        registration_only: Option<bool>,
+    ) -> CallBuilder {
+        let account_id: AccountId = account_id
+            .try_into_account_id()
+            .expect("invalid account ID");
+        trace::debug!(contract = %self.contract_id, account = %account_id, ?registration_only, "storage_deposit");
+
+        #[derive(Serialize)]
+        struct DepositArgs {
+            account_id: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            registration_only: Option<bool>,
+        }
+
+        self.transaction()
+            .call("storage_deposit")
+            .args(DepositArgs {
+                account_id: account_id.to_string(),
+                registration_only,
+            })
+            .deposit(deposit)
+            .gas(Gas::from_tgas(30))
+    }
+
+    /// Withdraw part of the signer's available storage balance (storage_withdraw).
+    ///
+    /// Attaches 1 yoctoNEAR as required by NEP-145.
+    pub fn storage_withdraw(&self, amount: impl IntoNearToken) -> CallBuilder {
+        let amount = amount
+            .into_near_token()
+            .expect("invalid withdrawal amount - use NearToken::from_str() for user input");
+        trace::debug!(contract = %self.contract_id, %amount, "storage_withdraw");
+        self.withdraw(Some(amount))
+    }
+
+    /// Withdraw all of the signer's available storage balance (storage_withdraw).
+    ///
+    /// Attaches 1 yoctoNEAR as required by NEP-145.
+    pub fn storage_withdraw_all(&self) -> CallBuilder {
+        trace::debug!(contract = %self.contract_id, "storage_withdraw");
+        self.withdraw(None)
+    }
+
+    fn withdraw(&self, amount: Option<NearToken>) -> CallBuilder {
+        #[derive(Serialize)]
+        struct WithdrawArgs {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            amount: Option<String>,
+        }
+
+        self.transaction()
+            .call("storage_withdraw")
+            .args(WithdrawArgs {
+                amount: amount.map(|a| a.as_yoctonear().to_string()),
+            })
+            .deposit(NearToken::from_yoctonear(1))
+            .gas(Gas::from_tgas(30))
+    }
+
+    /// Unregister the signer and refund its storage deposit (storage_unregister).
+    ///
+    /// Without `force`, contracts refuse to unregister an account that still
+    /// holds assets (e.g. a non-zero token balance). With `force`, those
+    /// assets are burnt or otherwise forfeited — check the contract's
+    /// semantics first. Attaches 1 yoctoNEAR as required by NEP-145.
+    pub fn storage_unregister(&self, force: bool) -> CallBuilder {
+        trace::debug!(contract = %self.contract_id, force, "storage_unregister");
+
+        #[derive(Serialize)]
+        struct UnregisterArgs {
+            force: bool,
+        }
+
+        self.transaction()
+            .call("storage_unregister")
+            .args(UnregisterArgs { force })
+            .deposit(NearToken::from_yoctonear(1))
+            .gas(Gas::from_tgas(30))
+    }
+
+    /// Register an account unless it already is.
+    ///
+    /// Deposits the contract's minimum storage balance with
+    /// `registration_only: true`. Returns `None` when the account was
+    /// already registered, or the outcome of the deposit transaction.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::StorageRegistrationFailed`] if the deposit transaction
+    /// failed on chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # async fn example() -> Result<(), near_kit::Error> {
+    /// let near = Near::mainnet()
+    ///     .credentials("ed25519:...", "alice.near")?
+    ///     .build();
+    /// let storage = near.storage(tokens::USDC)?;
+    ///
+    /// if let Some(outcome) = storage.ensure_registered("bob.near").await? {
+    ///     println!("Registered bob in {}", outcome.transaction_hash());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ensure_registered(
+        &self,
+        account_id: impl TryIntoAccountId,
+    ) -> Result<Option<FinalExecutionOutcome>, Error> {
+        let account_id: AccountId = account_id.try_into_account_id()?;
+        if self.is_registered(account_id.clone()).await? {
+            return Ok(None);
+        }
+        let min = self.storage_balance_bounds().await?.min;
+        let outcome = self
+            .storage_deposit(account_id.clone(), min, Some(true))
+            .await?;
+        if let Some(message) = outcome.failure_message() {
+            return Err(Error::StorageRegistrationFailed {
+                contract_id: self.contract_id.clone(),
+                account_id,
+                transaction_hash: *outcome.transaction_hash(),
+                message,
+            });
+        }
+        Ok(Some(outcome))
+    }
+}
+
+impl Clone for StorageManagement {
+    fn clone(&self) -> Self {
+        Self {
+            rpc: self.rpc.clone(),
+            signer: self.signer.clone(),
+            contract_id: self.contract_id.clone(),
+            storage_bounds: OnceCell::new(),
+            max_nonce_retries: self.max_nonce_retries,
+        }
+    }
+}
+
+impl std::fmt::Debug for StorageManagement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StorageManagement")
+            .field("contract_id", &self.contract_id)
+            .field("bounds_cached", &self.storage_bounds.initialized())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::testing::{FakeChain, MockTransport};
+    use crate::types::Action;
+
+    /// A contract that accepts every storage call and has alice registered.
+    fn chain_with_app() -> (FakeChain, MockTransport, crate::Near) {
+        let chain = FakeChain::new();
+        chain.add_account("app.near", NearToken::from_near(10));
+        for method in ["storage_deposit", "storage_withdraw", "storage_unregister"] {
+            chain.on_call("app.near", method, |_ctx| Ok(Vec::new()));
+        }
+        chain.on_call("app.near", "storage_balance_of", |ctx| {
+            let args: Value = ctx.args_json()?;
+            let balance = (args["account_id"] == "alice.near")
+                .then(|| json!({ "total": "1250000000000000000000", "available": "0" }));
+            Ok(serde_json::to_vec(&balance).unwrap())
+        });
+        let alice = chain
+            .add_account("alice.near", NearToken::from_near(10))
+            .signer("alice.near");
+        let mock = chain.transport();
+        let near = mock.near().signer(alice).build();
+        (chain, mock, near)
+    }
+
+    /// The method, JSON args and deposit of the last call sent.
+    fn last_call(mock: &MockTransport) -> (String, Value, NearToken) {
+        let tx = mock
+            .requests_for("send_tx")
+            .pop()
+            .unwrap()
+            .signed_transaction()
+            .unwrap();
+        match &tx.transaction.actions[..] {
+            [Action::FunctionCall(call)] => (
+                call.method_name.clone(),
+                serde_json::from_slice(&call.args).unwrap(),
+                call.deposit,
+            ),
+            actions => panic!("expected one function call, got {actions:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_storage_deposit_omits_unset_registration_only() {
+        let (_chain, mock, near) = chain_with_app();
+        let storage = near.storage("app.near").unwrap();
+
+        storage
+            .storage_deposit("bob.near", NearToken::from_millinear(10), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            last_call(&mock),
+            (
+                "storage_deposit".to_string(),
+                json!({ "account_id": "bob.near" }),
+                NearToken::from_millinear(10),
+            )
+        );
+
+        storage
+            .storage_deposit("bob.near", NearToken::from_millinear(10), Some(false))
+            .await
+            .unwrap();
+        assert_eq!(
+            last_call(&mock).1,
+            json!({ "account_id": "bob.near", "registration_only": false })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_and_unregister_attach_one_yocto() {
+        let (_chain, mock, near) = chain_with_app();
+        let storage = near.storage("app.near").unwrap();
+        let one_yocto = NearToken::from_yoctonear(1);
+
+        storage
+            .storage_withdraw(NearToken::from_millinear(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            last_call(&mock),
+            (
+                "storage_withdraw".to_string(),
+                json!({ "amount": "1000000000000000000000" }),
+                one_yocto,
+            )
+        );
+
+        storage.storage_withdraw_all().await.unwrap();
+        assert_eq!(
+            last_call(&mock),
+            ("storage_withdraw".to_string(), json!({}), one_yocto)
+        );
+
+        storage.storage_unregister(true).await.unwrap();
+        assert_eq!(
+            last_call(&mock),
+            (
+                "storage_unregister".to_string(),
+                json!({ "force": true }),
+                one_yocto,
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ensure_registered_skips_registered_account() {
+        let (_chain, mock, near) = chain_with_app();
+        let storage = near.storage("app.near").unwrap();
+
+        assert!(
+            storage
+                .ensure_registered("alice.near")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(mock.requests_for("send_tx").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_ensure_registered_fails_when_deposit_fails() {
+        let chain = FakeChain::new();
+        chain.add_account("app.near", NearToken::from_near(10));
+        chain.add_account("bob.near", NearToken::from_near(1));
+        let alice = chain
+            .add_account("alice.near", NearToken::from_near(10))
+            .signer("alice.near");
+        chain.on_call(
+            "app.near",
+            "storage_balance_of",
+            |_ctx| Ok(b"null".to_vec()),
+        );
+        chain.on_call("app.near", "storage_balance_bounds", |_ctx| {
+            Ok(br#"{"min":"1250000000000000000000","max":null}"#.to_vec())
+        });
+        chain.on_call("app.near", "storage_deposit", |_ctx| {
+            Err("The attached deposit is less than the minimum storage balance".to_string())
+        });
+        let near = chain.transport().near().signer(alice).build();
+
+        let err = near
+            .storage("app.near")
+            .unwrap()
+            .ensure_registered("bob.near")
+            .await
+            .unwrap_err();
+        match err {
+            Error::StorageRegistrationFailed {
+                contract_id,
+                account_id,
+                message,
+                ..
+            } => {
+                assert_eq!(contract_id.as_str(), "app.near");
+                assert_eq!(account_id.as_str(), "bob.near");
+                assert!(message.contains("minimum storage balance"), "{message}");
+            }
+            other => panic!("expected StorageRegistrationFailed, got {other:?}"),
+        }
+    }
+}
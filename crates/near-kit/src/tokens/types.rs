@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::fmt;
 
 use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
 
 use crate::error::ParseAmountError;
 use crate::types::NearToken;
@@ -355,6 +356,48 @@ pub struct NftToken {
     pub approved_account_ids: Option<HashMap<String, u64>>,
 }
 
+/// NEP-199 Payout.
+///
+/// How the proceeds of a sale are split between accounts. Returned by the
+/// `nft_payout` view function and by `nft_transfer_payout`.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Payout {
+    /// Raw amount (yoctoNEAR, or the sale token's smallest unit) owed to
+    /// each account
+    #[serde_as(as = "HashMap<_, DisplayFromStr>")]
+    pub payout: HashMap<String, u128>,
+}
+
+impl Payout {
+    /// Sum of all amounts in the payout.
+    ///
+    /// Returns `None` on overflow.
+    pub fn total(&self) -> Option<u128> {
+        self.payout
+            .values()
+            .try_fold(0u128, |sum, amount| sum.checked_add(*amount))
+    }
+}
+
+// =============================================================================
+// Multi Token Types (NEP-245)
+// =============================================================================
+
+/// NEP-245 Token.
+///
+/// Returned by the `mt_token` view function. Fungible-style tokens in a
+/// multi token contract have no single owner, so `owner_id` is often `None`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtToken {
+    /// Unique token identifier within this contract
+    pub token_id: String,
+
+    /// Owner of the token, for tokens with a single owner
+    #[serde(default)]
+    pub owner_id: Option<String>,
+}
+
 // =============================================================================
 // Helper Functions
 // =============================================================================
@@ -684,4 +727,22 @@ mod tests {
         let amount = FtAmount::new(1_500_000, 6, "USDC");
         assert_eq!(amount.format_amount(), "1.5");
     }
+
+    // ─── Payout Tests ───
+
+    #[test]
+    fn test_payout_deserialize() {
+        let payout: Payout = serde_json::from_str(
+            r#"{"payout":{"creator.near":"100000000000000000000000","seller.near":"900000000000000000000000"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            payout.payout["creator.near"],
+            100_000_000_000_000_000_000_000
+        );
+        assert_eq!(payout.total(), Some(1_000_000_000_000_000_000_000_000));
+
+        let json = serde_json::to_value(&payout).unwrap();
+        assert_eq!(json["payout"]["seller.near"], "900000000000000000000000");
+    }
 }
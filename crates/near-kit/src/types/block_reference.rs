@@ -112,6 +112,64 @@ impl From<CryptoHash> for BlockReference {
     }
 }
 
+/// Reference to a chunk for the `chunk` and `EXPERIMENTAL_congestion_level`
+/// RPC methods: either the chunk's own hash, or a block and a shard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChunkReference {
+    /// Chunk by its hash.
+    Hash(CryptoHash),
+    /// The chunk of `shard_id` in the block at `height`.
+    BlockHeight {
+        /// Block height.
+        height: u64,
+        /// Shard ID.
+        shard_id: u64,
+    },
+    /// The chunk of `shard_id` in the block with `hash`.
+    BlockHash {
+        /// Block hash.
+        hash: CryptoHash,
+        /// Shard ID.
+        shard_id: u64,
+    },
+}
+
+impl ChunkReference {
+    /// Chunk by its hash.
+    pub fn chunk_hash(hash: CryptoHash) -> Self {
+        Self::Hash(hash)
+    }
+
+    /// The chunk of `shard_id` in the block at `height`.
+    pub fn at_height(height: u64, shard_id: u64) -> Self {
+        Self::BlockHeight { height, shard_id }
+    }
+
+    /// The chunk of `shard_id` in the block with `hash`.
+    pub fn at_block_hash(hash: CryptoHash, shard_id: u64) -> Self {
+        Self::BlockHash { hash, shard_id }
+    }
+
+    /// Convert to JSON for RPC requests.
+    pub fn to_rpc_params(&self) -> serde_json::Value {
+        match self {
+            ChunkReference::Hash(h) => serde_json::json!({ "chunk_id": h.to_string() }),
+            ChunkReference::BlockHeight { height, shard_id } => {
+                serde_json::json!({ "block_id": *height, "shard_id": *shard_id })
+            }
+            ChunkReference::BlockHash { hash, shard_id } => {
+                serde_json::json!({ "block_id": hash.to_string(), "shard_id": *shard_id })
+            }
+        }
+    }
+}
+
+impl From<CryptoHash> for ChunkReference {
+    fn from(hash: CryptoHash) -> Self {
+        Self::Hash(hash)
+    }
+}
+
 /// Finality level for queries.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -257,6 +315,23 @@ mod tests {
         assert_eq!(params["block_id"], 12345);
     }
 
+    #[test]
+    fn test_chunk_reference_rpc_params() {
+        let hash = CryptoHash::hash(b"chunk");
+        assert_eq!(
+            ChunkReference::chunk_hash(hash).to_rpc_params(),
+            serde_json::json!({ "chunk_id": hash.to_string() })
+        );
+        assert_eq!(
+            ChunkReference::at_height(12345, 3).to_rpc_params(),
+            serde_json::json!({ "block_id": 12345, "shard_id": 3 })
+        );
+        assert_eq!(
+            ChunkReference::at_block_hash(hash, 0).to_rpc_params(),
+            serde_json::json!({ "block_id": hash.to_string(), "shard_id": 0 })
+        );
+    }
+
     #[test]
     fn test_finality_as_str() {
         assert_eq!(Finality::Final.as_str(), "final");
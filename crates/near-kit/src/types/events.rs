@@ -0,0 +1,406 @@
+//! NEP-297 event logs.
+//!
+//! Contracts emit structured events as log lines of the form
+//! `EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[...]}`.
+//! [`NearEvent`] is one such line together with the receipt that emitted it;
+//! [`FinalExecutionOutcome::events`](super::FinalExecutionOutcome::events)
+//! collects them for a whole transaction.
+//!
+//! The payload of the token standards is decoded by [`NearEvent::ft_event`]
+//! (NEP-141), [`NearEvent::nft_event`] (NEP-171) and [`NearEvent::mt_event`]
+//! (NEP-245); any other standard can be decoded with [`NearEvent::data`].
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+
+use super::{AccountId, CryptoHash};
+
+/// Prefix marking a log line as a NEP-297 event.
+pub const EVENT_LOG_PREFIX: &str = "EVENT_JSON:";
+
+/// A NEP-297 event emitted during receipt execution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NearEvent {
+    /// Standard name, e.g. `nep141`.
+    pub standard: String,
+    /// Standard version, e.g. `1.0.0`.
+    pub version: String,
+    /// Event type, e.g. `ft_transfer`.
+    pub event: String,
+    /// Event payload (`null` when the event has none).
+    pub data: serde_json::Value,
+    /// Receipt whose execution logged the event.
+    pub receipt_id: CryptoHash,
+    /// Account that executed the receipt — the contract that emitted the event.
+    pub executor_id: AccountId,
+}
+
+/// The JSON body of an `EVENT_JSON:` log line.
+#[derive(Deserialize)]
+struct EventLog {
+    standard: String,
+    version: String,
+    event: String,
+    #[serde(default)]
+    data: serde_json::Value,
+}
+
+impl NearEvent {
+    /// Parse a log line emitted by `executor_id` while executing `receipt_id`.
+    ///
+    /// Returns `None` if the line is not an `EVENT_JSON:` log or its JSON is
+    /// not a valid NEP-297 event.
+    pub fn from_log(log: &str, receipt_id: CryptoHash, executor_id: AccountId) -> Option<Self> {
+        let json = log.strip_prefix(EVENT_LOG_PREFIX)?;
+        let EventLog {
+            standard,
+            version,
+            event,
+            data,
+        } = serde_json::from_str(json.trim()).ok()?;
+        Some(Self {
+            standard,
+            version,
+            event,
+            data,
+            receipt_id,
+            executor_id,
+        })
+    }
+
+    /// Deserialize the event payload.
+    pub fn data<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        T::deserialize(&self.data)
+    }
+
+    /// Decode a NEP-141 (fungible token) event.
+    ///
+    /// Returns `None` for other standards, unknown event types, or payloads
+    /// that do not match the standard.
+    pub fn ft_event(&self) -> Option<FtEvent> {
+        if self.standard != "nep141" {
+            return None;
+        }
+        match self.event.as_str() {
+            "ft_mint" => self.data().ok().map(FtEvent::Mint),
+            "ft_transfer" => self.data().ok().map(FtEvent::Transfer),
+            "ft_burn" => self.data().ok().map(FtEvent::Burn),
+            _ => None,
+        }
+    }
+
+    /// Decode a NEP-171 (non-fungible token) event.
+    ///
+    /// Returns `None` for other standards, unknown event types, or payloads
+    /// that do not match the standard.
+    pub fn nft_event(&self) -> Option<NftEvent> {
+        if self.standard != "nep171" {
+            return None;
+        }
+        match self.event.as_str() {
+            "nft_mint" => self.data().ok().map(NftEvent::Mint),
+            "nft_transfer" => self.data().ok().map(NftEvent::Transfer),
+            "nft_burn" => self.data().ok().map(NftEvent::Burn),
+            _ => None,
+        }
+    }
+
+    /// Decode a NEP-245 (multi token) event.
+    ///
+    /// Returns `None` for other standards, unknown event types, or payloads
+    /// that do not match the standard.
+    pub fn mt_event(&self) -> Option<MtEvent> {
+        if self.standard != "nep245" {
+            return None;
+        }
+        match self.event.as_str() {
+            "mt_mint" => self.data().ok().map(MtEvent::Mint),
+            "mt_transfer" => self.data().ok().map(MtEvent::Transfer),
+            "mt_burn" => self.data().ok().map(MtEvent::Burn),
+            _ => None,
+        }
+    }
+}
+
+// =============================================================================
+// NEP-141
+// =============================================================================
+
+/// A NEP-141 event. Each variant carries every entry of the event's `data`
+/// array — contracts batch several mints/transfers/burns into one log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FtEvent {
+    /// `ft_mint`
+    Mint(Vec<FtMintEvent>),
+    /// `ft_transfer`
+    Transfer(Vec<FtTransferEvent>),
+    /// `ft_burn`
+    Burn(Vec<FtBurnEvent>),
+}
+
+/// One `ft_mint` entry.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FtMintEvent {
+    /// Account receiving the minted tokens.
+    pub owner_id: AccountId,
+    /// Raw amount minted.
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount: u128,
+    /// Optional memo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// One `ft_transfer` entry.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FtTransferEvent {
+    /// Sender.
+    pub old_owner_id: AccountId,
+    /// Receiver.
+    pub new_owner_id: AccountId,
+    /// Raw amount transferred.
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount: u128,
+    /// Optional memo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// One `ft_burn` entry.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FtBurnEvent {
+    /// Account whose tokens were burnt.
+    pub owner_id: AccountId,
+    /// Raw amount burnt.
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount: u128,
+    /// Optional memo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+// =============================================================================
+// NEP-171
+// =============================================================================
+
+/// A NEP-171 event. Each variant carries every entry of the event's `data`
+/// array.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NftEvent {
+    /// `nft_mint`
+    Mint(Vec<NftMintEvent>),
+    /// `nft_transfer`
+    Transfer(Vec<NftTransferEvent>),
+    /// `nft_burn`
+    Burn(Vec<NftBurnEvent>),
+}
+
+/// One `nft_mint` entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NftMintEvent {
+    /// Account receiving the minted tokens.
+    pub owner_id: AccountId,
+    /// IDs of the minted tokens.
+    pub token_ids: Vec<String>,
+    /// Optional memo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// One `nft_transfer` entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NftTransferEvent {
+    /// Approved account that made the transfer on the owner's behalf, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<AccountId>,
+    /// Previous owner.
+    pub old_owner_id: AccountId,
+    /// New owner.
+    pub new_owner_id: AccountId,
+    /// IDs of the transferred tokens.
+    pub token_ids: Vec<String>,
+    /// Optional memo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// One `nft_burn` entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NftBurnEvent {
+    /// Approved account that burnt the tokens on the owner's behalf, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<AccountId>,
+    /// Owner of the burnt tokens.
+    pub owner_id: AccountId,
+    /// IDs of the burnt tokens.
+    pub token_ids: Vec<String>,
+    /// Optional memo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+// =============================================================================
+// NEP-245
+// =============================================================================
+
+/// A NEP-245 event. Each variant carries every entry of the event's `data`
+/// array.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MtEvent {
+    /// `mt_mint`
+    Mint(Vec<MtMintEvent>),
+    /// `mt_transfer`
+    Transfer(Vec<MtTransferEvent>),
+    /// `mt_burn`
+    Burn(Vec<MtBurnEvent>),
+}
+
+/// One `mt_mint` entry. `amounts[i]` is the amount of `token_ids[i]`.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MtMintEvent {
+    /// Account receiving the minted tokens.
+    pub owner_id: AccountId,
+    /// IDs of the minted tokens.
+    pub token_ids: Vec<String>,
+    /// Raw amount minted of each token.
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub amounts: Vec<u128>,
+    /// Optional memo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// One `mt_transfer` entry. `amounts[i]` is the amount of `token_ids[i]`.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MtTransferEvent {
+    /// Approved account that made the transfer on the owner's behalf, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<AccountId>,
+    /// Sender.
+    pub old_owner_id: AccountId,
+    /// Receiver.
+    pub new_owner_id: AccountId,
+    /// IDs of the transferred tokens.
+    pub token_ids: Vec<String>,
+    /// Raw amount transferred of each token.
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub amounts: Vec<u128>,
+    /// Optional memo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// One `mt_burn` entry. `amounts[i]` is the amount of `token_ids[i]`.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MtBurnEvent {
+    /// Approved account that burnt the tokens on the owner's behalf, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<AccountId>,
+    /// Owner of the burnt tokens.
+    pub owner_id: AccountId,
+    /// IDs of the burnt tokens.
+    pub token_ids: Vec<String>,
+    /// Raw amount burnt of each token.
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub amounts: Vec<u128>,
+    /// Optional memo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(log: &str) -> Option<NearEvent> {
+        NearEvent::from_log(log, CryptoHash::ZERO, "token.near".parse().unwrap())
+    }
+
+    #[test]
+    fn test_from_log_ignores_plain_and_malformed_logs() {
+        assert!(event("Transfer 10 from alice.near to bob.near").is_none());
+        assert!(event("EVENT_JSON:{not json").is_none());
+        assert!(event(r#"EVENT_JSON:{"standard":"nep141"}"#).is_none());
+    }
+
+    #[test]
+    fn test_ft_transfer_event() {
+        let event = event(
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"alice.near","new_owner_id":"bob.near","amount":"1000000","memo":"hi"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(event.standard, "nep141");
+        assert_eq!(event.executor_id.as_str(), "token.near");
+        assert!(event.nft_event().is_none());
+
+        let Some(FtEvent::Transfer(transfers)) = event.ft_event() else {
+            panic!("expected ft_transfer");
+        };
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].old_owner_id.as_str(), "alice.near");
+        assert_eq!(transfers[0].new_owner_id.as_str(), "bob.near");
+        assert_eq!(transfers[0].amount, 1_000_000);
+        assert_eq!(transfers[0].memo.as_deref(), Some("hi"));
+    }
+
+    #[test]
+    fn test_nft_mint_event_batch() {
+        let event = event(
+            r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_mint","data":[{"owner_id":"alice.near","token_ids":["1","2"]},{"owner_id":"bob.near","token_ids":["3"]}]}"#,
+        )
+        .unwrap();
+
+        let Some(NftEvent::Mint(mints)) = event.nft_event() else {
+            panic!("expected nft_mint");
+        };
+        assert_eq!(mints.len(), 2);
+        assert_eq!(mints[0].token_ids, vec!["1", "2"]);
+        assert_eq!(mints[1].owner_id.as_str(), "bob.near");
+    }
+
+    #[test]
+    fn test_mt_transfer_event() {
+        let event = event(
+            r#"EVENT_JSON:{"standard":"nep245","version":"1.0.0","event":"mt_transfer","data":[{"authorized_id":"market.near","old_owner_id":"alice.near","new_owner_id":"bob.near","token_ids":["nep141:usdc.near","nep141:wrap.near"],"amounts":["1000000","340282366920938463463374607431768211455"]}]}"#,
+        )
+        .unwrap();
+        assert!(event.ft_event().is_none());
+        assert!(event.nft_event().is_none());
+
+        let Some(MtEvent::Transfer(transfers)) = event.mt_event() else {
+            panic!("expected mt_transfer");
+        };
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(
+            transfers[0].authorized_id.as_ref().map(|id| id.as_str()),
+            Some("market.near")
+        );
+        assert_eq!(
+            transfers[0].token_ids,
+            vec!["nep141:usdc.near", "nep141:wrap.near"]
+        );
+        assert_eq!(transfers[0].amounts, vec![1_000_000, u128::MAX]);
+        assert!(transfers[0].memo.is_none());
+    }
+
+    #[test]
+    fn test_unknown_event_falls_back_to_raw_data() {
+        let event = event(
+            r#"EVENT_JSON:{"standard":"nep393","version":"1.0.0","event":"sbt_mint","data":[{"owner_id":"alice.near"}]}"#,
+        )
+        .unwrap();
+        assert!(event.ft_event().is_none());
+        assert!(event.nft_event().is_none());
+        assert!(event.mt_event().is_none());
+
+        let data: Vec<serde_json::Value> = event.data().unwrap();
+        assert_eq!(data[0]["owner_id"], "alice.near");
+    }
+}
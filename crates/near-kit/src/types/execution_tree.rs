@@ -0,0 +1,447 @@
+//! Receipt tree view of a [`FinalExecutionOutcome`].
+//!
+//! The RPC returns a transaction's receipt outcomes as a flat list; which
+//! receipt spawned which is only recorded in each outcome's `receipt_ids`.
+//! [`ExecutionTree`] links them back up so cross-contract call chains can be
+//! walked, searched for failures, and printed.
+//!
+//! ```rust,no_run
+//! # use near_kit::*;
+//! # fn example(outcome: &FinalExecutionOutcome) {
+//! let tree = outcome.execution_tree();
+//! println!("{tree}");
+//!
+//! for node in tree.failures() {
+//!     let parent = tree.parent(node).map(|p| p.executor_id().to_string());
+//!     println!("{} failed (called from {parent:?})", node.executor_id());
+//! }
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::rpc::{
+    ActionView, ExecutionOutcomeWithId, ExecutionStatus, FinalExecutionOutcome, Receipt,
+    ReceiptContent,
+};
+use super::{AccountId, CryptoHash, Gas};
+
+/// Predecessor of protocol-generated receipts, such as gas and deposit refunds.
+const SYSTEM_ACCOUNT: &str = "system";
+
+/// The receipts of a transaction, linked into a tree rooted at the
+/// transaction outcome.
+///
+/// Built with [`FinalExecutionOutcome::execution_tree`]. Nodes are visited
+/// depth-first in execution order by [`iter`](Self::iter); the `Display`
+/// impl renders the same order as an indented tree.
+///
+/// Receipt details ([`ExecutionNode::receipt`]) are only available when the
+/// outcome came from `tx_status` / `EXPERIMENTAL_tx_status`, which populate
+/// [`FinalExecutionOutcome::receipts`]; `send_tx` leaves them out.
+#[derive(Debug, Clone)]
+pub struct ExecutionTree<'a> {
+    nodes: Vec<ExecutionNode<'a>>,
+    index: HashMap<CryptoHash, usize>,
+}
+
+/// One execution outcome — the transaction itself or a receipt — in an
+/// [`ExecutionTree`].
+#[derive(Debug, Clone)]
+pub struct ExecutionNode<'a> {
+    /// The execution outcome.
+    pub outcome: &'a ExecutionOutcomeWithId,
+    /// The receipt that was executed, when the outcome carries receipt
+    /// details. Always `None` for the transaction node.
+    pub receipt: Option<&'a Receipt>,
+    /// Distance from the root; the transaction node is at depth 0.
+    pub depth: usize,
+    transaction: bool,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+impl<'a> ExecutionTree<'a> {
+    /// Link the outcomes of `outcome` into a tree.
+    ///
+    /// Receipt outcomes not reachable from the transaction (which the RPC
+    /// should never return) are kept as additional roots rather than dropped.
+    pub fn new(outcome: &'a FinalExecutionOutcome) -> Self {
+        let receipts: HashMap<CryptoHash, &Receipt> = outcome
+            .receipts
+            .iter()
+            .map(|receipt| (receipt.receipt_id, receipt))
+            .collect();
+        let outcomes: HashMap<CryptoHash, &ExecutionOutcomeWithId> = outcome
+            .receipts_outcome
+            .iter()
+            .map(|receipt| (receipt.id, receipt))
+            .collect();
+
+        let mut tree = Self {
+            nodes: Vec::with_capacity(outcome.receipts_outcome.len() + 1),
+            index: HashMap::new(),
+        };
+        tree.insert_subtree(&outcome.transaction_outcome, &outcomes, &receipts);
+        for receipt in &outcome.receipts_outcome {
+            if !tree.index.contains_key(&receipt.id) {
+                tree.insert_subtree(receipt, &outcomes, &receipts);
+            }
+        }
+        tree
+    }
+
+    /// Depth-first insertion, so `nodes` ends up in display order.
+    fn insert_subtree(
+        &mut self,
+        root: &'a ExecutionOutcomeWithId,
+        outcomes: &HashMap<CryptoHash, &'a ExecutionOutcomeWithId>,
+        receipts: &HashMap<CryptoHash, &'a Receipt>,
+    ) {
+        let transaction = self.nodes.is_empty();
+        let mut stack: Vec<(_, Option<usize>, usize)> = vec![(root, None, 0)];
+        while let Some((outcome, parent, depth)) = stack.pop() {
+            // A receipt listed twice would otherwise be walked twice (or
+            // loop forever on a malformed response).
+            if self.index.contains_key(&outcome.id) {
+                continue;
+            }
+            let index = self.nodes.len();
+            self.index.insert(outcome.id, index);
+            if let Some(parent) = parent {
+                self.nodes[parent].children.push(index);
+            }
+            self.nodes.push(ExecutionNode {
+                outcome,
+                receipt: receipts.get(&outcome.id).copied(),
+                depth,
+                transaction: transaction && parent.is_none(),
+                parent,
+                children: Vec::new(),
+            });
+            // Reversed so the first child is popped (and visited) first.
+            for child in outcome.outcome.receipt_ids.iter().rev() {
+                if let Some(child) = outcomes.get(child) {
+                    stack.push((child, Some(index), depth + 1));
+                }
+            }
+        }
+    }
+
+    /// The transaction outcome.
+    pub fn root(&self) -> &ExecutionNode<'a> {
+        &self.nodes[0]
+    }
+
+    /// Look up a node by transaction or receipt ID.
+    pub fn get(&self, id: &CryptoHash) -> Option<&ExecutionNode<'a>> {
+        self.index.get(id).map(|&index| &self.nodes[index])
+    }
+
+    /// The node whose execution created `node`, or `None` for a root.
+    pub fn parent(&self, node: &ExecutionNode<'a>) -> Option<&ExecutionNode<'a>> {
+        node.parent.map(|index| &self.nodes[index])
+    }
+
+    /// The receipts `node` created, in the order it created them.
+    pub fn children<'t>(
+        &'t self,
+        node: &'t ExecutionNode<'a>,
+    ) -> impl Iterator<Item = &'t ExecutionNode<'a>> + 't {
+        node.children.iter().map(|&index| &self.nodes[index])
+    }
+
+    /// Every node, depth-first in execution order.
+    pub fn iter(&self) -> impl Iterator<Item = &ExecutionNode<'a>> {
+        self.nodes.iter()
+    }
+
+    /// Number of nodes, including the transaction.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Always `false`: a tree holds at least the transaction node.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Nodes whose execution failed.
+    pub fn failures(&self) -> impl Iterator<Item = &ExecutionNode<'a>> {
+        self.iter().filter(|node| node.is_failure())
+    }
+
+    /// Refund receipts.
+    ///
+    /// Exact when receipt details are available; otherwise this uses the
+    /// zero-gas-leaf heuristic described on [`ExecutionNode::is_refund`] and
+    /// can include non-refund receipts.
+    pub fn refunds(&self) -> impl Iterator<Item = &ExecutionNode<'a>> {
+        self.iter().filter(|node| node.is_refund())
+    }
+
+    fn fmt_node(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        index: usize,
+        prefix: &str,
+        last: bool,
+    ) -> fmt::Result {
+        let node = &self.nodes[index];
+        let (branch, indent) = match (node.parent, last) {
+            (None, _) => ("", ""),
+            (Some(_), false) => ("├─ ", "│  "),
+            (Some(_), true) => ("└─ ", "   "),
+        };
+        writeln!(f, "{prefix}{branch}{node}")?;
+
+        let prefix = format!("{prefix}{indent}");
+        let continues = if node.children.is_empty() {
+            "   "
+        } else {
+            "│  "
+        };
+        for log in node.logs() {
+            writeln!(f, "{prefix}{continues}log: {log}")?;
+        }
+        for (i, &child) in node.children.iter().enumerate() {
+            self.fmt_node(f, child, &prefix, i + 1 == node.children.len())?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ExecutionTree<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, node) in self.nodes.iter().enumerate() {
+            if node.parent.is_none() {
+                self.fmt_node(f, index, "", true)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ExecutionNode<'_> {
+    /// Transaction or receipt ID.
+    pub fn id(&self) -> &CryptoHash {
+        &self.outcome.id
+    }
+
+    /// Account that executed this receipt (the signer, for the transaction).
+    pub fn executor_id(&self) -> &AccountId {
+        &self.outcome.outcome.executor_id
+    }
+
+    /// Account that created this receipt, when receipt details are available.
+    pub fn predecessor_id(&self) -> Option<&AccountId> {
+        self.receipt.map(|receipt| &receipt.predecessor_id)
+    }
+
+    /// Names of the functions this receipt calls, when receipt details are
+    /// available.
+    pub fn method_names(&self) -> impl Iterator<Item = &str> {
+        let actions = match self.receipt.map(|receipt| &receipt.receipt) {
+            Some(ReceiptContent::Action(data)) => data.actions.as_slice(),
+            _ => &[],
+        };
+        actions.iter().filter_map(|action| match action {
+            ActionView::FunctionCall { method_name, .. } => Some(method_name.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Execution status.
+    pub fn status(&self) -> &ExecutionStatus {
+        &self.outcome.outcome.status
+    }
+
+    /// Gas burnt by this execution alone.
+    pub fn gas_burnt(&self) -> Gas {
+        self.outcome.outcome.gas_burnt
+    }
+
+    /// Logs emitted by this execution.
+    pub fn logs(&self) -> &[String] {
+        &self.outcome.outcome.logs
+    }
+
+    /// Whether this is the transaction node rather than a receipt.
+    pub fn is_transaction(&self) -> bool {
+        self.transaction
+    }
+
+    /// Whether execution failed.
+    pub fn is_failure(&self) -> bool {
+        matches!(self.status(), ExecutionStatus::Failure(_))
+    }
+
+    /// Whether this is a refund receipt.
+    ///
+    /// Refunds are created by the protocol, with `system` as predecessor.
+    ///
+    /// Without receipt details (outcomes from `send_tx`) this falls back to a
+    /// heuristic: a leaf receipt that burnt no gas is taken to be a refund.
+    /// Other receipts can also report zero gas, so the fallback may
+    /// misclassify them; fetch the outcome with `tx_status` when the
+    /// distinction matters.
+    pub fn is_refund(&self) -> bool {
+        match self.receipt {
+            Some(receipt) => receipt.predecessor_id.as_str() == SYSTEM_ACCOUNT,
+            None => {
+                self.parent.is_some() && self.children.is_empty() && self.gas_burnt().as_gas() == 0
+            }
+        }
+    }
+}
+
+impl fmt::Display for ExecutionNode<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.is_transaction() {
+            "transaction"
+        } else if self.is_refund() {
+            "refund"
+        } else {
+            "receipt"
+        };
+        write!(
+            f,
+            "{kind} {} on {} ({}): ",
+            self.id(),
+            self.executor_id(),
+            self.gas_burnt()
+        )?;
+        match self.status() {
+            ExecutionStatus::Unknown => write!(f, "unknown"),
+            ExecutionStatus::Failure(err) => write!(f, "FAILED: {err}"),
+            ExecutionStatus::SuccessValue(value) if value.is_empty() => write!(f, "ok"),
+            ExecutionStatus::SuccessValue(value) => {
+                write!(f, "ok, returned {}", String::from_utf8_lossy(value))
+            }
+            ExecutionStatus::SuccessReceiptId(id) => write!(f, "ok, result from {id}"),
+        }
+    }
+}
+
+impl FinalExecutionOutcome {
+    /// Link the transaction's receipt outcomes into an [`ExecutionTree`].
+    pub fn execution_tree(&self) -> ExecutionTree<'_> {
+        ExecutionTree::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TX: &str = "9FtHUFBQsZ2MG77K3x3MJ9wjX3UT8zE1TczCrhZEcG8U";
+    const CALL: &str = "3GTGoiN3FEoJenSw5ob4YMmFEV2Fbiichj3FDBnM78xK";
+    const CALLBACK: &str = "A6DJpKBhmAMmBuQXtY3dWbo8dGVSQ9yH7BQSJBfn8rBo";
+    const REFUND: &str = "11111111111111111111111111111111";
+
+    fn outcome_json(
+        id: &str,
+        executor: &str,
+        gas: u64,
+        children: &[&str],
+        status: serde_json::Value,
+    ) -> serde_json::Value {
+        let logs: Vec<&str> = if executor == "token.near" {
+            vec!["called"]
+        } else {
+            vec![]
+        };
+        serde_json::json!({
+            "id": id,
+            "outcome": {
+                "executor_id": executor,
+                "gas_burnt": gas,
+                "tokens_burnt": "0",
+                "logs": logs,
+                "receipt_ids": children,
+                "status": status
+            },
+            "block_hash": TX,
+            "proof": []
+        })
+    }
+
+    /// alice.near → token.near (fails) → callback on alice.near → refund.
+    fn outcome() -> FinalExecutionOutcome {
+        let failure = serde_json::json!({"Failure": {"ActionError": {
+            "index": 0,
+            "kind": {"FunctionCallError": {"ExecutionError": "Smart contract panicked: nope"}}
+        }}});
+        let ok = serde_json::json!({"SuccessValue": ""});
+        let to_call = serde_json::json!({"SuccessReceiptId": CALL});
+        serde_json::from_value(serde_json::json!({
+            "status": {"SuccessValue": ""},
+            "transaction": {
+                "signer_id": "alice.near",
+                "public_key": "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+                "nonce": 1,
+                "receiver_id": "token.near",
+                "actions": [],
+                "signature": "ed25519:3s1dvMqNDCByoMnDnkhB4GPjTSXCRt4nt3Af5n1RX8W7aJ2FC6MfRf5BNXZ52EBifNJnNVBsGvke6GRYuaEYJXt5",
+                "hash": TX
+            },
+            "transaction_outcome": outcome_json(TX, "alice.near", 100, &[CALL], to_call),
+            // Deliberately out of execution order.
+            "receipts_outcome": [
+                outcome_json(REFUND, "alice.near", 0, &[], ok.clone()),
+                outcome_json(CALLBACK, "alice.near", 300, &[REFUND], ok),
+                outcome_json(CALL, "token.near", 200, &[CALLBACK], failure),
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tree_links_receipts() {
+        let outcome = outcome();
+        let tree = outcome.execution_tree();
+        assert_eq!(tree.len(), 4);
+
+        let root = tree.root();
+        assert!(root.is_transaction());
+        assert_eq!(root.executor_id().as_str(), "alice.near");
+
+        let order: Vec<String> = tree.iter().map(|node| node.id().to_string()).collect();
+        assert_eq!(order, vec![TX, CALL, CALLBACK, REFUND]);
+
+        let call = tree.get(&CALL.parse().unwrap()).unwrap();
+        assert_eq!(call.depth, 1);
+        assert_eq!(tree.parent(call).unwrap().id(), root.id());
+        let children: Vec<_> = tree.children(call).collect();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].id().to_string(), CALLBACK);
+        assert_eq!(call.logs(), ["called"]);
+    }
+
+    #[test]
+    fn test_tree_reports_failures_and_refunds() {
+        let outcome = outcome();
+        let tree = outcome.execution_tree();
+
+        let failures: Vec<_> = tree.failures().map(|node| node.id().to_string()).collect();
+        assert_eq!(failures, vec![CALL]);
+        let refunds: Vec<_> = tree.refunds().map(|node| node.id().to_string()).collect();
+        assert_eq!(refunds, vec![REFUND]);
+    }
+
+    #[test]
+    fn test_tree_display() {
+        let outcome = outcome();
+        let rendered = outcome.execution_tree().to_string();
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with(&format!("transaction {TX} on alice.near")));
+        assert!(lines[1].starts_with(&format!("└─ receipt {CALL} on token.near")));
+        assert!(lines[1].contains("FAILED: action #0"));
+        assert_eq!(lines[2], "   │  log: called");
+        assert!(lines[3].starts_with(&format!("   └─ receipt {CALLBACK}")));
+        assert!(lines[4].starts_with(&format!("      └─ refund {REFUND}")));
+    }
+}
@@ -0,0 +1,789 @@
+//! Offline light client verification.
+//!
+//! Checks data returned by `next_light_client_block` and
+//! `EXPERIMENTAL_light_client_proof` against a trusted head, so an execution
+//! outcome can be proven without trusting the RPC provider that served it.
+//! Everything here is local hashing and signature checks — it works without
+//! the `rpc` feature.
+//!
+//! The verifier follows the [light client spec]:
+//!
+//! - **Head updates** — a new [`LightClientBlockView`] is accepted when more
+//!   than 2/3 of its epoch's block producer stake signed the endorsement of the
+//!   block after next, and any `next_bps` it carries hash to `next_bp_hash`.
+//! - **Execution proofs** — the outcome hashes up its merkle path to the
+//!   chunk's outcome root, then to the block's outcome root, and the block
+//!   hashes up its merkle path to the head's `block_merkle_root`.
+//!
+//! # Example
+//!
+#![cfg_attr(feature = "rpc", doc = "```rust,no_run")]
+#![cfg_attr(not(feature = "rpc"), doc = "```rust,ignore")]
+//! use near_kit::*;
+//! use near_kit::light_client::LightClient;
+//!
+//! # async fn example(checkpoint: LightClientBlockView, tx_hash: CryptoHash) -> Result<(), Error> {
+//! let near = Near::mainnet().build();
+//!
+//! // A checkpoint obtained out of band (e.g. pinned in config) is the root of trust.
+//! let mut client = LightClient::new(checkpoint)?;
+//!
+//! // Follow the chain, one epoch at a time.
+//! while let Some(block) = near.rpc().next_light_client_block(&client.head_hash()).await? {
+//!     client.advance(block)?;
+//! }
+//!
+//! // Prove a transaction outcome against the verified head.
+//! let request = LightClientProofRequest::Transaction {
+//!     transaction_hash: tx_hash,
+//!     sender_id: "alice.near".parse()?,
+//! };
+//! let proof = near.rpc().light_client_proof(&request, &client.head_hash()).await?;
+//! client.verify_proof(&proof)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [light client spec]: https://nomicon.io/ChainSpec/LightClient
+
+use std::collections::HashMap;
+
+use borsh::BorshSerialize;
+
+use crate::error::LightClientError;
+use crate::types::{
+    CryptoHash, ExecutionOutcomeWithId, ExecutionStatus, LightClientBlockLiteView,
+    LightClientBlockView, LightClientProof, MerkleDirection, MerklePathItem, PublicKey,
+    ValidatorStakeView,
+};
+
+// ============================================================================
+// Hashing
+// ============================================================================
+
+/// Borsh layout of `BlockHeaderInnerLite`, the part of the header a light
+/// client sees in full.
+#[derive(BorshSerialize)]
+struct InnerLite {
+    height: u64,
+    epoch_id: CryptoHash,
+    next_epoch_id: CryptoHash,
+    prev_state_root: CryptoHash,
+    outcome_root: CryptoHash,
+    timestamp: u64,
+    next_bp_hash: CryptoHash,
+    block_merkle_root: CryptoHash,
+}
+
+/// Borsh layout of `ValidatorStake` (the `V1` variant), as hashed into
+/// `next_bp_hash`.
+#[derive(BorshSerialize)]
+enum ValidatorStake<'a> {
+    V1 {
+        account_id: &'a str,
+        public_key: &'a PublicKey,
+        stake: u128,
+    },
+}
+
+/// Borsh layout of `PartialExecutionOutcome` — the outcome fields that are
+/// committed to the outcome root (logs are hashed separately).
+#[derive(BorshSerialize)]
+struct PartialExecutionOutcome<'a> {
+    receipt_ids: &'a [CryptoHash],
+    gas_burnt: u64,
+    tokens_burnt: u128,
+    executor_id: &'a str,
+    status: PartialExecutionStatus<'a>,
+}
+
+/// Borsh layout of `PartialExecutionStatus`: failures are committed without
+/// their error details.
+#[derive(BorshSerialize)]
+enum PartialExecutionStatus<'a> {
+    Unknown,
+    Failure,
+    SuccessValue(&'a [u8]),
+    SuccessReceiptId(CryptoHash),
+}
+
+fn hash_borsh(value: &impl BorshSerialize) -> CryptoHash {
+    CryptoHash::hash(&borsh::to_vec(value).expect("borsh serialization to a Vec cannot fail"))
+}
+
+fn combine_hash(left: &CryptoHash, right: &CryptoHash) -> CryptoHash {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(left.as_bytes());
+    data[32..].copy_from_slice(right.as_bytes());
+    CryptoHash::hash(&data)
+}
+
+impl LightClientBlockLiteView {
+    /// Compute the block hash from the lite header.
+    ///
+    /// `sha256(sha256(sha256(inner_lite) || inner_rest_hash) || prev_block_hash)` —
+    /// the same hash the chain assigns to the full block.
+    pub fn hash(&self) -> CryptoHash {
+        block_hash(
+            &self.inner_lite,
+            &self.inner_rest_hash,
+            &self.prev_block_hash,
+        )
+    }
+}
+
+impl LightClientBlockView {
+    /// Compute the hash of this block.
+    pub fn hash(&self) -> CryptoHash {
+        block_hash(
+            &self.inner_lite,
+            &self.inner_rest_hash,
+            &self.prev_block_hash,
+        )
+    }
+
+    /// The lite header of this block (without next block producers or approvals).
+    pub fn to_lite(&self) -> LightClientBlockLiteView {
+        LightClientBlockLiteView {
+            prev_block_hash: self.prev_block_hash,
+            inner_rest_hash: self.inner_rest_hash,
+            inner_lite: self.inner_lite.clone(),
+        }
+    }
+}
+
+fn block_hash(
+    inner_lite: &crate::types::BlockHeaderInnerLiteView,
+    inner_rest_hash: &CryptoHash,
+    prev_block_hash: &CryptoHash,
+) -> CryptoHash {
+    let inner_lite_hash = hash_borsh(&InnerLite {
+        height: inner_lite.height,
+        epoch_id: inner_lite.epoch_id,
+        next_epoch_id: inner_lite.next_epoch_id,
+        prev_state_root: inner_lite.prev_state_root,
+        outcome_root: inner_lite.outcome_root,
+        timestamp: inner_lite.timestamp,
+        next_bp_hash: inner_lite.next_bp_hash,
+        block_merkle_root: inner_lite.block_merkle_root,
+    });
+    let inner_hash = combine_hash(&inner_lite_hash, inner_rest_hash);
+    combine_hash(&inner_hash, prev_block_hash)
+}
+
+/// Hash a block producer set the way headers commit it in `next_bp_hash`.
+pub fn block_producers_hash(block_producers: &[ValidatorStakeView]) -> CryptoHash {
+    let stakes: Vec<_> = block_producers
+        .iter()
+        .map(|bp| ValidatorStake::V1 {
+            account_id: bp.account_id().as_str(),
+            public_key: bp.public_key(),
+            stake: bp.stake().as_yoctonear(),
+        })
+        .collect();
+    hash_borsh(&stakes)
+}
+
+/// Hash an execution outcome the way chunks commit it in their outcome root.
+///
+/// The leaf is `sha256(borsh([id, sha256(borsh(partial_outcome)), sha256(log)...]))`;
+/// execution metadata and failure details are not committed.
+pub fn outcome_hash(outcome: &ExecutionOutcomeWithId) -> CryptoHash {
+    let o = &outcome.outcome;
+    let status = match &o.status {
+        ExecutionStatus::Unknown => PartialExecutionStatus::Unknown,
+        ExecutionStatus::Failure(_) => PartialExecutionStatus::Failure,
+        ExecutionStatus::SuccessValue(value) => PartialExecutionStatus::SuccessValue(value),
+        ExecutionStatus::SuccessReceiptId(id) => PartialExecutionStatus::SuccessReceiptId(*id),
+    };
+    let partial = PartialExecutionOutcome {
+        receipt_ids: &o.receipt_ids,
+        gas_burnt: o.gas_burnt.as_gas(),
+        tokens_burnt: o.tokens_burnt.as_yoctonear(),
+        executor_id: o.executor_id.as_str(),
+        status,
+    };
+
+    let mut hashes = Vec::with_capacity(2 + o.logs.len());
+    hashes.push(outcome.id);
+    hashes.push(hash_borsh(&partial));
+    hashes.extend(o.logs.iter().map(|log| CryptoHash::hash(log.as_bytes())));
+    hash_borsh(&hashes)
+}
+
+/// Fold a merkle path from a leaf up to the root it proves.
+pub fn compute_root_from_path(path: &[MerklePathItem], leaf: CryptoHash) -> CryptoHash {
+    path.iter().fold(leaf, |acc, item| match item.direction {
+        MerkleDirection::Left => combine_hash(&item.hash, &acc),
+        MerkleDirection::Right => combine_hash(&acc, &item.hash),
+    })
+}
+
+// ============================================================================
+// Verification
+// ============================================================================
+
+/// Verify an `EXPERIMENTAL_light_client_proof` response against the
+/// `block_merkle_root` of the head it was requested for.
+///
+/// Checks that the outcome is committed by the proof's block header, and that
+/// the header's block is an ancestor of the head. The head itself must already
+/// be trusted — see [`LightClient`] for verifying heads.
+pub fn verify_execution_proof(
+    proof: &LightClientProof,
+    head_block_merkle_root: &CryptoHash,
+) -> Result<(), LightClientError> {
+    let shard_outcome_root = compute_root_from_path(
+        &proof.outcome_proof.proof,
+        outcome_hash(&proof.outcome_proof),
+    );
+    let outcome_root = compute_root_from_path(
+        &proof.outcome_root_proof,
+        CryptoHash::hash(shard_outcome_root.as_bytes()),
+    );
+    let expected = proof.block_header_lite.inner_lite.outcome_root;
+    if outcome_root != expected {
+        return Err(LightClientError::OutcomeRootMismatch {
+            expected,
+            computed: outcome_root,
+        });
+    }
+
+    let block_merkle_root =
+        compute_root_from_path(&proof.block_proof, proof.block_header_lite.hash());
+    if block_merkle_root != *head_block_merkle_root {
+        return Err(LightClientError::BlockMerkleRootMismatch {
+            expected: *head_block_merkle_root,
+            computed: block_merkle_root,
+        });
+    }
+
+    Ok(())
+}
+
+/// The message block producers sign to endorse the block after `block`.
+///
+/// `borsh(ApprovalInner::Endorsement(next_block_hash)) || le_bytes(height + 2)`.
+fn approval_message(block: &LightClientBlockView, block_hash: &CryptoHash) -> Vec<u8> {
+    let next_block_hash = combine_hash(&block.next_block_inner_hash, block_hash);
+    let mut message = Vec::with_capacity(1 + 32 + 8);
+    message.push(0); // ApprovalInner::Endorsement
+    message.extend_from_slice(next_block_hash.as_bytes());
+    message.extend_from_slice(&(block.inner_lite.height + 2).to_le_bytes());
+    message
+}
+
+/// A light client that tracks a verified head.
+///
+/// Starts from a trusted checkpoint and only moves forward to blocks signed by
+/// more than 2/3 of the stake of the block producers it already trusts. Keeps
+/// the block producers of the head's epoch and the next one.
+#[derive(Debug, Clone)]
+pub struct LightClient {
+    head: LightClientBlockLiteView,
+    head_hash: CryptoHash,
+    epoch_block_producers: HashMap<CryptoHash, Vec<ValidatorStakeView>>,
+}
+
+impl LightClient {
+    /// Start from a trusted checkpoint.
+    ///
+    /// The checkpoint must carry `next_bps` (blocks returned by
+    /// `next_light_client_block` always do), so the next epoch's block
+    /// producers are known. It is trusted as-is: obtain it from a source you
+    /// trust, not from the RPC node you are verifying.
+    pub fn new(checkpoint: LightClientBlockView) -> Result<Self, LightClientError> {
+        let next_bps = checkpoint
+            .next_bps
+            .as_deref()
+            .ok_or(LightClientError::MissingNextBlockProducers)?;
+        check_next_block_producers(&checkpoint, next_bps)?;
+
+        let mut epoch_block_producers = HashMap::new();
+        epoch_block_producers.insert(checkpoint.inner_lite.next_epoch_id, next_bps.to_vec());
+        Ok(Self {
+            head_hash: checkpoint.hash(),
+            head: checkpoint.to_lite(),
+            epoch_block_producers,
+        })
+    }
+
+    /// Trust `block_producers` as the producers of `epoch_id`.
+    ///
+    /// Only needed to accept blocks from the checkpoint's own epoch, whose
+    /// producers the checkpoint does not carry.
+    pub fn with_block_producers(
+        mut self,
+        epoch_id: CryptoHash,
+        block_producers: Vec<ValidatorStakeView>,
+    ) -> Self {
+        self.epoch_block_producers.insert(epoch_id, block_producers);
+        self
+    }
+
+    /// The current trusted head.
+    pub fn head(&self) -> &LightClientBlockLiteView {
+        &self.head
+    }
+
+    /// Hash of the current trusted head — pass it as `last_block_hash` or
+    /// `light_client_head` to the light client RPC methods.
+    pub fn head_hash(&self) -> CryptoHash {
+        self.head_hash
+    }
+
+    /// Check that `block` may become the new head, without applying it.
+    pub fn validate_block(&self, block: &LightClientBlockView) -> Result<(), LightClientError> {
+        let head = &self.head.inner_lite;
+        let new = &block.inner_lite;
+
+        if new.height <= head.height {
+            return Err(LightClientError::StaleBlock {
+                height: new.height,
+                head_height: head.height,
+            });
+        }
+        if new.epoch_id != head.epoch_id && new.epoch_id != head.next_epoch_id {
+            return Err(LightClientError::UnexpectedEpoch {
+                epoch_id: new.epoch_id,
+            });
+        }
+        if new.epoch_id == head.next_epoch_id && block.next_bps.is_none() {
+            return Err(LightClientError::MissingNextBlockProducers);
+        }
+
+        let block_producers = self.epoch_block_producers.get(&new.epoch_id).ok_or(
+            LightClientError::UnknownBlockProducers {
+                epoch_id: new.epoch_id,
+            },
+        )?;
+
+        let message = approval_message(block, &block.hash());
+        let mut total_stake: u128 = 0;
+        let mut approved_stake: u128 = 0;
+        for (i, bp) in block_producers.iter().enumerate() {
+            let stake = bp.stake().as_yoctonear();
+            total_stake = total_stake.saturating_add(stake);
+
+            let Some(Some(signature)) = block.approvals_after_next.get(i) else {
+                continue;
+            };
+            if !signature.verify(&message, bp.public_key()) {
+                return Err(LightClientError::InvalidApprovalSignature {
+                    account_id: bp.account_id().clone(),
+                });
+            }
+            approved_stake = approved_stake.saturating_add(stake);
+        }
+        if approved_stake.saturating_mul(3) <= total_stake.saturating_mul(2) {
+            return Err(LightClientError::InsufficientApprovals {
+                approved: approved_stake,
+                total: total_stake,
+            });
+        }
+
+        if let Some(next_bps) = &block.next_bps {
+            check_next_block_producers(block, next_bps)?;
+        }
+        Ok(())
+    }
+
+    /// Validate `block` and make it the new head.
+    ///
+    /// On error the head is left unchanged.
+    pub fn advance(&mut self, block: LightClientBlockView) -> Result<(), LightClientError> {
+        self.validate_block(&block)?;
+
+        let epoch_id = block.inner_lite.epoch_id;
+        let next_epoch_id = block.inner_lite.next_epoch_id;
+        if let Some(next_bps) = block.next_bps.clone() {
+            self.epoch_block_producers.insert(next_epoch_id, next_bps);
+        }
+        self.epoch_block_producers
+            .retain(|id, _| *id == epoch_id || *id == next_epoch_id);
+
+        self.head_hash = block.hash();
+        self.head = block.to_lite();
+        Ok(())
+    }
+
+    /// Verify an execution proof requested with this client's
+    /// [`head_hash`](Self::head_hash) as the light client head.
+    pub fn verify_proof(&self, proof: &LightClientProof) -> Result<(), LightClientError> {
+        verify_execution_proof(proof, &self.head.inner_lite.block_merkle_root)
+    }
+}
+
+fn check_next_block_producers(
+    block: &LightClientBlockView,
+    next_bps: &[ValidatorStakeView],
+) -> Result<(), LightClientError> {
+    let computed = block_producers_hash(next_bps);
+    let expected = block.inner_lite.next_bp_hash;
+    if computed != expected {
+        return Err(LightClientError::NextBlockProducersMismatch { expected, computed });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        AccountId, BlockHeaderInnerLiteView, NearToken, SecretKey, ValidatorStakeViewV1,
+    };
+
+    // ========================================================================
+    // Fixtures
+    // ========================================================================
+
+    fn inner_lite(
+        height: u64,
+        epoch_id: CryptoHash,
+        next_epoch_id: CryptoHash,
+    ) -> BlockHeaderInnerLiteView {
+        BlockHeaderInnerLiteView {
+            height,
+            epoch_id,
+            next_epoch_id,
+            prev_state_root: CryptoHash::hash(b"state"),
+            outcome_root: CryptoHash::hash(b"outcome"),
+            timestamp: 1_700_000_000_000_000_000,
+            timestamp_nanosec: "1700000000000000000".to_string(),
+            next_bp_hash: CryptoHash::ZERO,
+            block_merkle_root: CryptoHash::hash(b"blocks"),
+        }
+    }
+
+    fn producers(keys: &[SecretKey], stake: u128) -> Vec<ValidatorStakeView> {
+        keys.iter()
+            .enumerate()
+            .map(|(i, key)| {
+                ValidatorStakeView::V1(ValidatorStakeViewV1 {
+                    account_id: format!("bp{i}.near").parse().unwrap(),
+                    public_key: key.public_key(),
+                    stake: NearToken::from_yoctonear(stake),
+                })
+            })
+            .collect()
+    }
+
+    /// A block in `epoch_id` signed by `signers` (indices into `keys`).
+    fn signed_block(
+        height: u64,
+        epoch_id: CryptoHash,
+        next_epoch_id: CryptoHash,
+        next_bps: Vec<ValidatorStakeView>,
+        keys: &[SecretKey],
+        signers: &[usize],
+    ) -> LightClientBlockView {
+        let mut lite = inner_lite(height, epoch_id, next_epoch_id);
+        lite.next_bp_hash = block_producers_hash(&next_bps);
+        let mut block = LightClientBlockView {
+            prev_block_hash: CryptoHash::hash(&height.to_le_bytes()),
+            next_block_inner_hash: CryptoHash::hash(b"next inner"),
+            inner_lite: lite,
+            inner_rest_hash: CryptoHash::hash(b"rest"),
+            next_bps: Some(next_bps),
+            approvals_after_next: vec![],
+        };
+        let message = approval_message(&block, &block.hash());
+        block.approvals_after_next = (0..keys.len())
+            .map(|i| signers.contains(&i).then(|| keys[i].sign(&message)))
+            .collect();
+        block
+    }
+
+    fn epoch(n: u8) -> CryptoHash {
+        CryptoHash::from_bytes([n; 32])
+    }
+
+    fn keys(n: usize) -> Vec<SecretKey> {
+        (0..n).map(|_| SecretKey::generate_ed25519()).collect()
+    }
+
+    fn checkpoint(keys: &[SecretKey]) -> LightClientBlockView {
+        signed_block(100, epoch(1), epoch(2), producers(keys, 100), keys, &[])
+    }
+
+    // ========================================================================
+    // Head updates
+    // ========================================================================
+
+    #[test]
+    fn test_lite_view_hash_matches_full_view_hash() {
+        let keys = keys(1);
+        let block = checkpoint(&keys);
+        assert_eq!(block.hash(), block.to_lite().hash());
+        assert_ne!(block.hash(), CryptoHash::ZERO);
+    }
+
+    #[test]
+    fn test_new_requires_next_block_producers() {
+        let mut block = checkpoint(&keys(1));
+        block.next_bps = None;
+        assert_eq!(
+            LightClient::new(block).unwrap_err(),
+            LightClientError::MissingNextBlockProducers
+        );
+    }
+
+    #[test]
+    fn test_new_rejects_next_bp_hash_mismatch() {
+        let mut block = checkpoint(&keys(2));
+        block.next_bps.as_mut().unwrap().pop();
+        assert!(matches!(
+            LightClient::new(block).unwrap_err(),
+            LightClientError::NextBlockProducersMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn test_advance_with_supermajority() {
+        let keys = keys(3);
+        let mut client = LightClient::new(checkpoint(&keys)).unwrap();
+
+        let next_keys = self::keys(2);
+        let block = signed_block(
+            200,
+            epoch(2),
+            epoch(3),
+            producers(&next_keys, 50),
+            &keys,
+            &[0, 1, 2],
+        );
+        let hash = block.hash();
+        client.advance(block).unwrap();
+
+        assert_eq!(client.head_hash(), hash);
+        assert_eq!(client.head().inner_lite.height, 200);
+
+        // The next epoch is signed by the producers the previous block announced.
+        let block = signed_block(
+            300,
+            epoch(3),
+            epoch(4),
+            producers(&keys, 1),
+            &next_keys,
+            &[0, 1],
+        );
+        client.advance(block).unwrap();
+        assert_eq!(client.head().inner_lite.epoch_id, epoch(3));
+    }
+
+    #[test]
+    fn test_advance_rejects_two_thirds_or_less() {
+        let keys = keys(3);
+        let mut client = LightClient::new(checkpoint(&keys)).unwrap();
+        let head = client.head_hash();
+
+        // Exactly 2/3 of the stake is not enough.
+        let block = signed_block(200, epoch(2), epoch(3), producers(&keys, 1), &keys, &[0, 1]);
+        assert_eq!(
+            client.advance(block).unwrap_err(),
+            LightClientError::InsufficientApprovals {
+                approved: 200,
+                total: 300,
+            }
+        );
+        assert_eq!(client.head_hash(), head);
+    }
+
+    #[test]
+    fn test_advance_rejects_bad_signature() {
+        let keys = keys(3);
+        let client = LightClient::new(checkpoint(&keys)).unwrap();
+
+        let mut block = signed_block(
+            200,
+            epoch(2),
+            epoch(3),
+            producers(&keys, 1),
+            &keys,
+            &[0, 1, 2],
+        );
+        // Swap in a signature over a different message.
+        block.approvals_after_next[1] = Some(keys[1].sign(b"something else"));
+        assert_eq!(
+            client.validate_block(&block).unwrap_err(),
+            LightClientError::InvalidApprovalSignature {
+                account_id: "bp1.near".parse::<AccountId>().unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_advance_rejects_tampered_header() {
+        let keys = keys(3);
+        let client = LightClient::new(checkpoint(&keys)).unwrap();
+
+        let mut block = signed_block(
+            200,
+            epoch(2),
+            epoch(3),
+            producers(&keys, 1),
+            &keys,
+            &[0, 1, 2],
+        );
+        // Approvals sign the block hash, so changing any committed field breaks them.
+        block.inner_lite.outcome_root = CryptoHash::hash(b"forged");
+        assert!(matches!(
+            client.validate_block(&block).unwrap_err(),
+            LightClientError::InvalidApprovalSignature { .. }
+        ));
+    }
+
+    #[test]
+    fn test_advance_rejects_stale_and_unknown_epochs() {
+        let keys = keys(1);
+        let client = LightClient::new(checkpoint(&keys)).unwrap();
+
+        let block = signed_block(100, epoch(2), epoch(3), producers(&keys, 1), &keys, &[0]);
+        assert!(matches!(
+            client.validate_block(&block).unwrap_err(),
+            LightClientError::StaleBlock {
+                height: 100,
+                head_height: 100
+            }
+        ));
+
+        let block = signed_block(200, epoch(7), epoch(8), producers(&keys, 1), &keys, &[0]);
+        assert_eq!(
+            client.validate_block(&block).unwrap_err(),
+            LightClientError::UnexpectedEpoch { epoch_id: epoch(7) }
+        );
+
+        // Same epoch as the checkpoint: producers unknown unless supplied.
+        let block = signed_block(150, epoch(1), epoch(2), producers(&keys, 1), &keys, &[0]);
+        assert_eq!(
+            client.validate_block(&block).unwrap_err(),
+            LightClientError::UnknownBlockProducers { epoch_id: epoch(1) }
+        );
+        let client = client.with_block_producers(epoch(1), producers(&keys, 1));
+        client.validate_block(&block).unwrap();
+    }
+
+    // ========================================================================
+    // Execution proofs
+    // ========================================================================
+
+    fn outcome() -> ExecutionOutcomeWithId {
+        serde_json::from_value(serde_json::json!({
+            "id": CryptoHash::hash(b"tx").to_string(),
+            "outcome": {
+                "executor_id": "alice.near",
+                "gas_burnt": 2428135649664u64,
+                "tokens_burnt": "242813564966400000000",
+                "logs": ["EVENT_JSON:{}", "hello"],
+                "receipt_ids": [CryptoHash::hash(b"receipt").to_string()],
+                "status": { "SuccessReceiptId": CryptoHash::hash(b"receipt").to_string() },
+            },
+            "proof": [
+                { "hash": CryptoHash::hash(b"sibling").to_string(), "direction": "Right" },
+            ],
+            "block_hash": CryptoHash::hash(b"block").to_string(),
+        }))
+        .unwrap()
+    }
+
+    fn path(items: &[(&[u8], MerkleDirection)]) -> Vec<MerklePathItem> {
+        items
+            .iter()
+            .map(|(data, direction)| MerklePathItem {
+                hash: CryptoHash::hash(data),
+                direction: direction.clone(),
+            })
+            .collect()
+    }
+
+    /// A proof whose roots are consistent; returns it with the head's block merkle root.
+    fn consistent_proof() -> (LightClientProof, CryptoHash) {
+        let outcome_proof = outcome();
+        let outcome_root_proof = path(&[(b"shard0", MerkleDirection::Left)]);
+
+        let shard_root = compute_root_from_path(&outcome_proof.proof, outcome_hash(&outcome_proof));
+        let mut header = checkpoint(&keys(1)).to_lite();
+        header.inner_lite.outcome_root =
+            compute_root_from_path(&outcome_root_proof, CryptoHash::hash(shard_root.as_bytes()));
+
+        let block_proof = path(&[
+            (b"older", MerkleDirection::Left),
+            (b"newer", MerkleDirection::Right),
+        ]);
+        let head_block_merkle_root = compute_root_from_path(&block_proof, header.hash());
+
+        let proof = LightClientProof {
+            outcome_proof,
+            outcome_root_proof,
+            block_header_lite: header,
+            block_proof,
+        };
+        (proof, head_block_merkle_root)
+    }
+
+    #[test]
+    fn test_compute_root_from_path_directions() {
+        let leaf = CryptoHash::hash(b"leaf");
+        let left = CryptoHash::hash(b"left");
+        let right = CryptoHash::hash(b"right");
+        let root = compute_root_from_path(
+            &[
+                MerklePathItem {
+                    hash: left,
+                    direction: MerkleDirection::Left,
+                },
+                MerklePathItem {
+                    hash: right,
+                    direction: MerkleDirection::Right,
+                },
+            ],
+            leaf,
+        );
+        assert_eq!(root, combine_hash(&combine_hash(&left, &leaf), &right));
+        assert_eq!(compute_root_from_path(&[], leaf), leaf);
+    }
+
+    #[test]
+    fn test_outcome_hash_commits_logs_and_status() {
+        let base = outcome();
+        let mut without_log = base.clone();
+        without_log.outcome.logs.pop();
+        assert_ne!(outcome_hash(&base), outcome_hash(&without_log));
+
+        let mut other_status = base.clone();
+        other_status.outcome.status = ExecutionStatus::SuccessValue(vec![]);
+        assert_ne!(outcome_hash(&base), outcome_hash(&other_status));
+    }
+
+    #[test]
+    fn test_verify_execution_proof_accepts_consistent_proof() {
+        let (proof, root) = consistent_proof();
+        verify_execution_proof(&proof, &root).unwrap();
+    }
+
+    #[test]
+    fn test_verify_execution_proof_rejects_tampered_outcome() {
+        let (mut proof, root) = consistent_proof();
+        proof.outcome_proof.outcome.gas_burnt = crate::types::Gas::from_gas(1);
+        assert!(matches!(
+            verify_execution_proof(&proof, &root).unwrap_err(),
+            LightClientError::OutcomeRootMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn test_verify_execution_proof_rejects_foreign_head() {
+        let (proof, _) = consistent_proof();
+        let other_root = CryptoHash::hash(b"other chain");
+        assert_eq!(
+            verify_execution_proof(&proof, &other_root).unwrap_err(),
+            LightClientError::BlockMerkleRootMismatch {
+                expected: other_root,
+                computed: compute_root_from_path(
+                    &proof.block_proof,
+                    proof.block_header_lite.hash()
+                ),
+            }
+        );
+    }
+}
@@ -48,11 +48,14 @@
 mod account;
 mod action;
 mod block_reference;
-mod csprng;
+pub(crate) mod csprng;
 mod error;
+mod events;
+mod execution_tree;
 mod hash;
 mod hd;
 mod key;
+pub mod light_client;
 pub mod nep413;
 mod network;
 pub(crate) mod rpc;
@@ -73,13 +76,21 @@ pub use action::{
     TransferToGasKeyAction, UseGlobalContractAction, VersionedDelegateActionPayload,
     VersionedSignedDelegateAction, WithdrawFromGasKeyAction,
 };
-pub use block_reference::{BlockReference, Finality, SyncCheckpoint, TxExecutionStatus};
+pub use block_reference::{
+    BlockReference, ChunkReference, Finality, SyncCheckpoint, TxExecutionStatus,
+};
 pub use error::{
     ActionError, ActionErrorKind, ActionsValidationError, CompilationError,
     DepositCostFailureReason, FunctionCallError, HostError, InvalidAccessKeyError, InvalidTxError,
     MethodResolveError, PrepareError, ReceiptValidationError, StorageError, TxExecutionError,
     UnknownError, WasmTrap,
 };
+pub use events::{
+    EVENT_LOG_PREFIX, FtBurnEvent, FtEvent, FtMintEvent, FtTransferEvent, MtBurnEvent, MtEvent,
+    MtMintEvent, MtTransferEvent, NearEvent, NftBurnEvent, NftEvent, NftMintEvent,
+    NftTransferEvent,
+};
+pub use execution_tree::{ExecutionNode, ExecutionTree};
 pub use hash::CryptoHash;
 pub use key::{
     DEFAULT_HD_PATH, DEFAULT_ML_DSA_65_WORD_COUNT, DEFAULT_WORD_COUNT, KeyPair, KeyType,
@@ -92,7 +103,7 @@ pub use rpc::{
     AccessKeyDetails, AccessKeyInfoView, AccessKeyListView, AccessKeyPermissionView, AccessKeyView,
     AccountBalance, AccountContractView, AccountView, ActionReceiptData, ActionView,
     BandwidthRequest, BandwidthRequestBitmap, BandwidthRequests, BandwidthRequestsV1, BlockEffects,
-    BlockHeaderView, BlockView, ChunkHeaderView, CongestionInfoView, ContractCodeView,
+    BlockHeaderView, BlockView, ChunkHeaderView, ChunkView, CongestionInfoView, ContractCodeView,
     DataReceiptData, DataReceiverView, DelegateActionV2View, DelegateActionView, ExecutionMetadata,
     ExecutionOutcome, ExecutionOutcomeWithId, ExecutionStatus, FinalExecutionOutcome,
     FinalExecutionStatus, GasKeyNoncesView, GasPrice, GasProfileEntry,
@@ -104,9 +115,14 @@ pub use rpc::{
     ViewFunctionResult, ViewStateAllResult, ViewStateResult,
 };
 pub use rpc_extra::{
-    BlockHeaderInnerLiteView, CurrentEpochValidatorInfo, EpochValidatorInfo,
-    LightClientBlockLiteView, LightClientBlockView, NextEpochValidatorInfo, StateChangeCauseView,
-    StateChangeValueView, StateChangeWithCauseView, ValidatorKickoutReason, ValidatorKickoutView,
+    AccessKeyCreationConfigView, AccountCreationConfigView, AccountInfo, AccountWithPublicKey,
+    ActionCreationConfigView, BlockHeaderInnerLiteView, CongestionLevelResponse,
+    CurrentEpochValidatorInfo, DataReceiptCreationConfigView, EpochValidatorInfo, FeeView,
+    GenesisConfig, KnownProducerView, LightClientBlockLiteView, LightClientBlockView,
+    LightClientProof, LightClientProofRequest, NetworkInfoView, NextEpochValidatorInfo,
+    PeerInfoView, ProtocolConfigView, RuntimeConfigView, RuntimeFeesConfigView,
+    StateChangeCauseView, StateChangeValueView, StateChangeWithCauseView, StateChangesRequest,
+    StateChangesResponse, StorageUsageConfigView, ValidatorKickoutReason, ValidatorKickoutView,
 };
 pub use transaction::{
     Nonce, NonceIndex, NonceMode as TransactionNonceMode, SignedTransaction, SignedTransactionV1,
@@ -396,13 +396,7 @@ pub fn serialize_message(params: &SignMessageParams) -> CryptoHash {
     let tag_bytes = NEP413_TAG.to_le_bytes();
 
     // Serialize payload
-    let payload = Nep413Payload {
-        message: params.message.clone(),
-        nonce: params.nonce,
-        recipient: params.recipient.clone(),
-        callback_url: params.callback_url.clone(),
-    };
-    let payload_bytes = borsh::to_vec(&payload).expect("Borsh serialization should not fail");
+    let payload_bytes = payload_bytes(params);
 
     // Concatenate tag + payload
     let mut combined = Vec::with_capacity(tag_bytes.len() + payload_bytes.len());
@@ -413,6 +407,20 @@ pub fn serialize_message(params: &SignMessageParams) -> CryptoHash {
     CryptoHash::hash(&combined)
 }
 
+/// Borsh encoding of the NEP-413 payload, without the tag prefix.
+///
+/// This is what hardware wallets expect: they add the tag and hash it
+/// themselves.
+pub(crate) fn payload_bytes(params: &SignMessageParams) -> Vec<u8> {
+    let payload = Nep413Payload {
+        message: params.message.clone(),
+        nonce: params.nonce,
+        recipient: params.recipient.clone(),
+        callback_url: params.callback_url.clone(),
+    };
+    borsh::to_vec(&payload).expect("Borsh serialization should not fail")
+}
+
 /// Verify a NEP-413 signature without RPC (cryptographic verification only).
 ///
 /// This checks:
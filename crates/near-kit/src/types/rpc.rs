@@ -18,6 +18,7 @@ use super::action::{
 };
 use super::block_reference::TxExecutionStatus;
 use super::error::{ActionError, TxExecutionError};
+use super::events::NearEvent;
 use super::transaction::TransactionNonce;
 use super::{AccountId, CryptoHash, Gas, NearToken, Nonce, PublicKey, PublicKeyHandle, Signature};
 use crate::error::ActionViewConversionError;
@@ -568,6 +569,13 @@ impl ValidatorStakeView {
         }
     }
 
+    /// Get the public key.
+    pub fn public_key(&self) -> &PublicKey {
+        match self {
+            Self::V1(v) => &v.public_key,
+        }
+    }
+
     /// Get the stake amount.
     pub fn stake(&self) -> NearToken {
         match self {
@@ -641,6 +649,21 @@ pub struct ChunkHeaderView {
     pub signature: Signature,
 }
 
+/// Chunk contents from the `chunk` RPC.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChunkView {
+    /// Chunk producer account ID.
+    pub author: AccountId,
+    /// Chunk header.
+    pub header: ChunkHeaderView,
+    /// Transactions included in this chunk.
+    #[serde(default)]
+    pub transactions: Vec<TransactionView>,
+    /// Receipts applied in this chunk.
+    #[serde(default)]
+    pub receipts: Vec<Receipt>,
+}
+
 /// Bandwidth requests for a chunk (versioned).
 #[derive(Debug, Clone, Deserialize)]
 pub enum BandwidthRequests {
@@ -916,6 +939,31 @@ impl FinalExecutionOutcome {
         &self.transaction_outcome.id
     }
 
+    /// NEP-297 events emitted by the transaction's receipts, in execution
+    /// order.
+    ///
+    /// Receipts that failed are skipped: their state changes were rolled
+    /// back, so the events they logged did not happen.
+    ///
+    /// ```rust,no_run
+    /// # use near_kit::*;
+    /// # fn example(outcome: &FinalExecutionOutcome) {
+    /// for event in outcome.events() {
+    ///     if let Some(FtEvent::Transfer(transfers)) = event.ft_event() {
+    ///         for t in transfers {
+    ///             println!("{} -> {}: {}", t.old_owner_id, t.new_owner_id, t.amount);
+    ///         }
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn events(&self) -> impl Iterator<Item = NearEvent> + '_ {
+        self.receipts_outcome
+            .iter()
+            .filter(|receipt| !matches!(receipt.outcome.status, ExecutionStatus::Failure(_)))
+            .flat_map(|receipt| receipt.events())
+    }
+
     /// Get total gas used across all receipts.
     pub fn total_gas_used(&self) -> Gas {
         let tx_gas = self.transaction_outcome.outcome.gas_burnt.as_gas();
@@ -1697,6 +1745,19 @@ pub struct ExecutionOutcomeWithId {
     pub block_hash: CryptoHash,
 }
 
+impl ExecutionOutcomeWithId {
+    /// NEP-297 events logged by this execution, in log order.
+    ///
+    /// Log lines that are not `EVENT_JSON:` events, or whose JSON is not a
+    /// valid event, are skipped.
+    pub fn events(&self) -> impl Iterator<Item = NearEvent> + '_ {
+        self.outcome
+            .logs
+            .iter()
+            .filter_map(|log| NearEvent::from_log(log, self.id, self.outcome.executor_id.clone()))
+    }
+}
+
 /// Execution outcome details.
 #[derive(Debug, Clone, Deserialize)]
 pub struct ExecutionOutcome {
@@ -2730,6 +2791,71 @@ mod tests {
         assert!(outcome.failure_error().is_some());
     }
 
+    #[test]
+    fn test_final_execution_outcome_events_skip_failed_receipts() {
+        let receipt = |id: &str, status: serde_json::Value, logs: Vec<&str>| {
+            serde_json::json!({
+                "id": id,
+                "outcome": {
+                    "executor_id": "token.near",
+                    "gas_burnt": 0,
+                    "tokens_burnt": "0",
+                    "logs": logs,
+                    "receipt_ids": [],
+                    "status": status
+                },
+                "block_hash": "A6DJpKBhmAMmBuQXtY3dWbo8dGVSQ9yH7BQSJBfn8rBo",
+                "proof": []
+            })
+        };
+        let transfer = r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"alice.near","new_owner_id":"bob.near","amount":"5"}]}"#;
+        let burn = r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_burn","data":[{"owner_id":"alice.near","amount":"1"}]}"#;
+        let json = serde_json::json!({
+            "status": {"SuccessValue": ""},
+            "transaction": {
+                "signer_id": "alice.near",
+                "public_key": "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+                "nonce": 1,
+                "receiver_id": "token.near",
+                "actions": [],
+                "signature": "ed25519:3s1dvMqNDCByoMnDnkhB4GPjTSXCRt4nt3Af5n1RX8W7aJ2FC6MfRf5BNXZ52EBifNJnNVBsGvke6GRYuaEYJXt5",
+                "hash": "9FtHUFBQsZ2MG77K3x3MJ9wjX3UT8zE1TczCrhZEcG8U"
+            },
+            "transaction_outcome": receipt(
+                "9FtHUFBQsZ2MG77K3x3MJ9wjX3UT8zE1TczCrhZEcG8U",
+                serde_json::json!("Unknown"),
+                vec![],
+            ),
+            "receipts_outcome": [
+                receipt(
+                    "3GTGoiN3FEoJenSw5ob4YMmFEV2Fbiichj3FDBnM78xK",
+                    serde_json::json!({"SuccessValue": ""}),
+                    vec!["Transfer 5 from alice.near to bob.near", transfer],
+                ),
+                receipt(
+                    "A6DJpKBhmAMmBuQXtY3dWbo8dGVSQ9yH7BQSJBfn8rBo",
+                    serde_json::json!({"Failure": {"ActionError": {
+                        "index": 0,
+                        "kind": {"FunctionCallError": {"ExecutionError": "Smart contract panicked"}}
+                    }}}),
+                    vec![burn],
+                ),
+            ]
+        });
+        let outcome: FinalExecutionOutcome = serde_json::from_value(json).unwrap();
+
+        let events: Vec<NearEvent> = outcome.events().collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "ft_transfer");
+        assert_eq!(
+            events[0].receipt_id,
+            "3GTGoiN3FEoJenSw5ob4YMmFEV2Fbiichj3FDBnM78xK"
+                .parse::<CryptoHash>()
+                .unwrap()
+        );
+        assert_eq!(events[0].executor_id.as_str(), "token.near");
+    }
+
     // ========================================================================
     // FinalExecutionOutcome result/json tests
     // ========================================================================
@@ -1,11 +1,16 @@
-//! Additional RPC response types for validators, light client, and state changes.
+//! Additional RPC response types for validators, light client, state changes,
+//! and protocol/network configuration.
 
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_with::{base64::Base64, serde_as};
 
-use super::rpc::{AccessKeyDetails, ValidatorStakeView};
-use super::{AccountId, CryptoHash, NearToken, PublicKey, PublicKeyHandle, Signature};
+use super::rpc::{AccessKeyDetails, ExecutionOutcomeWithId, MerklePathItem, ValidatorStakeView};
+use super::{
+    AccessKeyPermission, AccountId, Action, CryptoHash, Gas, NearToken, PublicKey, PublicKeyHandle,
+    Signature,
+};
 
 // ============================================================================
 // Validators / Epoch types
@@ -214,10 +219,616 @@ pub struct BlockHeaderInnerLiteView {
     pub block_merkle_root: CryptoHash,
 }
 
+/// Which execution outcome to prove with `EXPERIMENTAL_light_client_proof`.
+///
+/// The light client head the proof is built against is passed separately
+/// (see `RpcClient::light_client_proof`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LightClientProofRequest {
+    /// The outcome of a transaction (its conversion into a receipt).
+    Transaction {
+        /// Transaction hash.
+        transaction_hash: CryptoHash,
+        /// Account that signed the transaction.
+        sender_id: AccountId,
+    },
+    /// The outcome of a receipt.
+    Receipt {
+        /// Receipt ID.
+        receipt_id: CryptoHash,
+        /// Account the receipt executed on.
+        receiver_id: AccountId,
+    },
+}
+
+/// Response from `EXPERIMENTAL_light_client_proof`.
+///
+/// Proves that an execution outcome was committed by a block, and that the
+/// block is an ancestor of the light client head the proof was requested for.
+/// Check it offline with [`light_client::verify_execution_proof`] or
+/// [`LightClient::verify_proof`].
+///
+/// [`light_client::verify_execution_proof`]: crate::light_client::verify_execution_proof
+/// [`LightClient::verify_proof`]: crate::light_client::LightClient::verify_proof
+#[derive(Debug, Clone, Deserialize)]
+pub struct LightClientProof {
+    /// The outcome, with the merkle path from its hash to the chunk's outcome root.
+    pub outcome_proof: ExecutionOutcomeWithId,
+    /// Merkle path from the chunk's outcome root to the block's outcome root.
+    pub outcome_root_proof: Vec<MerklePathItem>,
+    /// Header of the block whose outcome root commits the outcome.
+    pub block_header_lite: LightClientBlockLiteView,
+    /// Merkle path from that block's hash to the head's block merkle root.
+    pub block_proof: Vec<MerklePathItem>,
+}
+
+// ============================================================================
+// Protocol / genesis configuration types
+// ============================================================================
+
+/// Protocol configuration at a block, from `EXPERIMENTAL_protocol_config`.
+///
+/// Unlike [`GenesisConfig`], this reflects the protocol version and runtime
+/// parameters in force at the queried block, including every upgrade since
+/// genesis. Fields not modelled here are kept in [`extra`](Self::extra).
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProtocolConfigView {
+    /// Protocol version in force.
+    pub protocol_version: u32,
+    /// Genesis time (RFC 3339).
+    pub genesis_time: String,
+    /// Chain ID (e.g. `mainnet`, `testnet`).
+    pub chain_id: String,
+    /// Height of the genesis block.
+    pub genesis_height: u64,
+    /// Number of blocks per epoch.
+    pub epoch_length: u64,
+    /// Gas limit per chunk.
+    pub gas_limit: Gas,
+    /// Minimum gas price.
+    pub min_gas_price: NearToken,
+    /// Maximum gas price.
+    pub max_gas_price: NearToken,
+    /// Gas price adjustment rate, as `(numerator, denominator)`.
+    #[serde(default)]
+    pub gas_price_adjustment_rate: Option<(u64, u64)>,
+    /// Number of blocks a transaction stays valid for after its block hash.
+    pub transaction_validity_period: u64,
+    /// Number of block producer seats.
+    #[serde(default)]
+    pub num_block_producer_seats: Option<u64>,
+    /// Protocol treasury account.
+    #[serde(default)]
+    pub protocol_treasury_account: Option<AccountId>,
+    /// Protocol reward rate, as `(numerator, denominator)`.
+    #[serde(default)]
+    pub protocol_reward_rate: Option<(u64, u64)>,
+    /// Maximum inflation rate, as `(numerator, denominator)`.
+    #[serde(default)]
+    pub max_inflation_rate: Option<(u64, u64)>,
+    /// Expected number of blocks per year.
+    #[serde(default)]
+    pub num_blocks_per_year: Option<u64>,
+    /// Runtime parameters: storage cost, action fees, limits.
+    pub runtime_config: RuntimeConfigView,
+    /// Shard layout (versioned; kept as raw JSON).
+    #[serde(default)]
+    pub shard_layout: serde_json::Value,
+    /// Remaining fields, as raw JSON.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Runtime parameters, from [`ProtocolConfigView::runtime_config`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct RuntimeConfigView {
+    /// Cost of storing one byte of state.
+    pub storage_amount_per_byte: NearToken,
+    /// Gas fees for transactions, receipts and actions.
+    pub transaction_costs: RuntimeFeesConfigView,
+    /// Account creation rules.
+    #[serde(default)]
+    pub account_creation_config: Option<AccountCreationConfigView>,
+    /// WASM VM limits and costs (kept as raw JSON).
+    #[serde(default)]
+    pub wasm_config: serde_json::Value,
+    /// Remaining fields, as raw JSON.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Gas fees charged for transactions and actions.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RuntimeFeesConfigView {
+    /// Fee for creating an action receipt.
+    pub action_receipt_creation_config: FeeView,
+    /// Fees for creating a data receipt.
+    pub data_receipt_creation_config: DataReceiptCreationConfigView,
+    /// Per-action fees.
+    pub action_creation_config: ActionCreationConfigView,
+    /// Storage usage accounting.
+    pub storage_usage_config: StorageUsageConfigView,
+    /// Share of burnt gas rewarded to the contract, as `(numerator, denominator)`.
+    pub burnt_gas_reward: (u64, u64),
+    /// Gas price inflation applied to pessimistic prepaid gas, as
+    /// `(numerator, denominator)`.
+    pub pessimistic_gas_price_inflation_ratio: (u64, u64),
+}
+
+impl RuntimeFeesConfigView {
+    /// Send plus execution fee of the action receipt itself, charged once per
+    /// receipt on top of its actions' fees.
+    pub fn receipt_fee(&self, sender_is_receiver: bool) -> Gas {
+        self.action_receipt_creation_config
+            .total(sender_is_receiver)
+    }
+
+    /// Send plus execution fee of a single action.
+    ///
+    /// This is the static protocol fee only — gas burnt while running a
+    /// function call is on top of it. Returns `None` for actions whose fees
+    /// this view does not model (global contracts, deterministic state init,
+    /// gas key transfers and `DelegateV2`).
+    pub fn action_fee(&self, action: &Action, sender_is_receiver: bool) -> Option<Gas> {
+        let fees = &self.action_creation_config;
+        let sir = sender_is_receiver;
+        let gas = match action {
+            Action::CreateAccount(_) => fees.create_account_cost.total(sir),
+            Action::DeployContract(deploy) => per_byte_fee(
+                &fees.deploy_contract_cost,
+                &fees.deploy_contract_cost_per_byte,
+                deploy.code.len() as u64,
+                sir,
+            ),
+            Action::FunctionCall(call) => per_byte_fee(
+                &fees.function_call_cost,
+                &fees.function_call_cost_per_byte,
+                (call.method_name.len() + call.args.len()) as u64,
+                sir,
+            ),
+            Action::Transfer(_) => fees.transfer_cost.total(sir),
+            Action::Stake(_) => fees.stake_cost.total(sir),
+            Action::AddKey(add_key) => match &add_key.access_key.permission {
+                AccessKeyPermission::FullAccess | AccessKeyPermission::GasKeyFullAccess(_) => {
+                    fees.add_key_cost.full_access_cost.total(sir)
+                }
+                AccessKeyPermission::FunctionCall(permission)
+                | AccessKeyPermission::GasKeyFunctionCall(_, permission) => {
+                    // nearcore counts a null terminator after each method name.
+                    let bytes = permission
+                        .method_names
+                        .iter()
+                        .map(|name| name.len() as u64 + 1)
+                        .sum();
+                    per_byte_fee(
+                        &fees.add_key_cost.function_call_cost,
+                        &fees.add_key_cost.function_call_cost_per_byte,
+                        bytes,
+                        sir,
+                    )
+                }
+            },
+            Action::DeleteKey(_) => fees.delete_key_cost.total(sir),
+            Action::DeleteAccount(_) => fees.delete_account_cost.total(sir),
+            Action::Delegate(signed) => {
+                // The delegate action pays for the inner receipt it creates,
+                // priced by the inner sender/receiver relationship.
+                let inner = &signed.delegate_action;
+                let inner_sir = inner.sender_id == inner.receiver_id;
+                let mut total = fees
+                    .delegate_cost
+                    .total(sir)
+                    .as_gas()
+                    .saturating_add(self.receipt_fee(inner_sir).as_gas());
+                for action in &inner.actions {
+                    let fee = self.action_fee(action.inner(), inner_sir)?;
+                    total = total.saturating_add(fee.as_gas());
+                }
+                Gas::from_gas(total)
+            }
+            Action::DeployGlobalContract(_)
+            | Action::UseGlobalContract(_)
+            | Action::DeterministicStateInit(_)
+            | Action::TransferToGasKey(_)
+            | Action::WithdrawFromGasKey(_)
+            | Action::DelegateV2(_) => return None,
+        };
+        Some(gas)
+    }
+}
+
+/// `base + per_byte * bytes`, saturating.
+fn per_byte_fee(base: &FeeView, per_byte: &FeeView, bytes: u64, sir: bool) -> Gas {
+    Gas::from_gas(
+        base.total(sir)
+            .as_gas()
+            .saturating_add(per_byte.total(sir).as_gas().saturating_mul(bytes)),
+    )
+}
+
+/// A gas fee, split into the send and execution parts.
+///
+/// The send fee is charged on the sender's shard when the receipt is created;
+/// which one applies depends on whether sender and receiver are the same
+/// account (`sir`, "sender is receiver").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct FeeView {
+    /// Send fee when sender is receiver.
+    pub send_sir: Gas,
+    /// Send fee when sender is not receiver.
+    pub send_not_sir: Gas,
+    /// Execution fee, charged on the receiver's shard.
+    pub execution: Gas,
+}
+
+impl FeeView {
+    /// The send fee for the given sender/receiver relationship.
+    pub fn send_fee(&self, sender_is_receiver: bool) -> Gas {
+        if sender_is_receiver {
+            self.send_sir
+        } else {
+            self.send_not_sir
+        }
+    }
+
+    /// Send plus execution fee.
+    pub fn total(&self, sender_is_receiver: bool) -> Gas {
+        self.send_fee(sender_is_receiver)
+            .checked_add(self.execution)
+            .unwrap_or(Gas::from_gas(u64::MAX))
+    }
+}
+
+/// Fees for data receipts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct DataReceiptCreationConfigView {
+    /// Base fee per data receipt.
+    pub base_cost: FeeView,
+    /// Additional fee per byte of data.
+    pub cost_per_byte: FeeView,
+}
+
+/// Per-action fees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct ActionCreationConfigView {
+    /// `CreateAccount`.
+    pub create_account_cost: FeeView,
+    /// `DeployContract` base fee.
+    pub deploy_contract_cost: FeeView,
+    /// `DeployContract` fee per byte of code.
+    pub deploy_contract_cost_per_byte: FeeView,
+    /// `FunctionCall` base fee.
+    pub function_call_cost: FeeView,
+    /// `FunctionCall` fee per byte of method name and arguments.
+    pub function_call_cost_per_byte: FeeView,
+    /// `Transfer`.
+    pub transfer_cost: FeeView,
+    /// `Stake`.
+    pub stake_cost: FeeView,
+    /// `AddKey`.
+    pub add_key_cost: AccessKeyCreationConfigView,
+    /// `DeleteKey`.
+    pub delete_key_cost: FeeView,
+    /// `DeleteAccount`.
+    pub delete_account_cost: FeeView,
+    /// `Delegate`.
+    pub delegate_cost: FeeView,
+}
+
+/// `AddKey` fees by permission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct AccessKeyCreationConfigView {
+    /// Full access key.
+    pub full_access_cost: FeeView,
+    /// Function call key base fee.
+    pub function_call_cost: FeeView,
+    /// Function call key fee per byte of method names.
+    pub function_call_cost_per_byte: FeeView,
+}
+
+/// Storage usage accounting parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct StorageUsageConfigView {
+    /// Bytes charged for an account record.
+    pub num_bytes_account: u64,
+    /// Extra bytes charged per storage record.
+    pub num_extra_bytes_record: u64,
+}
+
+/// Account creation rules.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccountCreationConfigView {
+    /// Shortest top-level account name anyone may create.
+    pub min_allowed_top_level_account_length: u8,
+    /// Account allowed to create shorter top-level accounts.
+    pub registrar_account_id: AccountId,
+}
+
+/// Genesis configuration, from `genesis_config`.
+///
+/// The chain's parameters at genesis — for the parameters in force now, use
+/// [`ProtocolConfigView`]. Fields not modelled here are kept in
+/// [`extra`](Self::extra).
+#[derive(Debug, Clone, Deserialize)]
+pub struct GenesisConfig {
+    /// Protocol version at genesis.
+    pub protocol_version: u32,
+    /// Genesis time (RFC 3339).
+    pub genesis_time: String,
+    /// Chain ID (e.g. `mainnet`, `testnet`).
+    pub chain_id: String,
+    /// Height of the genesis block.
+    pub genesis_height: u64,
+    /// Number of blocks per epoch.
+    pub epoch_length: u64,
+    /// Gas limit per chunk.
+    pub gas_limit: Gas,
+    /// Minimum gas price.
+    pub min_gas_price: NearToken,
+    /// Maximum gas price.
+    pub max_gas_price: NearToken,
+    /// Total token supply at genesis.
+    pub total_supply: NearToken,
+    /// Number of blocks a transaction stays valid for after its block hash.
+    pub transaction_validity_period: u64,
+    /// Validators at genesis.
+    #[serde(default)]
+    pub validators: Vec<AccountInfo>,
+    /// Number of block producer seats.
+    #[serde(default)]
+    pub num_block_producer_seats: Option<u64>,
+    /// Protocol treasury account.
+    #[serde(default)]
+    pub protocol_treasury_account: Option<AccountId>,
+    /// Protocol reward rate, as `(numerator, denominator)`.
+    #[serde(default)]
+    pub protocol_reward_rate: Option<(u64, u64)>,
+    /// Maximum inflation rate, as `(numerator, denominator)`.
+    #[serde(default)]
+    pub max_inflation_rate: Option<(u64, u64)>,
+    /// Expected number of blocks per year.
+    #[serde(default)]
+    pub num_blocks_per_year: Option<u64>,
+    /// Shard layout (versioned; kept as raw JSON).
+    #[serde(default)]
+    pub shard_layout: serde_json::Value,
+    /// Remaining fields, as raw JSON.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A staked account, as listed in [`GenesisConfig::validators`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccountInfo {
+    /// Validator account ID.
+    pub account_id: AccountId,
+    /// Validator public key.
+    pub public_key: PublicKey,
+    /// Staked amount.
+    pub amount: NearToken,
+}
+
+// ============================================================================
+// Network types
+// ============================================================================
+
+/// Peer connectivity of the node, from `network_info`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NetworkInfoView {
+    /// Currently connected peers.
+    pub active_peers: Vec<PeerInfoView>,
+    /// Number of connected peers.
+    pub num_active_peers: u64,
+    /// Maximum number of peers.
+    pub peer_max_count: u32,
+    /// Outbound traffic.
+    pub sent_bytes_per_sec: u64,
+    /// Inbound traffic.
+    pub received_bytes_per_sec: u64,
+    /// Block producers the node knows how to reach.
+    #[serde(default)]
+    pub known_producers: Vec<KnownProducerView>,
+}
+
+/// A connected peer.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PeerInfoView {
+    /// Peer ID.
+    pub id: String,
+    /// Peer address, if known.
+    #[serde(default)]
+    pub addr: Option<String>,
+    /// Validator account of the peer, if any.
+    #[serde(default)]
+    pub account_id: Option<AccountId>,
+}
+
+/// A block producer reachable from the node.
+#[derive(Debug, Clone, Deserialize)]
+pub struct KnownProducerView {
+    /// Block producer account ID.
+    pub account_id: AccountId,
+    /// Address, if known.
+    #[serde(default)]
+    pub addr: Option<String>,
+    /// Peer ID.
+    pub peer_id: String,
+    /// Peers through which the producer is routed.
+    #[serde(default)]
+    pub next_hops: Option<Vec<String>>,
+}
+
+/// Congestion of a shard, from `EXPERIMENTAL_congestion_level`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct CongestionLevelResponse {
+    /// From `0.0` (not congested) to `1.0` (fully congested, rejecting new
+    /// transactions to the shard).
+    pub congestion_level: f64,
+}
+
 // ============================================================================
 // State change types
 // ============================================================================
 
+/// Which state changes to fetch with `EXPERIMENTAL_changes`.
+///
+/// Serializes to the `changes_type`-tagged params the node expects; the block
+/// to read is passed separately (see `RpcClient::changes` and
+/// `Near::state_changes`).
+///
+/// # Example
+///
+/// ```
+/// use near_kit::{AccountId, StateChangesRequest};
+///
+/// let alice: AccountId = "alice.near".parse().unwrap();
+/// let request = StateChangesRequest::data_changes([alice], b"STATE");
+/// assert_eq!(request.changes_type(), "data_changes");
+/// ```
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "changes_type", rename_all = "snake_case")]
+pub enum StateChangesRequest {
+    /// Account updates and deletions (balance, storage usage, code hash).
+    AccountChanges {
+        /// Accounts to report on.
+        account_ids: Vec<AccountId>,
+    },
+    /// Contract storage writes and deletions under a key prefix.
+    DataChanges {
+        /// Accounts to report on.
+        account_ids: Vec<AccountId>,
+        /// Raw key prefix (base64-encoded on the wire). Empty matches every key.
+        #[serde(rename = "key_prefix_base64")]
+        #[serde_as(as = "Base64")]
+        key_prefix: Vec<u8>,
+    },
+    /// Changes to every access key of the given accounts.
+    AllAccessKeyChanges {
+        /// Accounts to report on.
+        account_ids: Vec<AccountId>,
+    },
+    /// Changes to specific access keys.
+    SingleAccessKeyChanges {
+        /// The `(account, key)` pairs to report on.
+        keys: Vec<AccountWithPublicKey>,
+    },
+    /// Contract code deployments and deletions.
+    ContractCodeChanges {
+        /// Accounts to report on.
+        account_ids: Vec<AccountId>,
+    },
+    /// Changes to every gas key of the given accounts, including nonce updates.
+    AllGasKeyChanges {
+        /// Accounts to report on.
+        account_ids: Vec<AccountId>,
+    },
+    /// Changes to specific gas keys, including nonce updates.
+    SingleGasKeyChanges {
+        /// The `(account, key)` pairs to report on.
+        keys: Vec<AccountWithPublicKey>,
+    },
+}
+
+impl StateChangesRequest {
+    /// Account updates and deletions for `account_ids`.
+    pub fn account_changes(account_ids: impl IntoIterator<Item = AccountId>) -> Self {
+        Self::AccountChanges {
+            account_ids: account_ids.into_iter().collect(),
+        }
+    }
+
+    /// Storage changes under `key_prefix` for `account_ids`.
+    pub fn data_changes(
+        account_ids: impl IntoIterator<Item = AccountId>,
+        key_prefix: impl AsRef<[u8]>,
+    ) -> Self {
+        Self::DataChanges {
+            account_ids: account_ids.into_iter().collect(),
+            key_prefix: key_prefix.as_ref().to_vec(),
+        }
+    }
+
+    /// Changes to every access key of `account_ids`.
+    pub fn all_access_key_changes(account_ids: impl IntoIterator<Item = AccountId>) -> Self {
+        Self::AllAccessKeyChanges {
+            account_ids: account_ids.into_iter().collect(),
+        }
+    }
+
+    /// Changes to the given `(account, public key)` access keys.
+    pub fn single_access_key_changes(
+        keys: impl IntoIterator<Item = (AccountId, PublicKey)>,
+    ) -> Self {
+        Self::SingleAccessKeyChanges {
+            keys: keys.into_iter().map(AccountWithPublicKey::from).collect(),
+        }
+    }
+
+    /// Contract code changes for `account_ids`.
+    pub fn contract_code_changes(account_ids: impl IntoIterator<Item = AccountId>) -> Self {
+        Self::ContractCodeChanges {
+            account_ids: account_ids.into_iter().collect(),
+        }
+    }
+
+    /// Changes to every gas key of `account_ids`.
+    pub fn all_gas_key_changes(account_ids: impl IntoIterator<Item = AccountId>) -> Self {
+        Self::AllGasKeyChanges {
+            account_ids: account_ids.into_iter().collect(),
+        }
+    }
+
+    /// Changes to the given `(account, public key)` gas keys.
+    pub fn single_gas_key_changes(keys: impl IntoIterator<Item = (AccountId, PublicKey)>) -> Self {
+        Self::SingleGasKeyChanges {
+            keys: keys.into_iter().map(AccountWithPublicKey::from).collect(),
+        }
+    }
+
+    /// The `changes_type` value sent to the node.
+    pub fn changes_type(&self) -> &'static str {
+        match self {
+            Self::AccountChanges { .. } => "account_changes",
+            Self::DataChanges { .. } => "data_changes",
+            Self::AllAccessKeyChanges { .. } => "all_access_key_changes",
+            Self::SingleAccessKeyChanges { .. } => "single_access_key_changes",
+            Self::ContractCodeChanges { .. } => "contract_code_changes",
+            Self::AllGasKeyChanges { .. } => "all_gas_key_changes",
+            Self::SingleGasKeyChanges { .. } => "single_gas_key_changes",
+        }
+    }
+}
+
+/// An account and one of its keys, as named in a [`StateChangesRequest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AccountWithPublicKey {
+    /// Account that holds the key.
+    pub account_id: AccountId,
+    /// The key.
+    pub public_key: PublicKey,
+}
+
+impl From<(AccountId, PublicKey)> for AccountWithPublicKey {
+    fn from((account_id, public_key): (AccountId, PublicKey)) -> Self {
+        Self {
+            account_id,
+            public_key,
+        }
+    }
+}
+
+/// Response from `EXPERIMENTAL_changes`: the matching state changes in one
+/// block, each with its cause.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StateChangesResponse {
+    /// Hash of the block the changes were read from.
+    pub block_hash: CryptoHash,
+    /// The matching changes, in the order the node applied them.
+    pub changes: Vec<StateChangeWithCauseView>,
+}
+
 /// State change with its cause (from `EXPERIMENTAL_changes` RPC).
 ///
 /// On the wire, `type` (discriminator) and `change` (payload) are siblings of
@@ -581,6 +1192,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_state_changes_request_serialization() {
+        let alice: AccountId = "alice.near".parse().unwrap();
+        let key: PublicKey = "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+            .parse()
+            .unwrap();
+
+        let data = StateChangesRequest::data_changes([alice.clone()], b"STATE");
+        assert_eq!(
+            serde_json::to_value(&data).unwrap(),
+            serde_json::json!({
+                "changes_type": "data_changes",
+                "account_ids": ["alice.near"],
+                "key_prefix_base64": "U1RBVEU=",
+            })
+        );
+
+        let single = StateChangesRequest::single_access_key_changes([(alice.clone(), key)]);
+        assert_eq!(
+            serde_json::to_value(&single).unwrap(),
+            serde_json::json!({
+                "changes_type": "single_access_key_changes",
+                "keys": [{
+                    "account_id": "alice.near",
+                    "public_key": "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+                }],
+            })
+        );
+
+        // `changes_type()` always agrees with the serialized tag.
+        for request in [
+            StateChangesRequest::account_changes([alice.clone()]),
+            StateChangesRequest::all_access_key_changes([alice.clone()]),
+            StateChangesRequest::contract_code_changes([alice.clone()]),
+            StateChangesRequest::all_gas_key_changes([alice.clone()]),
+            StateChangesRequest::single_gas_key_changes([]),
+            data,
+            single,
+        ] {
+            let json = serde_json::to_value(&request).unwrap();
+            assert_eq!(json["changes_type"], request.changes_type());
+        }
+    }
+
     #[test]
     fn test_epoch_validator_info_validator_reward_paid_prev_epoch() {
         let json = serde_json::json!({
@@ -614,4 +1269,218 @@ mod tests {
         let info: EpochValidatorInfo = serde_json::from_value(json).unwrap();
         assert!(info.validator_reward_paid_prev_epoch.is_empty());
     }
+
+    fn fee(send: u64, exec: u64) -> serde_json::Value {
+        serde_json::json!({ "send_sir": send, "send_not_sir": send, "execution": exec })
+    }
+
+    #[test]
+    fn test_protocol_config_runtime_fees_deserialization() {
+        let json = serde_json::json!({
+            "protocol_version": 80,
+            "genesis_time": "2020-07-21T16:55:51.591948Z",
+            "chain_id": "mainnet",
+            "genesis_height": 9820210,
+            "epoch_length": 43200,
+            "gas_limit": 1000000000000000u64,
+            "min_gas_price": "100000000",
+            "max_gas_price": "10000000000000000000000",
+            "gas_price_adjustment_rate": [1, 100],
+            "transaction_validity_period": 86400,
+            "protocol_treasury_account": "treasury.near",
+            "max_inflation_rate": [1, 20],
+            "avg_hidden_validator_seats_per_shard": [0, 0, 0, 0],
+            "runtime_config": {
+                "storage_amount_per_byte": "10000000000000000000",
+                "transaction_costs": {
+                    "action_receipt_creation_config": fee(108059500000u64, 108059500000u64),
+                    "data_receipt_creation_config": {
+                        "base_cost": fee(36486732312u64, 36486732312u64),
+                        "cost_per_byte": fee(17212011, 17212011),
+                    },
+                    "action_creation_config": {
+                        "create_account_cost": fee(3850000000000u64, 3850000000000u64),
+                        "deploy_contract_cost": fee(184765750000u64, 184765750000u64),
+                        "deploy_contract_cost_per_byte": fee(6812999, 64572944),
+                        "function_call_cost": fee(200000000000u64, 780000000000u64),
+                        "function_call_cost_per_byte": fee(2235934, 2235934),
+                        "transfer_cost": fee(115123062500u64, 115123062500u64),
+                        "stake_cost": fee(141715687500u64, 102217625000u64),
+                        "add_key_cost": {
+                            "full_access_cost": fee(101765125000u64, 101765125000u64),
+                            "function_call_cost": fee(102217625000u64, 102217625000u64),
+                            "function_call_cost_per_byte": fee(1925331, 1925331),
+                        },
+                        "delete_key_cost": fee(94946625000u64, 94946625000u64),
+                        "delete_account_cost": fee(147489000000u64, 147489000000u64),
+                        "delegate_cost": fee(200000000000u64, 200000000000u64),
+                    },
+                    "storage_usage_config": {
+                        "num_bytes_account": 100,
+                        "num_extra_bytes_record": 40,
+                    },
+                    "burnt_gas_reward": [3, 10],
+                    "pessimistic_gas_price_inflation_ratio": [103, 100],
+                },
+                "wasm_config": { "limit_config": { "max_gas_burnt": 300000000000000u64 } },
+                "account_creation_config": {
+                    "min_allowed_top_level_account_length": 65,
+                    "registrar_account_id": "registrar",
+                },
+                "congestion_control_config": { "max_congestion_incoming_gas": 400000000000000000u64 },
+            },
+            "shard_layout": { "V2": {} },
+        });
+
+        let config: ProtocolConfigView = serde_json::from_value(json).unwrap();
+        assert_eq!(config.protocol_version, 80);
+        assert_eq!(config.gas_price_adjustment_rate, Some((1, 100)));
+        assert_eq!(
+            config.runtime_config.storage_amount_per_byte,
+            NearToken::from_yoctonear(10_000_000_000_000_000_000)
+        );
+
+        let costs = &config.runtime_config.transaction_costs;
+        let transfer = costs.action_creation_config.transfer_cost;
+        assert_eq!(transfer.total(false), Gas::from_gas(230_246_125_000));
+        assert_eq!(
+            costs.action_creation_config.function_call_cost.execution,
+            Gas::from_gas(780_000_000_000)
+        );
+        assert_eq!(costs.burnt_gas_reward, (3, 10));
+        assert_eq!(costs.storage_usage_config.num_bytes_account, 100);
+
+        // Unmodelled fields stay reachable.
+        assert!(
+            config
+                .extra
+                .contains_key("avg_hidden_validator_seats_per_shard")
+        );
+        assert!(
+            config
+                .runtime_config
+                .extra
+                .contains_key("congestion_control_config")
+        );
+    }
+
+    #[test]
+    fn test_runtime_fees_action_fee() {
+        let costs: RuntimeFeesConfigView = serde_json::from_value(serde_json::json!({
+            "action_receipt_creation_config": fee(100, 100),
+            "data_receipt_creation_config": {
+                "base_cost": fee(0, 0),
+                "cost_per_byte": fee(0, 0),
+            },
+            "action_creation_config": {
+                "create_account_cost": fee(0, 0),
+                "deploy_contract_cost": fee(1000, 1000),
+                "deploy_contract_cost_per_byte": fee(1, 2),
+                "function_call_cost": fee(200, 700),
+                "function_call_cost_per_byte": fee(1, 1),
+                "transfer_cost": fee(50, 50),
+                "stake_cost": fee(0, 0),
+                "add_key_cost": {
+                    "full_access_cost": fee(10, 10),
+                    "function_call_cost": fee(20, 20),
+                    "function_call_cost_per_byte": fee(1, 1),
+                },
+                "delete_key_cost": fee(0, 0),
+                "delete_account_cost": fee(0, 0),
+                "delegate_cost": fee(300, 300),
+            },
+            "storage_usage_config": { "num_bytes_account": 100, "num_extra_bytes_record": 40 },
+            "burnt_gas_reward": [3, 10],
+            "pessimistic_gas_price_inflation_ratio": [103, 100],
+        }))
+        .unwrap();
+
+        assert_eq!(costs.receipt_fee(false), Gas::from_gas(200));
+        assert_eq!(
+            costs.action_fee(&Action::transfer(NearToken::from_near(1)), false),
+            Some(Gas::from_gas(100))
+        );
+        // 3 bytes of method name + 2 bytes of args.
+        let call =
+            Action::function_call("foo", b"{}".to_vec(), Gas::from_tgas(30), NearToken::ZERO);
+        assert_eq!(costs.action_fee(&call, false), Some(Gas::from_gas(910)));
+        assert_eq!(
+            costs.action_fee(&Action::deploy_contract(vec![0; 10]), true),
+            Some(Gas::from_gas(2030))
+        );
+        // Method names count a null terminator each: ("a" + 1) + ("bc" + 1).
+        let key: PublicKey = "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+            .parse()
+            .unwrap();
+        let add_key = Action::add_function_call_key(
+            key,
+            "app.near".parse().unwrap(),
+            vec!["a".to_string(), "bc".to_string()],
+            None,
+        );
+        assert_eq!(costs.action_fee(&add_key, false), Some(Gas::from_gas(50)));
+        // Fees this view does not model are reported as unknown.
+        let publish = Action::publish(vec![0; 10], crate::types::PublishMode::Immutable);
+        assert_eq!(costs.action_fee(&publish, false), None);
+    }
+
+    #[test]
+    fn test_genesis_config_deserialization() {
+        let json = serde_json::json!({
+            "protocol_version": 29,
+            "genesis_time": "2020-07-21T16:55:51.591948Z",
+            "chain_id": "mainnet",
+            "genesis_height": 9820210,
+            "epoch_length": 43200,
+            "gas_limit": 1000000000000000u64,
+            "min_gas_price": "1000000000",
+            "max_gas_price": "10000000000000000000000",
+            "total_supply": "1000000000000000000000000000000000",
+            "transaction_validity_period": 86400,
+            "validators": [{
+                "account_id": "node0",
+                "public_key": "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+                "amount": "50000000000000000000000000000",
+            }],
+            "protocol_reward_rate": [1, 10],
+            "records": [],
+            "use_production_config": false,
+        });
+
+        let config: GenesisConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config.chain_id, "mainnet");
+        assert_eq!(config.validators.len(), 1);
+        assert_eq!(config.validators[0].account_id.as_str(), "node0");
+        assert_eq!(config.protocol_reward_rate, Some((1, 10)));
+        assert_eq!(
+            config.extra.get("use_production_config"),
+            Some(&false.into())
+        );
+    }
+
+    #[test]
+    fn test_network_info_deserialization() {
+        let json = serde_json::json!({
+            "active_peers": [
+                { "id": "ed25519:peer1", "addr": "1.2.3.4:24567", "account_id": null },
+                { "id": "ed25519:peer2", "addr": null, "account_id": "validator.near" },
+            ],
+            "num_active_peers": 2,
+            "peer_max_count": 40,
+            "sent_bytes_per_sec": 1024,
+            "received_bytes_per_sec": 2048,
+            "known_producers": [
+                { "account_id": "validator.near", "addr": null, "peer_id": "ed25519:peer2", "next_hops": null },
+            ],
+        });
+
+        let info: NetworkInfoView = serde_json::from_value(json).unwrap();
+        assert_eq!(info.num_active_peers, 2);
+        assert_eq!(info.active_peers[0].addr.as_deref(), Some("1.2.3.4:24567"));
+        assert_eq!(
+            info.active_peers[1].account_id.as_ref().map(|a| a.as_str()),
+            Some("validator.near")
+        );
+        assert_eq!(info.known_producers[0].peer_id, "ed25519:peer2");
+    }
 }
@@ -0,0 +1,88 @@
+//! Integration tests for `next_light_client_block`,
+//! `EXPERIMENTAL_light_client_proof`, and the offline verifier.
+
+use std::time::Duration;
+
+use near_kit::light_client::{self, LightClient};
+use near_kit::sandbox::{SANDBOX_ROOT_ACCOUNT, SandboxConfig};
+use near_kit::*;
+
+#[tokio::test]
+async fn test_next_light_client_block_hashes_to_real_block() {
+    let sandbox = SandboxConfig::shared().await;
+    let near = sandbox.client();
+
+    let genesis = near.rpc().block(BlockReference::genesis()).await.unwrap();
+    let block = near
+        .rpc()
+        .next_light_client_block(&genesis.header.hash)
+        .await
+        .unwrap()
+        .expect("sandbox has produced blocks past genesis");
+
+    // The locally computed hash must name a block the node knows, at the
+    // height the lite header claims.
+    let hash = block.hash();
+    let full = near
+        .rpc()
+        .block(BlockReference::at_hash(hash))
+        .await
+        .unwrap();
+    assert_eq!(full.header.height, block.inner_lite.height);
+    assert_eq!(
+        full.header.block_merkle_root,
+        block.inner_lite.block_merkle_root
+    );
+
+    // The checkpoint's next block producers hash to its `next_bp_hash`.
+    let client = LightClient::new(block).expect("checkpoint must be self-consistent");
+    assert_eq!(client.head_hash(), hash);
+}
+
+#[tokio::test]
+async fn test_light_client_proof_verifies_against_head() {
+    let sandbox = SandboxConfig::shared().await;
+    let near = sandbox.client();
+
+    let root: AccountId = SANDBOX_ROOT_ACCOUNT.parse().unwrap();
+    let outcome = near
+        .transfer(&root, NearToken::from_yoctonear(1))
+        .wait_until::<Final>()
+        .await
+        .expect("self-transfer must execute");
+    let outcome_block = near
+        .rpc()
+        .block(BlockReference::at_hash(
+            outcome.transaction_outcome.block_hash,
+        ))
+        .await
+        .unwrap();
+
+    // The head must be past the block whose outcome root commits the outcome.
+    let head = loop {
+        let head = near.rpc().block(BlockReference::final_()).await.unwrap();
+        if head.header.height > outcome_block.header.height + 2 {
+            break head;
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    };
+
+    let request = LightClientProofRequest::Transaction {
+        transaction_hash: *outcome.transaction_hash(),
+        sender_id: root,
+    };
+    let proof = near
+        .rpc()
+        .light_client_proof(&request, &head.header.hash)
+        .await
+        .expect("light_client_proof");
+
+    light_client::verify_execution_proof(&proof, &head.header.block_merkle_root)
+        .expect("proof must verify against the head it was requested for");
+
+    // The same proof does not verify against an unrelated head.
+    let genesis = near.rpc().block(BlockReference::genesis()).await.unwrap();
+    assert!(
+        light_client::verify_execution_proof(&proof, &genesis.header.block_merkle_root).is_err()
+    );
+}
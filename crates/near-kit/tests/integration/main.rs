@@ -15,12 +15,14 @@ mod error_handling_integration;
 mod exec_metadata_integration;
 mod gas_key_transaction_integration;
 mod global_contracts_integration;
+mod light_client_integration;
 mod ml_dsa_integration;
 mod offline_signing_integration;
 mod rpc_types_integration;
 mod sandbox_integration;
 mod signer_edge_cases_integration;
 mod stabilized_rpc_integration;
+mod state_changes_integration;
 mod token_error_integration;
 mod token_integration;
 #[cfg(feature = "tracing")]
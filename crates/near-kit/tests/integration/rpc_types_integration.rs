@@ -111,10 +111,81 @@ async fn test_chunk_header_view_full_fields() {
     }
 }
 
+#[tokio::test]
+async fn test_chunk_view_by_hash_and_by_shard() {
+    let sandbox = SandboxConfig::shared().await;
+    let near = sandbox.client();
+
+    let block = near.rpc().block(BlockReference::final_()).await.unwrap();
+    let header = &block.chunks[0];
+
+    let by_hash = near
+        .rpc()
+        .chunk(ChunkReference::chunk_hash(header.chunk_hash))
+        .await
+        .unwrap();
+    assert_eq!(by_hash.header.chunk_hash, header.chunk_hash);
+
+    let by_shard = near
+        .rpc()
+        .chunk(ChunkReference::at_block_hash(
+            block.header.hash,
+            header.shard_id,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(by_shard.header.chunk_hash, header.chunk_hash);
+    assert_eq!(by_shard.transactions.len(), by_hash.transactions.len());
+    assert_eq!(by_shard.receipts.len(), by_hash.receipts.len());
+
+    let level = near
+        .rpc()
+        .congestion_level(ChunkReference::chunk_hash(header.chunk_hash))
+        .await
+        .unwrap();
+    assert!((0.0..=1.0).contains(&level.congestion_level));
+}
+
 // ============================================================================
 // Status and Node Info Tests
 // ============================================================================
 
+#[tokio::test]
+async fn test_protocol_config_runtime_fees() {
+    let sandbox = SandboxConfig::shared().await;
+    let near = sandbox.client();
+
+    let status = near.rpc().status().await.unwrap();
+    let config = near
+        .rpc()
+        .protocol_config(BlockReference::final_())
+        .await
+        .unwrap();
+
+    assert_eq!(config.protocol_version, status.protocol_version);
+    assert_eq!(config.chain_id, status.chain_id);
+    assert_eq!(
+        config.runtime_config.storage_amount_per_byte,
+        NearToken::from_yoctonear(STORAGE_AMOUNT_PER_BYTE)
+    );
+    let transfer = config
+        .runtime_config
+        .transaction_costs
+        .action_creation_config
+        .transfer_cost;
+    assert!(transfer.total(false) > Gas::from_gas(0));
+}
+
+#[tokio::test]
+async fn test_network_info() {
+    let sandbox = SandboxConfig::shared().await;
+    let near = sandbox.client();
+
+    // A single-node sandbox has no peers; the call parsing is what matters.
+    let info = near.rpc().network_info().await.unwrap();
+    assert_eq!(info.num_active_peers as usize, info.active_peers.len());
+}
+
 #[tokio::test]
 async fn test_status_response_full_fields() {
     let sandbox = SandboxConfig::shared().await;
@@ -22,16 +22,15 @@ async fn test_genesis_config_returns_chain_config() {
 
     let config = near.rpc().genesis_config().await.expect("genesis_config");
     // The genesis document always carries a chain id and a protocol version.
+    let status = near.rpc().status().await.expect("status");
+    assert_eq!(config.chain_id, status.chain_id);
     assert!(
-        config.get("chain_id").and_then(|v| v.as_str()).is_some(),
-        "genesis_config should include chain_id, got: {config}"
+        config.protocol_version > 0,
+        "genesis_config should include a protocol_version, got: {config:?}"
     );
     assert!(
-        config
-            .get("protocol_version")
-            .and_then(|v| v.as_u64())
-            .is_some(),
-        "genesis_config should include a numeric protocol_version, got: {config}"
+        !config.validators.is_empty(),
+        "sandbox genesis has at least one validator"
     );
 }
 
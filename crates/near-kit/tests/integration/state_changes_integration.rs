@@ -0,0 +1,92 @@
+//! Integration tests for `EXPERIMENTAL_changes` via `Near::state_changes` and
+//! `RpcClient::changes`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use near_kit::sandbox::{SANDBOX_ROOT_ACCOUNT, SandboxConfig};
+use near_kit::*;
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+fn unique_account() -> AccountId {
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("changes{n}.{SANDBOX_ROOT_ACCOUNT}")
+        .parse()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_state_changes_for_created_account() {
+    let sandbox = SandboxConfig::shared().await;
+    let near = sandbox.client();
+
+    let key = SecretKey::generate_ed25519();
+    let account_id = unique_account();
+    let outcome = near
+        .transaction(&account_id)
+        .create_account()
+        .transfer(NearToken::from_near(3))
+        .add_full_access_key(key.public_key())
+        .send()
+        .wait_until::<Final>()
+        .await
+        .expect("create_account must execute");
+
+    // The account and its key are written when the receipt executes, which
+    // may be a later block than the one that included the transaction.
+    let receipt_block = outcome.receipts_outcome[0].block_hash;
+
+    let account_changes = near
+        .state_changes(StateChangesRequest::account_changes([account_id.clone()]))
+        .at_block_hash(receipt_block)
+        .await
+        .expect("account_changes must parse");
+    assert_eq!(account_changes.block_hash, receipt_block);
+    assert!(
+        account_changes.changes.iter().any(|c| matches!(
+            &c.value,
+            StateChangeValueView::AccountUpdate { account_id: id, .. } if id == &account_id
+        )),
+        "expected an AccountUpdate for {account_id}, got: {:?}",
+        account_changes.changes
+    );
+
+    let key_changes = near
+        .rpc()
+        .changes(
+            &StateChangesRequest::single_access_key_changes([(
+                account_id.clone(),
+                key.public_key(),
+            )]),
+            BlockReference::at_hash(receipt_block),
+        )
+        .await
+        .expect("single_access_key_changes must parse");
+    assert!(
+        key_changes
+            .changes
+            .iter()
+            .any(|c| matches!(&c.value, StateChangeValueView::AccessKeyUpdate { .. })),
+        "expected an AccessKeyUpdate, got: {:?}",
+        key_changes.changes
+    );
+}
+
+#[tokio::test]
+async fn test_changes_in_block_matches_block_effects() {
+    let sandbox = SandboxConfig::shared().await;
+    let near = sandbox.client();
+
+    let block = near.rpc().block(BlockReference::final_()).await.unwrap();
+    let block_ref = BlockReference::at_hash(block.header.hash);
+
+    let legacy = near.rpc().changes_in_block(block_ref).await;
+    let stabilized = near.rpc().block_effects(block_ref).await.unwrap();
+
+    // Nodes that no longer serve the EXPERIMENTAL name are fine; when both
+    // answer they must agree.
+    if let Ok(legacy) = legacy {
+        assert_eq!(legacy.block_hash, stabilized.block_hash);
+        assert_eq!(legacy.changes.len(), stabilized.changes.len());
+    }
+}
@@ -595,6 +595,79 @@ async fn test_nft_transfer() {
     println!("Token transferred successfully to {}", receiver_id);
 }
 
+#[tokio::test]
+async fn test_nft_approve_and_revoke() {
+    let sandbox = SandboxConfig::shared().await;
+    let root_near = sandbox.client();
+
+    // Create owner account
+    let owner_key = SecretKey::generate_ed25519();
+    let owner_id = unique_account();
+
+    root_near
+        .transaction(&owner_id)
+        .create_account()
+        .transfer(NearToken::from_near(100))
+        .add_full_access_key(owner_key.public_key())
+        .send()
+        .wait_until::<Final>()
+        .await
+        .unwrap();
+
+    let owner_near = Near::sandbox(sandbox)
+        .with_signer(InMemorySigner::new(&owner_id, owner_key.to_string()).unwrap());
+    let market_id: AccountId = format!("market.{}", owner_id).parse().unwrap();
+
+    // Deploy NFT contract and mint a token to owner
+    let (nft_id, nft_key) = deploy_nft_contract(&root_near, &owner_id).await.unwrap();
+    mint_nft(&root_near, &nft_id, &nft_key, "approve-test", &owner_id)
+        .await
+        .unwrap();
+
+    let nft = owner_near.nft(&nft_id).unwrap();
+    assert!(
+        !nft.is_approved("approve-test", &market_id, None)
+            .await
+            .unwrap()
+    );
+
+    // The attached approval storage deposit must cover the contract's charge
+    let outcome = nft
+        .approve("approve-test", &market_id)
+        .wait_until::<Final>()
+        .await
+        .unwrap();
+    assert!(outcome.is_success(), "{:?}", outcome.failure_message());
+
+    assert!(
+        nft.is_approved("approve-test", &market_id, None)
+            .await
+            .unwrap()
+    );
+    let token = nft.token("approve-test").await.unwrap().unwrap();
+    let approval_id = token.approved_account_ids.unwrap()[market_id.as_str()];
+    assert!(
+        nft.is_approved("approve-test", &market_id, Some(approval_id))
+            .await
+            .unwrap()
+    );
+    assert!(
+        !nft.is_approved("approve-test", &market_id, Some(approval_id + 1))
+            .await
+            .unwrap()
+    );
+
+    nft.revoke("approve-test", &market_id)
+        .wait_until::<Final>()
+        .await
+        .unwrap();
+    assert!(
+        !nft.is_approved("approve-test", &market_id, None)
+            .await
+            .unwrap()
+    );
+}
+
 #[tokio::test]
 async fn test_nft_total_supply() {
     let sandbox = SandboxConfig::shared().await;